@@ -5,6 +5,36 @@ use kirin_interpreter::{Continuation, Interpretable, Interpreter, InterpreterErr
 
 use crate::Bitwise;
 
+/// Capability for width-aware rotate operations on interpreter values,
+/// required of `I::Value` the same way `Shl`/`Shr` already are: implemented
+/// directly by a backend's value type.
+///
+/// `rol(x, k)` over an N-bit value equals `(x << k) | (x >> (N-k))` with `k`
+/// taken modulo N. Returns `None` when `self` has no well-defined bit width
+/// to rotate within (e.g. a floating-point value), so the interpreter can
+/// report an [`InterpreterError`] instead of producing nonsense.
+pub trait RotateOps: Sized {
+    fn rotate_left(&self, amount: &Self) -> Option<Self>;
+    fn rotate_right(&self, amount: &Self) -> Option<Self>;
+}
+
+/// Capability for bit-counting/reversal operations on interpreter values.
+/// See [`RotateOps`] for why these return `Option`.
+pub trait BitCount: Sized {
+    fn popcount(&self) -> Option<Self>;
+    fn clz(&self) -> Option<Self>;
+    fn ctz(&self) -> Option<Self>;
+    fn bit_reverse(&self) -> Option<Self>;
+    fn byte_swap(&self) -> Option<Self>;
+}
+
+/// The value given to `rol`/`ror`/`popcount`/... has no well-defined bit
+/// width (e.g. a floating-point interpreter value), so the operation cannot
+/// be carried out.
+#[derive(Debug, thiserror::Error)]
+#[error("bitwise operation has no well-defined bit width for this value")]
+pub struct UndefinedBitWidth;
+
 impl<'ir, I, L, T> Interpretable<'ir, I, L> for Bitwise<T>
 where
     I: Interpreter<'ir>,
@@ -14,7 +44,9 @@ where
         + BitXor<Output = I::Value>
         + Not<Output = I::Value>
         + Shl<Output = I::Value>
-        + Shr<Output = I::Value>,
+        + Shr<Output = I::Value>
+        + RotateOps
+        + BitCount,
     I::Error: From<InterpreterError>,
     L: Dialect,
     T: CompileTimeValue + Default,
@@ -68,6 +100,78 @@ where
                 interp.write(*result, a >> b)?;
                 Ok(Continuation::Continue)
             }
+            Bitwise::Rol {
+                lhs, rhs, result, ..
+            } => {
+                let a = interp.read(*lhs)?;
+                let b = interp.read(*rhs)?;
+                let r = a
+                    .rotate_left(&b)
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
+            Bitwise::Ror {
+                lhs, rhs, result, ..
+            } => {
+                let a = interp.read(*lhs)?;
+                let b = interp.read(*rhs)?;
+                let r = a
+                    .rotate_right(&b)
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
+            Bitwise::Popcount {
+                operand, result, ..
+            } => {
+                let a = interp.read(*operand)?;
+                let r = a
+                    .popcount()
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
+            Bitwise::Clz {
+                operand, result, ..
+            } => {
+                let a = interp.read(*operand)?;
+                let r = a
+                    .clz()
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
+            Bitwise::Ctz {
+                operand, result, ..
+            } => {
+                let a = interp.read(*operand)?;
+                let r = a
+                    .ctz()
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
+            Bitwise::BitReverse {
+                operand, result, ..
+            } => {
+                let a = interp.read(*operand)?;
+                let r = a
+                    .bit_reverse()
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
+            Bitwise::ByteSwap {
+                operand, result, ..
+            } => {
+                let a = interp.read(*operand)?;
+                let r = a
+                    .byte_swap()
+                    .ok_or_else(|| InterpreterError::custom(UndefinedBitWidth))?;
+                interp.write(*result, r)?;
+                Ok(Continuation::Continue)
+            }
         }
     }
 }
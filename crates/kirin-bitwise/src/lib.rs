@@ -30,6 +30,13 @@
 //! %r = not %a -> i16
 //! %r = shl %a, %b -> u32
 //! %r = shr %a, %b -> i32
+//! %r = rol %a, %b -> u32
+//! %r = ror %a, %b -> u32
+//! %r = popcount %a -> i32
+//! %r = clz %a -> i32
+//! %r = ctz %a -> i32
+//! %r = bitreverse %a -> u32
+//! %r = byteswap %a -> u32
 //! ```
 //!
 //! # Semantics
@@ -40,6 +47,14 @@
 //!   determines arithmetic vs logical shift semantics.
 //! - Verifier passes are expected to enforce type compatibility, including the
 //!   RFC rule that shift count type must match the shifted value type.
+//! - `rol`/`ror` rotate by `rhs` modulo the value's bit width: `rol(x, k)`
+//!   over an N-bit value equals `(x << k) | (x >> (N-k))`. They are pure and
+//!   speculatable, since unlike `shl`/`shr` every shift amount is
+//!   well-defined.
+//! - `popcount`, `clz`, `ctz`, `bitreverse`, `byteswap` are pure and
+//!   speculatable. The interpreter reports an error rather than panicking
+//!   for a value with no well-defined bit width (see the `RotateOps` and
+//!   `BitCount` capability traits in `interpret_impl`).
 
 use kirin::prelude::*;
 
@@ -98,4 +113,62 @@ pub enum Bitwise<T: CompileTimeValue + Default> {
         #[kirin(default)]
         marker: std::marker::PhantomData<T>,
     },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = rol {lhs}, {rhs} -> {result:type}")]
+    Rol {
+        lhs: SSAValue,
+        rhs: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = ror {lhs}, {rhs} -> {result:type}")]
+    Ror {
+        lhs: SSAValue,
+        rhs: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = popcount {operand} -> {result:type}")]
+    Popcount {
+        operand: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = clz {operand} -> {result:type}")]
+    Clz {
+        operand: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = ctz {operand} -> {result:type}")]
+    Ctz {
+        operand: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = bitreverse {operand} -> {result:type}")]
+    BitReverse {
+        operand: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
+    #[kirin(speculatable)]
+    #[chumsky(format = "{result:name} = byteswap {operand} -> {result:type}")]
+    ByteSwap {
+        operand: SSAValue,
+        result: ResultValue,
+        #[kirin(default)]
+        marker: std::marker::PhantomData<T>,
+    },
 }
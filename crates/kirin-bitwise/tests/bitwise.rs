@@ -94,6 +94,37 @@ fn test_roundtrip_all_operations_with_integer_types() {
     );
 }
 
+#[test]
+fn test_roundtrip_rotate_and_bit_scan_operations() {
+    assert_roundtrip(
+        "%ri_rol = rol %a, %b -> u32",
+        &[("a", ArithType::U32), ("b", ArithType::U32)],
+        true,
+    );
+    assert_roundtrip(
+        "%ri_ror = ror %a, %b -> u32",
+        &[("a", ArithType::U32), ("b", ArithType::U32)],
+        true,
+    );
+    assert_roundtrip(
+        "%ri_popcount = popcount %a -> i32",
+        &[("a", ArithType::I32)],
+        true,
+    );
+    assert_roundtrip("%ri_clz = clz %a -> i32", &[("a", ArithType::I32)], true);
+    assert_roundtrip("%ri_ctz = ctz %a -> i32", &[("a", ArithType::I32)], true);
+    assert_roundtrip(
+        "%ri_bitreverse = bitreverse %a -> u32",
+        &[("a", ArithType::U32)],
+        true,
+    );
+    assert_roundtrip(
+        "%ri_byteswap = byteswap %a -> u32",
+        &[("a", ArithType::U32)],
+        true,
+    );
+}
+
 #[test]
 fn test_shift_operations_are_pure_but_not_speculatable() {
     assert_roundtrip(
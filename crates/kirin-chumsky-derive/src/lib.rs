@@ -7,6 +7,10 @@
 //! - `WithAbstractSyntaxTree`: Generates AST types and implements the `WithAbstractSyntaxTree` trait
 //! - `EmitIR`: Implements the `EmitIR` trait for converting AST to IR nodes
 //! - `DialectParser`: Combined macro that derives all three above
+//! - `WithPrinter`: Implements the `WithPrinter` trait for rendering AST nodes back to text
+//! - `Visitor`: Implements `VisitChildren`/`VisitMutChildren`/`FoldChildren` for traversing AST nodes
+//! - `StructEq`: Implements `struct_eq` for comparing AST nodes while ignoring `Spanned` offsets
+//! - `TreeSitterGrammar`: Implements `HasTreeSitterGrammar`, used to emit an external tree-sitter grammar
 //!
 //! # Example
 //!
@@ -160,6 +164,136 @@ pub fn derive_emit_ir(input: TokenStream) -> TokenStream {
     generator.generate(&ir_input).into()
 }
 
+/// Derives the `WithPrinter` trait for an AST type.
+///
+/// This macro generates an implementation of `WithPrinter` that renders a
+/// parsed AST node back to the surface syntax described by its
+/// `#[chumsky(format = "...")]` attribute, so `parse -> print -> parse` is a
+/// stable round-trip.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(WithAbstractSyntaxTree, WithPrinter)]
+/// #[kirin(type_lattice = Type)]
+/// pub enum ArithOps {
+///     #[chumsky(format = "{res} = add {lhs}, {rhs}")]
+///     Add { res: ResultValue, lhs: SSAValue, rhs: SSAValue },
+/// }
+/// ```
+#[proc_macro_derive(WithPrinter, attributes(kirin, chumsky, wraps))]
+pub fn derive_with_printer(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+
+    let ir_input =
+        match kirin_derive_core::ir::Input::<kirin_chumsky_format::ChumskyLayout>::from_derive_input(
+            &ast,
+        ) {
+            Ok(ir) => ir,
+            Err(err) => return err.write_errors().into(),
+        };
+
+    let generator = kirin_chumsky_format::GenerateAstPrinter::new(&ir_input);
+    generator.generate(&ir_input).into()
+}
+
+/// Derives `VisitChildren`, `VisitMutChildren`, and `FoldChildren` for an AST
+/// type, so a hand-written [`kirin_chumsky::Visit`], [`kirin_chumsky::VisitMut`],
+/// or [`kirin_chumsky::Fold`] impl gets the recursion into nested `Block`/
+/// `Region` statements for free.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(WithAbstractSyntaxTree, Visitor)]
+/// #[kirin(type_lattice = Type)]
+/// pub enum ArithOps {
+///     #[chumsky(format = "{res} = add {lhs}, {rhs}")]
+///     Add { res: ResultValue, lhs: SSAValue, rhs: SSAValue },
+/// }
+/// ```
+#[proc_macro_derive(Visitor, attributes(kirin, chumsky, wraps))]
+pub fn derive_visitor(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+
+    let ir_input =
+        match kirin_derive_core::ir::Input::<kirin_chumsky_format::ChumskyLayout>::from_derive_input(
+            &ast,
+        ) {
+            Ok(ir) => ir,
+            Err(err) => return err.write_errors().into(),
+        };
+
+    let generator = kirin_chumsky_format::GenerateVisitor::new(&ir_input);
+    generator.generate(&ir_input).into()
+}
+
+/// Derives `StructEq` for an AST type: a `struct_eq(&self, other) -> bool`
+/// that compares two parsed nodes while ignoring the byte offsets carried by
+/// every `Spanned` field, so differently-formatted-but-equivalent source
+/// parses to equal ASTs.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(WithAbstractSyntaxTree, StructEq)]
+/// #[kirin(type_lattice = Type)]
+/// pub enum ArithOps {
+///     #[chumsky(format = "{res} = add {lhs}, {rhs}")]
+///     Add { res: ResultValue, lhs: SSAValue, rhs: SSAValue },
+/// }
+/// ```
+#[proc_macro_derive(StructEq, attributes(kirin, chumsky, wraps))]
+pub fn derive_struct_eq(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+
+    let ir_input =
+        match kirin_derive_core::ir::Input::<kirin_chumsky_format::ChumskyLayout>::from_derive_input(
+            &ast,
+        ) {
+            Ok(ir) => ir,
+            Err(err) => return err.write_errors().into(),
+        };
+
+    let generator = kirin_chumsky_format::GenerateStructEq::new(&ir_input);
+    generator.generate(&ir_input).into()
+}
+
+/// Derives `HasTreeSitterGrammar` for a dialect type: one
+/// [`kirin_chumsky::treesitter::TreeSitterRule`] per
+/// `#[chumsky(format = "...")]` variant/struct, built from the format string
+/// at macro-expansion time. Feed the dialect type to
+/// `kirin_chumsky::treesitter::emit_grammar` to render a complete
+/// `grammar.js` for an external tree-sitter parser.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(TreeSitterGrammar)]
+/// #[kirin(type_lattice = Type)]
+/// pub enum ArithOps {
+///     #[chumsky(format = "{res} = add {lhs}, {rhs}")]
+///     Add { res: ResultValue, lhs: SSAValue, rhs: SSAValue },
+/// }
+///
+/// let grammar_js = kirin_chumsky::treesitter::emit_grammar::<ArithOps>("arith_ops");
+/// ```
+#[proc_macro_derive(TreeSitterGrammar, attributes(kirin, chumsky, wraps))]
+pub fn derive_tree_sitter_grammar(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+
+    let ir_input =
+        match kirin_derive_core::ir::Input::<kirin_chumsky_format::ChumskyLayout>::from_derive_input(
+            &ast,
+        ) {
+            Ok(ir) => ir,
+            Err(err) => return err.write_errors().into(),
+        };
+
+    let generator = kirin_chumsky_format::GenerateTreeSitterGrammar::new(&ir_input);
+    generator.generate(&ir_input).into()
+}
+
 /// Combined derive macro that implements both `HasRecursiveParser` and `WithAbstractSyntaxTree`.
 ///
 /// This is a convenience macro that combines both derives into one.
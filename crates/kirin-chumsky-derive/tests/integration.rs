@@ -3,9 +3,16 @@
 use chumsky::input::Stream;
 use chumsky::prelude::*;
 use kirin::ir::{Dialect, FiniteLattice, Lattice, ResultValue, SSAValue, Successor, TypeLattice};
-use kirin_chumsky::{BoxedParser, HasParser, TokenInput};
+use kirin_chumsky::ast::{Block, BlockHeader, BlockLabel, Spanned};
+use kirin_chumsky::inference::{TypingRule, infer_block, meet_of};
+use kirin_chumsky::{
+    BoxedParser, HasParser, HasTreeSitterGrammar, TokenInput, WithPrinter,
+    parse_dialect_recovering, parse_session, render_diagnostics,
+};
 use kirin_chumsky_derive::{
-    DialectParser, HasRecursiveParser as DeriveRecursiveParser, WithAbstractSyntaxTree,
+    DialectParser, HasRecursiveParser as DeriveRecursiveParser,
+    TreeSitterGrammar as DeriveTreeSitterGrammar, WithAbstractSyntaxTree,
+    WithPrinter as DeriveWithPrinter,
 };
 use kirin_lexer::{Logos, Token};
 
@@ -55,6 +62,20 @@ impl FiniteLattice for SimpleType {
 
 impl TypeLattice for SimpleType {}
 
+impl std::fmt::Display for SimpleType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            SimpleType::I32 => "i32",
+            SimpleType::I64 => "i64",
+            SimpleType::F32 => "f32",
+            SimpleType::F64 => "f64",
+            SimpleType::Bool => "bool",
+            SimpleType::Unit => "unit",
+        };
+        f.write_str(text)
+    }
+}
+
 // Implement HasParser for SimpleType
 impl<'tokens, 'src: 'tokens> HasParser<'tokens, 'src> for SimpleType {
     type Output = SimpleType;
@@ -131,7 +152,10 @@ fn parse_input(input: &str) -> Result<TestLangAST<'_, '_, TestLang>, Vec<String>
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -247,6 +271,18 @@ pub enum BlockRegionLang {
         body: kirin::ir::Region,
     },
 
+    // Statement with a bare (`#[kirin(bare)]`) Block field: the body has no
+    // `^label(...)` header of its own; the field's parser supplies the
+    // delimiting `{`/`}` itself.
+    // Syntax: `%res = if %cond { %x = id %cond -> i32; }`
+    #[chumsky(format = "{res} = if {cond} {body}")]
+    If {
+        res: ResultValue,
+        cond: SSAValue,
+        #[kirin(bare)]
+        body: kirin::ir::Block,
+    },
+
     // Return statement (terminator)
     #[chumsky(format = "ret {0}")]
     Ret(SSAValue),
@@ -272,7 +308,10 @@ fn parse_block_region_input(
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -584,7 +623,10 @@ fn parse_control_flow_input(
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -687,7 +729,10 @@ fn parse_combined_lang_input(
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -765,7 +810,9 @@ fn test_parse_ssa_default_without_type() {
 // ============================================================================
 
 /// A dialect with tuple variants using multiple positional fields.
-#[derive(Debug, Clone, PartialEq, Dialect, DeriveRecursiveParser, WithAbstractSyntaxTree)]
+#[derive(
+    Debug, Clone, PartialEq, Dialect, DeriveRecursiveParser, WithAbstractSyntaxTree, DeriveWithPrinter
+)]
 #[kirin(type_lattice = SimpleType)]
 #[chumsky(crate = kirin_chumsky)]
 pub enum TupleLang {
@@ -797,7 +844,10 @@ fn parse_tuple_lang_input(input: &str) -> Result<TupleLangAST<'_, '_, TupleLang>
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -839,21 +889,43 @@ fn test_parse_named_fields_four_fields() {
     }
 }
 
+#[test]
+fn test_tuple_lang_roundtrips() {
+    for input in ["swap %a %b", "%out = sel %cond %left %right -> i32"] {
+        let ast = parse_tuple_lang_input(input).expect("parse failed");
+        assert_eq!(ast.print(), input);
+    }
+}
+
 // ============================================================================
 // Tests for ResultValue :name only (no :type)
 // ============================================================================
 
 /// A dialect where some operations don't have a result type in the syntax.
-#[derive(Debug, Clone, PartialEq, Dialect, DeriveRecursiveParser, WithAbstractSyntaxTree)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Dialect,
+    DeriveRecursiveParser,
+    WithAbstractSyntaxTree,
+    DeriveWithPrinter,
+    DeriveTreeSitterGrammar,
+)]
 #[kirin(type_lattice = SimpleType)]
 #[chumsky(crate = kirin_chumsky)]
 pub enum UnaryLang {
     // Result type not in syntax - inferred later
     #[chumsky(format = "{res:name} = neg {arg}")]
     Neg { res: ResultValue, arg: SSAValue },
-    // Result type explicitly in syntax
-    #[chumsky(format = "{res:name} = abs {arg} -> {res:type}")]
+    // Result type explicitly in syntax, but optional: `abs` can appear with
+    // or without its `-> type` tail.
+    #[chumsky(format = "{res:name} = abs {arg} [-> {res:type}]?")]
     Abs { res: ResultValue, arg: SSAValue },
+    // An alternative group picking between keyword synonyms; see
+    // `test_unary_lang_roundtrips_alternative_group`.
+    #[chumsky(format = "{res:name} = sign {arg} (pos|neg)")]
+    Sign { res: ResultValue, arg: SSAValue },
 }
 
 fn parse_unary_lang_input(input: &str) -> Result<UnaryLangAST<'_, '_, UnaryLang>, Vec<String>> {
@@ -872,7 +944,10 @@ fn parse_unary_lang_input(input: &str) -> Result<UnaryLangAST<'_, '_, UnaryLang>
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -908,6 +983,159 @@ fn test_parse_result_name_and_type() {
     }
 }
 
+#[test]
+fn test_unary_lang_roundtrips() {
+    // `Neg` omits `-> ty` (res.ty is None), `Abs` includes it.
+    for input in ["%x = neg %y", "%x = abs %y -> i32"] {
+        let ast = parse_unary_lang_input(input).expect("parse failed");
+        assert_eq!(ast.print(), input);
+    }
+}
+
+#[test]
+fn test_unary_lang_roundtrips_alternative_group() {
+    // Both inputs select a different branch of the `(pos|neg)` alternative
+    // group; since an alternative group has no AST slot recording which
+    // branch matched (see `FormatElement::Alternative`'s doc comment),
+    // printing always re-emits the first branch, "pos".
+    let positive = parse_unary_lang_input("%x = sign %y pos").expect("parse failed");
+    assert_eq!(positive.print(), "%x = sign %y pos");
+
+    let negative = parse_unary_lang_input("%x = sign %y neg").expect("parse failed");
+    assert_eq!(negative.print(), "%x = sign %y pos");
+}
+
+/// `UnaryLang::Neg`'s result type is the type of its one operand -- there's
+/// no join/meet of several operands to take, but `meet_of` degrades to "the
+/// type, if it's the only one known" in that case, so it still fits.
+impl<'tokens, 'src: 'tokens> TypingRule<'tokens, 'src, UnaryLang, SimpleType>
+    for UnaryLangAST<'tokens, 'src, UnaryLang>
+{
+    fn reads(&self) -> Vec<&'src str> {
+        match self {
+            UnaryLangAST::Neg { arg, .. }
+            | UnaryLangAST::Abs { arg, .. }
+            | UnaryLangAST::Sign { arg, .. } => vec![arg.name.value],
+        }
+    }
+
+    fn defines(&self) -> Option<&'src str> {
+        match self {
+            UnaryLangAST::Neg { res, .. }
+            | UnaryLangAST::Abs { res, .. }
+            | UnaryLangAST::Sign { res, .. } => Some(res.name.value),
+        }
+    }
+
+    fn known_type(&self) -> Option<SimpleType> {
+        match self {
+            UnaryLangAST::Neg { res, .. }
+            | UnaryLangAST::Abs { res, .. }
+            | UnaryLangAST::Sign { res, .. } => res.ty.clone(),
+        }
+    }
+
+    fn set_inferred_type(&mut self, ty: SimpleType) {
+        match self {
+            UnaryLangAST::Neg { res, .. }
+            | UnaryLangAST::Abs { res, .. }
+            | UnaryLangAST::Sign { res, .. } => res.ty = Some(ty),
+        }
+    }
+
+    fn infer(&self, operand_types: &[Option<SimpleType>]) -> Option<SimpleType> {
+        meet_of(operand_types)
+    }
+}
+
+#[test]
+fn test_unary_lang_infers_neg_result_type_from_prior_statement() {
+    // `%y`'s type only becomes known once `abs`'s explicit `-> i32` is
+    // parsed; `neg` never names a type at all, so its result type can only
+    // come from threading `%y`'s type through the block's environment.
+    let abs = parse_unary_lang_input("%y = abs %z -> i32").expect("parse failed");
+    let neg = parse_unary_lang_input("%x = neg %y").expect("parse failed");
+    let UnaryLangAST::Neg { res: neg_res, .. } = &neg else {
+        panic!("Expected Neg variant, got {:?}", neg);
+    };
+    assert!(neg_res.ty.is_none(), "neg's result type shouldn't be known yet");
+
+    let span = chumsky::span::SimpleSpan::from(0..0);
+    let mut block = Block {
+        header: Spanned {
+            value: BlockHeader {
+                label: BlockLabel {
+                    name: Spanned { value: "bb0", span },
+                },
+                arguments: Vec::new(),
+            },
+            span,
+        },
+        statements: vec![
+            Spanned { value: abs, span },
+            Spanned { value: neg, span },
+        ],
+    };
+
+    let reports = infer_block(&mut block, &mut std::collections::HashMap::new());
+    assert!(reports.is_empty(), "expected full resolution: {reports:?}");
+
+    match &block.statements[1].value {
+        UnaryLangAST::Neg { res, .. } => assert_eq!(res.ty, Some(SimpleType::I32)),
+        other => panic!("Expected Neg variant, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unary_lang_tree_sitter_grammar_renders_optional_tail_as_optional() {
+    let grammar = UnaryLang::tree_sitter_grammar("unary_lang");
+
+    // `Abs`'s `[-> {res:type}]?` tail must become an `optional(seq(...))`,
+    // not be silently dropped or forced into every parse the way
+    // `GenerateAstPrinter`'s `flatten_groups` would render it.
+    assert!(
+        grammar.contains("optional(seq("),
+        "expected an optional(seq(...)) rule in:\n{grammar}"
+    );
+    assert!(grammar.contains("'->'"), "expected the `->` token to still appear:\n{grammar}");
+
+    // `Neg` has no optional group at all, so its rule shouldn't gain one.
+    let neg_rule_start = grammar.find("neg: $ =>").expect("missing neg rule");
+    let neg_rule_end = grammar[neg_rule_start..].find('\n').unwrap() + neg_rule_start;
+    assert!(!grammar[neg_rule_start..neg_rule_end].contains("optional("));
+}
+
+#[test]
+fn test_parse_session_keeps_each_statements_own_diagnostics() {
+    const SRC: &str = "%x = neg %y; %bad ???; %z = abs %y -> i32;";
+
+    let tokens: Vec<_> = Token::lexer(SRC)
+        .spanned()
+        .map(|(tok, span)| {
+            let token = tok.unwrap_or(Token::Error);
+            (token, chumsky::span::SimpleSpan::from(span))
+        })
+        .collect();
+    let stream = Stream::from_iter(tokens).map((0..SRC.len()).into(), |(t, s): (_, _)| (t, s));
+
+    let items = parse_session::<_, UnaryLang>(stream);
+    assert_eq!(items.len(), 3, "expected one session item per statement: {items:?}");
+
+    assert!(items[0].output.is_some(), "first statement should parse cleanly");
+    assert!(items[0].diagnostics.is_empty());
+
+    assert!(items[1].output.is_none(), "second statement is malformed");
+    assert_eq!(items[1].diagnostics.len(), 1);
+    assert!(
+        SRC[items[1].span.into_range()].contains("bad"),
+        "the failed item's span should cover its own source text, not its neighbours': {:?}",
+        items[1].span
+    );
+
+    assert!(items[2].output.is_some(), "third statement should parse cleanly");
+    assert!(items[2].diagnostics.is_empty());
+}
+
 // ============================================================================
 // Tests for compile-time value fields (non-IR types with HasParser)
 // ============================================================================
@@ -916,6 +1144,12 @@ fn test_parse_result_name_and_type() {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Opcode(pub String);
 
+impl std::fmt::Display for Opcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl<'tokens, 'src: 'tokens> HasParser<'tokens, 'src> for Opcode {
     type Output = Opcode;
 
@@ -932,7 +1166,9 @@ impl<'tokens, 'src: 'tokens> HasParser<'tokens, 'src> for Opcode {
 }
 
 /// A dialect that uses a compile-time value field.
-#[derive(Debug, Clone, PartialEq, Dialect, DeriveRecursiveParser, WithAbstractSyntaxTree)]
+#[derive(
+    Debug, Clone, PartialEq, Dialect, DeriveRecursiveParser, WithAbstractSyntaxTree, DeriveWithPrinter
+)]
 #[kirin(type_lattice = SimpleType)]
 #[chumsky(crate = kirin_chumsky)]
 pub enum ValueLang {
@@ -960,7 +1196,10 @@ fn parse_value_lang_input(input: &str) -> Result<ValueLangAST<'_, '_, ValueLang>
     if result.has_output() {
         Ok(result.into_output().unwrap())
     } else {
-        Err(result.errors().map(|e| format!("{:?}", e)).collect())
+        Err(result
+            .errors()
+            .map(|e| render_diagnostics(input, std::slice::from_ref(e), false))
+            .collect())
     }
 }
 
@@ -996,6 +1235,14 @@ fn test_parse_compile_time_value_different() {
     }
 }
 
+#[test]
+fn test_value_lang_roundtrips() {
+    for input in ["%r = apply custom_op %x -> i32", "%r = apply another %x -> f32"] {
+        let ast = parse_value_lang_input(input).expect("parse failed");
+        assert_eq!(ast.print(), input);
+    }
+}
+
 // ============================================================================
 // Tests for deep recursive nesting
 // ============================================================================
@@ -1107,6 +1354,84 @@ fn test_parse_deeply_nested_structure() {
     }
 }
 
+#[test]
+fn test_parse_deeply_nested_structure_recovers_multiple_diagnostics() {
+    // Same scope -> block -> loop -> block -> scope -> block shape as
+    // `test_parse_deeply_nested_structure`, but with three malformed
+    // statements scattered across the nesting levels. Recovery should skip
+    // each one independently and still parse the well-formed statements
+    // around them, reporting all three mistakes at once instead of stopping
+    // at the first.
+    const SRC: &str = "%out: unit = scope { \
+        ^bb0() { \
+            %bad0 ???; \
+            %loop_res: i64 = loop ^loop0() { \
+                %bad1 ???; \
+                %scope_res: bool = scope { ^bb1() { %bad2 ???; } }; \
+            }; \
+        }; \
+    }";
+
+    let tokens: Vec<_> = Token::lexer(SRC)
+        .spanned()
+        .map(|(tok, span)| {
+            let token = tok.unwrap_or(Token::Error);
+            (token, chumsky::span::SimpleSpan::from(span))
+        })
+        .collect();
+    let stream = Stream::from_iter(tokens).map((0..SRC.len()).into(), |(t, s): (_, _)| (t, s));
+
+    let (ast, reports) = parse_dialect_recovering::<_, BlockRegionLang>(stream);
+
+    assert_eq!(
+        reports.len(),
+        3,
+        "expected one diagnostic per bad statement: {reports:?}"
+    );
+
+    match ast.expect("recovery should still produce an AST") {
+        BlockRegionLangAST::Scope { body, .. } => {
+            // `%bad0` is skipped, leaving only the `loop` statement in bb0.
+            assert_eq!(body.blocks[0].value.statements.len(), 1);
+        }
+        other => panic!("Expected Scope variant, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_bare_block_recovers_from_malformed_statement() {
+    // A bare block carries no `^label(...)` header, but its statement
+    // sequence still goes through `bare_block`'s `recovering()` wrapper, so
+    // a malformed statement inside it should be skipped rather than
+    // aborting the whole parse.
+    const SRC: &str = "%out: unit = if %cond { %bad ???; %ok = id %cond -> i32; }";
+
+    let tokens: Vec<_> = Token::lexer(SRC)
+        .spanned()
+        .map(|(tok, span)| {
+            let token = tok.unwrap_or(Token::Error);
+            (token, chumsky::span::SimpleSpan::from(span))
+        })
+        .collect();
+    let stream = Stream::from_iter(tokens).map((0..SRC.len()).into(), |(t, s): (_, _)| (t, s));
+
+    let (ast, reports) = parse_dialect_recovering::<_, BlockRegionLang>(stream);
+
+    assert_eq!(
+        reports.len(),
+        1,
+        "expected one diagnostic for the bad statement: {reports:?}"
+    );
+
+    match ast.expect("recovery should still produce an AST") {
+        BlockRegionLangAST::If { body, .. } => {
+            // `%bad` is skipped, leaving only the well-formed `id` statement.
+            assert_eq!(body.value.statements.len(), 1);
+        }
+        other => panic!("Expected If variant, got {:?}", other),
+    }
+}
+
 // ============================================================================
 // Additional edge case tests
 // ============================================================================
@@ -0,0 +1,50 @@
+//! Tests for the `WithPrinter` derive macro.
+//!
+//! These tests verify that a parsed AST node can be rendered back to its
+//! surface syntax, and that `parse -> print -> parse` is a stable round-trip.
+
+use kirin::ir::{Dialect, ResultValue, SSAValue};
+use kirin_chumsky::{WithPrinter, parse_ast};
+use kirin_chumsky_derive::{HasRecursiveParser, WithAbstractSyntaxTree, WithPrinter as DeriveWithPrinter};
+use kirin_test_utils::SimpleType;
+
+#[derive(Debug, Clone, PartialEq, Dialect, HasRecursiveParser, WithAbstractSyntaxTree, DeriveWithPrinter)]
+#[kirin(type_lattice = SimpleType)]
+#[chumsky(crate = kirin_chumsky)]
+pub enum TestLang {
+    #[chumsky(format = "{res:name} = add {lhs} {rhs} -> {res:type}")]
+    Add {
+        res: ResultValue,
+        lhs: SSAValue,
+        rhs: SSAValue,
+    },
+    #[chumsky(format = "return {0}")]
+    Return(SSAValue),
+}
+
+#[test]
+fn test_print_add_roundtrips() {
+    let input = "%result = add %a %b -> i32";
+    let ast = parse_ast::<TestLang>(input).expect("parse failed");
+    assert_eq!(ast.print(), input);
+}
+
+#[test]
+fn test_print_return_roundtrips() {
+    let input = "return %value";
+    let ast = parse_ast::<TestLang>(input).expect("parse failed");
+    assert_eq!(ast.print(), input);
+}
+
+#[test]
+fn test_print_omits_absent_return_type() {
+    // `Return`'s `SSAValue` type annotation is optional; printing should
+    // only emit it when the source actually supplied one.
+    let input = "return %value: i32";
+    let ast = parse_ast::<TestLang>(input).expect("parse failed");
+    assert_eq!(ast.print(), input);
+
+    let input = "return %value";
+    let ast = parse_ast::<TestLang>(input).expect("parse failed");
+    assert_eq!(ast.print(), input);
+}
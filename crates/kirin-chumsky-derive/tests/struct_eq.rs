@@ -0,0 +1,42 @@
+//! Tests for the `StructEq` derive macro.
+//!
+//! These tests verify that two ASTs parsed from differently-formatted but
+//! equivalent source compare equal via `struct_eq`, even though their
+//! `Spanned` byte offsets differ.
+
+use kirin::ir::{Dialect, ResultValue, SSAValue};
+use kirin_chumsky::{StructEq, assert_struct_eq, parse_ast};
+use kirin_chumsky_derive::{
+    HasRecursiveParser, StructEq as DeriveStructEq, WithAbstractSyntaxTree,
+};
+use kirin_test_utils::SimpleType;
+
+#[derive(Debug, Clone, PartialEq, Dialect, HasRecursiveParser, WithAbstractSyntaxTree, DeriveStructEq)]
+#[kirin(type_lattice = SimpleType)]
+#[chumsky(crate = kirin_chumsky)]
+pub enum TestLang {
+    #[chumsky(format = "{res:name} = add {lhs} {rhs} -> {res:type}")]
+    Add {
+        res: ResultValue,
+        lhs: SSAValue,
+        rhs: SSAValue,
+    },
+    #[chumsky(format = "return {0}")]
+    Return(SSAValue),
+}
+
+#[test]
+fn test_struct_eq_ignores_whitespace_differences() {
+    let tight = parse_ast::<TestLang>("%a = add %x %y -> i32").expect("parse failed");
+    let spaced = parse_ast::<TestLang>("%a  =  add  %x  %y  ->  i32").expect("parse failed");
+
+    assert_struct_eq!(tight, spaced);
+}
+
+#[test]
+fn test_struct_eq_rejects_different_names() {
+    let lhs = parse_ast::<TestLang>("%a = add %x %y -> i32").expect("parse failed");
+    let rhs = parse_ast::<TestLang>("%b = add %x %y -> i32").expect("parse failed");
+
+    assert!(!lhs.struct_eq(&rhs));
+}
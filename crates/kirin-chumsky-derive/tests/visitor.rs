@@ -0,0 +1,72 @@
+//! Tests for the `Visitor` derive macro.
+//!
+//! These tests verify that a hand-written `Visit`/`Fold` pass, combined with
+//! the derive-generated `VisitChildren`/`FoldChildren` recursion, can inspect
+//! and rewrite a parsed AST without hand-rolled `match` boilerplate.
+
+use kirin::ir::{Dialect, ResultValue, SSAValue};
+use kirin_chumsky::{Fold, FoldChildren, Visit, VisitChildren, parse_ast};
+use kirin_chumsky_derive::{
+    HasRecursiveParser, Visitor as DeriveVisitor, WithAbstractSyntaxTree,
+};
+use kirin_test_utils::SimpleType;
+
+#[derive(Debug, Clone, PartialEq, Dialect, HasRecursiveParser, WithAbstractSyntaxTree, DeriveVisitor)]
+#[kirin(type_lattice = SimpleType)]
+#[chumsky(crate = kirin_chumsky)]
+pub enum TestLang {
+    #[chumsky(format = "{res:name} = add {lhs} {rhs} -> {res:type}")]
+    Add {
+        res: ResultValue,
+        lhs: SSAValue,
+        rhs: SSAValue,
+    },
+    #[chumsky(format = "return {0}")]
+    Return(SSAValue),
+}
+
+#[derive(Default)]
+struct AddCounter {
+    count: usize,
+}
+
+impl Visit<TestLangASTSelf<'_, '_, SimpleType>> for AddCounter {
+    fn visit(&mut self, node: &TestLangASTSelf<'_, '_, SimpleType>) {
+        if let TestLangAST::Add { .. } = &node.0 {
+            self.count += 1;
+        }
+        node.visit_children(self);
+    }
+}
+
+#[test]
+fn test_visit_counts_add_statements() {
+    let ast = parse_ast::<TestLang>("%result = add %a %b -> i32").expect("parse failed");
+
+    let mut counter = AddCounter::default();
+    counter.visit(&ast);
+    assert_eq!(counter.count, 1);
+}
+
+struct IdentityFold;
+
+impl Fold<TestLangASTSelf<'_, '_, SimpleType>> for IdentityFold {
+    fn fold(
+        &mut self,
+        node: TestLangASTSelf<'_, '_, SimpleType>,
+    ) -> TestLangASTSelf<'_, '_, SimpleType> {
+        node.fold_children(self)
+    }
+}
+
+#[test]
+fn test_fold_is_identity_without_overrides() {
+    // A `Fold` pass that doesn't override anything should leave the tree
+    // untouched, exercising only the derive-generated `fold_children`
+    // recursion/rebuild plumbing.
+    let ast = parse_ast::<TestLang>("return %value").expect("parse failed");
+
+    let mut pass = IdentityFold;
+    let folded = pass.fold(ast.clone());
+    assert_eq!(folded, ast);
+}
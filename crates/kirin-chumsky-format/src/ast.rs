@@ -98,7 +98,7 @@ impl<'src> ScanExtra<'src, Struct<'src, DeriveAST>, Vec<SyntaxField>> for Derive
                 "Missing 'format' attribute on struct",
             ));
         };
-        scan_fields(format, self, node.fields())
+        scan_fields(format, self, node.fields(), node.source_ident().span())
     }
 }
 
@@ -113,7 +113,7 @@ impl<'src> ScanExtra<'src, Variant<'_, 'src, DeriveAST>, Vec<SyntaxField>> for D
                 "Missing 'format' attribute on struct",
             ));
         };
-        scan_fields(format, self, node.fields())
+        scan_fields(format, self, node.fields(), node.source_ident().span())
     }
 }
 
@@ -121,14 +121,55 @@ fn scan_fields<'src>(
     format: &String,
     ctx: &DeriveAST,
     node: Fields<'_, 'src, DeriveAST>,
+    stmt_span: proc_macro2::Span,
 ) -> syn::Result<Vec<SyntaxField>> {
     let format = Format::parse(&format, None)?;
     // ResultValue field will not appear in the generated AST node, because the upper-level AST
     // will always hold the ResultValue directly as syntax `<result> = <statement>`
     let mut fs = Vec::new();
     let mut err = Vec::new();
+
+    let field_idents: Vec<String> = node.iter().map(|f| f.source_ident().to_string()).collect();
+    for name in format.field_names() {
+        // Tuple-struct/variant fields are referenced positionally (`{0}`),
+        // not by ident, so a numeric placeholder has no field name to match.
+        if name.parse::<usize>().is_ok() {
+            continue;
+        }
+        if !field_idents.iter().any(|f| f == name) {
+            err.push(syn::Error::new(
+                stmt_span,
+                format!(
+                    "no field `{}`; available fields: {}",
+                    name,
+                    if field_idents.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        field_idents.join(", ")
+                    }
+                ),
+            ));
+        }
+    }
+
     for f in node.iter() {
         let s = f.source_ident().to_string();
+
+        // A ResultValue field is never written in the format string (it's
+        // always the implicit `<result> = <statement>` prefix), but every
+        // other field that's missing from the format will simply never be
+        // populated by the parser.
+        if format.get_field(&s).is_none() && !matches!(&f.extra().kind, FieldKind::ResultValue) {
+            err.push(syn::Error::new(
+                f.source().span(),
+                format!(
+                    "field '{s}' does not appear in the format string and will never be \
+                     parsed; add it as {{{s}}}/{{{s}:name}}/{{{s}:type}}, or remove the field",
+                ),
+            ));
+            continue;
+        }
+
         let kind = match (format.get_field(&s), &f.extra().kind) {
             (Some(FormatOption::Default), FieldKind::SSAValue) => SyntaxFieldKind::SSAValue,
             (Some(FormatOption::Default), FieldKind::ResultValue) => {
@@ -267,4 +308,48 @@ mod tests {
 
         derive_ctx.print(&input).unwrap();
     }
+
+    #[test]
+    fn test_struct_unknown_placeholder_lists_available_fields() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[chumsky(format = "{name} = add {lhs}, {rhs}, {typo}")]
+            pub struct Add {
+                pub name: SSAValue,
+                pub lhs: SSAValue,
+                pub rhs: SSAValue,
+            }
+        };
+
+        let derive_ctx = DeriveAST {
+            crate_path: syn::parse_quote! { kirin_chumsky },
+        };
+
+        let err = derive_ctx.print(&input).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("no field `typo`"), "{msg}");
+        assert!(msg.contains("available fields: name, lhs, rhs"), "{msg}");
+    }
+
+    #[test]
+    fn test_struct_field_missing_from_format_is_reported() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[chumsky(format = "{name} = add {lhs}")]
+            pub struct Add {
+                pub name: SSAValue,
+                pub lhs: SSAValue,
+                pub rhs: SSAValue,
+            }
+        };
+
+        let derive_ctx = DeriveAST {
+            crate_path: syn::parse_quote! { kirin_chumsky },
+        };
+
+        let err = derive_ctx.print(&input).unwrap_err();
+        let msg = err.to_string();
+        assert!(
+            msg.contains("field 'rhs' does not appear in the format string"),
+            "{msg}"
+        );
+    }
 }
\ No newline at end of file
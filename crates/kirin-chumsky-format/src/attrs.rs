@@ -12,6 +12,23 @@ pub struct ChumskyGlobalAttrs {
 
     /// Default format string for all variants/statements.
     pub format: Option<String>,
+
+    /// Opt in to error-recovering parsing for every variant of this dialect.
+    ///
+    /// Can be overridden per-variant via `#[chumsky(recover)]` on the variant.
+    /// See [`ChumskyStatementAttrs::recover`].
+    #[darling(default)]
+    pub recover: bool,
+
+    /// Override the inferred `HasParser`/`HasDialectParser` where-clause
+    /// predicates for this dialect, e.g. `#[chumsky(bound = "T: MyTrait")]`.
+    ///
+    /// By default each type parameter actually used by a value field gets an
+    /// inferred bound (see `GenericsBuilder::field_bound_predicates`); set
+    /// this when the inference picks the wrong bound or misses one that
+    /// isn't visible from field types alone, mirroring serde's
+    /// `#[serde(bound = "...")]`.
+    pub bound: Option<String>,
 }
 
 /// Attributes applied to individual statements or enum variants.
@@ -24,11 +41,53 @@ pub struct ChumskyStatementAttrs {
     /// Override crate path for this statement.
     #[darling(rename = "crate")]
     pub crate_path: Option<syn::Path>,
+
+    /// Opt in to error-recovering parsing for this statement: on a syntax
+    /// error inside its body, skip tokens up to a synchronizing delimiter
+    /// and produce an `{AST}::Error` node instead of failing the whole parse.
+    #[darling(default)]
+    pub recover: bool,
+
+    /// Additional accepted format strings for this statement, tried in
+    /// declaration order after `format`, e.g. a sugared alias of a verbose
+    /// spelling. Declare as many as needed via repeated
+    /// `#[chumsky(format_alias = "...")]` attributes (mirrors serde's
+    /// `#[serde(alias = ...)]`).
+    ///
+    /// A field that appears in some alternatives but not others must have a
+    /// `#[kirin(default)]` so the AST constructor can fill it in whenever
+    /// the alternative that matched is one that omits it.
+    #[darling(multiple, rename = "format_alias")]
+    pub format_aliases: Vec<String>,
 }
 
 /// Attributes applied to individual fields.
 #[derive(Debug, Clone, Default, FromField)]
 #[darling(attributes(chumsky))]
 pub struct ChumskyFieldAttrs {
-    // Currently no field-level chumsky attributes
+    /// Override the generated chumsky parser for this field with a custom
+    /// parser function, e.g. `#[chumsky(parse_with = parse_radian_fraction)]`.
+    ///
+    /// Use this when the field's textual form isn't expressible with the
+    /// kind-derived parser (a domain-specific literal, a custom keyword
+    /// spelling, etc). The path is called with no arguments (`#some_path()`)
+    /// and must return a chumsky parser; its output still passes through
+    /// `field.collection.wrap_parser` so it composes with `Vec`/`Option`
+    /// wrappers the same way the built-in parsers do.
+    pub parse_with: Option<syn::Path>,
+
+    /// Map the output of `parse_with` with `#some_path` before it's fed into
+    /// the AST constructor, e.g. `#[chumsky(map_with = Angle::from_fraction)]`.
+    /// Only meaningful alongside `parse_with`; ignored otherwise.
+    pub map_with: Option<syn::Path>,
+
+    /// Opt this field out of getting a fresh lifetime parameter for its
+    /// elided borrows (`&str`, `&[Foo]`, `Cow<'_, T>`); it borrows `'src`
+    /// directly instead, e.g. `#[chumsky(borrow_src)]`.
+    ///
+    /// Use this when the field is known to borrow straight from the source
+    /// text being parsed rather than from some shorter-lived intermediate,
+    /// so it doesn't need its own `'fieldN` parameter on the AST type.
+    #[darling(default)]
+    pub borrow_src: bool,
 }
@@ -5,7 +5,10 @@
 
 use std::collections::HashSet;
 
-use kirin_derive_core::ir::{fields::Collection, DefaultValue};
+use kirin_derive_core::ir::{
+    fields::{Collection, FieldCategory, FieldInfo},
+    DefaultValue,
+};
 use kirin_derive_core::misc::is_type_in_generic;
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -22,12 +25,14 @@ pub enum FieldKind {
     SSAValue,
     /// ResultValue output field
     ResultValue,
-    /// Block field (owned control flow block)
-    Block,
+    /// Block field (owned control flow block). `true` if the field was
+    /// marked `#[kirin(bare)]` (unlabeled body, no `^label(...)` header).
+    Block(bool),
     /// Successor field (branch target)
     Successor,
-    /// Region field (nested scope)
-    Region,
+    /// Region field (nested scope). `true` if the field was marked
+    /// `#[kirin(bare)]`; see [`FieldKind::Block`].
+    Region(bool),
     /// Compile-time value field with its type
     Value(syn::Type),
 }
@@ -38,13 +43,32 @@ impl FieldKind {
         match self {
             FieldKind::SSAValue => "ssa_value",
             FieldKind::ResultValue => "result_value",
-            FieldKind::Block => "block",
+            FieldKind::Block(_) => "block",
             FieldKind::Successor => "successor",
-            FieldKind::Region => "region",
+            FieldKind::Region(_) => "region",
             FieldKind::Value(_) => "value",
         }
     }
 
+    /// Converts a parsed [`FieldInfo`] into its [`FieldKind`], used by format
+    /// validation to classify a field without going through
+    /// [`collect_fields`].
+    pub fn from_field_info(field: &FieldInfo<ChumskyLayout>) -> Self {
+        match field.category() {
+            FieldCategory::Argument => FieldKind::SSAValue,
+            FieldCategory::Result => FieldKind::ResultValue,
+            FieldCategory::Block => FieldKind::Block(field.is_bare()),
+            FieldCategory::Successor => FieldKind::Successor,
+            FieldCategory::Region => FieldKind::Region(field.is_bare()),
+            FieldCategory::Value => FieldKind::Value(
+                field
+                    .value_type()
+                    .expect("Value-category field must have a value type")
+                    .clone(),
+            ),
+        }
+    }
+
     /// Returns true if this field kind supports the :name and :type format options.
     pub fn supports_name_type_options(&self) -> bool {
         matches!(self, FieldKind::SSAValue | FieldKind::ResultValue)
@@ -74,16 +98,23 @@ impl FieldKind {
             FieldKind::ResultValue => {
                 quote! { #crate_path::ResultValue<'src, #type_ast> }
             }
-            FieldKind::Block => {
+            FieldKind::Block(false) => {
                 // Block parser returns Spanned<Block>, so we need Spanned wrapper
                 quote! { #crate_path::Spanned<#crate_path::Block<'src, #type_ast, #stmt_output>> }
             }
+            FieldKind::Block(true) => {
+                // Bare block parser returns Spanned<BareBlock>, with no header.
+                quote! { #crate_path::Spanned<#crate_path::BareBlock<'src, #stmt_output>> }
+            }
             FieldKind::Successor => {
                 quote! { #crate_path::BlockLabel<'src> }
             }
-            FieldKind::Region => {
+            FieldKind::Region(false) => {
                 quote! { #crate_path::Region<'src, #type_ast, #stmt_output> }
             }
+            FieldKind::Region(true) => {
+                quote! { Vec<#crate_path::BareBlock<'src, #stmt_output>> }
+            }
             FieldKind::Value(ty) => {
                 quote! { <#ty as #crate_path::HasParser<'tokens, 'src>>::Output }
             }
@@ -128,7 +159,7 @@ impl FieldKind {
                     quote! { #crate_path::result_value_with_optional_type::<_, Language, #type_lattice>() }
                 }
             },
-            FieldKind::Block => {
+            FieldKind::Block(false) => {
                 // Block parser uses Language as the language parameter.
                 // Parser returns Block<..., <Language as HasDialectParser>::Output>
                 // AST type is Block<..., AST<..., Language>>
@@ -144,10 +175,25 @@ impl FieldKind {
                         })
                 }
             }
+            FieldKind::Block(true) => {
+                // Bare block: no `^label(...)` header, just `{ stmt.repeated() }`.
+                // Deferring to the dialect's own recursive `language` parser is
+                // what lets this nest arbitrarily deep (e.g. an `if` inside an
+                // `if`'s then-block).
+                quote! {
+                    #crate_path::bare_block::<_, Language>(language.clone())
+                        .map(|b| unsafe {
+                            ::core::mem::transmute::<
+                                #crate_path::Spanned<#crate_path::BareBlock<'src, <Language as #crate_path::HasDialectParser<'tokens, 'src, Language>>::Output>>,
+                                #crate_path::Spanned<#crate_path::BareBlock<'src, #ast_name<'tokens, 'src, Language>>>
+                            >(b)
+                        })
+                }
+            }
             FieldKind::Successor => {
                 quote! { #crate_path::block_label() }
             }
-            FieldKind::Region => {
+            FieldKind::Region(false) => {
                 // Region parser uses Language as the language parameter.
                 // Parser returns Region<..., <Language as HasDialectParser>::Output>
                 // AST type is Region<..., AST<..., Language>>
@@ -163,6 +209,19 @@ impl FieldKind {
                         })
                 }
             }
+            FieldKind::Region(true) => {
+                // Bare region: a sequence of bare blocks, still deferring to
+                // the dialect's own recursive `language` parser for bodies.
+                quote! {
+                    #crate_path::bare_region::<_, Language>(language.clone())
+                        .map(|blocks| unsafe {
+                            ::core::mem::transmute::<
+                                Vec<#crate_path::BareBlock<'src, <Language as #crate_path::HasDialectParser<'tokens, 'src, Language>>::Output>>,
+                                Vec<#crate_path::BareBlock<'src, #ast_name<'tokens, 'src, Language>>>
+                            >(blocks)
+                        })
+                }
+            }
             FieldKind::Value(ty) => {
                 quote! { <#ty as #crate_path::HasParser<'tokens, 'src>>::parser() }
             }
@@ -244,7 +303,7 @@ impl FieldKind {
                     #prettyless_path::PrettyPrint::pretty_print(#field_ref, doc)
                 },
             },
-            FieldKind::Block | FieldKind::Successor | FieldKind::Region => quote! {
+            FieldKind::Block(_) | FieldKind::Successor | FieldKind::Region(_) => quote! {
                 #prettyless_path::PrettyPrint::pretty_print(#field_ref, doc)
             },
             FieldKind::Value(_ty) => {
@@ -255,6 +314,342 @@ impl FieldKind {
             }
         }
     }
+
+    /// Generates the `WithPrinter` print statement for one field occurrence,
+    /// appending its surface syntax directly to an `out: &mut String`
+    /// buffer rather than building a [`kirin_prettyless::Document`].
+    ///
+    /// `field_ref` must already be a reference to the field's AST value
+    /// (from pattern matching). `crate_path` is the path to the
+    /// `kirin_chumsky` crate, used to recurse into nested statements'
+    /// `WithPrinter` impls for `Block`/`Region` fields.
+    pub fn print_ast_expr(
+        &self,
+        crate_path: &syn::Path,
+        field_ref: &TokenStream,
+        opt: &FormatOption,
+    ) -> TokenStream {
+        match self {
+            FieldKind::SSAValue | FieldKind::ResultValue => match opt {
+                FormatOption::Name => quote! {
+                    out.push('%');
+                    out.push_str(#field_ref.name.value);
+                },
+                FormatOption::Type => quote! {
+                    if let Some(__ty) = &#field_ref.ty {
+                        out.push_str(&__ty.to_string());
+                    }
+                },
+                FormatOption::Default => quote! {
+                    out.push('%');
+                    out.push_str(#field_ref.name.value);
+                    if let Some(__ty) = &#field_ref.ty {
+                        out.push_str(": ");
+                        out.push_str(&__ty.to_string());
+                    }
+                },
+            },
+            FieldKind::Successor => quote! {
+                out.push('^');
+                out.push_str(#field_ref.name.value);
+            },
+            FieldKind::Block(false) => {
+                let block_body = print_block_body(crate_path, &quote! { __block });
+                quote! {
+                    {
+                        let __block = &#field_ref.value;
+                        #block_body
+                    }
+                }
+            }
+            FieldKind::Block(true) => {
+                let stmts_body = print_bare_statements(crate_path, &quote! { __block.statements });
+                quote! {
+                    {
+                        let __block = &#field_ref.value;
+                        out.push('{');
+                        #stmts_body
+                        out.push_str(" }");
+                    }
+                }
+            }
+            FieldKind::Region(false) => {
+                let block_body = print_block_body(crate_path, &quote! { __blk.value });
+                quote! {
+                    {
+                        out.push('{');
+                        for __blk in #field_ref.blocks.iter() {
+                            out.push(' ');
+                            #block_body
+                        }
+                        out.push_str(" }");
+                    }
+                }
+            }
+            FieldKind::Region(true) => {
+                let stmts_body = print_bare_statements(crate_path, &quote! { __blk.statements });
+                quote! {
+                    {
+                        out.push('{');
+                        for __blk in #field_ref.iter() {
+                            out.push(' ');
+                            out.push('{');
+                            #stmts_body
+                            out.push_str(" }");
+                        }
+                        out.push_str(" }");
+                    }
+                }
+            }
+            FieldKind::Value(_ty) => quote! {
+                out.push_str(&#field_ref.to_string());
+            },
+        }
+    }
+
+    /// Generates the `VisitChildren::visit_children` statement for one field
+    /// occurrence, visiting the nested statements of a `Block`/`Region`
+    /// field's own AST type. Every other field kind carries no nested AST
+    /// node to recurse into (see the `Visitor` derive's module docs for why
+    /// `Value` fields are out of scope) and generates nothing.
+    ///
+    /// `field_ref` must already be a reference to the field's AST value
+    /// (from pattern matching).
+    pub fn visit_expr(&self, field_ref: &TokenStream) -> TokenStream {
+        match self {
+            FieldKind::Block(_) => quote! {
+                for __stmt in #field_ref.value.statements.iter() {
+                    visitor.visit(&__stmt.value);
+                }
+            },
+            FieldKind::Region(false) => quote! {
+                for __blk in #field_ref.blocks.iter() {
+                    for __stmt in __blk.value.statements.iter() {
+                        visitor.visit(&__stmt.value);
+                    }
+                }
+            },
+            FieldKind::Region(true) => quote! {
+                for __blk in #field_ref.iter() {
+                    for __stmt in __blk.statements.iter() {
+                        visitor.visit(&__stmt.value);
+                    }
+                }
+            },
+            FieldKind::SSAValue
+            | FieldKind::ResultValue
+            | FieldKind::Successor
+            | FieldKind::Value(_) => TokenStream::new(),
+        }
+    }
+
+    /// The `VisitMutChildren::visit_children_mut` counterpart to
+    /// [`FieldKind::visit_expr`]: same recursion, through `&mut` bindings
+    /// and `visitor.visit_mut(...)`.
+    pub fn visit_mut_expr(&self, field_ref: &TokenStream) -> TokenStream {
+        match self {
+            FieldKind::Block(_) => quote! {
+                for __stmt in #field_ref.value.statements.iter_mut() {
+                    visitor.visit_mut(&mut __stmt.value);
+                }
+            },
+            FieldKind::Region(false) => quote! {
+                for __blk in #field_ref.blocks.iter_mut() {
+                    for __stmt in __blk.value.statements.iter_mut() {
+                        visitor.visit_mut(&mut __stmt.value);
+                    }
+                }
+            },
+            FieldKind::Region(true) => quote! {
+                for __blk in #field_ref.iter_mut() {
+                    for __stmt in __blk.statements.iter_mut() {
+                        visitor.visit_mut(&mut __stmt.value);
+                    }
+                }
+            },
+            FieldKind::SSAValue
+            | FieldKind::ResultValue
+            | FieldKind::Successor
+            | FieldKind::Value(_) => TokenStream::new(),
+        }
+    }
+
+    /// The `FoldChildren::fold_children` counterpart to
+    /// [`FieldKind::visit_expr`]: rebuilds each nested statement's AST node
+    /// through `visitor.fold(...)` in place, owning `field_ref` outright
+    /// (from a by-value match binding) rather than borrowing it.
+    ///
+    /// `crate_path` is needed to name the `kirin_chumsky` crate's `Spanned`
+    /// wrapper when rebuilding a statement list.
+    pub fn fold_expr(&self, crate_path: &syn::Path, field_ref: &TokenStream) -> TokenStream {
+        let fold_stmt = |stmts: TokenStream| {
+            quote! {
+                #stmts.into_iter()
+                    .map(|__stmt| #crate_path::Spanned {
+                        value: visitor.fold(__stmt.value),
+                        span: __stmt.span,
+                    })
+                    .collect()
+            }
+        };
+        match self {
+            FieldKind::Block(_) => {
+                let folded = fold_stmt(quote! { #field_ref.value.statements });
+                quote! {
+                    #field_ref.value.statements = #folded;
+                }
+            }
+            FieldKind::Region(false) => {
+                let folded = fold_stmt(quote! { __blk.value.statements });
+                quote! {
+                    #field_ref.blocks = #field_ref.blocks.into_iter().map(|mut __blk| {
+                        __blk.value.statements = #folded;
+                        __blk
+                    }).collect();
+                }
+            }
+            FieldKind::Region(true) => {
+                let folded = fold_stmt(quote! { __blk.statements });
+                quote! {
+                    #field_ref = #field_ref.into_iter().map(|mut __blk| {
+                        __blk.statements = #folded;
+                        __blk
+                    }).collect();
+                }
+            }
+            FieldKind::SSAValue
+            | FieldKind::ResultValue
+            | FieldKind::Successor
+            | FieldKind::Value(_) => TokenStream::new(),
+        }
+    }
+
+    /// Generates the boolean expression comparing one field occurrence
+    /// across two nodes for [`crate::generate::GenerateStructEq`], ignoring
+    /// every `Spanned` span along the way. `a`/`b` must already be
+    /// references to the field's AST value (from pattern matching).
+    pub fn struct_eq_expr(&self, crate_path: &syn::Path, a: &TokenStream, b: &TokenStream) -> TokenStream {
+        match self {
+            FieldKind::SSAValue | FieldKind::ResultValue => quote! {
+                #a.name.value == #b.name.value
+                    && #a.ty.as_ref().map(|__ty| &__ty.value) == #b.ty.as_ref().map(|__ty| &__ty.value)
+            },
+            FieldKind::Successor => quote! {
+                #a.name.value == #b.name.value
+            },
+            FieldKind::Block(false) => {
+                let block_body = struct_eq_block_body(
+                    crate_path,
+                    &quote! { #a.value },
+                    &quote! { #b.value },
+                );
+                quote! { #block_body }
+            }
+            FieldKind::Block(true) => {
+                let stmts_body = struct_eq_bare_statements(
+                    crate_path,
+                    &quote! { #a.value.statements },
+                    &quote! { #b.value.statements },
+                );
+                quote! { #stmts_body }
+            }
+            FieldKind::Region(false) => {
+                let block_body =
+                    struct_eq_block_body(crate_path, &quote! { __a_blk.value }, &quote! { __b_blk.value });
+                quote! {
+                    #a.blocks.len() == #b.blocks.len()
+                        && #a.blocks.iter().zip(#b.blocks.iter()).all(|(__a_blk, __b_blk)| #block_body)
+                }
+            }
+            FieldKind::Region(true) => {
+                let stmts_body = struct_eq_bare_statements(
+                    crate_path,
+                    &quote! { __a_blk.statements },
+                    &quote! { __b_blk.statements },
+                );
+                quote! {
+                    #a.len() == #b.len()
+                        && #a.iter().zip(#b.iter()).all(|(__a_blk, __b_blk)| #stmts_body)
+                }
+            }
+            FieldKind::Value(_ty) => quote! {
+                #a == #b
+            },
+        }
+    }
+}
+
+/// Renders a `^label(arg: ty, ...) { stmt; stmt }` block body, used by both
+/// a labeled `Block` field and every block of a `Region` field. `block_expr`
+/// must evaluate to `&Block<...>` (already unwrapped from its `Spanned`).
+fn print_block_body(crate_path: &syn::Path, block_expr: &TokenStream) -> TokenStream {
+    let stmts_body = print_bare_statements(crate_path, &quote! { #block_expr.statements });
+    quote! {
+        out.push('^');
+        out.push_str(#block_expr.header.value.label.name.value);
+        out.push('(');
+        for (__i, __arg) in #block_expr.header.value.arguments.iter().enumerate() {
+            if __i > 0 {
+                out.push_str(", ");
+            }
+            out.push('%');
+            out.push_str(__arg.value.name.value);
+            out.push_str(": ");
+            out.push_str(&__arg.value.ty.to_string());
+        }
+        out.push_str(") {");
+        #stmts_body
+        out.push_str(" }");
+    }
+}
+
+/// Renders a `Vec<Spanned<StmtOutput>>` statement list as `" stmt; stmt;"`,
+/// recursing into each statement's own `WithPrinter` impl.
+fn print_bare_statements(crate_path: &syn::Path, stmts_expr: &TokenStream) -> TokenStream {
+    quote! {
+        for __stmt in #stmts_expr.iter() {
+            out.push(' ');
+            #crate_path::WithPrinter::print_into(&__stmt.value, out);
+            out.push(';');
+        }
+    }
+}
+
+/// Compares two `^label(arg: ty, ...) { stmt; stmt }` block bodies for
+/// structural equality, ignoring every `Spanned` span: the label name, the
+/// argument names/types, and the statement list (via
+/// [`struct_eq_bare_statements`]). `a_expr`/`b_expr` must each evaluate to
+/// `&Block<...>` (already unwrapped from its `Spanned`).
+fn struct_eq_block_body(crate_path: &syn::Path, a_expr: &TokenStream, b_expr: &TokenStream) -> TokenStream {
+    let stmts_eq = struct_eq_bare_statements(
+        crate_path,
+        &quote! { #a_expr.statements },
+        &quote! { #b_expr.statements },
+    );
+    quote! {
+        {
+            #a_expr.header.value.label.name.value == #b_expr.header.value.label.name.value
+                && #a_expr.header.value.arguments.len() == #b_expr.header.value.arguments.len()
+                && #a_expr.header.value.arguments.iter().zip(#b_expr.header.value.arguments.iter()).all(
+                    |(__a_arg, __b_arg)| {
+                        __a_arg.value.name.value == __b_arg.value.name.value
+                            && __a_arg.value.ty.value == __b_arg.value.ty.value
+                    },
+                )
+                && #stmts_eq
+        }
+    }
+}
+
+/// Compares two `Vec<Spanned<StmtOutput>>` statement lists for structural
+/// equality, recursing into each pair's own `StructEq` impl.
+fn struct_eq_bare_statements(crate_path: &syn::Path, a_expr: &TokenStream, b_expr: &TokenStream) -> TokenStream {
+    quote! {
+        #a_expr.len() == #b_expr.len()
+            && #a_expr.iter().zip(#b_expr.iter()).all(|(__a_stmt, __b_stmt)| {
+                #crate_path::StructEq::struct_eq(&__a_stmt.value, &__b_stmt.value)
+            })
+    }
 }
 
 /// Collected field information used during code generation.
@@ -273,6 +668,19 @@ pub struct CollectedField {
     pub kind: FieldKind,
     /// The default value if specified via `#[kirin(default)]` or `#[kirin(default = ...)]`
     pub default: Option<DefaultValue>,
+    /// Custom parser override from `#[chumsky(parse_with = ...)]`, if any.
+    /// Only ever set for `FieldKind::Value` fields, since that's the only
+    /// kind that carries `ChumskyFieldAttrs`.
+    pub parse_with: Option<syn::Path>,
+    /// Custom output mapping from `#[chumsky(map_with = ...)]`, applied on
+    /// top of `parse_with`. See [`ChumskyFieldAttrs::map_with`].
+    pub map_with: Option<syn::Path>,
+    /// Fresh lifetime parameters this field's type needed for its elided
+    /// borrows (`&str` becomes `&'field0 str`, etc.), in occurrence order.
+    /// Empty for fields with no borrows, and for fields that opted out via
+    /// `#[chumsky(borrow_src)]` (those borrow `'src` directly instead of
+    /// getting their own parameter). See [`crate::generics::collect_elided_lifetimes`].
+    pub field_lifetimes: Vec<syn::Lifetime>,
 }
 
 impl std::fmt::Display for CollectedField {
@@ -294,63 +702,96 @@ pub fn collect_fields(
 ) -> Vec<CollectedField> {
     let mut fields = Vec::new();
 
-    for arg in stmt.arguments.iter() {
+    for arg in stmt.arguments() {
         fields.push(CollectedField {
-            index: arg.field.index,
-            ident: arg.field.ident.clone(),
+            index: arg.index,
+            ident: arg.ident.clone(),
             collection: arg.collection.clone(),
             kind: FieldKind::SSAValue,
             default: None, // SSAValue fields don't support defaults
+            parse_with: None,
+            map_with: None,
+            field_lifetimes: Vec::new(),
         });
     }
 
-    for res in stmt.results.iter() {
+    for res in stmt.results() {
         fields.push(CollectedField {
-            index: res.field.index,
-            ident: res.field.ident.clone(),
+            index: res.index,
+            ident: res.ident.clone(),
             collection: res.collection.clone(),
             kind: FieldKind::ResultValue,
             default: None, // ResultValue fields don't support defaults
+            parse_with: None,
+            map_with: None,
+            field_lifetimes: Vec::new(),
         });
     }
 
-    for block in stmt.blocks.iter() {
+    for block in stmt.blocks() {
         fields.push(CollectedField {
-            index: block.field.index,
-            ident: block.field.ident.clone(),
+            index: block.index,
+            ident: block.ident.clone(),
             collection: block.collection.clone(),
-            kind: FieldKind::Block,
+            kind: FieldKind::Block(block.is_bare()),
             default: None, // Block fields don't support defaults
+            parse_with: None,
+            map_with: None,
+            field_lifetimes: Vec::new(),
         });
     }
 
-    for succ in stmt.successors.iter() {
+    for succ in stmt.successors() {
         fields.push(CollectedField {
-            index: succ.field.index,
-            ident: succ.field.ident.clone(),
+            index: succ.index,
+            ident: succ.ident.clone(),
             collection: succ.collection.clone(),
             kind: FieldKind::Successor,
             default: None, // Successor fields don't support defaults
+            parse_with: None,
+            map_with: None,
+            field_lifetimes: Vec::new(),
         });
     }
 
-    for region in stmt.regions.iter() {
+    for region in stmt.regions() {
         fields.push(CollectedField {
-            index: region.field.index,
-            ident: region.field.ident.clone(),
+            index: region.index,
+            ident: region.ident.clone(),
             collection: region.collection.clone(),
-            kind: FieldKind::Region,
+            kind: FieldKind::Region(region.is_bare()),
             default: None, // Region fields don't support defaults
+            parse_with: None,
+            map_with: None,
+            field_lifetimes: Vec::new(),
         });
     }
 
-    for value in stmt.values.iter() {
+    for value in stmt.values() {
+        let extra = value
+            .extra()
+            .expect("Value-category field must carry ChumskyFieldAttrs");
+        let mut ty = value
+            .value_type()
+            .expect("Value-category field must have a value type")
+            .clone();
+        let field_lifetimes = if extra.borrow_src {
+            let src = syn::Lifetime::new("'src", proc_macro2::Span::call_site());
+            crate::generics::rewrite_elided_lifetimes_as(&mut ty, &src);
+            Vec::new()
+        } else {
+            crate::generics::collect_elided_lifetimes(&mut ty)
+        };
+
         fields.push(CollectedField {
-            index: value.field.index,
-            ident: value.field.ident.clone(),
+            index: value.index,
+            ident: value.ident.clone(),
             collection: Collection::Single,
-            kind: FieldKind::Value(value.ty.clone()),
-            default: value.default.clone(), // Compile-time values can have defaults
+            kind: FieldKind::Value(ty),
+            default: value.default_value().cloned(), // Compile-time values can have defaults
+            parse_with: extra.parse_with.clone(),
+            map_with: extra.map_with.clone(),
+            field_lifetimes,
         });
     }
 
@@ -359,6 +800,18 @@ pub fn collect_fields(
     fields
 }
 
+/// Collects every fresh lifetime introduced across `fields` by
+/// [`collect_elided_lifetimes`](crate::generics::collect_elided_lifetimes), in
+/// field order, for a caller that needs to feed them all into
+/// [`GenericsBuilder::with_field_lifetimes`](crate::generics::GenericsBuilder::with_field_lifetimes)
+/// at once.
+pub fn collect_all_field_lifetimes(fields: &[CollectedField]) -> Vec<syn::Lifetime> {
+    fields
+        .iter()
+        .flat_map(|field| field.field_lifetimes.iter().cloned())
+        .collect()
+}
+
 /// Collects Value field types that contain the given type parameters.
 ///
 /// For example, if a struct has `T: Clone` and a field `value: T`,
@@ -433,20 +886,41 @@ pub fn fields_in_format(
 ) -> HashSet<usize> {
     let map_by_ident = stmt.field_name_to_index();
     let mut indices = HashSet::new();
+    collect_field_indices(format.elements(), &map_by_ident, &mut indices);
+    indices
+}
 
-    for elem in format.elements() {
-        if let FormatElement::Field(name, _) = elem {
-            // Try to parse as index first, then look up by name
-            let index = name
-                .parse::<usize>()
-                .ok()
-                .or_else(|| map_by_ident.get(&name.to_string()).copied());
-            if let Some(idx) = index {
-                indices.insert(idx);
+/// Walks format elements collecting referenced field indices, recursing into
+/// [`FormatElement::OptionalGroup`] and [`FormatElement::Alternative`] so
+/// fields nested in a group or alternative branch are still counted as "in
+/// the format" (not defaulted away).
+fn collect_field_indices(
+    elements: &[FormatElement<'_>],
+    map_by_ident: &std::collections::HashMap<String, usize>,
+    indices: &mut HashSet<usize>,
+) {
+    for elem in elements {
+        match elem {
+            FormatElement::Field(name, _, _, _) => {
+                // Try to parse as index first, then look up by name
+                let index = name
+                    .parse::<usize>()
+                    .ok()
+                    .or_else(|| map_by_ident.get(&name.to_string()).copied());
+                if let Some(idx) = index {
+                    indices.insert(idx);
+                }
+            }
+            FormatElement::OptionalGroup(inner, _) => {
+                collect_field_indices(inner, map_by_ident, indices);
             }
+            FormatElement::Alternative(branches, _) => {
+                for branch in branches {
+                    collect_field_indices(branch, map_by_ident, indices);
+                }
+            }
+            FormatElement::Token(_, _) => {}
         }
     }
-
-    indices
 }
 
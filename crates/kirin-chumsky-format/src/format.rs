@@ -12,6 +12,51 @@
 //!
 //! Note: `}` characters don't need escaping since they're only special
 //! when closing an interpolation. Use `}` directly in the format string.
+//!
+//! There's no separate "literal token" escape for punctuation like `=` or
+//! `:` — they already lex and match as ordinary [`Token`] variants in
+//! `FormatElement::Token`, so they never need to be spelled differently from
+//! any other literal text in the format string.
+//!
+//! # Repetition and optionality
+//!
+//! A field backed by a `Vec` can carry a separator (and, optionally, a
+//! surrounding delimiter pair) so it round-trips a real list instead of a
+//! single occurrence:
+//! - `{args:sep(,)}` parses `args` as a comma-separated list
+//! - `{args:sep(,):delim([,])}` additionally requires the list to be
+//!   wrapped in `[` and `]`
+//!
+//! A field backed by an `Option` can instead use `{attr?}` to mark the
+//! occurrence as optional. See [`FieldRepeat`].
+//!
+//! A `Vec` field can also skip the separator and take `{args}*`/`{args}+`,
+//! for a run of back-to-back occurrences (`*` allows zero, `+` requires at
+//! least one); unlike `{args:sep(,)}`, these are spelled right after the
+//! closing `}`, the same way an optional group's `?` follows its `]` rather
+//! than living inside it. `{args},*` is shorthand for `{args:sep(,)}`.
+//!
+//! # Stacked options
+//!
+//! A single interpolation can request more than one [`FormatOption`] at once
+//! by chaining `:`-suffixes, e.g. `{res:name:type}` expands to the same two
+//! `FormatElement::Field` occurrences as writing `{res:name} {res:type}`
+//! separately, but sharing one span. Plain `{field}` (no suffix at all) is
+//! also accepted directly, since the lexer recognizes a bare `{identifier}`
+//! as a single [`Token::Quote`] rather than the `{`/identifier/`}` token
+//! triple the decorated forms produce.
+//!
+//! # Groups
+//!
+//! A run of elements can be wrapped in `[ ... ]?` to make the whole run
+//! optional, e.g. `"br %cond [if %then]? else %else"`. See
+//! [`FormatElement::OptionalGroup`].
+//!
+//! Two or more branches can instead be wrapped in `( ... )` with `|`
+//! separating them, e.g. `"mul (signed|unsigned) {lhs} {rhs}"`, to pick
+//! between keyword synonyms. Every branch must be literal tokens only (no
+//! field interpolations), since there's no AST slot to record which branch
+//! matched. See [`FormatElement::Alternative`].
 
 use chumsky::input::Stream;
 use chumsky::prelude::*;
@@ -30,10 +75,75 @@ pub struct Format<'src> {
 /// An element in a format string.
 #[derive(Debug, Clone)]
 pub enum FormatElement<'src> {
-    /// Literal tokens to match exactly.
-    Token(Vec<Token<'src>>),
-    /// A field interpolation like `{name}` or `{name:type}`.
-    Field(&'src str, FormatOption),
+    /// Literal tokens to match exactly, with the byte-offset span they
+    /// occupied in the format string.
+    Token(Vec<Token<'src>>, SimpleSpan),
+    /// A field interpolation like `{name}` or `{name:type}`, along with any
+    /// repetition/optionality syntax attached to it and the byte-offset span
+    /// of the `{...}` interpolation in the format string.
+    ///
+    /// The span lets callers (e.g. `build_field_occurrences`) point "unknown
+    /// field"/"invalid option" diagnostics at the exact interpolation rather
+    /// than at the whole statement.
+    Field(&'src str, FormatOption, FieldRepeat<'src>, SimpleSpan),
+    /// An optional group like `[{attr}]?`: the enclosed elements are parsed
+    /// (and printed) together or not at all. Every field inside the group
+    /// must map to an `Option<T>` AST field, the same target a bare `{field?}`
+    /// would use; the difference is a group can wrap more than one field (and
+    /// any literal tokens between them) behind a single presence check.
+    OptionalGroup(Vec<FormatElement<'src>>, SimpleSpan),
+    /// An alternative group like `(true|false)`: exactly one of the branches
+    /// must match. Unlike [`FormatElement::OptionalGroup`], a branch carries
+    /// no presence information of its own, so every branch is required to be
+    /// built from literal tokens only (no field interpolations) -- there's no
+    /// AST slot to record *which* branch matched. This covers picking between
+    /// keyword synonyms (e.g. `(signed|unsigned)`), not branches that bind
+    /// different fields.
+    Alternative(Vec<Vec<FormatElement<'src>>>, SimpleSpan),
+}
+
+impl<'src> FormatElement<'src> {
+    /// Returns the byte-offset span this element occupied in the format
+    /// string.
+    pub fn span(&self) -> SimpleSpan {
+        match self {
+            FormatElement::Token(_, span) => *span,
+            FormatElement::Field(_, _, _, span) => *span,
+            FormatElement::OptionalGroup(_, span) => *span,
+            FormatElement::Alternative(_, span) => *span,
+        }
+    }
+}
+
+/// Repetition/optionality syntax attached to a field interpolation.
+///
+/// This is orthogonal to [`FormatOption`]: it describes how many times the
+/// field occurs in the surface syntax, not which aspect (name/type/value) is
+/// being parsed.
+#[derive(Debug, Clone, Default)]
+pub enum FieldRepeat<'src> {
+    /// No repetition syntax; the field occurs exactly once.
+    #[default]
+    None,
+    /// `{field?}`, for an `Option` field: the occurrence may be absent.
+    Optional,
+    /// `{field:sep(tok)}`, optionally followed by `:delim(open,close)`, for
+    /// a `Vec` field parsed as a separated (and possibly delimited) list.
+    /// Also produced by the `{field},*` shorthand, which is sugar for
+    /// `{field:sep(,)}`.
+    Separated {
+        /// The token that separates successive elements.
+        separator: Token<'src>,
+        /// The surrounding `(open, close)` delimiter pair, if given.
+        delimiters: Option<(Token<'src>, Token<'src>)>,
+    },
+    /// `{field}*` or `{field}+`, for a `Vec` field parsed as a run of
+    /// back-to-back occurrences with no separator in between.
+    Repeated {
+        /// Whether at least one occurrence is required (`+`), as opposed to
+        /// zero being allowed (`*`).
+        at_least_one: bool,
+    },
 }
 
 /// Options for field interpolation.
@@ -53,16 +163,40 @@ impl Default for FormatOption {
     }
 }
 
-impl<'src> Format<'src> {
-    /// Creates a new format from parsed elements.
-    pub fn new(elements: Vec<FormatElement<'src>>) -> Self {
-        let mut fields = IndexMap::new();
-        for elem in &elements {
-            if let FormatElement::Field(name, _) = elem {
+/// What the `:`-suffixes after a field name introduce: either one or more
+/// stacked [`FormatOption`]s (`:name`, `:type`, or `:name:type`) or a single
+/// [`FieldRepeat`] (`:sep(...)`). The two are mutually exclusive.
+enum ColonSuffixes<'src> {
+    Options(Vec<FormatOption>),
+    Repeat(FieldRepeat<'src>),
+}
+
+/// Registers every field occurrence's first-seen order into `fields`,
+/// recursing into [`FormatElement::OptionalGroup`] so group-nested fields are
+/// indexed the same as top-level ones.
+fn register_fields<'src>(elements: &[FormatElement<'src>], fields: &mut IndexMap<&'src str, usize>) {
+    for elem in elements {
+        match elem {
+            FormatElement::Field(name, _, _, _) => {
                 let len = fields.len();
                 fields.entry(*name).or_insert(len);
             }
+            FormatElement::OptionalGroup(inner, _) => register_fields(inner, fields),
+            FormatElement::Alternative(branches, _) => {
+                for branch in branches {
+                    register_fields(branch, fields);
+                }
+            }
+            FormatElement::Token(_, _) => {}
         }
+    }
+}
+
+impl<'src> Format<'src> {
+    /// Creates a new format from parsed elements.
+    pub fn new(elements: Vec<FormatElement<'src>>) -> Self {
+        let mut fields = IndexMap::new();
+        register_fields(&elements, &mut fields);
         Self { elements, fields }
     }
 
@@ -77,11 +211,36 @@ impl<'src> Format<'src> {
             .get(name)
             .map(|idx| self.elements[*idx].clone())
             .and_then(|elem| match elem {
-                FormatElement::Field(_, opt) => Some(opt),
+                FormatElement::Field(_, opt, _, _) => Some(opt),
                 _ => None,
             })
     }
 
+    /// Returns the repetition/optionality syntax for a field by name.
+    pub fn get_field_repeat(&self, name: &str) -> Option<FieldRepeat<'src>> {
+        self.fields
+            .get(name)
+            .map(|idx| self.elements[*idx].clone())
+            .and_then(|elem| match elem {
+                FormatElement::Field(_, _, repeat, _) => Some(repeat),
+                _ => None,
+            })
+    }
+
+    /// Returns the byte-offset span of a field's interpolation by name.
+    pub fn get_field_span(&self, name: &str) -> Option<SimpleSpan> {
+        self.fields
+            .get(name)
+            .map(|idx| self.elements[*idx].span())
+    }
+
+    /// Returns the name of every field interpolation in the format string,
+    /// in first-seen order. Used to cross-check placeholders against the
+    /// struct/variant's actual fields.
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().copied()
+    }
+
     /// Returns all elements in the format string.
     pub fn elements(&self) -> &[FormatElement<'src>] {
         &self.elements
@@ -95,59 +254,237 @@ impl<'src> Format<'src> {
     /// Creates a parser for format strings.
     fn parser<'tokens, I>()
     -> impl Parser<'tokens, I, Format<'src>, extra::Err<Rich<'tokens, Token<'src>, SimpleSpan>>>
+    where
+        'src: 'tokens,
+        I: chumsky::input::ValueInput<'tokens, Token = Token<'src>, Span = SimpleSpan>,
+    {
+        recursive(|element| Self::element_parser(element))
+            .repeated()
+            .collect::<Vec<Vec<FormatElement>>>()
+            .map(|elements| Format::new(elements.into_iter().flatten().collect()))
+    }
+
+    /// A single format element: a literal token run, a field interpolation,
+    /// an optional group, or an alternative group. `element` is the same
+    /// parser passed back in via [`recursive`] so a group's (or branch's)
+    /// contents can recurse through this same grammar.
+    fn element_parser<'tokens, I>(
+        element: impl Parser<'tokens, I, Vec<FormatElement<'src>>, extra::Err<Rich<'tokens, Token<'src>, SimpleSpan>>>
+        + Clone
+        + 'tokens,
+    ) -> impl Parser<'tokens, I, Vec<FormatElement<'src>>, extra::Err<Rich<'tokens, Token<'src>, SimpleSpan>>>
     where
         'src: 'tokens,
         I: chumsky::input::ValueInput<'tokens, Token = Token<'src>, Span = SimpleSpan>,
     {
         // Parse escaped braces: {{ -> literal {, }} -> literal }
         // The lexer produces EscapedLBrace/EscapedRBrace tokens for {{ and }}
-        let escaped_lbrace =
-            just(Token::EscapedLBrace).to(FormatElement::Token(vec![Token::EscapedLBrace]));
+        let escaped_lbrace = just(Token::EscapedLBrace)
+            .map_with(|_, e| vec![FormatElement::Token(vec![Token::EscapedLBrace], e.span())]);
+
+        let escaped_rbrace = just(Token::EscapedRBrace)
+            .map_with(|_, e| vec![FormatElement::Token(vec![Token::EscapedRBrace], e.span())]);
+
+        // `:sep(<tok>)`, optionally followed by `:delim(<open>,<close>)`, for
+        // a `Vec` field parsed as a (possibly delimited) separated list.
+        let separated = just(Token::Identifier("sep"))
+            .ignore_then(just(Token::LParen))
+            .ignore_then(any())
+            .then_ignore(just(Token::RParen))
+            .then(
+                just(Token::Colon)
+                    .ignore_then(just(Token::Identifier("delim")))
+                    .ignore_then(just(Token::LParen))
+                    .ignore_then(any())
+                    .then_ignore(just(Token::Comma))
+                    .then(any())
+                    .then_ignore(just(Token::RParen))
+                    .or_not(),
+            )
+            .map(|(separator, delimiters)| FieldRepeat::Separated {
+                separator,
+                delimiters,
+            });
+
+        // A field may stack multiple `:name`/`:type` options in one
+        // interpolation, e.g. `{res:name:type}`, which expands to one
+        // `FormatElement::Field` occurrence per option (in the order
+        // written). This is orthogonal to `:sep(...)`, which instead
+        // attaches repetition syntax and cannot be stacked with `:name`/
+        // `:type`.
+        let name_or_type = just(Token::Colon).ignore_then(select! {
+            Token::Identifier("type") => FormatOption::Type,
+            Token::Identifier("name") => FormatOption::Name,
+        });
+
+        let colon_suffix = name_or_type
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(ColonSuffixes::Options)
+            .or(just(Token::Colon)
+                .ignore_then(separated)
+                .map(ColonSuffixes::Repeat));
+
+        // `?` marks an `Option` field, e.g. `{attr?}`.
+        let optional_suffix = just(Token::Question).to(());
 
-        let escaped_rbrace =
-            just(Token::EscapedRBrace).to(FormatElement::Token(vec![Token::EscapedRBrace]));
+        // Parse field interpolations like {name}, {name:type}, {name?},
+        // {name:name:type}, {name:sep(,)}, or {name:sep(,):delim([,])}.
+        //
+        // A bare `{name}` (no colon/`?` suffix) already lexes as a single
+        // `Token::Quote` token (see `kirin_lexer::Token::Quote`), since
+        // nothing follows the identifier before the closing brace; anything
+        // with extra syntax decomposes into explicit `{`/`}` tokens instead.
+        let bare_field = select! { Token::Quote(name) => name }
+            .map_with(|name, e| {
+                vec![FormatElement::Field(
+                    name,
+                    FormatOption::default(),
+                    FieldRepeat::None,
+                    e.span(),
+                )]
+            });
 
-        // Parse field interpolations like {name} or {name:type}
-        let interpolation = just(Token::LBrace)
+        let decorated_field = just(Token::LBrace)
             .ignore_then(
                 select! {
                     Token::Identifier(name) => name,
                     Token::Int(name) => name
                 }
-                .then(
-                    just(Token::Colon)
-                        .ignore_then(select! {
-                            Token::Identifier("type") => FormatOption::Type,
-                            Token::Identifier("name") => FormatOption::Name,
-                        })
-                        .or_not(),
-                ),
+                .then(colon_suffix.or_not())
+                .then(optional_suffix.or_not()),
             )
             .then_ignore(just(Token::RBrace))
-            .map(|(name, opt)| FormatElement::Field(name, opt.unwrap_or_default()));
+            .map_with(|((name, suffix), optional), e| {
+                let span = e.span();
+                let (options, repeat) = match suffix {
+                    Some(ColonSuffixes::Options(opts)) => (opts, FieldRepeat::None),
+                    Some(ColonSuffixes::Repeat(repeat)) => (Vec::new(), repeat),
+                    None => (Vec::new(), FieldRepeat::None),
+                };
+                let repeat = if optional.is_some() {
+                    FieldRepeat::Optional
+                } else {
+                    repeat
+                };
+                if options.is_empty() {
+                    vec![FormatElement::Field(
+                        name,
+                        FormatOption::default(),
+                        repeat,
+                        span,
+                    )]
+                } else {
+                    options
+                        .into_iter()
+                        .map(|opt| FormatElement::Field(name, opt, repeat.clone(), span))
+                        .collect()
+                }
+            });
+
+        // A trailing `*`/`+`/`,*` right after the interpolation's closing
+        // `}` overrides the field's repetition syntax to a variadic run:
+        // `*` allows zero occurrences, `+` requires at least one, and `,*`
+        // is sugar for the equivalent `:sep(,)` (comma-separated, zero or
+        // more). Unlike `?`, these are spelled outside the braces, the same
+        // way an optional group's own `?` follows its closing `]` rather
+        // than living inside it.
+        let comma_star = just(Token::Comma)
+            .ignore_then(just(Token::Star))
+            .to(FieldRepeat::Separated {
+                separator: Token::Comma,
+                delimiters: None,
+            });
+        let star = just(Token::Star).to(FieldRepeat::Repeated { at_least_one: false });
+        let plus = just(Token::Plus).to(FieldRepeat::Repeated { at_least_one: true });
+        let quantifier = comma_star.or(star).or(plus);
+
+        let interpolation = bare_field.or(decorated_field).then(quantifier.or_not()).map(
+            |(elements, quantifier)| match quantifier {
+                None => elements,
+                Some(repeat) => elements
+                    .into_iter()
+                    .map(|elem| match elem {
+                        FormatElement::Field(name, opt, _, span) => {
+                            FormatElement::Field(name, opt, repeat.clone(), span)
+                        }
+                        other => other,
+                    })
+                    .collect(),
+            },
+        );
 
-        // Parse literal tokens (anything that's not `{` or escaped braces)
+        // Parse literal tokens (anything that's not `{` or escaped braces).
+        // `[` and `(` are left unfiltered so a `[`/`(` that doesn't turn out
+        // to start a valid `[ ... ]?` group or `(a|b)` alternative (tried
+        // first, below) still matches here as a plain literal token, same as
+        // before groups existed. `]`, `)`, and `|` *are* excluded: without
+        // that, this repeated literal run would greedily swallow a group's
+        // closing `]` (or an alternative's `)`/branch-separating `|`) before
+        // `group`/`alternative`'s own closing/separator parsers ever got a
+        // chance to match them, since both recurse through this same element
+        // parser for their contents.
         // Note: Regular `}` is allowed in literal tokens since it's only special after `{`
         let other = any()
             .filter(|t: &Token| {
                 !matches!(
                     t,
-                    Token::LBrace | Token::EscapedLBrace | Token::EscapedRBrace
+                    Token::LBrace
+                        | Token::EscapedLBrace
+                        | Token::EscapedRBrace
+                        | Token::RBracket
+                        | Token::RParen
+                        | Token::Pipe
                 )
             })
             .repeated()
             .at_least(1)
             .collect()
-            .map(FormatElement::Token);
+            .map_with(|tokens, e| vec![FormatElement::Token(tokens, e.span())]);
 
-        // Order matters: try escaped braces first, then interpolation, then other
+        // `[ ... ]?` marks the enclosed elements as an optional group: they
+        // parse/print together or not at all, threading into `Option<T>`
+        // fields the same way `{field?}` does. Recurses through `element` so
+        // a group can contain any mix of literal tokens and fields.
+        let group = just(Token::LBracket)
+            .ignore_then(element.clone().repeated().collect::<Vec<Vec<FormatElement>>>())
+            .then_ignore(just(Token::RBracket))
+            .then_ignore(just(Token::Question))
+            .map_with(|elements, e| {
+                vec![FormatElement::OptionalGroup(
+                    elements.into_iter().flatten().collect(),
+                    e.span(),
+                )]
+            });
+
+        // `(a|b|...)` marks its branches as an alternative group: exactly one
+        // branch is matched. Each branch recurses through `element` the same
+        // way a group's contents do, so a branch can mix literal tokens
+        // freely (fields are rejected later, in `collect_field_occurrences`,
+        // since there's no AST slot to record which branch matched).
+        let branch = element.repeated().collect::<Vec<Vec<FormatElement>>>();
+        let alternative = just(Token::LParen)
+            .ignore_then(branch.separated_by(just(Token::Pipe)).at_least(2).collect::<Vec<_>>())
+            .then_ignore(just(Token::RParen))
+            .map_with(|branches, e| {
+                vec![FormatElement::Alternative(
+                    branches
+                        .into_iter()
+                        .map(|b| b.into_iter().flatten().collect())
+                        .collect(),
+                    e.span(),
+                )]
+            });
+
+        // Order matters: try escaped braces first, then interpolation, then
+        // groups, then other.
         escaped_lbrace
             .or(escaped_rbrace)
             .or(interpolation)
+            .or(group)
+            .or(alternative)
             .or(other)
-            .repeated()
-            .collect()
-            .map(Format::new)
     }
 
     /// Parses a format string.
@@ -224,4 +561,95 @@ mod tests {
 
         insta::assert_debug_snapshot!(format);
     }
+
+    #[test]
+    fn test_format_parser_bare_field() {
+        // A plain `{field}` interpolation (no `:`/`?` suffix) lexes as a
+        // single `Token::Quote` rather than `{`/identifier/`}`, so it needs
+        // its own parser branch; this confirms it's recognized as a field.
+        let input = "{lhs} + {rhs}";
+        let format = Format::parse(input, None).expect("Failed to parse format");
+
+        assert_eq!(format.get_field_index("lhs"), Some(0));
+        assert_eq!(format.get_field_index("rhs"), Some(1));
+    }
+
+    #[test]
+    fn test_format_parser_stacked_options() {
+        // `{res:name:type}` should expand to two `Field` occurrences that
+        // share a span, same as writing `{res:name} {res:type}` separately.
+        let input = "{res:name:type} = neg {arg}";
+        let format = Format::parse(input, None).expect("Failed to parse format");
+
+        let field_occurrences: Vec<_> = format
+            .elements()
+            .iter()
+            .filter(|e| matches!(e, FormatElement::Field(name, _, _, _) if *name == "res"))
+            .collect();
+        assert_eq!(field_occurrences.len(), 2);
+        assert_eq!(field_occurrences[0].span(), field_occurrences[1].span());
+    }
+
+    #[test]
+    fn test_format_parser_optional_group() {
+        // `[ ... ]?` wraps a field as a single optional group.
+        let input = "{addr} [{align}]?";
+        let format = Format::parse(input, None).expect("Failed to parse format");
+
+        assert!(matches!(
+            format.elements().last(),
+            Some(FormatElement::OptionalGroup(_, _))
+        ));
+        // Fields nested in the group are still indexed like top-level fields.
+        assert_eq!(format.get_field_index("addr"), Some(0));
+        assert_eq!(format.get_field_index("align"), Some(1));
+    }
+
+    #[test]
+    fn test_format_parser_alternative_group() {
+        // `(a|b)` picks between two literal-token branches.
+        let input = "mul (signed|unsigned) {lhs} {rhs}";
+        let format = Format::parse(input, None).expect("Failed to parse format");
+
+        let alternatives: Vec<_> = format
+            .elements()
+            .iter()
+            .filter(|e| matches!(e, FormatElement::Alternative(_, _)))
+            .collect();
+        assert_eq!(alternatives.len(), 1);
+        match alternatives[0] {
+            FormatElement::Alternative(branches, _) => assert_eq!(branches.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_format_parser_variadic_quantifiers() {
+        // `{field}*`, `{field}+`, and `{field},*` attach repetition syntax
+        // after the closing `}`, the same way a group's `?` follows its `]`.
+        let input = "call {callee} {args}* {more}+ {trailing},*";
+        let format = Format::parse(input, None).expect("Failed to parse format");
+
+        let repeats: Vec<_> = format
+            .elements()
+            .iter()
+            .filter_map(|e| match e {
+                FormatElement::Field(name, _, repeat, _) => Some((*name, repeat.clone())),
+                _ => None,
+            })
+            .collect();
+
+        assert!(matches!(
+            repeats.iter().find(|(n, _)| *n == "args").unwrap().1,
+            FieldRepeat::Repeated { at_least_one: false }
+        ));
+        assert!(matches!(
+            repeats.iter().find(|(n, _)| *n == "more").unwrap().1,
+            FieldRepeat::Repeated { at_least_one: true }
+        ));
+        assert!(matches!(
+            &repeats.iter().find(|(n, _)| *n == "trailing").unwrap().1,
+            FieldRepeat::Separated { separator: Token::Comma, delimiters: None }
+        ));
+    }
 }
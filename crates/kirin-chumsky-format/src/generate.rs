@@ -1,15 +1,21 @@
 //! Code generation for chumsky derive macros.
 
 mod ast;
+mod ast_printer;
 mod bounds;
+mod ctxt;
 mod emit_ir;
 mod parser;
 mod pretty_print;
+mod struct_eq;
+mod treesitter;
+mod visitor;
 
 #[cfg(test)]
 mod tests;
 
 pub(crate) use bounds::BoundsBuilder;
+pub(crate) use ctxt::Ctxt;
 
 use std::collections::HashSet;
 
@@ -24,9 +30,13 @@ use crate::field_kind::{ValueTypeScanner, collect_fields, fields_in_format};
 use crate::format::Format;
 
 pub use self::ast::GenerateAST;
+pub use self::ast_printer::GenerateAstPrinter;
 pub use self::emit_ir::GenerateEmitIR;
 pub use self::parser::GenerateHasDialectParser;
 pub use self::pretty_print::GeneratePrettyPrint;
+pub use self::struct_eq::GenerateStructEq;
+pub use self::treesitter::GenerateTreeSitterGrammar;
+pub use self::visitor::GenerateVisitor;
 
 /// Shared configuration for code generators.
 ///
@@ -165,6 +175,24 @@ where
         .or(ir_input.extra_attrs.global_format())
 }
 
+/// Gets every accepted format string for a statement: the primary format
+/// from [`format_for_statement`], followed by its
+/// `#[chumsky(format_alias = ...)]` aliases in declaration order. Empty if
+/// the statement has no format at all (e.g. a wrapper variant).
+pub(crate) fn formats_for_statement<L>(
+    ir_input: &kirin_derive_core::ir::Input<L>,
+    stmt: &kirin_derive_core::ir::Statement<L>,
+) -> Vec<String>
+where
+    L: Layout<ExtraStatementAttrs = ChumskyStatementAttrs>,
+    L::ExtraGlobalAttrs: HasGlobalFormat,
+{
+    format_for_statement(ir_input, stmt)
+        .into_iter()
+        .chain(stmt.extra_attrs.format_aliases.iter().cloned())
+        .collect()
+}
+
 /// Trait for global attrs that may provide a fallback format string.
 pub(crate) trait HasGlobalFormat {
     fn global_format(&self) -> Option<String>;
@@ -182,21 +210,94 @@ impl HasGlobalFormat for crate::PrettyGlobalAttrs {
     }
 }
 
+/// Parses a dialect's `#[chumsky(bound = "...")]` override, if any, into the
+/// where-predicates it should use *instead of* the inferred
+/// `GenericsBuilder::field_bound_predicates` set.
+///
+/// Mirrors serde's `#[serde(bound = "...")]`: the string is parsed as a
+/// comma-separated predicate list, the same syntax that would follow a
+/// `where` keyword.
+pub(crate) fn bound_override_predicates<L>(
+    ir_input: &kirin_derive_core::ir::Input<L>,
+) -> Option<Vec<syn::WherePredicate>>
+where
+    L: Layout,
+    L::ExtraGlobalAttrs: HasGlobalBound,
+{
+    let bound = ir_input.extra_attrs.global_bound()?;
+    let where_clause: syn::WhereClause = syn::parse_str(&format!("where {bound}"))
+        .unwrap_or_else(|e| panic!("invalid `#[chumsky(bound = \"{bound}\")]`: {e}"));
+    Some(where_clause.predicates.into_iter().collect())
+}
+
+/// Trait for global attrs that may provide a bound override.
+pub(crate) trait HasGlobalBound {
+    fn global_bound(&self) -> Option<String>;
+}
+
+impl HasGlobalBound for crate::ChumskyGlobalAttrs {
+    fn global_bound(&self) -> Option<String> {
+        self.bound.clone()
+    }
+}
+
+impl HasGlobalBound for crate::PrettyGlobalAttrs {
+    fn global_bound(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Gets whether error-recovering parsing is enabled for a statement from a
+/// layout that uses `ChumskyStatementAttrs`.
+///
+/// Checks statement-level `#[chumsky(recover)]`, falling back to the
+/// dialect-level `#[chumsky(recover)]` default.
+pub(crate) fn recover_for_statement<L>(
+    ir_input: &kirin_derive_core::ir::Input<L>,
+    stmt: &kirin_derive_core::ir::Statement<L>,
+) -> bool
+where
+    L: Layout<ExtraStatementAttrs = ChumskyStatementAttrs>,
+    L::ExtraGlobalAttrs: HasGlobalRecover,
+{
+    stmt.extra_attrs.recover || ir_input.extra_attrs.global_recover()
+}
+
+/// Trait for global attrs that may provide a fallback recovery default.
+pub(crate) trait HasGlobalRecover {
+    fn global_recover(&self) -> bool;
+}
+
+impl HasGlobalRecover for crate::ChumskyGlobalAttrs {
+    fn global_recover(&self) -> bool {
+        self.recover
+    }
+}
+
 /// Gets the set of field indices that are in the format string.
 ///
-/// If there's no format string (e.g., wrapper variants), includes all fields.
+/// If there's no format string (e.g., wrapper variants), includes all
+/// fields. When the statement has alternative formats (see
+/// [`formats_for_statement`]), this is the *union* of fields mentioned
+/// across every alternative, since the AST needs to be able to store
+/// whichever occurrences the alternative that matched happened to parse.
 pub(crate) fn get_fields_in_format(
     ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
     stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
 ) -> HashSet<usize> {
-    let Some(format_str) = format_for_statement(ir_input, stmt) else {
+    let formats = formats_for_statement(ir_input, stmt);
+    if formats.is_empty() {
         return collect_fields(stmt).iter().map(|f| f.index).collect();
-    };
+    }
 
-    match Format::parse(&format_str, None) {
-        Ok(format) => fields_in_format(&format, stmt),
-        Err(_) => collect_fields(stmt).iter().map(|f| f.index).collect(),
+    let mut fields = HashSet::new();
+    for format_str in &formats {
+        match Format::parse(format_str, None) {
+            Ok(format) => fields.extend(fields_in_format(&format, stmt)),
+            Err(_) => return collect_fields(stmt).iter().map(|f| f.index).collect(),
+        }
     }
+    fields
 }
 
 /// Collects all Value field types that contain type parameters from all statements.
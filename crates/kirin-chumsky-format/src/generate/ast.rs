@@ -10,7 +10,7 @@ use kirin_derive_core::codegen::deduplicate_types;
 
 use super::{
     GeneratorConfig, collect_all_value_types_needing_bounds, filter_ast_fields,
-    get_fields_in_format,
+    get_fields_in_format, recover_for_statement,
 };
 
 /// Generator for AST type definitions.
@@ -714,7 +714,7 @@ impl GenerateAST {
         use kirin_derive_core::ir::VariantRef;
         let crate_path = &self.config.crate_path;
 
-        let variants: Vec<TokenStream> = data
+        let mut variants: Vec<TokenStream> = data
             .iter_variants()
             .map(|variant| match variant {
                 VariantRef::Wrapper { name, wrapper, .. } => {
@@ -740,6 +740,19 @@ impl GenerateAST {
             })
             .collect();
 
+        // If any variant opts into `#[chumsky(recover)]` (or the dialect does via
+        // the global default), add a synthetic `Error` variant the generated
+        // parser can produce instead of failing the whole parse. See
+        // `GenerateHasDialectParser::build_statement_parser`.
+        if data.variants.iter().any(|v| recover_for_statement(ir_input, v)) {
+            variants.push(quote! {
+                Error {
+                    span: #crate_path::chumsky::span::SimpleSpan,
+                    message: ::std::string::String,
+                }
+            });
+        }
+
         quote! { #(#variants,)* }
     }
 
@@ -817,7 +830,9 @@ impl GenerateAST {
         // Collect all variant names and their types for pattern matching.
         // For regular variants, we filter to only include fields that are in the AST
         // (i.e., fields in format string or fields without defaults).
-        let variant_arms_clone: Vec<TokenStream> = data
+        let has_recover = data.variants.iter().any(|v| recover_for_statement(ir_input, v));
+
+        let mut variant_arms_clone: Vec<TokenStream> = data
             .iter_variants()
             .map(|variant| match variant {
                 VariantRef::Wrapper { name, .. } => {
@@ -853,7 +868,13 @@ impl GenerateAST {
             })
             .collect();
 
-        let variant_arms_debug: Vec<TokenStream> = data
+        if has_recover {
+            variant_arms_clone.push(quote! {
+                #ast_name::Error { span, message } => #ast_name::Error { span: *span, message: message.clone() }
+            });
+        }
+
+        let mut variant_arms_debug: Vec<TokenStream> = data
             .iter_variants()
             .map(|variant| match variant {
                 VariantRef::Wrapper { name, .. } => {
@@ -897,7 +918,13 @@ impl GenerateAST {
             })
             .collect();
 
-        let variant_arms_eq: Vec<TokenStream> = data
+        if has_recover {
+            variant_arms_debug.push(quote! {
+                #ast_name::Error { span, message } => f.debug_struct("Error").field("span", span).field("message", message).finish()
+            });
+        }
+
+        let mut variant_arms_eq: Vec<TokenStream> = data
             .iter_variants()
             .map(|variant| match variant {
                 VariantRef::Wrapper { name, .. } => {
@@ -961,6 +988,12 @@ impl GenerateAST {
             })
             .collect();
 
+        if has_recover {
+            variant_arms_eq.push(quote! {
+                (#ast_name::Error { span: span_a, message: message_a }, #ast_name::Error { span: span_b, message: message_b }) => span_a == span_b && message_a == message_b
+            });
+        }
+
         // Generate additional bounds for traits
         // Clone and PartialEq bounds are needed for wrapper variants.
         // Debug does NOT need bounds because we print a placeholder for wrapper variants.
@@ -0,0 +1,383 @@
+//! Code generation for the `WithPrinter` derive macro.
+//!
+//! This generates an implementation of `kirin_chumsky::WithPrinter` for a
+//! dialect's generated `*AST` type, reusing the same segment list the
+//! parser derive already built from the `#[chumsky(format = "...")]`
+//! string and emitting it back out in order. Unlike [`super::GeneratePrettyPrint`],
+//! which prints the dialect IR type through a `kirin_prettyless::Document`,
+//! this targets the `*AST` type's own generics (the way [`super::GenerateEmitIR`]
+//! does) and writes directly into a `String` buffer, since printing an AST
+//! node back to source doesn't need the dialect's pretty-printing arena.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use indexmap::IndexMap;
+use kirin_derive_core::ir::fields::FieldInfo;
+
+use crate::ChumskyLayout;
+use crate::field_kind::{FieldKind, collect_fields};
+use crate::format::{FieldRepeat, Format, FormatElement, FormatOption};
+
+use super::pretty_print::{build_field_map, flatten_groups, token_text, tokens_to_string_with_spacing};
+use super::{GeneratorConfig, collect_all_value_types_needing_bounds, generate_enum_match};
+
+/// Generator for the `WithPrinter` trait implementation.
+pub struct GenerateAstPrinter {
+    config: GeneratorConfig,
+}
+
+impl GenerateAstPrinter {
+    /// Creates a new generator.
+    pub fn new(ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> Self {
+        Self {
+            config: GeneratorConfig::new(ir_input),
+        }
+    }
+
+    /// Generates `impl WithPrinter for <Dialect>AST`.
+    ///
+    /// For wrapper structs, no AST type of its own exists (the `HasParser`
+    /// impl forwards to the wrapped type's), so there's nothing to print.
+    pub fn generate(&self, ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> TokenStream {
+        if let kirin_derive_core::ir::Data::Struct(data) = &ir_input.data {
+            if data.0.wraps.is_some() {
+                return TokenStream::new();
+            }
+        }
+
+        let ast_name = syn::Ident::new(&format!("{}AST", ir_input.name), ir_input.name.span());
+        let ast_generics = super::build_ast_generics(&ir_input.generics, false);
+        let (impl_generics, _, _) = ast_generics.split_for_impl();
+        let ty_generics = self.build_ast_ty_generics(ir_input);
+        let crate_path = &self.config.crate_path;
+
+        let print_body = match &ir_input.data {
+            kirin_derive_core::ir::Data::Struct(s) => {
+                self.generate_struct_print(ir_input, &s.0, &ast_name, None)
+            }
+            kirin_derive_core::ir::Data::Enum(e) => self.generate_enum_print(ir_input, e, &ast_name),
+        };
+
+        let value_types = collect_all_value_types_needing_bounds(ir_input);
+        let has_parser_bounds: Vec<_> = value_types
+            .iter()
+            .map(|ty| {
+                quote! {
+                    #ty: #crate_path::HasParser<'tokens, 'src> + 'tokens,
+                    <#ty as #crate_path::HasParser<'tokens, 'src>>::Output: ::core::fmt::Display,
+                }
+            })
+            .collect();
+
+        quote! {
+            impl #impl_generics #crate_path::WithPrinter for #ast_name #ty_generics
+            where
+                TypeOutput: Clone + PartialEq + 'tokens + ::core::fmt::Display,
+                LanguageOutput: Clone + PartialEq + 'tokens + #crate_path::WithPrinter,
+                #(#has_parser_bounds,)*
+            {
+                fn print_into(&self, out: &mut ::std::string::String) {
+                    #print_body
+                }
+            }
+        }
+    }
+
+    /// Builds just the type generics for the AST type (without Language),
+    /// mirroring [`super::GenerateEmitIR::build_ast_ty_generics`].
+    fn build_ast_ty_generics(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+    ) -> TokenStream {
+        let type_params: Vec<TokenStream> = ir_input
+            .generics
+            .type_params()
+            .map(|p| {
+                let ident = &p.ident;
+                quote! { #ident }
+            })
+            .collect();
+
+        if type_params.is_empty() {
+            quote! { <'tokens, 'src, TypeOutput, LanguageOutput> }
+        } else {
+            quote! { <'tokens, 'src, #(#type_params,)* TypeOutput, LanguageOutput> }
+        }
+    }
+
+    fn generate_struct_print(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+    ) -> TokenStream {
+        let (pattern, print_stmts) =
+            self.build_print_components(ir_input, stmt, ast_name, variant_name);
+
+        quote! {
+            let #pattern = self;
+            #print_stmts
+        }
+    }
+
+    /// Builds the pattern and print statements for a statement, shared
+    /// between struct and variant print generation.
+    fn build_print_components(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+    ) -> (TokenStream, TokenStream) {
+        let format_str = super::format_for_statement(ir_input, stmt)
+            .expect("Statement must have format string");
+        let format = Format::parse(&format_str, None).expect("Format string should be valid");
+
+        // Unlike the dialect type `GeneratePrettyPrint` matches on, the AST
+        // type only has the fields `filter_ast_fields` kept (those in the
+        // format string, plus any without a default), in the same order
+        // `super::GenerateAST` laid out the struct/variant in. Build field
+        // vars over that filtered, correctly ordered set instead of
+        // `Statement::field_bindings`, which covers every original field.
+        let collected = collect_fields(stmt);
+        let fields_in_fmt = super::get_fields_in_format(ir_input, stmt);
+        let is_tuple = stmt.is_tuple_style();
+        let mut ast_fields = super::filter_ast_fields(&collected, &fields_in_fmt);
+        if is_tuple {
+            ast_fields.sort_by_key(|f| f.index);
+        }
+
+        let field_vars: Vec<syn::Ident> = ast_fields
+            .iter()
+            .map(|f| match &f.ident {
+                Some(ident) => syn::Ident::new(&format!("f_{ident}"), ident.span()),
+                None => syn::Ident::new(&format!("f{}", f.index), proc_macro2::Span::call_site()),
+            })
+            .collect();
+
+        let ast_fields_owned: Vec<_> = ast_fields.iter().map(|f| (*f).clone()).collect();
+        let field_map = build_field_map(&ast_fields_owned);
+
+        let print_stmts = self.generate_format_print(&format, &field_map, &field_vars);
+
+        let pattern = if ast_fields.is_empty() {
+            match variant_name {
+                Some(v) if is_tuple => quote! { #ast_name::#v(..) },
+                Some(v) => quote! { #ast_name::#v { .. } },
+                None if is_tuple => quote! { #ast_name(..) },
+                None => quote! { #ast_name { .. } },
+            }
+        } else if is_tuple {
+            match variant_name {
+                Some(v) => quote! { #ast_name::#v(#(#field_vars,)* ..) },
+                None => quote! { #ast_name(#(#field_vars,)* ..) },
+            }
+        } else {
+            let pat: Vec<_> = ast_fields
+                .iter()
+                .zip(&field_vars)
+                .map(|(f, v)| {
+                    let orig = f.ident.as_ref().unwrap();
+                    quote! { #orig: #v }
+                })
+                .collect();
+            match variant_name {
+                Some(v) => quote! { #ast_name::#v { #(#pat,)* .. } },
+                None => quote! { #ast_name { #(#pat,)* .. } },
+            }
+        };
+
+        (pattern, print_stmts)
+    }
+
+    /// Generates enum print code. Wrapper variants delegate to the wrapped
+    /// type's own `WithPrinter` impl.
+    fn generate_enum_print(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        data: &kirin_derive_core::ir::DataEnum<ChumskyLayout>,
+        ast_name: &syn::Ident,
+    ) -> TokenStream {
+        let crate_path = &self.config.crate_path;
+
+        let marker = quote! {
+            #ast_name::__Marker(_, unreachable) => match *unreachable {},
+        };
+
+        generate_enum_match(
+            ast_name,
+            data,
+            |_name, _wrapper| {
+                quote! { #crate_path::WithPrinter::print_into(inner, out) }
+            },
+            |name, variant| self.generate_variant_print(ir_input, variant, ast_name, name),
+            Some(marker),
+        )
+    }
+
+    /// Generates print code for a single enum variant.
+    fn generate_variant_print(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        variant: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        ast_name: &syn::Ident,
+        variant_name: &syn::Ident,
+    ) -> TokenStream {
+        let (pattern, print_stmts) =
+            self.build_print_components(ir_input, variant, ast_name, Some(variant_name));
+
+        quote! {
+            #pattern => {
+                #print_stmts
+            }
+        }
+    }
+
+    /// Generates the imperative print statements for a format string.
+    ///
+    /// A literal immediately followed by an optional `{field:type}`
+    /// occurrence (the type annotation on an `SSAValue`/`ResultValue`
+    /// whose `ty` may be `None`) is folded into that occurrence's `if let
+    /// Some(..)` guard, so the literal is suppressed along with the type
+    /// whenever the value carries no type — e.g. the `->` in `"{res:name}
+    /// = add {lhs} {rhs} -> {res:type}"` only prints when `res.ty.is_some()`.
+    fn generate_format_print(
+        &self,
+        format: &Format,
+        field_map: &IndexMap<String, (usize, &FieldInfo<ChumskyLayout>)>,
+        field_vars: &[syn::Ident],
+    ) -> TokenStream {
+        let crate_path = &self.config.crate_path;
+        let elements = flatten_groups(format.elements());
+
+        let mut stmts: Vec<TokenStream> = Vec::new();
+        let mut pending_literal: Option<String> = None;
+
+        for (i, elem) in elements.iter().enumerate() {
+            let is_first = i == 0;
+            let prev_is_field =
+                i > 0 && matches!(elements[i - 1], FormatElement::Field(_, _, _, _));
+            let next_is_field =
+                i + 1 < elements.len() && matches!(elements[i + 1], FormatElement::Field(_, _, _, _));
+
+            match elem {
+                FormatElement::Token(tokens, _) => {
+                    if let Some(text) = pending_literal.take() {
+                        stmts.push(quote! { out.push_str(#text); });
+                    }
+                    let text = tokens_to_string_with_spacing(tokens, prev_is_field, next_is_field);
+                    pending_literal = Some(text);
+                }
+                FormatElement::Field(name, opt, repeat, _) => {
+                    let name_str = name.to_string();
+                    let Some((idx, field)) = field_map.get(&name_str) else {
+                        continue;
+                    };
+                    let var = &field_vars[*idx];
+                    let var_ref = quote! { #var };
+                    let kind = FieldKind::from_field_info(field);
+
+                    let is_optional_type = matches!(opt, FormatOption::Type)
+                        && kind.supports_name_type_options()
+                        && matches!(repeat, FieldRepeat::None);
+
+                    let field_stmt = self.generate_field_print(&kind, crate_path, &var_ref, opt, repeat);
+                    let space_stmt = if !is_first && prev_is_field {
+                        quote! { out.push(' '); }
+                    } else {
+                        TokenStream::new()
+                    };
+
+                    if is_optional_type {
+                        let literal = pending_literal.take().map(|text| quote! { out.push_str(#text); });
+                        stmts.push(quote! {
+                            if #var_ref.ty.is_some() {
+                                #space_stmt
+                                #literal
+                                #field_stmt
+                            }
+                        });
+                    } else {
+                        if let Some(text) = pending_literal.take() {
+                            stmts.push(quote! { out.push_str(#text); });
+                        }
+                        stmts.push(quote! {
+                            #space_stmt
+                            #field_stmt
+                        });
+                    }
+                }
+                FormatElement::OptionalGroup(_, _) | FormatElement::Alternative(_, _) => {
+                    unreachable!("flatten_groups removes all OptionalGroup/Alternative elements")
+                }
+            }
+        }
+
+        if let Some(text) = pending_literal {
+            stmts.push(quote! { out.push_str(#text); });
+        }
+
+        quote! { #(#stmts)* }
+    }
+
+    /// Generates the print statement for one field occurrence, honoring the
+    /// repetition syntax it was parsed with, mirroring
+    /// [`super::GeneratePrettyPrint::generate_field_print`].
+    fn generate_field_print(
+        &self,
+        kind: &FieldKind,
+        crate_path: &syn::Path,
+        var_ref: &TokenStream,
+        opt: &FormatOption,
+        repeat: &FieldRepeat<'_>,
+    ) -> TokenStream {
+        match repeat {
+            FieldRepeat::None => kind.print_ast_expr(crate_path, var_ref, opt),
+            FieldRepeat::Optional => {
+                let inner = kind.print_ast_expr(crate_path, &quote! { __inner }, opt);
+                quote! {
+                    if let Some(__inner) = #var_ref {
+                        #inner
+                    }
+                }
+            }
+            FieldRepeat::Separated {
+                separator,
+                delimiters,
+            } => {
+                let sep_text = token_text(separator);
+                let item_stmt = kind.print_ast_expr(crate_path, &quote! { __item }, opt);
+                let list = quote! {
+                    for (__i, __item) in #var_ref.iter().enumerate() {
+                        if __i > 0 {
+                            out.push_str(#sep_text);
+                        }
+                        #item_stmt
+                    }
+                };
+                match delimiters {
+                    Some((open, close)) => {
+                        let open_text = token_text(open);
+                        let close_text = token_text(close);
+                        quote! {
+                            out.push_str(#open_text);
+                            #list
+                            out.push_str(#close_text);
+                        }
+                    }
+                    None => list,
+                }
+            }
+            FieldRepeat::Repeated { .. } => {
+                let item_stmt = kind.print_ast_expr(crate_path, &quote! { __item }, opt);
+                quote! {
+                    for __item in #var_ref.iter() {
+                        #item_stmt
+                    }
+                }
+            }
+        }
+    }
+}
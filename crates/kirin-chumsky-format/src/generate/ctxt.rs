@@ -0,0 +1,63 @@
+//! A diagnostic-collecting context for accumulating multiple `syn::Error`s.
+//!
+//! Modeled after `serde_derive_internals::Ctxt` and argh's `Errors`: rather
+//! than bailing out with `?` on the first problem found while generating a
+//! statement's parser, callers record every problem via [`Ctxt::error_at`]
+//! and call [`Ctxt::check`] once at the end to get back a single combined
+//! `syn::Error` (via `syn::Error::combine`) covering everything that went
+//! wrong. This lets a user with several mistakes in one format string see
+//! all of them instead of fixing one, recompiling, and finding the next.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+
+/// Accumulates `syn::Error`s recorded during validation.
+///
+/// Must be consumed with [`Ctxt::check`] before it goes out of scope; a
+/// `Ctxt` that's dropped without being checked panics, since that would
+/// otherwise silently discard any errors it collected.
+pub(crate) struct Ctxt {
+    errors: RefCell<Option<Vec<syn::Error>>>,
+}
+
+impl Ctxt {
+    /// Creates a new, empty context.
+    pub(crate) fn new() -> Self {
+        Ctxt {
+            errors: RefCell::new(Some(Vec::new())),
+        }
+    }
+
+    /// Records an error at the given span without stopping traversal.
+    pub(crate) fn error_at(&self, span: proc_macro2::Span, msg: impl Display) {
+        self.errors
+            .borrow_mut()
+            .as_mut()
+            .expect("Ctxt::error_at called after check()")
+            .push(syn::Error::new(span, msg.to_string()));
+    }
+
+    /// Consumes the context, returning `Ok(())` if no errors were recorded,
+    /// or a single `syn::Error` combining all of them otherwise.
+    pub(crate) fn check(self) -> syn::Result<()> {
+        let errors = self.errors.borrow_mut().take().unwrap();
+        let mut iter = errors.into_iter();
+        match iter.next() {
+            Some(mut combined) => {
+                for error in iter {
+                    combined.combine(error);
+                }
+                Err(combined)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Ctxt {
+    fn drop(&mut self) {
+        if !std::thread::panicking() && self.errors.borrow().is_some() {
+            panic!("Ctxt dropped without calling check() -- errors would have been lost");
+        }
+    }
+}
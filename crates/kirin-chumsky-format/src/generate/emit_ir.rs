@@ -15,7 +15,7 @@ use crate::field_kind::{FieldKind, collect_fields};
 
 use super::{
     BoundsBuilder, GeneratorConfig, collect_all_value_types_needing_bounds, filter_ast_fields,
-    generate_enum_match, get_fields_in_format,
+    generate_enum_match, get_fields_in_format, recover_for_statement,
 };
 
 /// Generator for the `EmitIR` trait implementation.
@@ -573,7 +573,21 @@ impl GenerateEmitIR {
         original_ty_generics: &syn::TypeGenerics<'_>,
         ast_name: &syn::Ident,
     ) -> TokenStream {
+        // Statements recovered from a parse error (see `#[chumsky(recover)]`)
+        // have no well-formed IR to emit; callers are expected to check for
+        // and report `Error` nodes as diagnostics before reaching this point.
+        let error_arm = if data.variants.iter().any(|v| recover_for_statement(ir_input, v)) {
+            quote! {
+                #ast_name::Error { message, .. } => {
+                    panic!("cannot emit IR for a recovered parse error: {}", message)
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
         let marker = quote! {
+            #error_arm
             #ast_name::__Marker(_, unreachable) => match *unreachable {}
         };
 
@@ -688,7 +702,7 @@ impl GenerateEmitIR {
                             let #emitted_var: #ir_path::ResultValue = #crate_path::EmitIR::emit(#var, ctx);
                         }
                     }
-                    FieldKind::Block => {
+                    FieldKind::Block(_) => {
                         quote! {
                             let #emitted_var: #ir_path::Block = #crate_path::EmitIR::emit(#var, ctx);
                         }
@@ -698,7 +712,7 @@ impl GenerateEmitIR {
                             let #emitted_var: #ir_path::Successor = #crate_path::EmitIR::emit(#var, ctx);
                         }
                     }
-                    FieldKind::Region => {
+                    FieldKind::Region(_) => {
                         quote! {
                             let #emitted_var: #ir_path::Region = #crate_path::EmitIR::emit(#var, ctx);
                         }
@@ -7,10 +7,13 @@ use quote::quote;
 
 use crate::ChumskyLayout;
 use crate::field_kind::{CollectedField, collect_fields};
-use crate::format::{Format, FormatElement, FormatOption};
+use crate::format::{FieldRepeat, Format, FormatElement, FormatOption};
 use crate::generics::GenericsBuilder;
 
-use super::{GeneratorConfig, collect_all_value_types_needing_bounds, format_for_statement};
+use super::{
+    Ctxt, GeneratorConfig, collect_all_value_types_needing_bounds, format_for_statement,
+    formats_for_statement, recover_for_statement,
+};
 
 /// Represents an occurrence of a field in the format string.
 #[derive(Debug)]
@@ -19,6 +22,9 @@ struct FieldOccurrence<'a> {
     field: &'a CollectedField,
     /// The format option for this occurrence.
     option: FormatOption,
+    /// Repetition/optionality syntax attached to this occurrence (e.g.
+    /// `{field:sep(,)}` or `{field?}`), if any.
+    repeat: FieldRepeat<'a>,
     /// The unique variable name for this occurrence.
     var_name: syn::Ident,
 }
@@ -87,6 +93,41 @@ impl GenerateHasDialectParser {
             (None, None) => None,
         };
 
+        // A `#[chumsky(bound = "...")]` override replaces the inferred
+        // predicates entirely, for the rare case where field-type inference
+        // picks the wrong bound (or misses one field types don't reveal).
+        if let Some(bound_predicates) = super::bound_override_predicates(ir_input) {
+            let where_clause = match combined_where {
+                Some(mut wc) => {
+                    wc.predicates.extend(bound_predicates);
+                    quote! { #wc }
+                }
+                None => quote! { where #(#bound_predicates),* },
+            };
+            let ast_type = self.build_ast_type_reference(ir_input, ast_name);
+            return quote! {
+                impl #impl_generics #crate_path::HasParser<'tokens, 'src> for #original_name #ty_generics
+                #where_clause
+                {
+                    type Output = #ast_type;
+
+                    fn parser<I>() -> #crate_path::BoxedParser<'tokens, 'src, I, Self::Output>
+                    where
+                        I: #crate_path::TokenInput<'tokens, 'src>,
+                    {
+                        use #crate_path::chumsky::prelude::*;
+                        #crate_path::chumsky::recursive::recursive(|language| {
+                            <#original_name #ty_generics as #crate_path::HasDialectParser<
+                                'tokens,
+                                'src,
+                                #original_name #ty_generics,
+                            >>::recursive_parser(language)
+                        }).boxed()
+                    }
+                }
+            };
+        }
+
         // Add the TypeLattice: HasParser bound needed for type annotations
         // This bound is required because HasDialectParser sets TypeAST = <TypeLattice as HasParser>::Output,
         // and parsers like ssa_value require TypeAST: HasParser
@@ -281,14 +322,49 @@ impl GenerateHasDialectParser {
         crate_path: &syn::Path,
     ) -> TokenStream {
         let ast_generics = self.config.build_ast_generics(ir_input);
-        match self.build_statement_parser(ir_input, stmt, ast_name, &ast_generics, None, crate_path)
-        {
+        // A lone struct statement is passed no variant, so
+        // `build_statement_parser` never wraps it for recovery, even with
+        // `#[chumsky(recover)]` set: only an enum AST has an `Error` variant.
+        match self.build_statement_parser(
+            ir_input,
+            stmt,
+            ast_name,
+            &ast_generics,
+            None,
+            crate_path,
+            &[],
+        ) {
             Ok(body) => body,
             Err(err) => err.to_compile_error(),
         }
     }
 
+    /// Returns the mnemonic keyword a statement's format begins with, when
+    /// the format starts with a literal `Identifier` token (the common case
+    /// for IR statements, e.g. `"add {lhs}, {rhs}"`).
+    ///
+    /// Used by `generate_enum_parser_body` to group variants by their
+    /// leading keyword.
+    fn leading_keyword<'a>(format: &Format<'a>) -> Option<&'a str> {
+        match format.elements().first()? {
+            FormatElement::Token(tokens, _) => match tokens.first()? {
+                kirin_lexer::Token::Identifier(name) => Some(*name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// Generates the enum parser body (without the impl wrapper).
+    ///
+    /// Variants whose format begins with a mnemonic keyword are grouped by
+    /// that keyword; each group is labelled with its keyword and the whole
+    /// choice with "expected one of {keywords}", so a parse failure names
+    /// the accepted keywords. The groups, and the variants inside each
+    /// group, are tried in order through `.or()`: the next token is not
+    /// looked up to pick one group. Variants without a usable leading
+    /// keyword (wrapper variants, or statements that start with a field)
+    /// are tried after all the groups, in declaration order.
     fn generate_enum_parser_body(
         &self,
         ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
@@ -297,29 +373,83 @@ impl GenerateHasDialectParser {
         crate_path: &syn::Path,
     ) -> TokenStream {
         let ast_generics = self.config.build_ast_generics(ir_input);
-        let mut variant_parsers = Vec::new();
-        for variant in &data.variants {
-            let parser = self.build_statement_parser(
+
+        // Every variant's leading keyword, gathered up front: a variant that
+        // opts into `#[chumsky(recover)]` needs to know about ALL of its
+        // siblings' mnemonics (not just its own) to build a synchronization
+        // token set to recover against.
+        let variant_keywords: Vec<Option<String>> = data
+            .variants
+            .iter()
+            .map(|variant| {
+                if variant.wraps.is_some() {
+                    None
+                } else {
+                    format_for_statement(ir_input, variant)
+                        .and_then(|fmt_str| Format::parse(&fmt_str, None).ok())
+                        .and_then(|format| Self::leading_keyword(&format).map(str::to_owned))
+                }
+            })
+            .collect();
+        let all_keywords: Vec<String> = variant_keywords.iter().flatten().cloned().collect();
+
+        let mut keyword_groups: Vec<(String, Vec<TokenStream>)> = Vec::new();
+        let mut fallback_parsers: Vec<TokenStream> = Vec::new();
+
+        for (variant, keyword) in data.variants.iter().zip(variant_keywords) {
+            let parser = match self.build_statement_parser(
                 ir_input,
                 variant,
                 ast_name,
                 &ast_generics,
                 Some(&variant.name),
                 crate_path,
-            );
-            match parser {
-                Ok(p) => variant_parsers.push(p),
-                Err(err) => variant_parsers.push(err.to_compile_error()),
+                &all_keywords,
+            ) {
+                Ok(p) => p,
+                Err(err) => {
+                    fallback_parsers.push(err.to_compile_error());
+                    continue;
+                }
+            };
+
+            match keyword {
+                Some(kw) => match keyword_groups.iter_mut().find(|(k, _)| *k == kw) {
+                    Some((_, parsers)) => parsers.push(parser),
+                    None => keyword_groups.push((kw, vec![parser])),
+                },
+                None => fallback_parsers.push(parser),
             }
         }
 
-        if variant_parsers.is_empty() {
-            quote! { #crate_path::chumsky::prelude::empty().map(|_: ()| unreachable!()) }
+        let mut groups: Vec<TokenStream> = keyword_groups
+            .iter()
+            .map(|(kw, parsers)| {
+                let group = parsers
+                    .iter()
+                    .cloned()
+                    .reduce(|acc, p| quote! { #acc.or(#p) })
+                    .expect("keyword group always has at least one parser");
+                quote! { (#group).labelled(#kw) }
+            })
+            .collect();
+        groups.extend(fallback_parsers);
+
+        if groups.is_empty() {
+            return quote! { #crate_path::chumsky::prelude::empty().map(|_: ()| unreachable!()) };
+        }
+
+        let combined = groups
+            .into_iter()
+            .reduce(|acc, parser| quote! { #acc.or(#parser) })
+            .unwrap();
+
+        if keyword_groups.is_empty() {
+            combined
         } else {
-            variant_parsers
-                .into_iter()
-                .reduce(|acc, parser| quote! { #acc.or(#parser) })
-                .unwrap()
+            let keywords: Vec<&str> = keyword_groups.iter().map(|(k, _)| k.as_str()).collect();
+            let expected_msg = format!("expected one of {}", keywords.join(", "));
+            quote! { (#combined).labelled(#expected_msg) }
         }
     }
 
@@ -402,6 +532,7 @@ impl GenerateHasDialectParser {
         ast_generics: &syn::Generics,
         variant: Option<&syn::Ident>,
         crate_path: &syn::Path,
+        sync_keywords: &[String],
     ) -> syn::Result<TokenStream> {
         // Build dialect type (e.g., `TestLang` or `MyDialect<T>`)
         let original_name = &ir_input.name;
@@ -421,164 +552,402 @@ impl GenerateHasDialectParser {
             );
         }
 
-        let format_str = format_for_statement(ir_input, stmt)
-            .ok_or_else(|| syn::Error::new(stmt.name.span(), "missing chumsky format attribute"))?;
-
-        let format = Format::parse(&format_str, None)?;
+        let formats = formats_for_statement(ir_input, stmt);
+        if formats.is_empty() {
+            return Err(syn::Error::new(
+                stmt.name.span(),
+                "missing chumsky format attribute",
+            ));
+        }
         let collected = collect_fields(stmt);
 
-        // Build field occurrences - each format field becomes an occurrence
-        let occurrences = self.build_field_occurrences(stmt, &format, &collected)?;
+        // Every validation problem found while building the occurrences,
+        // parser chain, and AST constructor is recorded on `ctxt` instead of
+        // bailing out immediately, so a statement with several mistakes
+        // reports all of them in one compile rather than one per recompile.
+        // Each alternative format is validated independently: a field that's
+        // absent from one alternative but present in another just needs
+        // `#[kirin(default)]` to cover the alternatives that omit it.
+        let ctxt = Ctxt::new();
 
         // Get the type lattice for type annotation parsers
         let type_lattice = &ir_input.attrs.type_lattice;
 
-        // Build parser chain properly handling the tuple nesting
-        let parser_expr = self.build_parser_chain_v2(
-            &format,
-            &occurrences,
-            crate_path,
-            &dialect_type,
-            ast_name,
-            type_lattice,
-        )?;
-
-        // Generate pattern matching for the parser output
-        let var_names: Vec<_> = occurrences.iter().map(|o| o.var_name.clone()).collect();
-        let pattern = self.build_pattern_v2(&var_names);
-        let constructor =
-            self.ast_constructor_v2(ast_name, variant, &collected, &occurrences, crate_path);
-
         // Use explicit return type annotation to pin the lifetimes correctly.
         // Without this, Rust would infer anonymous lifetimes '_ for the constructor.
         // Use generic Language since this is inside HasDialectParser::recursive_parser.
         let return_type = self.build_ast_type_reference_generic(ir_input, ast_name);
+
+        // Build one parser chain per accepted format string (the primary
+        // format plus any `#[chumsky(format_alias = ...)]`s), combined with
+        // `.or(...)` in declaration order -- the first alternative that
+        // matches wins, mirroring serde's `#[serde(alias = ...)]`.
+        let mut leading_tokens: Vec<String> = Vec::new();
+        let alternatives: Vec<TokenStream> = formats
+            .iter()
+            .map(|format_str| {
+                let format = match Format::parse(format_str, None) {
+                    Ok(format) => format,
+                    Err(err) => {
+                        ctxt.error_at(stmt.name.span(), err);
+                        return quote! {
+                            #crate_path::chumsky::prelude::empty().map(|_: ()| unreachable!())
+                        };
+                    }
+                };
+                if let Some(kw) = Self::leading_keyword(&format) {
+                    leading_tokens.push(kw.to_owned());
+                }
+
+                let occurrences = self.build_field_occurrences(&ctxt, stmt, &format, &collected);
+                let (parser_expr, var_fragments) = self.build_parser_chain_v2(
+                    &ctxt,
+                    &format,
+                    &occurrences,
+                    crate_path,
+                    &dialect_type,
+                    ast_name,
+                    type_lattice,
+                );
+                let pattern = self.build_pattern_v2(&var_fragments);
+                let constructor = self.ast_constructor_v2(
+                    &ctxt,
+                    ast_name,
+                    variant,
+                    &collected,
+                    &occurrences,
+                    crate_path,
+                );
+                quote! {
+                    #parser_expr.map(|#pattern| -> #return_type { #constructor })
+                }
+            })
+            .collect();
+
+        ctxt.check()?;
+
+        let parsed = alternatives
+            .into_iter()
+            .reduce(|acc, alt| quote! { (#acc).or(#alt) })
+            .expect("at least one format string, checked above");
+        // When there's more than one accepted spelling, label the combined
+        // parser with the leading keyword of each so a total parse failure
+        // reports "expected one of add, add_sugar, legacy_add" rather than
+        // the error of whichever alternative happened to be tried last.
+        let parsed = if leading_tokens.len() > 1 {
+            let expected_msg = format!("expected one of {}", leading_tokens.join(", "));
+            quote! { (#parsed).labelled(#expected_msg) }
+        } else {
+            parsed
+        };
+
+        // Variants opted into `#[chumsky(recover)]` (directly, or via the
+        // dialect-wide default) get wrapped so that a syntax error inside
+        // their body doesn't fail the whole `.or()` chain. On failure, tokens
+        // are skipped one at a time (guaranteeing progress) until a
+        // synchronization token is seen -- the leading keyword of some
+        // sibling statement, the statement terminator `;`, or end-of-input --
+        // and an `{AST}::Error` node is produced in place of the failed
+        // statement instead of aborting the whole parse. This mirrors
+        // rust-analyzer's `token_set`-based recovery points: the parser never
+        // skips past the chosen sync token, so the next statement is always
+        // re-attempted from a known-good boundary.
+        let body = if variant.is_some() && recover_for_statement(ir_input, stmt) {
+            let sync_keyword_arms: Vec<TokenStream> = sync_keywords
+                .iter()
+                .map(|kw| {
+                    quote! { .or(#crate_path::chumsky::prelude::just(Token::Identifier(#kw)).ignored()) }
+                })
+                .collect();
+            let sync_set = quote! {
+                #crate_path::chumsky::prelude::just(Token::Semicolon).ignored()
+                    #(#sync_keyword_arms)*
+                    .or(#crate_path::chumsky::prelude::end())
+            };
+            quote! {
+                #parsed.recover_with(#crate_path::chumsky::recovery::via_parser(
+                    #crate_path::chumsky::recovery::skip_then_retry_until(
+                        #crate_path::chumsky::prelude::any().ignored(),
+                        #sync_set,
+                    )
+                    .map_with(|_, extra| #return_type::Error {
+                        span: extra.span(),
+                        message: ::std::string::String::from("failed to parse statement"),
+                    }),
+                ))
+            }
+        } else {
+            parsed
+        };
+
         Ok(quote! {{
             use #crate_path::Token;
-            #parser_expr.map(|#pattern| -> #return_type { #constructor })
+            #body
         }})
     }
 
     /// Builds field occurrences from the format string.
-    /// Each field in the format string becomes an occurrence with a unique variable name.
+    ///
+    /// Each field in the format string becomes an occurrence with a unique
+    /// variable name. Every problem found (unknown field, mismatched
+    /// repetition syntax, misplaced `:name`/`:type`, duplicate default, ...)
+    /// is recorded on `ctxt` via [`Ctxt::error_at`] instead of aborting, so
+    /// the caller can report every mistake in the format string at once. An
+    /// occurrence that can't be resolved (unknown field/index) is skipped
+    /// rather than pushed, since [`Ctxt::check`] will turn the recorded
+    /// error(s) into a compile failure before the returned `Vec` is used for
+    /// anything besides counting.
     fn build_field_occurrences<'a>(
         &self,
+        ctxt: &Ctxt,
         stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
         format: &Format<'_>,
         collected: &'a [CollectedField],
-    ) -> syn::Result<Vec<FieldOccurrence<'a>>> {
+    ) -> Vec<FieldOccurrence<'a>> {
         let map_by_ident = stmt.field_name_to_index();
 
-        // Validate that no fields use Vec or Option collection types.
-        // Format strings don't support list/optional syntax, so these must be rejected.
+        let mut occurrences = Vec::new();
+
+        Self::collect_field_occurrences(
+            ctxt,
+            stmt,
+            format.elements(),
+            collected,
+            &map_by_ident,
+            false,
+            &mut occurrences,
+        );
+
+        // Validate that all fields are mentioned in the format string,
+        // unless they have a default value specified via #[kirin(default = ...)].
         for field in collected {
-            match field.collection {
-                kirin_derive_core::ir::fields::Collection::Vec => {
-                    return Err(syn::Error::new(
-                        stmt.name.span(),
-                        format!(
-                            "field '{}' has type Vec<...> which is not supported in format-derived parsers. \
-                             Format strings do not define list syntax (separators, delimiters). \
-                             Consider using a single-element field or implementing HasDialectParser manually.",
-                            field
-                        ),
-                    ));
-                }
-                kirin_derive_core::ir::fields::Collection::Option => {
-                    return Err(syn::Error::new(
+            let is_mentioned = occurrences.iter().any(|o| o.field.index == field.index);
+            if !is_mentioned && field.default.is_none() {
+                ctxt.error_at(
+                    stmt.name.span(),
+                    format!(
+                        "field '{}' is not mentioned in the format string. \
+                         All fields must appear in the format string unless they have a default value. \
+                         Use {{{}}} or {{{}:name}}/{{{}:type}} to include this field, \
+                         or add #[kirin(default)] or #[kirin(default = expr)] to provide a default value.",
+                        field, field, field, field
+                    ),
+                );
+            }
+        }
+
+        // Validate that SSAValue/ResultValue fields have at least {field} or {field:name}.
+        // These field types require a name to be parsed; only having {field:type} is insufficient.
+        for field in collected {
+            if field.kind.supports_name_type_options() {
+                let has_name_occurrence = occurrences.iter().any(|o| {
+                    o.field.index == field.index
+                        && matches!(o.option, FormatOption::Default | FormatOption::Name)
+                });
+                if !has_name_occurrence {
+                    ctxt.error_at(
                         stmt.name.span(),
                         format!(
-                            "field '{}' has type Option<...> which is not supported in format-derived parsers. \
-                             Format strings do not define optional syntax. \
-                             Consider using a required field or implementing HasDialectParser manually.",
-                            field
+                            "SSA/Result field '{}' must have {{{}}} or {{{}:name}} in the format string. \
+                             Using only {{{}:type}} is not sufficient because the name cannot be inferred.",
+                            field, field, field, field
                         ),
-                    ));
+                    );
                 }
-                kirin_derive_core::ir::fields::Collection::Single => {}
             }
         }
 
-        let mut occurrences = Vec::new();
-
-        for elem in format.elements() {
-            if let FormatElement::Field(name, opt) = elem {
-                let key = name.to_string();
-                let index = name
-                    .parse::<usize>()
-                    .ok()
-                    .or_else(|| map_by_ident.get(&key).copied())
-                    .ok_or_else(|| {
-                        syn::Error::new(
-                            stmt.name.span(),
-                            format!("unknown field '{}' in format string", name),
-                        )
-                    })?;
+        occurrences
+    }
 
-                let field = collected.iter().find(|f| f.index == index).ok_or_else(|| {
-                    syn::Error::new(stmt.name.span(), format!("field index {} not found", index))
-                })?;
+    /// Walks format elements building field occurrences, recursing into
+    /// [`FormatElement::OptionalGroup`]. `in_group` is threaded down so a
+    /// field nested inside a group can use plain `{field}` (no `?` suffix)
+    /// even when backed by an `Option<T>`: the group's own `.or_not()`
+    /// already supplies the optionality, the same way `{field?}` would for a
+    /// top-level occurrence.
+    fn collect_field_occurrences<'a>(
+        ctxt: &Ctxt,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        elements: &[FormatElement<'_>],
+        collected: &'a [CollectedField],
+        map_by_ident: &std::collections::HashMap<String, usize>,
+        in_group: bool,
+        occurrences: &mut Vec<FieldOccurrence<'a>>,
+    ) {
+        for elem in elements {
+            match elem {
+                FormatElement::OptionalGroup(inner, _) => {
+                    Self::collect_field_occurrences(
+                        ctxt,
+                        stmt,
+                        inner,
+                        collected,
+                        map_by_ident,
+                        true,
+                        occurrences,
+                    );
+                }
+                FormatElement::Alternative(branches, span) => {
+                    // Every branch is required to be literal-tokens-only:
+                    // there's no AST slot to record which branch matched, so
+                    // a field occurrence inside one would have no way to be
+                    // populated when a different branch is the one that
+                    // actually parses.
+                    for branch in branches {
+                        if branch
+                            .iter()
+                            .any(|e| matches!(e, FormatElement::Field(_, _, _, _)))
+                        {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!(
+                                    "alternative group `(a|b)` branches cannot bind fields; \
+                                     each branch must consist only of literal tokens, since \
+                                     there's no AST slot to record which branch matched (at {:?})",
+                                    span
+                                ),
+                            );
+                        }
+                    }
+                }
+                FormatElement::Token(_, _) => {}
+                FormatElement::Field(name, opt, repeat, span) => {
+                    let key = name.to_string();
+                    let index = match name
+                        .parse::<usize>()
+                        .ok()
+                        .or_else(|| map_by_ident.get(&key).copied())
+                    {
+                        Some(index) => index,
+                        None => {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!("unknown field '{}' in format string at {:?}", name, span),
+                            );
+                            continue;
+                        }
+                    };
 
-                // Validate that :name and :type options are only used on SSA/Result fields
-                if matches!(opt, FormatOption::Name | FormatOption::Type)
-                    && !field.kind.supports_name_type_options()
-                {
-                    let option_name = match opt {
-                        FormatOption::Name => ":name",
-                        FormatOption::Type => ":type",
-                        FormatOption::Default => unreachable!(),
+                    let field = match collected.iter().find(|f| f.index == index) {
+                        Some(field) => field,
+                        None => {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!("field index {} not found (at {:?})", index, span),
+                            );
+                            continue;
+                        }
                     };
-                    return Err(syn::Error::new(
-                        stmt.name.span(),
-                        format!(
-                            "format option '{}' cannot be used on {} field '{}'. \
-                             The :name and :type options are only valid for SSAValue and ResultValue fields.",
-                            option_name,
-                            field.kind.name(),
-                            field
-                        ),
-                    ));
-                }
 
-                // Check for duplicate default occurrences
-                if matches!(opt, FormatOption::Default) {
-                    let existing_default = occurrences.iter().any(|o: &FieldOccurrence<'_>| {
-                        o.field.index == index && matches!(o.option, FormatOption::Default)
-                    });
-                    if existing_default {
-                        return Err(syn::Error::new(
+                    // A field's collection type must agree with the repetition syntax
+                    // used on its occurrence: `Vec` fields need `:sep(...)` or a `*`/`+`/
+                    // `,*` quantifier, `Option` fields need `?`, and `Single` fields need
+                    // neither. A field inside an optional group is exempt from needing its
+                    // own `?`: the group's `.or_not()` already supplies the optionality.
+                    match (&field.collection, repeat) {
+                        (kirin_derive_core::ir::fields::Collection::Vec, FieldRepeat::Separated { .. }) => {}
+                        (kirin_derive_core::ir::fields::Collection::Vec, FieldRepeat::Repeated { .. }) => {}
+                        (kirin_derive_core::ir::fields::Collection::Vec, _) => {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!(
+                                    "field '{}' has type Vec<...> and must use {{{}:sep(tok)}} \
+                                     (optionally followed by `:delim(open,close)`), {{{}}}*, {{{}}}+, \
+                                     or {{{}}},* to specify how the list is parsed (at {:?}).",
+                                    field, field, field, field, field, span
+                                ),
+                            );
+                        }
+                        (kirin_derive_core::ir::fields::Collection::Option, FieldRepeat::Optional) => {}
+                        (kirin_derive_core::ir::fields::Collection::Option, FieldRepeat::None) if in_group => {}
+                        (kirin_derive_core::ir::fields::Collection::Option, _) => {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!(
+                                    "field '{}' has type Option<...> and must use {{{}?}} to mark \
+                                     the occurrence as optional (at {:?}).",
+                                    field, field, span
+                                ),
+                            );
+                        }
+                        (kirin_derive_core::ir::fields::Collection::Single, FieldRepeat::None) => {}
+                        (kirin_derive_core::ir::fields::Collection::Single, _) => {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!(
+                                    "field '{}' is not a Vec or Option, so it cannot use `:sep(...)`, \
+                                     `?`, or `*`/`+`/`,*` repetition syntax (at {:?}).",
+                                    field, span
+                                ),
+                            );
+                        }
+                    }
+
+                    // Validate that :name and :type options are only used on SSA/Result fields
+                    if matches!(opt, FormatOption::Name | FormatOption::Type)
+                        && !field.kind.supports_name_type_options()
+                    {
+                        let option_name = match opt {
+                            FormatOption::Name => ":name",
+                            FormatOption::Type => ":type",
+                            FormatOption::Default => unreachable!(),
+                        };
+                        ctxt.error_at(
                             stmt.name.span(),
                             format!(
-                                "field '{}' appears multiple times with default format option. \
-                                 Each field can only have one default occurrence. \
-                                 Use {{{}:name}} or {{{}:type}} for additional occurrences.",
-                                field, field, field
+                                "format option '{}' cannot be used on {} field '{}'. \
+                                 The :name and :type options are only valid for SSAValue and ResultValue fields \
+                                 (at {:?}).",
+                                option_name,
+                                field.kind.name(),
+                                field,
+                                span
                             ),
-                        ));
+                        );
                     }
-                }
 
-                // Generate unique variable name based on field and option
-                let var_name = match opt {
-                    FormatOption::Name => {
-                        syn::Ident::new(&format!("{}_name", field), proc_macro2::Span::call_site())
-                    }
-                    FormatOption::Type => {
-                        syn::Ident::new(&format!("{}_type", field), proc_macro2::Span::call_site())
-                    }
-                    FormatOption::Default => {
-                        // Since we reject duplicate defaults above, this is the only default occurrence
-                        field.ident.clone().unwrap_or_else(|| {
-                            syn::Ident::new(&format!("{}", field), proc_macro2::Span::call_site())
-                        })
+                    // Check for duplicate default occurrences
+                    if matches!(opt, FormatOption::Default) {
+                        let existing_default = occurrences.iter().any(|o: &FieldOccurrence<'_>| {
+                            o.field.index == index && matches!(o.option, FormatOption::Default)
+                        });
+                        if existing_default {
+                            ctxt.error_at(
+                                stmt.name.span(),
+                                format!(
+                                    "field '{}' appears multiple times with default format option. \
+                                     Each field can only have one default occurrence. \
+                                     Use {{{}:name}} or {{{}:type}} for additional occurrences (at {:?}).",
+                                    field, field, field, span
+                                ),
+                            );
+                        }
                     }
-                };
 
-                occurrences.push(FieldOccurrence {
-                    field,
-                    option: opt.clone(),
-                    var_name,
-                });
+                    // Generate unique variable name based on field and option
+                    let var_name = match opt {
+                        FormatOption::Name => {
+                            syn::Ident::new(&format!("{}_name", field), proc_macro2::Span::call_site())
+                        }
+                        FormatOption::Type => {
+                            syn::Ident::new(&format!("{}_type", field), proc_macro2::Span::call_site())
+                        }
+                        FormatOption::Default => {
+                            // Since we reject duplicate defaults above, this is the only default occurrence
+                            field.ident.clone().unwrap_or_else(|| {
+                                syn::Ident::new(&format!("{}", field), proc_macro2::Span::call_site())
+                            })
+                        }
+                    };
+
+                    occurrences.push(FieldOccurrence {
+                        field,
+                        option: opt.clone(),
+                        repeat: repeat.clone(),
+                        var_name,
+                    });
+                }
             }
         }
 
@@ -587,7 +956,7 @@ impl GenerateHasDialectParser {
         for field in collected {
             let is_mentioned = occurrences.iter().any(|o| o.field.index == field.index);
             if !is_mentioned && field.default.is_none() {
-                return Err(syn::Error::new(
+                ctxt.error_at(
                     stmt.name.span(),
                     format!(
                         "field '{}' is not mentioned in the format string. \
@@ -596,7 +965,7 @@ impl GenerateHasDialectParser {
                          or add #[kirin(default)] or #[kirin(default = expr)] to provide a default value.",
                         field, field, field, field
                     ),
-                ));
+                );
             }
         }
 
@@ -609,57 +978,192 @@ impl GenerateHasDialectParser {
                         && matches!(o.option, FormatOption::Default | FormatOption::Name)
                 });
                 if !has_name_occurrence {
-                    return Err(syn::Error::new(
+                    ctxt.error_at(
                         stmt.name.span(),
                         format!(
                             "SSA/Result field '{}' must have {{{}}} or {{{}:name}} in the format string. \
                              Using only {{{}:type}} is not sufficient because the name cannot be inferred.",
                             field, field, field, field
                         ),
-                    ));
+                    );
                 }
             }
         }
 
-        Ok(occurrences)
+        occurrences
     }
 
     fn build_parser_chain_v2(
         &self,
+        ctxt: &Ctxt,
         format: &Format<'_>,
         occurrences: &[FieldOccurrence<'_>],
         crate_path: &syn::Path,
         dialect_type: &TokenStream,
         ast_name: &syn::Ident,
         type_lattice: &syn::Path,
-    ) -> syn::Result<TokenStream> {
+    ) -> (TokenStream, Vec<TokenStream>) {
         let mut occurrence_iter = occurrences.iter();
+        self.build_chain_parts(
+            ctxt,
+            format.elements(),
+            &mut occurrence_iter,
+            crate_path,
+            dialect_type,
+            ast_name,
+            type_lattice,
+        )
+    }
+
+    /// Builds the parser chain for a sequence of format elements, along with
+    /// the pattern fragment each field/group contributes to the chain's
+    /// output tuple (a bare identifier for a plain field, or a parenthesized
+    /// tuple pattern for an optional group binding more than one field).
+    ///
+    /// Used both for a whole format string and, recursively, for the
+    /// contents of a `[ ... ]?` optional group or a `(a|b)` alternative
+    /// group's branch.
+    fn build_chain_parts(
+        &self,
+        ctxt: &Ctxt,
+        elements: &[FormatElement<'_>],
+        occurrence_iter: &mut std::slice::Iter<'_, FieldOccurrence<'_>>,
+        crate_path: &syn::Path,
+        dialect_type: &TokenStream,
+        ast_name: &syn::Ident,
+        type_lattice: &syn::Path,
+    ) -> (TokenStream, Vec<TokenStream>) {
         let mut parser_parts: Vec<ParserPart> = Vec::new();
+        let mut fragments: Vec<TokenStream> = Vec::new();
 
-        for elem in format.elements() {
+        for elem in elements {
             match elem {
-                FormatElement::Token(tokens) => {
+                FormatElement::Token(tokens, _) => {
                     parser_parts.push(ParserPart::Token(self.token_parser(tokens)));
                 }
-                FormatElement::Field(_, _) => {
-                    let occurrence = occurrence_iter
-                        .next()
-                        .expect("occurrence sequence mismatch");
-                    parser_parts.push(ParserPart::Field(self.field_parser_v2(
+                FormatElement::Field(_, _, _, span) => {
+                    // Normally there's exactly one occurrence per `Field`
+                    // element; a mismatch here means `build_field_occurrences`
+                    // already recorded a validation error on `ctxt` and
+                    // skipped producing an occurrence for it, so fall back
+                    // to a never-succeeding parser rather than panicking --
+                    // `Ctxt::check` turns the recorded error into a compile
+                    // failure before this fallback could ever run.
+                    match occurrence_iter.next() {
+                        Some(occurrence) => {
+                            let var = &occurrence.var_name;
+                            fragments.push(quote! { #var });
+                            parser_parts.push(ParserPart::Field(self.field_parser_v2(
+                                crate_path,
+                                occurrence.field,
+                                &occurrence.option,
+                                dialect_type,
+                                ast_name,
+                                type_lattice,
+                                &occurrence.repeat,
+                            )));
+                        }
+                        None => {
+                            ctxt.error_at(
+                                proc_macro2::Span::call_site(),
+                                format!("internal error: no occurrence recorded for field at {:?}", span),
+                            );
+                            fragments.push(quote! { _ });
+                            parser_parts.push(ParserPart::Field(
+                                quote! { #crate_path::chumsky::prelude::empty().map(|_: ()| unreachable!()) },
+                            ));
+                        }
+                    }
+                }
+                FormatElement::OptionalGroup(inner, span) => {
+                    let (inner_chain, inner_fragments) = self.build_chain_parts(
+                        ctxt,
+                        inner,
+                        occurrence_iter,
                         crate_path,
-                        occurrence.field,
-                        &occurrence.option,
                         dialect_type,
                         ast_name,
                         type_lattice,
-                    )));
+                    );
+                    if inner_fragments.is_empty() {
+                        ctxt.error_at(
+                            proc_macro2::Span::call_site(),
+                            format!(
+                                "optional group `[...]?` must contain at least one field, \
+                                 otherwise its presence/absence isn't observable (at {:?})",
+                                span
+                            ),
+                        );
+                    }
+
+                    // A group wraps its whole contents in `.or_not()`. With a
+                    // single field that already produces the `Option<T>` the
+                    // field's own type expects; with several, the tuple the
+                    // inner chain produces is unzipped into one `Option<_>`
+                    // per field so each still binds independently.
+                    let (group_chain, fragment) = if inner_fragments.len() <= 1 {
+                        let fragment = inner_fragments
+                            .into_iter()
+                            .next()
+                            .unwrap_or_else(|| quote! { _ });
+                        (quote! { (#inner_chain).or_not() }, fragment)
+                    } else {
+                        let inner_pattern = self.build_pattern_v2(&inner_fragments);
+                        let some_vals = inner_fragments
+                            .iter()
+                            .map(|f| quote! { ::core::option::Option::Some(#f) });
+                        let none_vals = inner_fragments.iter().map(|_| quote! { ::core::option::Option::None });
+                        let chain = quote! {
+                            (#inner_chain).or_not().map(|__group| match __group {
+                                ::core::option::Option::Some(#inner_pattern) => (#(#some_vals),*),
+                                ::core::option::Option::None => (#(#none_vals),*),
+                            })
+                        };
+                        (chain, quote! { (#(#inner_fragments),*) })
+                    };
+
+                    fragments.push(fragment);
+                    parser_parts.push(ParserPart::Field(group_chain));
+                }
+                FormatElement::Alternative(branches, _) => {
+                    // Validation in `collect_field_occurrences` already
+                    // rejected any branch with a field, so every branch's
+                    // sub-chain here binds nothing -- there's no fragment to
+                    // thread through, only a token-matching choice.
+                    let mut branch_chains = branches.iter().map(|branch| {
+                        let (chain, _fragments) = self.build_chain_parts(
+                            ctxt,
+                            branch,
+                            occurrence_iter,
+                            crate_path,
+                            dialect_type,
+                            ast_name,
+                            type_lattice,
+                        );
+                        chain
+                    });
+                    let first = branch_chains.next().unwrap_or_else(|| {
+                        quote! { #crate_path::chumsky::prelude::empty().ignored() }
+                    });
+                    let combined = branch_chains.fold(first, |acc, branch| {
+                        quote! { (#acc).or(#branch) }
+                    });
+                    parser_parts.push(ParserPart::Token(combined));
                 }
             }
         }
 
-        // Build the parser chain
+        (Self::combine_parser_parts(&parser_parts, crate_path), fragments)
+    }
+
+    /// Combines a flat sequence of token/field parser parts into a single
+    /// chumsky parser chain: literal tokens are consumed with
+    /// `.then_ignore`/`.ignore_then` and fields are threaded through with
+    /// `.then`, so the resulting parser's output is a left-nested tuple of
+    /// just the field values, in order.
+    fn combine_parser_parts(parser_parts: &[ParserPart], crate_path: &syn::Path) -> TokenStream {
         if parser_parts.is_empty() {
-            return Ok(quote! { #crate_path::chumsky::prelude::empty() });
+            return quote! { #crate_path::chumsky::prelude::empty() };
         }
 
         // Find the first field parser
@@ -719,10 +1223,10 @@ impl GenerateHasDialectParser {
             }
         }
 
-        Ok(parser_expr.unwrap_or_else(|| quote! { #crate_path::chumsky::prelude::empty() }))
+        parser_expr.unwrap_or_else(|| quote! { #crate_path::chumsky::prelude::empty() })
     }
 
-    fn build_pattern_v2(&self, var_names: &[syn::Ident]) -> TokenStream {
+    fn build_pattern_v2(&self, var_names: &[TokenStream]) -> TokenStream {
         if var_names.is_empty() {
             return quote! { _ };
         }
@@ -732,14 +1236,15 @@ impl GenerateHasDialectParser {
         let first = iter.next().unwrap();
         let mut pattern = quote! { #first };
 
-        for ident in iter {
-            pattern = quote! { (#pattern, #ident) };
+        for fragment in iter {
+            pattern = quote! { (#pattern, #fragment) };
         }
 
         pattern
     }
 
-    /// Generate field parser based on field kind and format option.
+    /// Generate field parser based on field kind, format option, and any
+    /// repetition/optionality syntax attached to the occurrence.
     fn field_parser_v2(
         &self,
         crate_path: &syn::Path,
@@ -748,16 +1253,60 @@ impl GenerateHasDialectParser {
         dialect_type: &TokenStream,
         ast_name: &syn::Ident,
         type_lattice: &syn::Path,
+        repeat: &FieldRepeat<'_>,
     ) -> TokenStream {
-        let base = field
-            .kind
-            .parser_expr(crate_path, opt, dialect_type, ast_name, type_lattice);
-        field.collection.wrap_parser(base)
+        let base = match &field.parse_with {
+            Some(path) => match &field.map_with {
+                Some(map_path) => quote! { #path().map(#map_path) },
+                None => quote! { #path() },
+            },
+            None => field
+                .kind
+                .parser_expr(crate_path, opt, dialect_type, ast_name, type_lattice),
+        };
+
+        match repeat {
+            FieldRepeat::None => field.collection.wrap_parser(base),
+            FieldRepeat::Optional => quote! { #base.or_not() },
+            FieldRepeat::Separated {
+                separator,
+                delimiters,
+            } => {
+                let sep = self.token_parser(std::slice::from_ref(separator));
+                match delimiters {
+                    Some((open, close)) => {
+                        // A trailing separator right before the closing
+                        // delimiter is unambiguous (e.g. `(a, b, c,)`), so
+                        // it's worth allowing; without delimiters there's no
+                        // fixed token marking "end of list" to allow it against.
+                        let list = quote! { #base.separated_by(#sep).allow_trailing().collect() };
+                        let open = self.token_parser(std::slice::from_ref(open));
+                        let close = self.token_parser(std::slice::from_ref(close));
+                        quote! { #list.delimited_by(#open, #close) }
+                    }
+                    None => quote! { #base.separated_by(#sep).collect() },
+                }
+            }
+            FieldRepeat::Repeated { at_least_one } => {
+                if *at_least_one {
+                    quote! { #base.repeated().at_least(1).collect() }
+                } else {
+                    quote! { #base.repeated().collect() }
+                }
+            }
+        }
     }
 
     /// Generate AST constructor that combines field occurrences.
+    ///
+    /// The only ways this can fail (a field with no occurrences, or an
+    /// SSA/Result field with only a `:type` occurrence) are invariants that
+    /// `build_field_occurrences` should already have rejected; if they
+    /// happen anyway the problem is recorded on `ctxt` rather than panicking,
+    /// so [`Ctxt::check`] still surfaces it as a normal compile error.
     fn ast_constructor_v2(
         &self,
+        ctxt: &Ctxt,
         ast_name: &syn::Ident,
         variant: Option<&syn::Ident>,
         collected: &[CollectedField],
@@ -787,7 +1336,7 @@ impl GenerateHasDialectParser {
         if has_named {
             let assigns = ast_fields.iter().map(|field| {
                 let name = field.ident.as_ref().unwrap();
-                let value = self.build_field_value(field, &field_occurrences, crate_path);
+                let value = self.build_field_value(ctxt, field, &field_occurrences, crate_path);
                 quote! { #name: #value }
             });
             match variant {
@@ -802,7 +1351,7 @@ impl GenerateHasDialectParser {
 
             let values = sorted_ast_fields
                 .iter()
-                .map(|field| self.build_field_value(field, &field_occurrences, crate_path));
+                .map(|field| self.build_field_value(ctxt, field, &field_occurrences, crate_path));
             match variant {
                 Some(v) => quote! { #ast_name::#v ( #(#values),* ) },
                 // For tuple structs (not enum variants), add PhantomData at the end
@@ -814,6 +1363,7 @@ impl GenerateHasDialectParser {
     /// Build the value expression for a field based on its occurrences.
     fn build_field_value(
         &self,
+        ctxt: &Ctxt,
         field: &CollectedField,
         field_occurrences: &HashMap<usize, Vec<&FieldOccurrence>>,
         crate_path: &syn::Path,
@@ -823,11 +1373,17 @@ impl GenerateHasDialectParser {
         match occs {
             None => {
                 // Field not in format string - this should be caught by validation in
-                // build_field_occurrences, so this case is unreachable in practice.
-                unreachable!(
-                    "field '{}' not in format string - this should have been caught earlier",
-                    field
-                )
+                // build_field_occurrences, so this is an internal-invariant violation
+                // rather than a user-facing mistake; record it on ctxt instead of
+                // panicking so it still surfaces as a normal compile error.
+                ctxt.error_at(
+                    proc_macro2::Span::call_site(),
+                    format!(
+                        "internal error: field '{}' not in format string - this should have been caught earlier",
+                        field
+                    ),
+                );
+                quote! { unreachable!() }
             }
             Some(occs) if occs.len() == 1 => {
                 // Single occurrence - use the variable directly or wrap if needed
@@ -842,10 +1398,14 @@ impl GenerateHasDialectParser {
                         .unwrap_or_else(|| quote! { #var }),
                     // :type only should have been caught by validation
                     FormatOption::Type if field.kind.supports_name_type_options() => {
-                        unreachable!(
-                            "field '{}' has only :type occurrence - this should have been caught by validation",
-                            field
-                        )
+                        ctxt.error_at(
+                            proc_macro2::Span::call_site(),
+                            format!(
+                                "internal error: field '{}' has only :type occurrence - this should have been caught by validation",
+                                field
+                            ),
+                        );
+                        quote! { unreachable!() }
                     }
                     // Default case - variable is already the correct type
                     _ => quote! { #var },
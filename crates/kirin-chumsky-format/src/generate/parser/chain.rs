@@ -1,4 +1,10 @@
 //! Parser chain building and AST constructor generation.
+//!
+//! Every fallible step here accumulates into a `Vec<syn::Error>` rather than
+//! bailing out (or panicking) on the first problem, mirroring
+//! `ValidationVisitor`'s accumulation in `crate::validation`: all of them are
+//! folded into one combined `syn::Error` via `syn::Error::combine` so a
+//! dialect author sees every mismatch in one compile.
 
 use std::collections::HashMap;
 
@@ -31,27 +37,62 @@ impl GenerateHasDialectParser {
     ) -> syn::Result<TokenStream> {
         let mut occurrence_iter = occurrences.iter();
         let mut parser_parts: Vec<ParserPart> = Vec::new();
+        let mut errors: Vec<syn::Error> = Vec::new();
 
         for elem in format.elements() {
             match elem {
-                FormatElement::Token(tokens) => {
+                FormatElement::Token(tokens, _) => {
                     parser_parts.push(ParserPart::Token(self.token_parser(tokens)));
                 }
-                FormatElement::Field(_, _) => {
-                    let occurrence = occurrence_iter
-                        .next()
-                        .expect("occurrence sequence mismatch");
-                    parser_parts.push(ParserPart::Field(self.field_parser(
-                        crate_path,
-                        occurrence.field,
-                        &occurrence.option,
-                        ast_name,
-                        type_lattice,
-                    )));
+                FormatElement::Field(_, _, _, _) => match occurrence_iter.next() {
+                    Some(occurrence) => {
+                        parser_parts.push(ParserPart::Field(self.field_parser(
+                            crate_path,
+                            occurrence.field,
+                            &occurrence.option,
+                            ast_name,
+                            type_lattice,
+                        )));
+                    }
+                    // The format string has more field placeholders than
+                    // `occurrences` has entries for. `ValidationVisitor`
+                    // builds `occurrences` from this same format string, so
+                    // this should never trigger in practice; reported as a
+                    // diagnostic rather than a panic in case it ever does.
+                    None => errors.push(syn::Error::new(
+                        ast_name.span(),
+                        "internal error: ran out of field occurrences before the format \
+                         string did; please report this as a kirin-chumsky-format bug",
+                    )),
+                },
+                // Optional `[ ... ]?` groups aren't supported by this parser
+                // chain builder; see `generate/parser.rs`'s `build_chain_parts`
+                // for the supported implementation.
+                FormatElement::OptionalGroup(_, _) => {
+                    errors.push(syn::Error::new(
+                        ast_name.span(),
+                        "optional format groups (`[ ... ]?`) are not supported here",
+                    ));
+                }
+                // Alternative `(a|b)` groups aren't supported by this parser
+                // chain builder either; see `generate/parser.rs`'s
+                // `build_chain_parts` for the supported implementation.
+                FormatElement::Alternative(_, _) => {
+                    errors.push(syn::Error::new(
+                        ast_name.span(),
+                        "alternative format groups (`(a|b)`) are not supported here",
+                    ));
                 }
             }
         }
 
+        if let Some(mut combined) = errors.into_iter().reduce(|mut acc, err| {
+            acc.combine(err);
+            acc
+        }) {
+            return Err(combined);
+        }
+
         // Build the parser chain
         if parser_parts.is_empty() {
             return Ok(quote! { #crate_path::chumsky::prelude::empty() });
@@ -140,7 +181,7 @@ impl GenerateHasDialectParser {
         collected: &[CollectedField],
         occurrences: &[FieldOccurrence<'_>],
         crate_path: &syn::Path,
-    ) -> TokenStream {
+    ) -> syn::Result<TokenStream> {
         // Group occurrences by field index
         let mut field_occurrences: HashMap<usize, Vec<&FieldOccurrence>> = HashMap::new();
         for occ in occurrences {
@@ -161,30 +202,45 @@ impl GenerateHasDialectParser {
         // Check if we have named fields
         let has_named = ast_fields.first().and_then(|f| f.ident.as_ref()).is_some();
 
+        // Resolve every field's value expression up front, accumulating every
+        // problem instead of bailing out at the first one.
+        let mut errors: Vec<syn::Error> = Vec::new();
+        let mut values: Vec<TokenStream> = Vec::with_capacity(ast_fields.len());
+        for field in &ast_fields {
+            match self.build_field_value(field, &field_occurrences, crate_path) {
+                Ok(value) => values.push(value),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if let Some(mut combined) = errors.into_iter().reduce(|mut acc, err| {
+            acc.combine(err);
+            acc
+        }) {
+            return Err(combined);
+        }
+
         if has_named {
-            let assigns = ast_fields.iter().map(|field| {
+            let assigns = ast_fields.iter().zip(&values).map(|(field, value)| {
                 let name = field.ident.as_ref().unwrap();
-                let value = self.build_field_value(field, &field_occurrences, crate_path);
                 quote! { #name: #value }
             });
-            match variant {
+            Ok(match variant {
                 Some(v) => quote! { #ast_name::#v { #(#assigns),* } },
                 // For named structs (not enum variants), add the _marker field
                 None => quote! { #ast_name { #(#assigns,)* _marker: ::core::marker::PhantomData } },
-            }
+            })
         } else {
             // For tuple fields, sort by original index to match AST struct definition order
-            let mut sorted_ast_fields: Vec<_> = ast_fields.clone();
-            sorted_ast_fields.sort_by_key(|f| f.index);
+            let mut sorted: Vec<_> = ast_fields.iter().zip(&values).collect();
+            sorted.sort_by_key(|(field, _)| field.index);
 
-            let values = sorted_ast_fields
-                .iter()
-                .map(|field| self.build_field_value(field, &field_occurrences, crate_path));
-            match variant {
+            let values = sorted.into_iter().map(|(_, value)| value);
+            Ok(match variant {
                 Some(v) => quote! { #ast_name::#v ( #(#values),* ) },
                 // For tuple structs (not enum variants), add PhantomData at the end
                 None => quote! { #ast_name ( #(#values,)* ::core::marker::PhantomData ) },
-            }
+            })
         }
     }
 
@@ -194,17 +250,28 @@ impl GenerateHasDialectParser {
         field: &CollectedField,
         field_occurrences: &HashMap<usize, Vec<&FieldOccurrence>>,
         crate_path: &syn::Path,
-    ) -> TokenStream {
+    ) -> syn::Result<TokenStream> {
+        let field_span = field
+            .ident
+            .as_ref()
+            .map(syn::Ident::span)
+            .unwrap_or_else(proc_macro2::Span::call_site);
         let occs = field_occurrences.get(&field.index);
 
         match occs {
             None => {
-                // Field not in format string - this should be caught by validation in
-                // validate_format, so this case is unreachable in practice.
-                unreachable!(
-                    "field '{}' not in format string - this should have been caught earlier",
-                    field
-                )
+                // Every field that reaches here either has an occurrence or
+                // a default value (`validate_format` rejects anything
+                // else), so this is an internal-error fallback rather than
+                // an expected user mistake.
+                Err(syn::Error::new(
+                    field_span,
+                    format!(
+                        "internal error: field '{}' has neither a format-string occurrence nor \
+                         a default value; please report this as a kirin-chumsky-format bug",
+                        field
+                    ),
+                ))
             }
             Some(occs) if occs.len() == 1 => {
                 // Single occurrence - use the variable directly or wrap if needed
@@ -213,19 +280,25 @@ impl GenerateHasDialectParser {
 
                 match &occ.option {
                     // SSA/Result with only :name - need to create value with None type
-                    FormatOption::Name => field
+                    FormatOption::Name => Ok(field
                         .kind
                         .construct_from_name_only(crate_path, var)
-                        .unwrap_or_else(|| quote! { #var }),
-                    // :type only should have been caught by validation
+                        .unwrap_or_else(|| quote! { #var })),
+                    // `ValidationVisitor` rejects a lone `:type` occurrence
+                    // for kinds that support `:name`/`:type`, so this is an
+                    // internal-error fallback rather than a user mistake.
                     FormatOption::Type if field.kind.supports_name_type_options() => {
-                        unreachable!(
-                            "field '{}' has only :type occurrence - this should have been caught by validation",
-                            field
-                        )
+                        Err(syn::Error::new(
+                            field_span,
+                            format!(
+                                "internal error: field '{}' has only a `:type` occurrence; \
+                                 please report this as a kirin-chumsky-format bug",
+                                field
+                            ),
+                        ))
                     }
                     // Default case - variable is already the correct type
-                    _ => quote! { #var },
+                    _ => Ok(quote! { #var }),
                 }
             }
             Some(occs) => {
@@ -234,7 +307,7 @@ impl GenerateHasDialectParser {
                 let name_occ = occs.iter().find(|o| matches!(o.option, FormatOption::Name));
                 let type_occ = occs.iter().find(|o| matches!(o.option, FormatOption::Type));
 
-                match (name_occ, type_occ) {
+                Ok(match (name_occ, type_occ) {
                     // SSA/Result with both :name and :type
                     (Some(name), Some(ty)) => field
                         .kind
@@ -248,7 +321,7 @@ impl GenerateHasDialectParser {
                         let var = &occs[0].var_name;
                         quote! { #var }
                     }
-                }
+                })
             }
         }
     }
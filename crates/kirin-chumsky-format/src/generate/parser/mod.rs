@@ -138,15 +138,26 @@ impl GenerateHasDialectParser {
         let type_lattice = &ir_input.attrs.type_lattice;
 
         // Build parser chain properly handling the tuple nesting
-        let parser_expr =
-            self.build_parser_chain(&format, &occurrences, crate_path, ast_name, type_lattice)?;
+        let parser_chain_result =
+            self.build_parser_chain(&format, &occurrences, crate_path, ast_name, type_lattice);
 
         // Generate pattern matching for the parser output
         let var_names: Vec<_> = occurrences.iter().map(|o| o.var_name.clone()).collect();
         let pattern = chain::build_pattern(&var_names);
-        let constructor =
+        let constructor_result =
             self.ast_constructor(ast_name, variant, &collected, &occurrences, crate_path);
 
+        // Don't bail out on the first of these two: report both the parser
+        // chain's and the constructor's problems together when both fail.
+        let (parser_expr, constructor) = match (parser_chain_result, constructor_result) {
+            (Ok(p), Ok(c)) => (p, c),
+            (Err(mut err), Err(other)) => {
+                err.combine(other);
+                return Err(err);
+            }
+            (Err(err), _) | (_, Err(err)) => return Err(err),
+        };
+
         // Use explicit return type annotation to pin the lifetimes correctly.
         // Without this, Rust would infer anonymous lifetimes '_ for the constructor.
         // Use generic Language since this is inside HasDialectParser::recursive_parser.
@@ -12,7 +12,7 @@ use crate::ChumskyLayout;
 use kirin_derive_core::ir::fields::FieldInfo;
 
 use crate::field_kind::{FieldKind, collect_fields};
-use crate::format::{Format, FormatElement};
+use crate::format::{FieldRepeat, Format, FormatElement};
 use kirin_lexer::Token;
 
 use super::{GeneratorConfig, generate_enum_match};
@@ -284,7 +284,12 @@ impl GeneratePrettyPrint {
         field_vars: &[syn::Ident],
     ) -> TokenStream {
         let prettyless_path = &self.prettyless_path;
-        let elements = format.elements();
+        // Optional groups are flattened away and their contents always
+        // printed inline: a group's literal tokens are emitted whether or
+        // not its fields hold a value, and each field inside it prints as
+        // its own occurrence does. Only a bare `{field?}` occurrence checks
+        // for presence (see `generate_field_print`).
+        let elements = flatten_groups(format.elements());
 
         // Build the document expression by combining format elements
         let mut parts: Vec<TokenStream> = Vec::new();
@@ -292,16 +297,18 @@ impl GeneratePrettyPrint {
         for (i, elem) in elements.iter().enumerate() {
             let is_first = i == 0;
             let is_last = i == elements.len() - 1;
-            let prev_is_field = i > 0 && matches!(elements[i - 1], FormatElement::Field(_, _));
-            let next_is_field = !is_last && matches!(elements[i + 1], FormatElement::Field(_, _));
+            let prev_is_field =
+                i > 0 && matches!(elements[i - 1], FormatElement::Field(_, _, _, _));
+            let next_is_field =
+                !is_last && matches!(elements[i + 1], FormatElement::Field(_, _, _, _));
 
             match elem {
-                FormatElement::Token(tokens) => {
+                FormatElement::Token(tokens, _) => {
                     // Convert tokens to text with proper spacing
                     let text = tokens_to_string_with_spacing(tokens, prev_is_field, next_is_field);
                     parts.push(quote! { doc.text(#text) });
                 }
-                FormatElement::Field(name, opt) => {
+                FormatElement::Field(name, opt, repeat, _) => {
                     // Look up the field by name
                     let name_str = name.to_string();
                     if let Some((idx, field)) = field_map.get(&name_str) {
@@ -309,7 +316,7 @@ impl GeneratePrettyPrint {
                         let var_ref = quote! { #var };
 
                         let kind = FieldKind::from_field_info(field);
-                        let print_expr = kind.print_expr(prettyless_path, &var_ref, opt);
+                        let print_expr = self.generate_field_print(&kind, prettyless_path, &var_ref, opt, repeat);
 
                         // Add space before field if preceded by another field (no Token between)
                         if !is_first && prev_is_field {
@@ -319,6 +326,12 @@ impl GeneratePrettyPrint {
                         parts.push(print_expr);
                     }
                 }
+                FormatElement::OptionalGroup(_, _) => {
+                    unreachable!("flatten_groups removes all OptionalGroup elements")
+                }
+                FormatElement::Alternative(_, _) => {
+                    unreachable!("flatten_groups removes all Alternative elements")
+                }
             }
         }
 
@@ -333,13 +346,91 @@ impl GeneratePrettyPrint {
             }
         }
     }
+
+    /// Generates the print expression for one field occurrence, honoring the
+    /// repetition syntax (`{field?}` / `{field:sep(tok)}`) it was parsed
+    /// with, mirroring how [`super::parser::GenerateHasDialectParser::field_parser_v2`]
+    /// builds the matching parser for each case.
+    fn generate_field_print(
+        &self,
+        kind: &FieldKind,
+        prettyless_path: &syn::Path,
+        var_ref: &TokenStream,
+        opt: &crate::format::FormatOption,
+        repeat: &FieldRepeat<'_>,
+    ) -> TokenStream {
+        match repeat {
+            FieldRepeat::None => kind.print_expr(prettyless_path, var_ref, opt),
+            FieldRepeat::Optional => {
+                let inner = kind.print_expr(prettyless_path, &quote! { __inner }, opt);
+                quote! {
+                    match #var_ref {
+                        Some(__inner) => #inner,
+                        None => doc.nil(),
+                    }
+                }
+            }
+            FieldRepeat::Separated {
+                separator,
+                delimiters,
+            } => {
+                let sep_text = token_text(separator);
+                let item_print = kind.print_expr(prettyless_path, &quote! { __item }, opt);
+                let list = quote! {
+                    doc.list(#var_ref.iter(), #sep_text, |__item| #item_print)
+                };
+                match delimiters {
+                    Some((open, close)) => {
+                        let open_text = token_text(open);
+                        let close_text = token_text(close);
+                        quote! { doc.text(#open_text) + #list + doc.text(#close_text) }
+                    }
+                    None => list,
+                }
+            }
+            FieldRepeat::Repeated { .. } => {
+                // `*`/`+` have no separator between occurrences, unlike
+                // `:sep(tok)`/`,*`, which print `doc.list`'s `sep` text
+                // between items.
+                let item_print = kind.print_expr(prettyless_path, &quote! { __item }, opt);
+                quote! { doc.list(#var_ref.iter(), "", |__item| #item_print) }
+            }
+        }
+    }
+}
+
+/// Flattens `FormatElement::OptionalGroup`s into the elements they wrap and
+/// `FormatElement::Alternative`s into their first branch, so callers that
+/// don't special-case groups (like [`GeneratePrettyPrint::generate_format_print`])
+/// can treat a format string as a flat sequence of tokens/fields.
+///
+/// An alternative's branches bind no fields (validation in
+/// `generate::parser` rejects any that do), so there's no stored value to
+/// pick a branch by at print time; printing always reproduces the first
+/// branch, the same way a `Default`-derived enum variant would.
+pub(crate) fn flatten_groups<'src>(elements: &[FormatElement<'src>]) -> Vec<FormatElement<'src>> {
+    let mut out = Vec::new();
+    for elem in elements {
+        match elem {
+            FormatElement::OptionalGroup(inner, _) => out.extend(flatten_groups(inner)),
+            FormatElement::Alternative(branches, _) => {
+                if let Some(first) = branches.first() {
+                    out.extend(flatten_groups(first));
+                }
+            }
+            _ => out.push(elem.clone()),
+        }
+    }
+    out
 }
 
 /// Build a map from field name/index (string) to (index, FieldInfo)
 ///
 /// For named fields, both the field name and its index are added as keys.
 /// This allows format strings to use either `{field_name}` or `{0}` syntax.
-fn build_field_map(collected: &[FieldInfo<ChumskyLayout>]) -> IndexMap<String, (usize, &FieldInfo<ChumskyLayout>)> {
+pub(crate) fn build_field_map(
+    collected: &[FieldInfo<ChumskyLayout>],
+) -> IndexMap<String, (usize, &FieldInfo<ChumskyLayout>)> {
     let mut map = IndexMap::new();
     for (idx, field) in collected.iter().enumerate() {
         // Always add the index as a key (for {0}, {1}, etc. syntax)
@@ -353,11 +444,22 @@ fn build_field_map(collected: &[FieldInfo<ChumskyLayout>]) -> IndexMap<String, (
     map
 }
 
+/// Renders a single separator or delimiter token (from `:sep(tok)` /
+/// `:delim(open,close)`) as the literal text to print, handling the escaped
+/// brace tokens the same way [`tokens_to_string_with_spacing`] does.
+pub(crate) fn token_text(token: &Token) -> String {
+    match token {
+        Token::EscapedLBrace => "{".to_string(),
+        Token::EscapedRBrace => "}".to_string(),
+        other => other.to_string(),
+    }
+}
+
 /// Convert a sequence of tokens to a string for printing with proper spacing.
 ///
 /// - `add_leading_space`: Add a space before the first token
 /// - `add_trailing_space`: Add a space after the last token
-fn tokens_to_string_with_spacing(
+pub(crate) fn tokens_to_string_with_spacing(
     tokens: &[Token],
     add_leading_space: bool,
     add_trailing_space: bool,
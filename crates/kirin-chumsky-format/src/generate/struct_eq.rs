@@ -0,0 +1,285 @@
+//! Code generation for the `StructEq` derive macro.
+//!
+//! This generates a [`kirin_chumsky::StructEq`] implementation for a
+//! dialect's generated `*AST` type, comparing every field while ignoring the
+//! `Spanned` source span each one carries — so e.g. `%a = add %x %y -> i32`
+//! and `%a  =  add  %x  %y  ->  i32` produce equal ASTs regardless of
+//! spacing. Like [`super::GenerateAstPrinter`] and [`super::GenerateVisitor`],
+//! it targets the `*AST` type's own `TypeOutput`/`LanguageOutput` generics
+//! rather than a concrete `Dialect`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ChumskyLayout;
+use crate::field_kind::{CollectedField, collect_fields};
+
+use super::{
+    GeneratorConfig, collect_all_value_types_needing_bounds, filter_ast_fields,
+    get_fields_in_format,
+};
+
+/// Generator for the `StructEq` trait implementation.
+pub struct GenerateStructEq {
+    config: GeneratorConfig,
+}
+
+impl GenerateStructEq {
+    /// Creates a new generator.
+    pub fn new(ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> Self {
+        Self {
+            config: GeneratorConfig::new(ir_input),
+        }
+    }
+
+    /// Generates `impl StructEq for <Dialect>AST`.
+    ///
+    /// For wrapper structs, no AST type of its own exists (the `HasParser`
+    /// impl forwards to the wrapped type's), so there's nothing to generate.
+    pub fn generate(&self, ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> TokenStream {
+        if let kirin_derive_core::ir::Data::Struct(data) = &ir_input.data {
+            if data.0.wraps.is_some() {
+                return TokenStream::new();
+            }
+        }
+
+        let ast_name = syn::Ident::new(&format!("{}AST", ir_input.name), ir_input.name.span());
+        let ast_generics = super::build_ast_generics(&ir_input.generics, false);
+        let ty_generics = self.build_ast_ty_generics(ir_input);
+        let crate_path = &self.config.crate_path;
+        let (impl_generics, _, _) = ast_generics.split_for_impl();
+
+        let body = self.generate_body(ir_input, &ast_name);
+
+        let value_types = collect_all_value_types_needing_bounds(ir_input);
+        let has_parser_bounds: Vec<_> = value_types
+            .iter()
+            .map(|ty| {
+                quote! {
+                    #ty: #crate_path::HasParser<'tokens, 'src> + 'tokens,
+                    <#ty as #crate_path::HasParser<'tokens, 'src>>::Output: PartialEq,
+                }
+            })
+            .collect();
+
+        quote! {
+            impl #impl_generics #crate_path::StructEq for #ast_name #ty_generics
+            where
+                TypeOutput: Clone + PartialEq + 'tokens,
+                LanguageOutput: #crate_path::StructEq,
+                #(#has_parser_bounds)*
+            {
+                fn struct_eq(&self, other: &Self) -> bool {
+                    #body
+                }
+            }
+        }
+    }
+
+    /// Builds just the type generics for the AST type (without Language),
+    /// mirroring [`super::GenerateAstPrinter::build_ast_ty_generics`].
+    fn build_ast_ty_generics(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+    ) -> TokenStream {
+        let type_params: Vec<TokenStream> = ir_input
+            .generics
+            .type_params()
+            .map(|p| {
+                let ident = &p.ident;
+                quote! { #ident }
+            })
+            .collect();
+
+        if type_params.is_empty() {
+            quote! { <'tokens, 'src, TypeOutput, LanguageOutput> }
+        } else {
+            quote! { <'tokens, 'src, #(#type_params,)* TypeOutput, LanguageOutput> }
+        }
+    }
+
+    /// Builds the full `struct_eq` function body: a struct compares `self`
+    /// against `other` field-by-field directly; an enum first checks both
+    /// sides are the same variant (any mismatch, including against the
+    /// never-constructed `__Marker` variant, falls through to `false`), then
+    /// compares that variant's fields.
+    fn generate_body(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        ast_name: &syn::Ident,
+    ) -> TokenStream {
+        let crate_path = &self.config.crate_path;
+
+        match &ir_input.data {
+            kirin_derive_core::ir::Data::Struct(s) => {
+                let (pattern_a, field_vars, ast_fields, is_tuple) =
+                    self.build_pattern(ir_input, &s.0, ast_name, None, "a");
+                let pattern_b =
+                    self.build_pattern_for_vars(ast_name, None, &field_vars, is_tuple, "b");
+                let comparisons = self.field_comparisons(&ast_fields, &field_vars);
+                quote! {
+                    let #pattern_a = self;
+                    let #pattern_b = other;
+                    #comparisons
+                }
+            }
+            kirin_derive_core::ir::Data::Enum(e) => {
+                let arms: Vec<TokenStream> = e
+                    .iter_variants()
+                    .map(|variant| match variant {
+                        kirin_derive_core::ir::VariantRef::Wrapper { name, .. } => {
+                            quote! {
+                                (#ast_name::#name(__a), #ast_name::#name(__b)) => {
+                                    #crate_path::StructEq::struct_eq(__a, __b)
+                                }
+                            }
+                        }
+                        kirin_derive_core::ir::VariantRef::Regular { name, stmt } => {
+                            let (pattern_a, field_vars, ast_fields, is_tuple) =
+                                self.build_pattern(ir_input, stmt, ast_name, Some(name), "a");
+                            let pattern_b = self.build_pattern_for_vars(
+                                ast_name,
+                                Some(name),
+                                &field_vars,
+                                is_tuple,
+                                "b",
+                            );
+                            let comparisons = self.field_comparisons(&ast_fields, &field_vars);
+                            quote! {
+                                (#pattern_a, #pattern_b) => { #comparisons }
+                            }
+                        }
+                    })
+                    .collect();
+                quote! {
+                    match (self, other) {
+                        #(#arms)*
+                        _ => false,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Builds the `&&`-joined comparison expression for every AST field,
+    /// delegating the per-field-kind logic to
+    /// [`crate::field_kind::FieldKind::struct_eq_expr`]. An empty field list
+    /// (e.g. a unit variant) compares equal.
+    fn field_comparisons(
+        &self,
+        ast_fields: &[CollectedField],
+        field_vars: &[(Option<syn::Ident>, syn::Ident, syn::Ident)],
+    ) -> TokenStream {
+        let crate_path = &self.config.crate_path;
+        let comparisons: Vec<TokenStream> = ast_fields
+            .iter()
+            .zip(field_vars)
+            .map(|(f, (_, a, b))| f.kind.struct_eq_expr(crate_path, &quote! { #a }, &quote! { #b }))
+            .collect();
+        if comparisons.is_empty() {
+            quote! { true }
+        } else {
+            quote! { #(#comparisons)&&* }
+        }
+    }
+
+    /// Builds the match/let pattern for one statement (struct body or enum
+    /// variant) bound to `side` (`"a"`/`"b"`), plus the field-variable idents
+    /// in `ast_fields` order, mirroring
+    /// [`super::GenerateAstPrinter::build_print_components`]'s pattern
+    /// construction so the bound names line up with the actual generated
+    /// `*AST` fields.
+    ///
+    /// Returns `(pattern, field_vars, ast_fields, is_tuple)` where
+    /// `field_vars[i]` is `(ast_fields[i].ident.clone(), <side "a" bound
+    /// ident>, <side "b" bound ident>)`.
+    #[allow(clippy::type_complexity)]
+    fn build_pattern(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+        side: &str,
+    ) -> (
+        TokenStream,
+        Vec<(Option<syn::Ident>, syn::Ident, syn::Ident)>,
+        Vec<CollectedField>,
+        bool,
+    ) {
+        let collected = collect_fields(stmt);
+        let fields_in_fmt = get_fields_in_format(ir_input, stmt);
+        let is_tuple = stmt.is_tuple_style();
+        let mut ast_fields = filter_ast_fields(&collected, &fields_in_fmt);
+        if is_tuple {
+            ast_fields.sort_by_key(|f| f.index);
+        }
+
+        let field_vars: Vec<(Option<syn::Ident>, syn::Ident, syn::Ident)> = ast_fields
+            .iter()
+            .map(|f| {
+                let (a_var, b_var) = match &f.ident {
+                    Some(ident) => (
+                        syn::Ident::new(&format!("a_{ident}"), ident.span()),
+                        syn::Ident::new(&format!("b_{ident}"), ident.span()),
+                    ),
+                    None => (
+                        syn::Ident::new(&format!("a{}", f.index), proc_macro2::Span::call_site()),
+                        syn::Ident::new(&format!("b{}", f.index), proc_macro2::Span::call_site()),
+                    ),
+                };
+                (f.ident.clone(), a_var, b_var)
+            })
+            .collect();
+
+        let pattern = self.build_pattern_for_vars(ast_name, variant_name, &field_vars, is_tuple, side);
+        (
+            pattern,
+            field_vars,
+            ast_fields.into_iter().cloned().collect(),
+            is_tuple,
+        )
+    }
+
+    /// Renders a pattern binding each field in `field_vars` to whichever of
+    /// its two idents matches `side` (`"a"` or `"b"`).
+    fn build_pattern_for_vars(
+        &self,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+        field_vars: &[(Option<syn::Ident>, syn::Ident, syn::Ident)],
+        is_tuple: bool,
+        side: &str,
+    ) -> TokenStream {
+        if field_vars.is_empty() {
+            return match (variant_name, is_tuple) {
+                (Some(v), true) => quote! { #ast_name::#v(..) },
+                (Some(v), false) => quote! { #ast_name::#v { .. } },
+                (None, true) => quote! { #ast_name(..) },
+                (None, false) => quote! { #ast_name { .. } },
+            };
+        }
+        if is_tuple {
+            let vars: Vec<_> = field_vars
+                .iter()
+                .map(|(_, a, b)| if side == "a" { a } else { b })
+                .collect();
+            return match variant_name {
+                Some(v) => quote! { #ast_name::#v(#(#vars,)* ..) },
+                None => quote! { #ast_name(#(#vars,)* ..) },
+            };
+        }
+        let pat: Vec<_> = field_vars
+            .iter()
+            .map(|(ident, a, b)| {
+                let orig = ident.as_ref().expect("non-tuple field must have an ident");
+                let var = if side == "a" { a } else { b };
+                quote! { #orig: #var }
+            })
+            .collect();
+        match variant_name {
+            Some(v) => quote! { #ast_name::#v { #(#pat,)* .. } },
+            None => quote! { #ast_name { #(#pat,)* .. } },
+        }
+    }
+}
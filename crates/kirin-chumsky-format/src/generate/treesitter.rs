@@ -0,0 +1,215 @@
+//! Code generation for the `TreeSitterGrammar` derive macro.
+//!
+//! Unlike the other generators in this module, this one does almost none of
+//! its work through `quote!`: a variant's tree-sitter rule body is fully
+//! determined by its `#[chumsky(format = "...")]` string, so it's rendered
+//! as a plain `String` once, at macro-expansion time, and baked into the
+//! generated `HasTreeSitterGrammar::RULES` array as a string literal. There's
+//! no runtime component keeping the editor grammar in sync -- regenerating
+//! it is just re-running `kirin_chumsky::treesitter::emit_grammar` after the
+//! format strings change and re-exporting the result.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ChumskyLayout;
+use crate::field_kind::{FieldKind, collect_fields};
+use crate::format::{FieldRepeat, Format, FormatElement, FormatOption};
+
+use super::pretty_print::{build_field_map, tokens_to_string_with_spacing};
+use super::GeneratorConfig;
+
+/// Generator for the `HasTreeSitterGrammar` trait implementation.
+pub struct GenerateTreeSitterGrammar {
+    config: GeneratorConfig,
+}
+
+impl GenerateTreeSitterGrammar {
+    /// Creates a new generator.
+    pub fn new(ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> Self {
+        Self {
+            config: GeneratorConfig::new(ir_input),
+        }
+    }
+
+    /// Generates `impl HasTreeSitterGrammar for <Dialect>`.
+    ///
+    /// For wrapper structs, no rule of its own is needed (the `HasParser`
+    /// impl forwards to the wrapped type's, and so does its grammar).
+    pub fn generate(&self, ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> TokenStream {
+        if let kirin_derive_core::ir::Data::Struct(data) = &ir_input.data {
+            if data.0.wraps.is_some() {
+                return TokenStream::new();
+            }
+        }
+
+        let name = &ir_input.name;
+        let (impl_generics, ty_generics, where_clause) = ir_input.generics.split_for_impl();
+        let crate_path = &self.config.crate_path;
+
+        let rules: Vec<TokenStream> = match &ir_input.data {
+            kirin_derive_core::ir::Data::Struct(s) => {
+                vec![self.generate_rule(ir_input, &s.0, &to_snake_case(&ir_input.name.to_string()))]
+            }
+            kirin_derive_core::ir::Data::Enum(e) => e
+                .iter_variants()
+                .filter_map(|variant| match variant {
+                    kirin_derive_core::ir::VariantRef::Wrapper { .. } => None,
+                    kirin_derive_core::ir::VariantRef::Regular { name, stmt } => Some(
+                        self.generate_rule(ir_input, stmt, &to_snake_case(&name.to_string())),
+                    ),
+                })
+                .collect(),
+        };
+
+        quote! {
+            impl #impl_generics #crate_path::treesitter::HasTreeSitterGrammar for #name #ty_generics #where_clause {
+                const RULES: &'static [#crate_path::treesitter::TreeSitterRule] = &[
+                    #(#rules),*
+                ];
+            }
+        }
+    }
+
+    /// Builds one `TreeSitterRule { name, body }` literal for a statement.
+    fn generate_rule(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        rule_name: &str,
+    ) -> TokenStream {
+        let crate_path = &self.config.crate_path;
+        let format_str = super::format_for_statement(ir_input, stmt)
+            .expect("Statement must have format string");
+        let format = Format::parse(&format_str, None).expect("Format string should be valid");
+        let body = self.render_rule_body(&format, stmt);
+
+        quote! {
+            #crate_path::treesitter::TreeSitterRule {
+                name: #rule_name,
+                body: #body,
+            }
+        }
+    }
+
+    /// Renders a format string's elements as a tree-sitter `seq(...)` body:
+    /// literals become quoted string tokens and each field becomes the
+    /// shared rule reference matching its [`FieldKind`] (`$.ssa_value`,
+    /// `$.type`, `$.label`, `$.block`, `$.region`, ...), wrapped per its
+    /// [`FieldRepeat`] via [`render_repeat`] for `?`/`*`/`+`/separated-list
+    /// fields.
+    ///
+    /// Unlike [`super::GenerateAstPrinter::generate_format_print`], which
+    /// flattens optional/alternative groups down to one representative
+    /// branch since it only ever prints a value that's already been parsed,
+    /// a highlighting/folding grammar has to accept every branch a real
+    /// program might use: an `OptionalGroup` becomes tree-sitter's
+    /// `optional(seq(...))` (e.g. the `-> type` tail on a result that may or
+    /// may not carry one) and an `Alternative` becomes `choice(seq(...), ...)`
+    /// over its branches.
+    fn render_rule_body(
+        &self,
+        format: &Format,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+    ) -> String {
+        let collected = collect_fields(stmt);
+        let field_map = build_field_map(&collected);
+        let parts = self.render_elements(format.elements(), &field_map);
+        format!("seq({})", parts.join(", "))
+    }
+
+    /// Renders each element of a format string (or one of an
+    /// `OptionalGroup`/`Alternative`'s nested branches) to its tree-sitter
+    /// DSL fragment, recursing into nested groups.
+    fn render_elements(
+        &self,
+        elements: &[FormatElement],
+        field_map: &indexmap::IndexMap<
+            String,
+            (usize, &kirin_derive_core::ir::fields::FieldInfo<ChumskyLayout>),
+        >,
+    ) -> Vec<String> {
+        elements
+            .iter()
+            .filter_map(|elem| match elem {
+                FormatElement::Token(tokens, _) => {
+                    let text = tokens_to_string_with_spacing(tokens, false, false);
+                    Some(format!("'{}'", text.trim().replace('\'', "\\'")))
+                }
+                FormatElement::Field(name, opt, repeat, _) => {
+                    let (_, field) = field_map.get(*name)?;
+                    let term = field_term(&field.kind, opt);
+                    Some(render_repeat(term, repeat))
+                }
+                FormatElement::OptionalGroup(inner, _) => {
+                    let inner_parts = self.render_elements(inner, field_map);
+                    Some(format!("optional(seq({}))", inner_parts.join(", ")))
+                }
+                FormatElement::Alternative(branches, _) => {
+                    let rendered: Vec<String> = branches
+                        .iter()
+                        .map(|branch| format!("seq({})", self.render_elements(branch, field_map).join(", ")))
+                        .collect();
+                    Some(format!("choice({})", rendered.join(", ")))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Maps a field's [`FieldKind`] (and, for the value kinds that carry a
+/// `:name`/`:type` option, which one was used) to the shared rule
+/// [`kirin_chumsky::treesitter::emit_grammar`] defines for it.
+fn field_term(kind: &FieldKind, opt: &FormatOption) -> &'static str {
+    match kind {
+        FieldKind::SSAValue | FieldKind::ResultValue => match opt {
+            FormatOption::Type => "$.type",
+            FormatOption::Name | FormatOption::Default => match kind {
+                FieldKind::SSAValue => "$.ssa_value",
+                _ => "$.result_value",
+            },
+        },
+        FieldKind::Successor => "$.label",
+        FieldKind::Block(_) => "$.block",
+        FieldKind::Region(_) => "$.region",
+        FieldKind::Value(_) => "$.value",
+    }
+}
+
+/// Wraps a field's rule reference to match how many times [`FieldRepeat`]
+/// says it occurs: `?` for an optional field, `repeat`/`repeat1` for a
+/// back-to-back run, or a hand-rolled `seq`/`repeat` pair for a separated
+/// (optionally delimited) list, since tree-sitter has no built-in "sep by"
+/// combinator.
+fn render_repeat(term: &str, repeat: &FieldRepeat) -> String {
+    match repeat {
+        FieldRepeat::None => term.to_string(),
+        FieldRepeat::Optional => format!("optional({term})"),
+        FieldRepeat::Repeated { at_least_one: true } => format!("repeat1({term})"),
+        FieldRepeat::Repeated { at_least_one: false } => format!("repeat({term})"),
+        FieldRepeat::Separated { separator, delimiters } => {
+            let list = format!("optional(seq({term}, repeat(seq('{separator}', {term}))))");
+            match delimiters {
+                Some((open, close)) => format!("seq('{open}', {list}, '{close}')"),
+                None => list,
+            }
+        }
+    }
+}
+
+/// Converts a `PascalCase` variant/dialect name into the `snake_case` a
+/// tree-sitter rule name conventionally uses.
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.char_indices() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,410 @@
+//! Code generation for the `Visitor` derive macro.
+//!
+//! This generates [`kirin_chumsky::VisitChildren`], [`kirin_chumsky::VisitMutChildren`],
+//! and [`kirin_chumsky::FoldChildren`] implementations for a dialect's
+//! generated `*AST` type, recursing into the nested statements reachable
+//! through its `Block`/`Region` fields. Like [`super::GenerateAstPrinter`],
+//! it targets the `*AST` type's own `TypeOutput`/`LanguageOutput` generics
+//! rather than a concrete `Dialect`, and leaves `SSAValue`/`ResultValue`/
+//! `Successor`/`Value` fields alone: none of them nest another AST node, so
+//! there's nothing for a tree pass to walk into. A pass that wants to
+//! inspect, say, an SSA value's name can still do so by matching on the
+//! visited node itself inside its own `Visit`/`VisitMut`/`Fold` impl — this
+//! derive only supplies the boilerplate recursion through `Block`/`Region`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ChumskyLayout;
+use crate::field_kind::{CollectedField, collect_fields};
+
+use super::{GeneratorConfig, filter_ast_fields, generate_enum_match, get_fields_in_format};
+
+/// Generator for the `VisitChildren`/`VisitMutChildren`/`FoldChildren` trait
+/// implementations.
+pub struct GenerateVisitor {
+    config: GeneratorConfig,
+}
+
+impl GenerateVisitor {
+    /// Creates a new generator.
+    pub fn new(ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> Self {
+        Self {
+            config: GeneratorConfig::new(ir_input),
+        }
+    }
+
+    /// Generates `impl VisitChildren<V>`, `impl VisitMutChildren<V>`, and
+    /// `impl FoldChildren<F>` for `<Dialect>AST`.
+    ///
+    /// For wrapper structs, no AST type of its own exists (the `HasParser`
+    /// impl forwards to the wrapped type's), so there's nothing to generate.
+    pub fn generate(&self, ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>) -> TokenStream {
+        if let kirin_derive_core::ir::Data::Struct(data) = &ir_input.data {
+            if data.0.wraps.is_some() {
+                return TokenStream::new();
+            }
+        }
+
+        let ast_name = syn::Ident::new(&format!("{}AST", ir_input.name), ir_input.name.span());
+        let ast_generics = super::build_ast_generics(&ir_input.generics, false);
+        let ty_generics = self.build_ast_ty_generics(ir_input);
+        let crate_path = &self.config.crate_path;
+
+        let visit_body = self.generate_visit_body(ir_input, &ast_name, false);
+        let visit_mut_body = self.generate_visit_body(ir_input, &ast_name, true);
+        let fold_body = self.generate_fold_body(ir_input, &ast_name);
+
+        let visit_impl = {
+            let mut generics = ast_generics.clone();
+            generics.params.push(type_param("V"));
+            let (impl_generics, _, _) = generics.split_for_impl();
+            quote! {
+                impl #impl_generics #crate_path::VisitChildren<V> for #ast_name #ty_generics
+                where
+                    TypeOutput: Clone + PartialEq + 'tokens,
+                    V: #crate_path::Visit<LanguageOutput>,
+                {
+                    fn visit_children(&self, visitor: &mut V) {
+                        #visit_body
+                    }
+                }
+            }
+        };
+
+        let visit_mut_impl = {
+            let mut generics = ast_generics.clone();
+            generics.params.push(type_param("V"));
+            let (impl_generics, _, _) = generics.split_for_impl();
+            quote! {
+                impl #impl_generics #crate_path::VisitMutChildren<V> for #ast_name #ty_generics
+                where
+                    TypeOutput: Clone + PartialEq + 'tokens,
+                    V: #crate_path::VisitMut<LanguageOutput>,
+                {
+                    fn visit_children_mut(&mut self, visitor: &mut V) {
+                        #visit_mut_body
+                    }
+                }
+            }
+        };
+
+        let fold_impl = {
+            let mut generics = ast_generics.clone();
+            generics.params.push(type_param("F"));
+            let (impl_generics, _, _) = generics.split_for_impl();
+            quote! {
+                impl #impl_generics #crate_path::FoldChildren<F> for #ast_name #ty_generics
+                where
+                    TypeOutput: Clone + PartialEq + 'tokens,
+                    F: #crate_path::Fold<LanguageOutput>,
+                {
+                    fn fold_children(self, visitor: &mut F) -> Self {
+                        #fold_body
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #visit_impl
+            #visit_mut_impl
+            #fold_impl
+        }
+    }
+
+    /// Builds just the type generics for the AST type (without Language),
+    /// mirroring [`super::GenerateAstPrinter::build_ast_ty_generics`].
+    fn build_ast_ty_generics(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+    ) -> TokenStream {
+        let type_params: Vec<TokenStream> = ir_input
+            .generics
+            .type_params()
+            .map(|p| {
+                let ident = &p.ident;
+                quote! { #ident }
+            })
+            .collect();
+
+        if type_params.is_empty() {
+            quote! { <'tokens, 'src, TypeOutput, LanguageOutput> }
+        } else {
+            quote! { <'tokens, 'src, #(#type_params,)* TypeOutput, LanguageOutput> }
+        }
+    }
+
+    /// Generates the full function body for `visit_children`
+    /// (`mutable = false`) or `visit_children_mut` (`mutable = true`) —
+    /// only the field expression differs between the two
+    /// (`FieldKind::visit_expr` vs. `visit_mut_expr`), since the binding
+    /// mode (shared vs. mutable reference) follows automatically from
+    /// whether the caller matched on `&self` or `&mut self`.
+    fn generate_visit_body(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        ast_name: &syn::Ident,
+        mutable: bool,
+    ) -> TokenStream {
+        let field_stmts = |ast_fields: &[CollectedField],
+                            field_vars: &[(Option<syn::Ident>, syn::Ident)]| {
+            let stmts: Vec<TokenStream> = ast_fields
+                .iter()
+                .zip(field_vars)
+                .map(|(f, (_, v))| {
+                    let field_ref = quote! { #v };
+                    if mutable {
+                        f.kind.visit_mut_expr(&field_ref)
+                    } else {
+                        f.kind.visit_expr(&field_ref)
+                    }
+                })
+                .collect();
+            quote! { #(#stmts)* }
+        };
+
+        match &ir_input.data {
+            kirin_derive_core::ir::Data::Struct(s) => {
+                let (pattern, field_vars, ast_fields) = self.build_pattern(ir_input, &s.0, ast_name, None);
+                let stmts = field_stmts(&ast_fields, &field_vars);
+                quote! {
+                    let #pattern = self;
+                    #stmts
+                }
+            }
+            kirin_derive_core::ir::Data::Enum(e) => {
+                let marker = quote! { #ast_name::__Marker(_, unreachable) => match *unreachable {}, };
+                let crate_path = &self.config.crate_path;
+                generate_enum_match(
+                    ast_name,
+                    e,
+                    move |_name, _wrapper| {
+                        if mutable {
+                            quote! { #crate_path::VisitMutChildren::visit_children_mut(inner, visitor) }
+                        } else {
+                            quote! { #crate_path::VisitChildren::visit_children(inner, visitor) }
+                        }
+                    },
+                    |name, variant| {
+                        let (pattern, field_vars, ast_fields) =
+                            self.build_pattern(ir_input, variant, ast_name, Some(name));
+                        let stmts = field_stmts(&ast_fields, &field_vars);
+                        quote! { #pattern => { #stmts } }
+                    },
+                    Some(marker),
+                )
+            }
+        }
+    }
+
+    /// Builds the `Fold` counterpart of [`Self::generate_visit_body`]: since
+    /// `fold_children` consumes and rebuilds `self`, each arm also
+    /// reconstructs the matched variant/struct from its (possibly
+    /// rewritten) field bindings, rather than just running side-effecting
+    /// statements against borrowed fields.
+    fn generate_fold_body(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        ast_name: &syn::Ident,
+    ) -> TokenStream {
+        let crate_path = &self.config.crate_path;
+
+        // Fold statements plus the rebuild expression for one statement
+        // (struct body or enum variant), shared between the `let`-bound
+        // struct case and the per-variant match arms below.
+        let fold_and_rebuild = |stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+                                 variant_name: Option<&syn::Ident>| {
+            let (_, field_vars, ast_fields) = self.build_pattern(ir_input, stmt, ast_name, variant_name);
+            let is_tuple = stmt.is_tuple_style();
+
+            let fold_stmts: Vec<TokenStream> = ast_fields
+                .iter()
+                .zip(&field_vars)
+                .map(|(f, (_, var))| f.kind.fold_expr(crate_path, &quote! { #var }))
+                .filter(|stmt| !stmt.is_empty())
+                .collect();
+
+            let rebuild = self.build_rebuild(ast_name, variant_name, &field_vars, is_tuple);
+            (field_vars, is_tuple, quote! { #(#fold_stmts)* #rebuild })
+        };
+
+        match &ir_input.data {
+            kirin_derive_core::ir::Data::Struct(s) => {
+                let (field_vars, is_tuple, body) = fold_and_rebuild(&s.0, None);
+                let mut_pattern = self.build_pattern_mut(ast_name, None, &field_vars, is_tuple);
+                quote! {
+                    let #mut_pattern = self;
+                    #body
+                }
+            }
+            kirin_derive_core::ir::Data::Enum(e) => {
+                let marker = quote! { #ast_name::__Marker(_, unreachable) => match unreachable {}, };
+                generate_enum_match(
+                    ast_name,
+                    e,
+                    |name, _wrapper| {
+                        quote! {
+                            #ast_name::#name(#crate_path::FoldChildren::fold_children(inner, visitor))
+                        }
+                    },
+                    |name, variant| {
+                        let (field_vars, is_tuple, body) = fold_and_rebuild(variant, Some(name));
+                        let mut_pattern = self.build_pattern_mut(ast_name, Some(name), &field_vars, is_tuple);
+                        quote! { #mut_pattern => { #body } }
+                    },
+                    Some(marker),
+                )
+            }
+        }
+    }
+
+    /// Re-expresses the pattern [`Self::build_pattern`] produced, but with
+    /// every bound field marked `mut` so `fold_expr`'s in-place
+    /// reassignments type-check.
+    fn build_pattern_mut(
+        &self,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+        field_vars: &[(Option<syn::Ident>, syn::Ident)],
+        is_tuple: bool,
+    ) -> TokenStream {
+        if field_vars.is_empty() {
+            return match variant_name {
+                Some(v) => quote! { #ast_name::#v { .. } },
+                None => quote! { #ast_name { .. } },
+            };
+        }
+        if is_tuple {
+            let vars: Vec<_> = field_vars.iter().map(|(_, v)| v).collect();
+            return match variant_name {
+                Some(v) => quote! { #ast_name::#v(#(mut #vars,)* ..) },
+                None => quote! { #ast_name(#(mut #vars,)* ..) },
+            };
+        }
+        let pat: Vec<_> = field_vars
+            .iter()
+            .map(|(ident, var)| {
+                let orig = ident.as_ref().expect("non-tuple field must have an ident");
+                quote! { #orig: mut #var }
+            })
+            .collect();
+        match variant_name {
+            Some(v) => quote! { #ast_name::#v { #(#pat,)* .. } },
+            None => quote! { #ast_name { #(#pat,)* .. } },
+        }
+    }
+
+    /// Builds the constructor expression that hands the (possibly
+    /// fold-mutated) `field_vars` bindings straight back as a `Self` value.
+    fn build_rebuild(
+        &self,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+        field_vars: &[(Option<syn::Ident>, syn::Ident)],
+        is_tuple: bool,
+    ) -> TokenStream {
+        if field_vars.is_empty() {
+            return match variant_name {
+                Some(v) => quote! { #ast_name::#v {} },
+                None => quote! { #ast_name {} },
+            };
+        }
+        if is_tuple {
+            let vars: Vec<_> = field_vars.iter().map(|(_, v)| v).collect();
+            return match variant_name {
+                Some(v) => quote! { #ast_name::#v(#(#vars),*) },
+                None => quote! { #ast_name(#(#vars),*) },
+            };
+        }
+        let fields: Vec<_> = field_vars
+            .iter()
+            .map(|(ident, var)| {
+                let orig = ident.as_ref().expect("non-tuple field must have an ident");
+                quote! { #orig: #var }
+            })
+            .collect();
+        match variant_name {
+            Some(v) => quote! { #ast_name::#v { #(#fields),* } },
+            None => quote! { #ast_name { #(#fields),* } },
+        }
+    }
+
+    /// Builds the match pattern for one statement (struct body or enum
+    /// variant), plus the field-variable idents in `ast_fields` order,
+    /// mirroring [`super::GenerateAstPrinter::build_print_components`]'s
+    /// pattern construction so the bound names line up with the actual
+    /// generated `*AST` fields.
+    ///
+    /// Returns `(pattern, field_vars, ast_fields)` where `field_vars[i]` is
+    /// `(ast_fields[i].ident.clone(), <bound ident>)`.
+    fn build_pattern(
+        &self,
+        ir_input: &kirin_derive_core::ir::Input<ChumskyLayout>,
+        stmt: &kirin_derive_core::ir::Statement<ChumskyLayout>,
+        ast_name: &syn::Ident,
+        variant_name: Option<&syn::Ident>,
+    ) -> (
+        TokenStream,
+        Vec<(Option<syn::Ident>, syn::Ident)>,
+        Vec<CollectedField>,
+    ) {
+        let collected = collect_fields(stmt);
+        let fields_in_fmt = get_fields_in_format(ir_input, stmt);
+        let is_tuple = stmt.is_tuple_style();
+        let mut ast_fields = filter_ast_fields(&collected, &fields_in_fmt);
+        if is_tuple {
+            ast_fields.sort_by_key(|f| f.index);
+        }
+
+        let field_vars: Vec<(Option<syn::Ident>, syn::Ident)> = ast_fields
+            .iter()
+            .map(|f| {
+                let var = match &f.ident {
+                    Some(ident) => syn::Ident::new(&format!("f_{ident}"), ident.span()),
+                    None => syn::Ident::new(&format!("f{}", f.index), proc_macro2::Span::call_site()),
+                };
+                (f.ident.clone(), var)
+            })
+            .collect();
+
+        let pattern = if ast_fields.is_empty() {
+            match variant_name {
+                Some(v) if is_tuple => quote! { #ast_name::#v(..) },
+                Some(v) => quote! { #ast_name::#v { .. } },
+                None if is_tuple => quote! { #ast_name(..) },
+                None => quote! { #ast_name { .. } },
+            }
+        } else if is_tuple {
+            let vars: Vec<_> = field_vars.iter().map(|(_, v)| v).collect();
+            match variant_name {
+                Some(v) => quote! { #ast_name::#v(#(#vars,)* ..) },
+                None => quote! { #ast_name(#(#vars,)* ..) },
+            }
+        } else {
+            let pat: Vec<_> = field_vars
+                .iter()
+                .map(|(ident, var)| {
+                    let orig = ident.as_ref().expect("non-tuple field must have an ident");
+                    quote! { #orig: #var }
+                })
+                .collect();
+            match variant_name {
+                Some(v) => quote! { #ast_name::#v { #(#pat,)* .. } },
+                None => quote! { #ast_name { #(#pat,)* .. } },
+            }
+        };
+
+        (pattern, field_vars, ast_fields.into_iter().cloned().collect())
+    }
+}
+
+/// Builds a bare `syn::GenericParam::Type` for an extra impl-only type
+/// parameter (the visitor/folder type), appended to the AST type's own
+/// generics.
+fn type_param(name: &str) -> syn::GenericParam {
+    syn::GenericParam::Type(syn::TypeParam::from(syn::Ident::new(
+        name,
+        proc_macro2::Span::call_site(),
+    )))
+}
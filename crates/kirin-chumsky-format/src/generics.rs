@@ -3,6 +3,8 @@
 //! This module provides utilities for building generic parameters used in
 //! generated AST types and parser implementations.
 
+use std::collections::HashSet;
+
 use proc_macro2::Span;
 
 /// Builder for AST generics with 'tokens, 'src, and Language parameters.
@@ -19,9 +21,13 @@ impl<'a> GenericsBuilder<'a> {
     /// Builds generics with 'tokens, 'src: 'tokens lifetimes only.
     ///
     /// This is used for the original type's `HasDialectParser` impl where
-    /// the type is its own Language parameter.
+    /// the type is its own Language parameter. Every returned generics value
+    /// is an *impl header* view: type parameter defaults are stripped (they
+    /// are illegal on an `impl<..>` — see [`without_defaults`]) while const
+    /// generics pass through untouched, since an `impl` header still needs
+    /// to name them.
     pub fn with_lifetimes(&self, base: &syn::Generics) -> syn::Generics {
-        let mut generics = base.clone();
+        let mut generics = without_defaults(base);
 
         // Add 'tokens lifetime at the beginning if not present
         let tokens_lt = syn::Lifetime::new("'tokens", Span::call_site());
@@ -57,6 +63,8 @@ impl<'a> GenericsBuilder<'a> {
     ///
     /// This is used for AST types and their trait implementations.
     /// AST types only require `Language: Dialect`, not `HasDialectParser`.
+    /// Like [`with_lifetimes`](Self::with_lifetimes), this is the impl-header
+    /// view: type parameter defaults are stripped.
     pub fn with_language(&self, base: &syn::Generics) -> syn::Generics {
         let mut generics = self.with_lifetimes(base);
         let ir_path = self.ir_path;
@@ -83,6 +91,8 @@ impl<'a> GenericsBuilder<'a> {
     ///
     /// This is used for `HasDialectParser` impl where the `Language: Dialect` bound
     /// is specified in the where clause instead of on the type parameter.
+    /// Like [`with_lifetimes`](Self::with_lifetimes), this is the impl-header
+    /// view: type parameter defaults are stripped.
     pub fn with_language_unbounded(&self, base: &syn::Generics) -> syn::Generics {
         let mut generics = self.with_lifetimes(base);
 
@@ -100,6 +110,208 @@ impl<'a> GenericsBuilder<'a> {
 
         generics
     }
+
+    /// Inserts `lifetimes` (as produced by [`collect_elided_lifetimes`]) into
+    /// `base` right after `with_lifetimes`'s fixed `'tokens`/`'src` pair, each
+    /// with an outlives bound `'a: 'src`, so whatever a field borrows through
+    /// one of them lives at least as long as the source text.
+    ///
+    /// This is how a statement field declared as `&str` or `Cow<'_, T>` gets
+    /// its own lifetime parameter on the generated AST type instead of
+    /// silently borrowing whatever `'tokens`/`'src` happen to mean there.
+    pub fn with_field_lifetimes(&self, base: &syn::Generics, lifetimes: &[syn::Lifetime]) -> syn::Generics {
+        let mut generics = self.with_lifetimes(base);
+        let src_lt = syn::Lifetime::new("'src", Span::call_site());
+
+        // 'tokens and 'src were just guaranteed present at indices 0 and 1.
+        let mut insert_at = 2;
+        for lifetime in lifetimes {
+            let already_present = generics.params.iter().any(
+                |p| matches!(p, syn::GenericParam::Lifetime(l) if l.lifetime == *lifetime),
+            );
+            if already_present {
+                continue;
+            }
+            let mut param = syn::LifetimeParam::new(lifetime.clone());
+            param.bounds.push(src_lt.clone());
+            generics.params.insert(insert_at, syn::GenericParam::Lifetime(param));
+            insert_at += 1;
+        }
+
+        generics
+    }
+
+    /// Infers `where`-predicates bounding only the type parameters of `base`
+    /// that actually appear in `field_types`, instead of blanket-bounding
+    /// every type parameter regardless of use (serde's derive takes the same
+    /// approach for its `Serialize`/`Deserialize` impls). Each used parameter
+    /// gets one predicate `#param: #trait_bound`; unused parameters are left
+    /// alone so callers aren't forced to satisfy bounds they never need.
+    pub fn field_bound_predicates(
+        &self,
+        base: &syn::Generics,
+        field_types: &[syn::Type],
+        trait_bound: &syn::Path,
+    ) -> Vec<syn::WherePredicate> {
+        let type_params: HashSet<syn::Ident> = base
+            .params
+            .iter()
+            .filter_map(|p| match p {
+                syn::GenericParam::Type(t) => Some(t.ident.clone()),
+                _ => None,
+            })
+            .collect();
+
+        if type_params.is_empty() {
+            return Vec::new();
+        }
+
+        let mut used = HashSet::new();
+        for ty in field_types {
+            collect_used_type_params(ty, &type_params, &mut used);
+        }
+
+        // Iterate `base`'s own param order rather than `used`'s (a HashSet)
+        // so generated code is deterministic across runs.
+        base.params
+            .iter()
+            .filter_map(|p| match p {
+                syn::GenericParam::Type(t) if used.contains(&t.ident) => {
+                    let ident = &t.ident;
+                    Some(syn::parse_quote!(#ident: #trait_bound))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns `generics` with every type parameter's default stripped (defaults
+/// are illegal in `impl<..>` headers, mirroring serde's `without_defaults`).
+/// Lifetime and const parameters are unaffected.
+pub fn without_defaults(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        if let syn::GenericParam::Type(type_param) = param {
+            type_param.eq_token = None;
+            type_param.default = None;
+        }
+    }
+    generics
+}
+
+/// Rewrites every anonymous `&` reference and explicit `'_` inside `ty` to a
+/// fresh named lifetime (`'field0`, `'field1`, ...) and returns the
+/// lifetimes introduced, in occurrence order. An empty result means `ty`
+/// didn't elide any lifetime at all. Modeled on async-trait's
+/// `CollectLifetimes`.
+pub fn collect_elided_lifetimes(ty: &mut syn::Type) -> Vec<syn::Lifetime> {
+    let mut collector = CollectLifetimes::default();
+    syn::visit_mut::visit_type_mut(&mut collector, ty);
+    collector.lifetimes
+}
+
+/// Rewrites every anonymous `&` reference and explicit `'_` inside `ty` to
+/// `lifetime`, for fields opted out of getting their own fresh lifetime
+/// (they borrow `'src` directly instead). Returns whether anything was
+/// rewritten.
+pub fn rewrite_elided_lifetimes_as(ty: &mut syn::Type, lifetime: &syn::Lifetime) -> bool {
+    let mut rewriter = RewriteLifetimesAs {
+        lifetime: lifetime.clone(),
+        rewrote_any: false,
+    };
+    syn::visit_mut::visit_type_mut(&mut rewriter, ty);
+    rewriter.rewrote_any
+}
+
+#[derive(Default)]
+struct CollectLifetimes {
+    lifetimes: Vec<syn::Lifetime>,
+}
+
+impl CollectLifetimes {
+    fn next_lifetime(&mut self) -> syn::Lifetime {
+        let lifetime = syn::Lifetime::new(&format!("'field{}", self.lifetimes.len()), Span::call_site());
+        self.lifetimes.push(lifetime.clone());
+        lifetime
+    }
+}
+
+impl syn::visit_mut::VisitMut for CollectLifetimes {
+    fn visit_type_reference_mut(&mut self, node: &mut syn::TypeReference) {
+        if node.lifetime.is_none() {
+            node.lifetime = Some(self.next_lifetime());
+        }
+        syn::visit_mut::visit_type_reference_mut(self, node);
+    }
+
+    fn visit_lifetime_mut(&mut self, node: &mut syn::Lifetime) {
+        if node.ident == "_" {
+            *node = self.next_lifetime();
+        }
+    }
+}
+
+struct RewriteLifetimesAs {
+    lifetime: syn::Lifetime,
+    rewrote_any: bool,
+}
+
+impl syn::visit_mut::VisitMut for RewriteLifetimesAs {
+    fn visit_type_reference_mut(&mut self, node: &mut syn::TypeReference) {
+        if node.lifetime.is_none() {
+            node.lifetime = Some(self.lifetime.clone());
+            self.rewrote_any = true;
+        }
+        syn::visit_mut::visit_type_reference_mut(self, node);
+    }
+
+    fn visit_lifetime_mut(&mut self, node: &mut syn::Lifetime) {
+        if node.ident == "_" {
+            *node = self.lifetime.clone();
+            self.rewrote_any = true;
+        }
+    }
+}
+
+/// Recursively walks `ty`, adding every identifier in `params` that appears
+/// in it to `used`. Looks through references (`&T`, `&mut T`), tuples,
+/// slices/arrays, parens/groups, and the generic arguments of path types
+/// (`Vec<T>`, `Option<T>`, ...) so a parameter nested arbitrarily deep is
+/// still detected as used.
+fn collect_used_type_params(ty: &syn::Type, params: &HashSet<syn::Ident>, used: &mut HashSet<syn::Ident>) {
+    match ty {
+        syn::Type::Path(type_path) => {
+            if type_path.qself.is_none() {
+                if let Some(ident) = type_path.path.get_ident() {
+                    if params.contains(ident) {
+                        used.insert(ident.clone());
+                    }
+                }
+            }
+            for segment in &type_path.path.segments {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let syn::GenericArgument::Type(inner) = arg {
+                            collect_used_type_params(inner, params, used);
+                        }
+                    }
+                }
+            }
+        }
+        syn::Type::Reference(r) => collect_used_type_params(&r.elem, params, used),
+        syn::Type::Tuple(t) => {
+            for elem in &t.elems {
+                collect_used_type_params(elem, params, used);
+            }
+        }
+        syn::Type::Slice(s) => collect_used_type_params(&s.elem, params, used),
+        syn::Type::Array(a) => collect_used_type_params(&a.elem, params, used),
+        syn::Type::Paren(p) => collect_used_type_params(&p.elem, params, used),
+        syn::Type::Group(g) => collect_used_type_params(&g.elem, params, used),
+        syn::Type::Ptr(p) => collect_used_type_params(&p.elem, params, used),
+        _ => {}
+    }
 }
 
 #[cfg(test)]
@@ -166,4 +378,114 @@ mod tests {
 
         insta::assert_snapshot!("with_language_existing_type", format_generics(&result));
     }
+
+    #[test]
+    fn test_without_defaults_strips_type_param_default() {
+        let generics: syn::Generics = syn::parse_quote!(<T = i32, U>);
+        let result = without_defaults(&generics);
+
+        insta::assert_snapshot!("without_defaults_strips_default", format_generics(&result));
+    }
+
+    #[test]
+    fn test_field_bound_predicates_only_covers_used_params() {
+        let ir_path: syn::Path = syn::parse_quote!(::kirin::ir);
+        let builder = GenericsBuilder::new(&ir_path);
+        let bound: syn::Path = syn::parse_quote!(CompileTimeValue);
+
+        let base: syn::Generics = syn::parse_quote!(<T, U>);
+        let field_types: Vec<syn::Type> = vec![syn::parse_quote!(Vec<T>)];
+
+        let predicates = builder.field_bound_predicates(&base, &field_types, &bound);
+        let rendered: Vec<String> = predicates
+            .iter()
+            .map(|p| quote! { #p }.to_string())
+            .collect();
+
+        assert_eq!(rendered, vec!["T : CompileTimeValue".to_string()]);
+    }
+
+    #[test]
+    fn test_field_bound_predicates_empty_when_no_type_params_used() {
+        let ir_path: syn::Path = syn::parse_quote!(::kirin::ir);
+        let builder = GenericsBuilder::new(&ir_path);
+        let bound: syn::Path = syn::parse_quote!(CompileTimeValue);
+
+        let base: syn::Generics = syn::parse_quote!(<T>);
+        let field_types: Vec<syn::Type> = vec![syn::parse_quote!(String)];
+
+        assert!(
+            builder
+                .field_bound_predicates(&base, &field_types, &bound)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_collect_elided_lifetimes_reference() {
+        let mut ty: syn::Type = syn::parse_quote!(&str);
+        let lifetimes = collect_elided_lifetimes(&mut ty);
+
+        assert_eq!(lifetimes, vec![syn::Lifetime::new("'field0", Span::call_site())]);
+        assert_eq!(quote! { #ty }.to_string(), quote! { & 'field0 str }.to_string());
+    }
+
+    #[test]
+    fn test_collect_elided_lifetimes_explicit_underscore() {
+        let mut ty: syn::Type = syn::parse_quote!(Cow<'_, str>);
+        let lifetimes = collect_elided_lifetimes(&mut ty);
+
+        assert_eq!(lifetimes, vec![syn::Lifetime::new("'field0", Span::call_site())]);
+    }
+
+    #[test]
+    fn test_collect_elided_lifetimes_none_when_owned() {
+        let mut ty: syn::Type = syn::parse_quote!(String);
+        assert!(collect_elided_lifetimes(&mut ty).is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_elided_lifetimes_as_src() {
+        let mut ty: syn::Type = syn::parse_quote!(&[Foo]);
+        let src = syn::Lifetime::new("'src", Span::call_site());
+
+        assert!(rewrite_elided_lifetimes_as(&mut ty, &src));
+        assert_eq!(quote! { #ty }.to_string(), quote! { & 'src [Foo] }.to_string());
+    }
+
+    #[test]
+    fn test_with_field_lifetimes_inserts_after_tokens_src() {
+        let ir_path: syn::Path = syn::parse_quote!(::kirin::ir);
+        let builder = GenericsBuilder::new(&ir_path);
+
+        let base = syn::Generics::default();
+        let field_lifetime = syn::Lifetime::new("'field0", Span::call_site());
+        let result = builder.with_field_lifetimes(&base, &[field_lifetime]);
+
+        insta::assert_snapshot!("with_field_lifetimes_inserted", format_generics(&result));
+    }
+
+    #[test]
+    fn test_with_lifetimes_strips_type_param_default() {
+        let ir_path: syn::Path = syn::parse_quote!(::kirin::ir);
+        let builder = GenericsBuilder::new(&ir_path);
+
+        let base: syn::Generics = syn::parse_quote!(<T = Box<Foo>>);
+        let result = builder.with_lifetimes(&base);
+
+        let rendered = format_generics(&result);
+        assert!(!rendered.contains('='), "impl header must not carry a type param default: {rendered}");
+    }
+
+    #[test]
+    fn test_with_language_preserves_const_generic() {
+        let ir_path: syn::Path = syn::parse_quote!(::kirin::ir);
+        let builder = GenericsBuilder::new(&ir_path);
+
+        let base: syn::Generics = syn::parse_quote!(<const N: usize>);
+        let result = builder.with_language(&base);
+
+        let rendered = format_generics(&result);
+        assert!(rendered.contains("const N : usize"), "expected const generic preserved: {rendered}");
+    }
 }
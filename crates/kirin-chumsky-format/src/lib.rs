@@ -18,7 +18,10 @@ mod visitor;
 pub use attrs::{ChumskyFieldAttrs, ChumskyGlobalAttrs, ChumskyStatementAttrs, PrettyGlobalAttrs};
 pub use field_kind::{FieldKind, collect_fields};
 pub use format::{Format, FormatElement, FormatOption};
-pub use generate::{GenerateAST, GenerateEmitIR, GenerateHasDialectParser, GeneratePrettyPrint};
+pub use generate::{
+    GenerateAST, GenerateAstPrinter, GenerateEmitIR, GenerateHasDialectParser, GeneratePrettyPrint,
+    GenerateStructEq, GenerateTreeSitterGrammar, GenerateVisitor,
+};
 pub use generics::GenericsBuilder;
 pub use input::{parse_derive_input, parse_pretty_derive_input};
 pub use validation::{FieldOccurrence, ValidationResult, ValidationVisitor, validate_format};
@@ -1,12 +1,20 @@
 //! Validation for format strings and field usage.
 //!
 //! This module provides `ValidationVisitor` which validates that:
-//! - No fields use Vec or Option collection types
+//! - `Vec`/`Option` fields carry matching repetition syntax (`:sep(tok)` or
+//!   a `*`/`+`/`,*` suffix for `Vec`, `?` for `Option`) and non-collection
+//!   fields carry none of these
 //! - All field references in the format string are valid
 //! - :name/:type options are only used on SSA/Result fields
 //! - No duplicate default occurrences for the same field
 //! - All required fields are mentioned in the format string
 //! - SSA/Result fields have at least a name occurrence
+//!
+//! Violations don't short-circuit the traversal: `ValidationVisitor`
+//! accumulates every one it finds, each anchored to the span of the
+//! offending field, and folds them into a single combined `syn::Error` via
+//! `syn::Error::combine` so a dialect author sees all of them in one
+//! compile rather than fixing them one at a time.
 
 mod result;
 mod visitor;
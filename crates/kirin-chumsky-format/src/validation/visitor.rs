@@ -6,20 +6,22 @@ use kirin_derive_core::ir::Statement;
 use kirin_lexer::Token;
 
 use crate::ChumskyLayout;
-use kirin_derive_core::ir::fields::FieldInfo;
+use kirin_derive_core::ir::fields::{Collection, FieldInfo};
 
 use crate::field_kind::FieldKind;
-use crate::format::{Format, FormatOption};
+use crate::format::{FieldRepeat, Format, FormatOption};
 use crate::visitor::FormatVisitor;
 
 use super::result::{FieldOccurrence, ValidationResult};
 
 /// Visitor that validates format string usage.
 ///
-/// This performs all validation checks during format traversal,
-/// collecting field occurrences along the way.
+/// This performs all validation checks during format traversal, accumulating
+/// every violation it finds (rather than bailing out on the first one) so a
+/// single macro invocation reports every offending field/option in one pass.
 pub struct ValidationVisitor<'ir> {
-    /// The statement being validated (set in enter_statement)
+    /// The statement being validated (set in enter_statement), used as the
+    /// fallback span for violations that aren't tied to one field.
     stmt_span: proc_macro2::Span,
     /// Field occurrences found so far
     occurrences: Vec<FieldOccurrence<'ir>>,
@@ -29,7 +31,9 @@ pub struct ValidationVisitor<'ir> {
     referenced_fields: HashSet<usize>,
     /// Fields that have name occurrence (default or :name)
     name_occurrences: HashSet<usize>,
-    /// Accumulated errors
+    /// Accumulated errors, each already anchored to the span of the field
+    /// or format option that triggered it. Folded into one combined
+    /// `syn::Error` by `syn::Error::combine` once traversal finishes.
     errors: Vec<syn::Error>,
 }
 
@@ -57,14 +61,19 @@ impl<'ir> ValidationVisitor<'ir> {
 
         // Post-validation: check all required fields are present
         for field in collected {
+            let field_span = self.field_span(field);
+
             if !self.referenced_fields.contains(&field.index) && !field.has_default() {
-                self.add_error(format!(
-                    "field '{}' is not mentioned in the format string. \
-                     All fields must appear in the format string unless they have a default value. \
-                     Use {{{}}} or {{{}:name}}/{{{}:type}} to include this field, \
-                     or add #[kirin(default)] or #[kirin(default = expr)] to provide a default value.",
-                    field, field, field, field
-                ));
+                self.add_error(
+                    field_span,
+                    format!(
+                        "field '{}' is not mentioned in the format string. \
+                         All fields must appear in the format string unless they have a default value. \
+                         Use {{{}}} or {{{}:name}}/{{{}:type}} to include this field, \
+                         or add #[kirin(default)] or #[kirin(default = expr)] to provide a default value.",
+                        field, field, field, field
+                    ),
+                );
             }
 
             // Validate SSA/Result fields have name occurrence
@@ -73,11 +82,14 @@ impl<'ir> ValidationVisitor<'ir> {
                 && self.referenced_fields.contains(&field.index)
                 && !self.name_occurrences.contains(&field.index)
             {
-                self.add_error(format!(
-                    "SSA/Result field '{}' must have {{{}}} or {{{}:name}} in the format string. \
-                     Using only {{{}:type}} is not sufficient because the name cannot be inferred.",
-                    field, field, field, field
-                ));
+                self.add_error(
+                    field_span,
+                    format!(
+                        "SSA/Result field '{}' must have {{{}}} or {{{}:name}} in the format string. \
+                         Using only {{{}:type}} is not sufficient because the name cannot be inferred.",
+                        field, field, field, field
+                    ),
+                );
             }
         }
 
@@ -86,7 +98,9 @@ impl<'ir> ValidationVisitor<'ir> {
                 occurrences: self.occurrences,
             })
         } else {
-            // Combine all errors
+            // Fold every accumulated violation into one combined error so a
+            // single macro invocation reports all of them at once, each
+            // still pointing at its own span.
             let mut iter = self.errors.into_iter();
             let mut combined = iter.next().unwrap();
             for err in iter {
@@ -96,6 +110,17 @@ impl<'ir> ValidationVisitor<'ir> {
         }
     }
 
+    /// Returns the best span available for a field: its identifier's span
+    /// when the field is named, falling back to the statement's span for
+    /// tuple fields (whose only span is the struct/variant name).
+    fn field_span(&self, field: &FieldInfo<ChumskyLayout>) -> proc_macro2::Span {
+        field
+            .ident
+            .as_ref()
+            .map(syn::Ident::span)
+            .unwrap_or(self.stmt_span)
+    }
+
     /// Generates a unique variable name for a field occurrence.
     fn generate_var_name(
         &self,
@@ -115,9 +140,10 @@ impl<'ir> ValidationVisitor<'ir> {
         }
     }
 
-    fn add_error(&mut self, msg: impl std::fmt::Display) {
-        self.errors
-            .push(syn::Error::new(self.stmt_span, msg.to_string()));
+    /// Records a violation without stopping traversal, anchored at `span`
+    /// (the offending field's span, not necessarily the whole statement).
+    fn add_error(&mut self, span: proc_macro2::Span, msg: impl std::fmt::Display) {
+        self.errors.push(syn::Error::new(span, msg.to_string()));
     }
 }
 
@@ -141,9 +167,53 @@ impl<'ir> FormatVisitor<'ir> for ValidationVisitor<'ir> {
         &mut self,
         field: &'ir FieldInfo<ChumskyLayout>,
         option: &FormatOption,
+        repeat: &FieldRepeat<'_>,
     ) -> syn::Result<()> {
         // Track that this field was referenced
         self.referenced_fields.insert(field.index);
+        let field_span = self.field_span(field);
+
+        // A field's collection type must agree with the repetition syntax
+        // used on its occurrence: `Vec` fields need `:sep(...)` or a `*`/`+`/
+        // `,*` quantifier, `Option` fields need `?`, and `Single` fields need
+        // neither.
+        match (&field.collection, repeat) {
+            (Collection::Vec, FieldRepeat::Separated { .. }) => {}
+            (Collection::Vec, FieldRepeat::Repeated { .. }) => {}
+            (Collection::Vec, _) => {
+                self.add_error(
+                    field_span,
+                    format!(
+                        "field '{}' has type Vec<...> and must use {{{}:sep(tok)}} \
+                         (optionally followed by `:delim(open,close)`), {{{}}}*, {{{}}}+, \
+                         or {{{}}},* to specify how the list is parsed.",
+                        field, field, field, field, field
+                    ),
+                );
+            }
+            (Collection::Option, FieldRepeat::Optional) => {}
+            (Collection::Option, _) => {
+                self.add_error(
+                    field_span,
+                    format!(
+                        "field '{}' has type Option<...> and must use {{{}?}} to mark \
+                         the occurrence as optional.",
+                        field, field
+                    ),
+                );
+            }
+            (Collection::Single, FieldRepeat::None) => {}
+            (Collection::Single, _) => {
+                self.add_error(
+                    field_span,
+                    format!(
+                        "field '{}' is not a Vec or Option, so it cannot use `:sep(...)`, \
+                         `?`, or `*`/`+`/`,*` repetition syntax.",
+                        field
+                    ),
+                );
+            }
+        }
 
         // Validate that :name and :type options are only used on SSA/Result fields
         let kind = FieldKind::from_field_info(field);
@@ -155,25 +225,31 @@ impl<'ir> FormatVisitor<'ir> for ValidationVisitor<'ir> {
                 FormatOption::Type => ":type",
                 FormatOption::Default => unreachable!(),
             };
-            self.add_error(format!(
-                "format option '{}' cannot be used on {} field '{}'. \
-                 The :name and :type options are only valid for SSAValue and ResultValue fields.",
-                option_name,
-                kind.name(),
-                field
-            ));
+            self.add_error(
+                field_span,
+                format!(
+                    "format option '{}' cannot be used on {} field '{}'. \
+                     The :name and :type options are only valid for SSAValue and ResultValue fields.",
+                    option_name,
+                    kind.name(),
+                    field
+                ),
+            );
             return Ok(());
         }
 
         // Check for duplicate default occurrences
         if matches!(option, FormatOption::Default) {
             if self.default_occurrences.contains(&field.index) {
-                self.add_error(format!(
-                    "field '{}' appears multiple times with default format option. \
-                     Each field can only have one default occurrence. \
-                     Use {{{}:name}} or {{{}:type}} for additional occurrences.",
-                    field, field, field
-                ));
+                self.add_error(
+                    field_span,
+                    format!(
+                        "field '{}' appears multiple times with default format option. \
+                         Each field can only have one default occurrence. \
+                         Use {{{}:name}} or {{{}:type}} for additional occurrences.",
+                        field, field, field
+                    ),
+                );
                 return Ok(());
             }
             self.default_occurrences.insert(field.index);
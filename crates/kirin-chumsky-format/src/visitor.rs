@@ -9,7 +9,7 @@ use kirin_derive_core::ir::Statement;
 use kirin_lexer::Token;
 
 use crate::ChumskyLayout;
-use crate::format::{Format, FormatElement, FormatOption};
+use crate::format::{FieldRepeat, Format, FormatElement, FormatOption};
 use kirin_derive_core::ir::fields::FieldInfo;
 
 /// Visitor trait for format-driven traversal.
@@ -31,11 +31,14 @@ pub trait FormatVisitor<'ir> {
     /// Called for each field occurrence in format string order.
     ///
     /// A field may appear multiple times with different options
-    /// (e.g., `{x:name}` and `{x:type}`).
+    /// (e.g., `{x:name}` and `{x:type}`). `repeat` carries any
+    /// repetition/optionality syntax attached to this occurrence (e.g.
+    /// `{x:sep(,)}` or `{x?}`).
     fn visit_field_occurrence(
         &mut self,
         _field: &'ir FieldInfo<ChumskyLayout>,
         _option: &FormatOption,
+        _repeat: &FieldRepeat<'_>,
     ) -> syn::Result<()> {
         Ok(())
     }
@@ -97,20 +100,12 @@ pub fn visit_format<'ir, V: FormatVisitor<'ir>>(
     visitor.enter_statement(stmt, format)?;
 
     // Visit format elements in order
-    for elem in format.elements() {
-        match elem {
-            FormatElement::Token(tokens) => {
-                visitor.visit_tokens(tokens)?;
-            }
-            FormatElement::Field(name, option) => {
-                if let Some(field) = field_map.get(*name) {
-                    referenced_fields.insert(field.index);
-                    visitor.visit_field_occurrence(field, option)?;
-                }
-                // Note: Unknown fields are not an error here - validation handles that
-            }
-        }
-    }
+    visit_elements(
+        visitor,
+        format.elements(),
+        &field_map,
+        &mut referenced_fields,
+    )?;
 
     // Visit fields with defaults that weren't in the format
     for field in collected {
@@ -125,6 +120,41 @@ pub fn visit_format<'ir, V: FormatVisitor<'ir>>(
     Ok(())
 }
 
+/// Drives `visit_tokens`/`visit_field_occurrence` over a slice of elements,
+/// recursing into [`FormatElement::OptionalGroup`] and each branch of a
+/// [`FormatElement::Alternative`] so group-/branch-nested elements are
+/// visited the same as top-level ones.
+fn visit_elements<'ir, V: FormatVisitor<'ir>>(
+    visitor: &mut V,
+    elements: &[FormatElement<'_>],
+    field_map: &HashMap<String, &'ir FieldInfo<ChumskyLayout>>,
+    referenced_fields: &mut std::collections::HashSet<usize>,
+) -> syn::Result<()> {
+    for elem in elements {
+        match elem {
+            FormatElement::Token(tokens, _) => {
+                visitor.visit_tokens(tokens)?;
+            }
+            FormatElement::Field(name, option, repeat, _) => {
+                if let Some(field) = field_map.get(*name) {
+                    referenced_fields.insert(field.index);
+                    visitor.visit_field_occurrence(field, option, repeat)?;
+                }
+                // Note: Unknown fields are not an error here - validation handles that
+            }
+            FormatElement::OptionalGroup(inner, _) => {
+                visit_elements(visitor, inner, field_map, referenced_fields)?;
+            }
+            FormatElement::Alternative(branches, _) => {
+                for branch in branches {
+                    visit_elements(visitor, branch, field_map, referenced_fields)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Builds a map from field name (string or index) to FieldInfo.
 fn build_field_map<'a>(
     stmt: &Statement<ChumskyLayout>,
@@ -216,6 +246,7 @@ mod tests {
             &mut self,
             field: &'ir FieldInfo<ChumskyLayout>,
             option: &FormatOption,
+            _repeat: &FieldRepeat<'_>,
         ) -> syn::Result<()> {
             self.field_occurrences.push((field.index, option.clone()));
             Ok(())
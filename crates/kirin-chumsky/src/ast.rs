@@ -91,3 +91,18 @@ where
 {
     pub blocks: Vec<Spanned<Block<'tokens, 'src, L>>>,
 }
+
+/// A nested statement body with no `^label(...)` header, for `Block`/`Region`
+/// fields marked `#[kirin(bare)]`.
+///
+/// Unlike [`Block`], this has no `^label(...)` header: its statements sit
+/// directly between the `{`/`}` that [`bare_block`](crate::bare_block)
+/// parses, so it carries no [`BlockHeader`] of its own.
+#[derive(Debug, Clone)]
+pub struct BareBlock<'tokens, 'src: 'tokens, L: Dialect + HasParser<'tokens, 'src, L>>
+where
+    'src: 'tokens,
+    L::TypeLattice: HasParser<'tokens, 'src, L>,
+{
+    pub statements: Vec<Spanned<L::Output>>,
+}
@@ -0,0 +1,263 @@
+//! Rich, source-anchored diagnostics for raw chumsky parse failures.
+//!
+//! [`render_diagnostics`] renders a batch of chumsky [`Rich`] errors against
+//! the original source text as annotate-snippets-style reports: the
+//! offending line, a `^^^^` underline under the failing span, and the
+//! expected/found token info (which includes any `.labelled("...")` context
+//! chumsky attached while backtracking), in the style compiler diagnostics
+//! use, optionally colored for a terminal. [`write_diagnostics`] is the
+//! streaming counterpart, for callers that already have an output stream to
+//! print against rather than wanting a buffered `String`. [`ParseReport`]
+//! exposes the same information as structured data for callers that want
+//! spans rather than a pre-rendered string — e.g. an editor integration
+//! mapping errors onto squiggly underlines; [`render_reports`] and
+//! [`write_reports`] render those directly, without going back through
+//! chumsky's `Rich` type.
+//!
+//! [`parse_recovering`] runs a parser to completion and collects every
+//! [`ParseReport`] chumsky's recovery produced instead of stopping at the
+//! first one, for use with the statement-level recovery in
+//! [`block`](crate::block)/[`bare_block`](crate::bare_block);
+//! [`parse_dialect_recovering`] is the same thing specialized to a whole
+//! dialect, for callers that don't want to name `L::parser()` themselves.
+//!
+//! This is the low-level counterpart to
+//! [`render_report`](crate::function_text::render_report): that one renders
+//! the higher-level [`FunctionParseError`](crate::function_text::FunctionParseError)
+//! built up by the pipeline-text parser, this one renders chumsky's own
+//! [`Rich`] errors directly, for callers (like derived dialect parsers) that
+//! have nothing richer than what chumsky already collected.
+
+use std::io;
+
+use chumsky::Parser;
+use chumsky::error::Rich;
+use chumsky::span::SimpleSpan;
+
+use kirin_ir::Dialect;
+use kirin_lexer::Token;
+
+use crate::traits::{HasParser, ParserError, TokenInput};
+
+/// ANSI color for the `error:` label and its notes, independent of any
+/// particular dialect's syntax-highlighting scheme.
+const ERROR_COLOR: &str = "\x1b[31m"; // red
+const RESET: &str = "\x1b[0m";
+
+/// One parse failure, with its span and the expected-token set chumsky
+/// collected while backtracking, independent of any particular rendering.
+#[derive(Debug, Clone)]
+pub struct ParseReport {
+    /// Byte span where parsing failed.
+    pub span: SimpleSpan,
+    /// The `Display` form of the underlying chumsky error reason.
+    pub message: String,
+    /// Token/pattern descriptions chumsky expected at this position, e.g.
+    /// `["identifier", "\"(\""]`. Includes the nearest `.labelled("...")`
+    /// context (e.g. `"type"`) when chumsky attached one.
+    pub expected: Vec<String>,
+    /// The `Display` form of the token chumsky actually found at `span`, or
+    /// `None` when parsing failed by running out of input rather than
+    /// hitting a mismatched token.
+    pub found: Option<String>,
+}
+
+impl<'tokens, 'src> From<&Rich<'tokens, Token<'src>, SimpleSpan>> for ParseReport {
+    fn from(error: &Rich<'tokens, Token<'src>, SimpleSpan>) -> Self {
+        ParseReport {
+            span: *error.span(),
+            message: error.reason().to_string(),
+            expected: error.expected().map(ToString::to_string).collect(),
+            found: error.found().map(ToString::to_string),
+        }
+    }
+}
+
+/// Renders a batch of chumsky [`Rich`] errors against `src` as annotated
+/// snippets, one `error: ...` block per error (separated by a blank line):
+/// the offending line, a `^^^^` underline under the failing span, and
+/// `expected ...`/`found ...` notes. Pass `color` to wrap the label and
+/// notes in ANSI red for terminal output; pass `false` for plain text (e.g.
+/// test assertions, log files).
+pub fn render_diagnostics<'tokens, 'src>(
+    src: &str,
+    errors: &[Rich<'tokens, Token<'src>, SimpleSpan>],
+    color: bool,
+) -> String {
+    let reports: Vec<ParseReport> = errors.iter().map(ParseReport::from).collect();
+    render_reports(src, &reports, color)
+}
+
+/// Renders already-converted [`ParseReport`]s the same way
+/// [`render_diagnostics`] does. Useful when the `Rich` errors were already
+/// turned into reports once (e.g. by [`parse_recovering`]) and a caller
+/// wants to render that same set without going back through chumsky's
+/// error type.
+pub fn render_reports(src: &str, reports: &[ParseReport], color: bool) -> String {
+    let line_starts = line_starts(src);
+    reports
+        .iter()
+        .map(|report| render_one(report, src, &line_starts, color))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Writes [`render_diagnostics`]'s output straight to `writer`, so a caller
+/// with its own output stream (a REPL, an LSP's stderr, a log file) doesn't
+/// have to buffer the whole report into a `String` first.
+pub fn write_diagnostics<'tokens, 'src, W: io::Write>(
+    writer: &mut W,
+    src: &str,
+    errors: &[Rich<'tokens, Token<'src>, SimpleSpan>],
+    color: bool,
+) {
+    writer
+        .write_all(render_diagnostics(src, errors, color).as_bytes())
+        .expect("write failed");
+}
+
+/// Streaming counterpart to [`render_reports`], for the same reason
+/// [`write_diagnostics`] exists alongside [`render_diagnostics`].
+pub fn write_reports<W: io::Write>(
+    writer: &mut W,
+    src: &str,
+    reports: &[ParseReport],
+    color: bool,
+) {
+    writer
+        .write_all(render_reports(src, reports, color).as_bytes())
+        .expect("write failed");
+}
+
+/// Runs `parser` against `tokens` and returns both the best-effort output it
+/// managed to recover and every [`ParseReport`] collected along the way,
+/// instead of the all-or-nothing `Result<O, Vec<Rich<..>>>` that
+/// `.into_result()` gives.
+///
+/// Pairs with the statement-level `.recover_with(...)` that
+/// [`block`](crate::block)/[`bare_block`](crate::bare_block) apply to their
+/// bodies: a malformed statement no longer aborts parsing of the rest of
+/// the block, so a caller that wants to report every mistake in a function
+/// at once — rather than just the first one chumsky hit — should parse with
+/// this instead of `.into_result()`. The output is `None` only when chumsky
+/// couldn't recover far enough to produce anything at all (e.g. the input
+/// doesn't even start with a valid top-level item).
+pub fn parse_recovering<'tokens, 'src, I, O>(
+    parser: impl Parser<'tokens, I, O, ParserError<'tokens, 'src>>,
+    tokens: I,
+) -> (Option<O>, Vec<ParseReport>)
+where
+    I: TokenInput<'tokens, 'src>,
+{
+    let (output, errors) = parser.parse(tokens).into_output_errors();
+    (output, errors.iter().map(ParseReport::from).collect())
+}
+
+/// [`parse_recovering`] specialized to a whole dialect `L`, for callers that
+/// just want `(Option<L::Output>, Vec<ParseReport>)` for some token input
+/// without building `L::parser()` themselves.
+///
+/// A statement that fails inside a `block`/`bare_block` is still skipped
+/// rather than replaced by a placeholder AST node -- see
+/// [`bare_block`](crate::bare_block)'s doc comment -- so the returned tree has a hole
+/// where a bad statement was, and the matching [`ParseReport`] in the second
+/// element of the tuple is the only record that it was there at all. A
+/// caller that needs to know *where* a statement went missing should anchor
+/// on the reports' spans rather than on gaps in the tree.
+pub fn parse_dialect_recovering<'tokens, 'src, I, L>(tokens: I) -> (Option<L::Output>, Vec<ParseReport>)
+where
+    I: TokenInput<'tokens, 'src>,
+    L: Dialect + HasParser<'tokens, 'src> + 'tokens,
+{
+    parse_recovering(L::parser(), tokens)
+}
+
+fn render_one(report: &ParseReport, source: &str, line_starts: &[usize], color: bool) -> String {
+    let (open, reset) = if color { (ERROR_COLOR, RESET) } else { ("", "") };
+    let mut out = format!("{open}error: {}{reset}\n", report.message);
+    render_span_line(&mut out, source, line_starts, report.span, '^', None);
+
+    if !report.expected.is_empty() {
+        out.push_str(&format!(
+            "{open}  = note: expected {}{reset}\n",
+            report.expected.join(", ")
+        ));
+    }
+    if let Some(found) = &report.found {
+        out.push_str(&format!("{open}  = note: found {found}{reset}\n"));
+    }
+
+    // Drop the trailing newline so callers can freely wrap the report (e.g.
+    // joining several with blank lines) without doubling them up.
+    out.truncate(out.trim_end_matches('\n').len());
+    out
+}
+
+/// Writes one `gutter | text` line plus its underline into `out`, anchored
+/// at `span` within `source`. Shared by [`render_one`] above and the
+/// equivalent renderer in `function_text::report` for the higher-level
+/// `FunctionParseError` diagnostics.
+pub(crate) fn render_span_line(
+    out: &mut String,
+    source: &str,
+    line_starts: &[usize],
+    span: SimpleSpan,
+    underline: char,
+    trailing_message: Option<&str>,
+) {
+    let (line_no, col, text) = locate(source, line_starts, span.start);
+    let gutter = line_no.to_string();
+    let pad = " ".repeat(gutter.len());
+    let width = span
+        .end
+        .saturating_sub(span.start)
+        .max(1)
+        .min(text.len().saturating_sub(col).max(1));
+
+    out.push_str(&format!("{pad} |\n"));
+    out.push_str(&format!("{gutter} | {text}\n"));
+    match trailing_message {
+        Some(message) => out.push_str(&format!(
+            "{pad} | {}{} {message}\n",
+            " ".repeat(col),
+            underline.to_string().repeat(width)
+        )),
+        None => out.push_str(&format!(
+            "{pad} | {}{}\n",
+            " ".repeat(col),
+            underline.to_string().repeat(width)
+        )),
+    }
+}
+
+/// Byte offsets of the start of every line in `source`, including line 1.
+pub(crate) fn line_starts(source: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        source
+            .bytes()
+            .enumerate()
+            .filter(|(_, b)| *b == b'\n')
+            .map(|(i, _)| i + 1),
+    );
+    starts
+}
+
+/// Resolves a byte `offset` into a 1-based line number, 0-based column
+/// within that line, and the line's text (without its trailing newline).
+pub(crate) fn locate<'a>(
+    source: &'a str,
+    line_starts: &[usize],
+    offset: usize,
+) -> (usize, usize, &'a str) {
+    let line_index = match line_starts.binary_search(&offset) {
+        Ok(i) => i,
+        Err(i) => i.saturating_sub(1),
+    };
+    let line_start = line_starts[line_index];
+    let line_end = source[line_start..]
+        .find('\n')
+        .map(|i| line_start + i)
+        .unwrap_or(source.len());
+    (line_index + 1, offset - line_start, &source[line_start..line_end])
+}
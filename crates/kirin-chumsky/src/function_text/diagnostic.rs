@@ -0,0 +1,93 @@
+//! A renderer- and editor-agnostic diagnostic value: a primary labeled span,
+//! any number of secondary labeled spans, free-form notes, and a list of
+//! structured suggestions. [`FunctionParseError`](super::error::FunctionParseError)
+//! attaches one of these where it has enough positional information to
+//! offer more than a single message + span, so a downstream renderer (or an
+//! editor wiring up quick-fixes) doesn't have to re-derive it from the
+//! message string.
+
+use chumsky::span::SimpleSpan;
+
+/// A labeled source span: a location plus what it means in context.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub span: SimpleSpan,
+    pub message: String,
+}
+
+impl Label {
+    pub fn new(span: SimpleSpan, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// How confident a [`Suggestion`] is that applying it fixes the error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Applicability {
+    /// Applying the suggestion is known to produce valid, intended input;
+    /// safe for a tool to apply automatically.
+    MachineApplicable,
+    /// Applying the suggestion is plausible but not certain to be what the
+    /// author meant; a tool should ask before applying it.
+    MaybeIncorrect,
+}
+
+/// A suggested edit: replace `span` with `replacement`. `applicability`
+/// says whether a tool may apply it without asking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    pub span: SimpleSpan,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn new(
+        span: SimpleSpan,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+            applicability,
+        }
+    }
+}
+
+/// A structured diagnostic: a primary label plus whatever secondary context,
+/// notes, and suggestions help explain and fix it.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Diagnostic {
+    pub primary: Option<Label>,
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub fn new(primary: Label) -> Self {
+        Self {
+            primary: Some(primary),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_secondary(mut self, label: Label) -> Self {
+        self.secondary.push(label);
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+}
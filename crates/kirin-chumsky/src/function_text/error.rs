@@ -2,6 +2,8 @@ use chumsky::span::SimpleSpan;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
+use super::diagnostic::Diagnostic;
+
 /// Error categories for function-text parsing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FunctionParseErrorKind {
@@ -11,6 +13,26 @@ pub enum FunctionParseErrorKind {
     MissingStageDeclaration,
     BodyParseFailed,
     EmitFailed,
+    ShapeMismatch,
+    Multiple,
+}
+
+impl FunctionParseErrorKind {
+    /// A stable machine-readable code for this kind, for callers (JSON
+    /// emitters, editors) that want to match on the error category without
+    /// parsing the `Display` text, which is free to reword.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FunctionParseErrorKind::InvalidHeader => "invalid_header",
+            FunctionParseErrorKind::UnknownStage => "unknown_stage",
+            FunctionParseErrorKind::InconsistentFunctionName => "inconsistent_function_name",
+            FunctionParseErrorKind::MissingStageDeclaration => "missing_stage_declaration",
+            FunctionParseErrorKind::BodyParseFailed => "body_parse_failed",
+            FunctionParseErrorKind::EmitFailed => "emit_failed",
+            FunctionParseErrorKind::ShapeMismatch => "shape_mismatch",
+            FunctionParseErrorKind::Multiple => "multiple",
+        }
+    }
 }
 
 impl Display for FunctionParseErrorKind {
@@ -26,6 +48,8 @@ impl Display for FunctionParseErrorKind {
             }
             FunctionParseErrorKind::BodyParseFailed => write!(f, "function body parse failed"),
             FunctionParseErrorKind::EmitFailed => write!(f, "IR emission failed"),
+            FunctionParseErrorKind::ShapeMismatch => write!(f, "parameter shape mismatch"),
+            FunctionParseErrorKind::Multiple => write!(f, "multiple declarations failed to parse"),
         }
     }
 }
@@ -37,6 +61,15 @@ pub struct FunctionParseError {
     pub span: Option<SimpleSpan>,
     pub message: String,
     pub source: Option<Box<dyn Error + Send + Sync>>,
+    /// A renderer/editor-facing structured view of this error, when there's
+    /// enough positional information to offer more than `span` + `message`
+    /// (labeled secondary spans, notes, machine-applicable fixes).
+    pub diagnostic: Option<Diagnostic>,
+    /// Set when the failure was caused only by the token stream running out
+    /// while the parser still expected more (e.g. an unclosed `{`), never by
+    /// a genuine mismatch. An interactive caller (REPL) can use this to
+    /// buffer another line instead of reporting a hard error.
+    pub incomplete: bool,
 }
 
 impl FunctionParseError {
@@ -50,6 +83,8 @@ impl FunctionParseError {
             span,
             message: message.into(),
             source: None,
+            diagnostic: None,
+            incomplete: false,
         }
     }
 
@@ -57,6 +92,51 @@ impl FunctionParseError {
         self.source = Some(Box::new(source));
         self
     }
+
+    pub(crate) fn with_diagnostic(mut self, diagnostic: Diagnostic) -> Self {
+        self.diagnostic = Some(diagnostic);
+        self
+    }
+
+    pub(crate) fn with_incomplete(mut self, incomplete: bool) -> Self {
+        self.incomplete = incomplete;
+        self
+    }
+
+    /// Combine several recovered declaration errors into one, so a file with
+    /// multiple mistakes reports all of them instead of only the first.
+    /// `errors` must be non-empty.
+    pub(crate) fn many(mut errors: Vec<FunctionParseError>) -> Self {
+        let first = errors.remove(0);
+        if errors.is_empty() {
+            return first;
+        }
+        let span = first.span;
+        let mut all = vec![first];
+        all.append(&mut errors);
+        let incomplete = all.iter().all(|error| error.incomplete);
+        let message = format!("{} declarations failed to parse", all.len());
+        FunctionParseError::new(FunctionParseErrorKind::Multiple, span, message)
+            .with_source(MultipleParseErrors { errors: all })
+            .with_incomplete(incomplete)
+    }
+
+    /// The individual errors this represents: just `self` for every kind
+    /// except [`FunctionParseErrorKind::Multiple`] (built by [`Self::many`]),
+    /// which expands into the errors it combined — so a caller that wants
+    /// one diagnostic per real failure doesn't have to know about the
+    /// wrapper kind at all.
+    pub(crate) fn flatten(&self) -> Vec<&FunctionParseError> {
+        match self.kind {
+            FunctionParseErrorKind::Multiple => self
+                .source
+                .as_deref()
+                .and_then(|source| source.downcast_ref::<MultipleParseErrors>())
+                .map(|multiple| multiple.errors.iter().collect())
+                .unwrap_or_else(|| vec![self]),
+            _ => vec![self],
+        }
+    }
 }
 
 impl Display for FunctionParseError {
@@ -98,3 +178,22 @@ impl Display for DiagnosticError {
 }
 
 impl Error for DiagnosticError {}
+
+#[derive(Debug)]
+struct MultipleParseErrors {
+    errors: Vec<FunctionParseError>,
+}
+
+impl Display for MultipleParseErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for MultipleParseErrors {}
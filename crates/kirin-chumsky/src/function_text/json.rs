@@ -0,0 +1,118 @@
+//! JSON emission for [`FunctionParseError`], gated behind the
+//! `json-diagnostics` feature so a caller that only ever renders the
+//! `Display` text doesn't pull in a serde dependency.
+//!
+//! [`JsonDiagnostic`] is its own serializable view rather than a
+//! `Serialize` derive on [`Diagnostic`]: it takes `code`, span and message
+//! from the error itself and `secondary`/`notes`/`suggestions` from the
+//! attached [`Diagnostic`] (whose `primary` label is not written out).
+//! Spans are written out as plain `start`/`end` byte offsets instead of
+//! [`SimpleSpan`], and the error `kind` is written as its stable
+//! [`FunctionParseErrorKind::code`] string rather than the `Debug` variant
+//! name, so the shape stays stable even if the in-memory types change.
+
+use serde::Serialize;
+
+use super::diagnostic::{Applicability, Label, Suggestion};
+use super::error::FunctionParseError;
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JsonApplicability {
+    MachineApplicable,
+    MaybeIncorrect,
+}
+
+impl From<Applicability> for JsonApplicability {
+    fn from(applicability: Applicability) -> Self {
+        match applicability {
+            Applicability::MachineApplicable => JsonApplicability::MachineApplicable,
+            Applicability::MaybeIncorrect => JsonApplicability::MaybeIncorrect,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    start: usize,
+    end: usize,
+    message: String,
+}
+
+impl From<&Label> for JsonLabel {
+    fn from(label: &Label) -> Self {
+        Self {
+            start: label.span.start,
+            end: label.span.end,
+            message: label.message.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion {
+    start: usize,
+    end: usize,
+    replacement: String,
+    applicability: JsonApplicability,
+}
+
+impl From<&Suggestion> for JsonSuggestion {
+    fn from(suggestion: &Suggestion) -> Self {
+        Self {
+            start: suggestion.span.start,
+            end: suggestion.span.end,
+            replacement: suggestion.replacement.clone(),
+            applicability: suggestion.applicability.into(),
+        }
+    }
+}
+
+/// A machine-readable view of one [`FunctionParseError`]: a stable error
+/// `code`, the primary span as byte offsets, the message, and every
+/// secondary label / note / fix-it suggestion attached to it.
+#[derive(Serialize)]
+pub struct JsonDiagnostic {
+    code: &'static str,
+    start: Option<usize>,
+    end: Option<usize>,
+    message: String,
+    secondary: Vec<JsonLabel>,
+    notes: Vec<String>,
+    suggestions: Vec<JsonSuggestion>,
+}
+
+impl From<&FunctionParseError> for JsonDiagnostic {
+    fn from(error: &FunctionParseError) -> Self {
+        let (secondary, notes, suggestions) = match &error.diagnostic {
+            Some(diagnostic) => (
+                diagnostic.secondary.iter().map(JsonLabel::from).collect(),
+                diagnostic.notes.clone(),
+                diagnostic
+                    .suggestions
+                    .iter()
+                    .map(JsonSuggestion::from)
+                    .collect(),
+            ),
+            None => (Vec::new(), Vec::new(), Vec::new()),
+        };
+        Self {
+            code: error.kind.code(),
+            start: error.span.map(|span| span.start),
+            end: error.span.map(|span| span.end),
+            message: error.message.clone(),
+            secondary,
+            notes,
+            suggestions,
+        }
+    }
+}
+
+/// Flatten a [`FunctionParseError`] into one [`JsonDiagnostic`] per
+/// underlying failure, so a [`FunctionParseErrorKind::Multiple`](super::error::FunctionParseErrorKind::Multiple)
+/// wrapper (emitted when a file has more than one broken declaration) reads
+/// as a stream of real diagnostics instead of a single "N declarations
+/// failed" entry.
+pub fn to_json_diagnostics(error: &FunctionParseError) -> Vec<JsonDiagnostic> {
+    error.flatten().into_iter().map(JsonDiagnostic::from).collect()
+}
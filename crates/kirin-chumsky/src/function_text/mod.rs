@@ -1,9 +1,19 @@
+mod diagnostic;
 mod error;
+#[cfg(feature = "json-diagnostics")]
+mod json;
 mod parse_text;
+mod repl;
+mod report;
 mod syntax;
 
+pub use diagnostic::{Applicability, Diagnostic, Label, Suggestion};
 pub use error::{FunctionParseError, FunctionParseErrorKind};
-pub use parse_text::ParsePipelineText;
+#[cfg(feature = "json-diagnostics")]
+pub use json::JsonDiagnostic;
+pub use parse_text::{DebugArtifactCollector, DebugAsk, ParsePipelineText, StageArtifact};
+pub use repl::{PipelineRepl, ReplOutcome, ReplStep};
+pub use report::render_report;
 
 #[cfg(test)]
 mod tests;
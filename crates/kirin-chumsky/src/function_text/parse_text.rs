@@ -70,12 +70,73 @@
 //! - pass 2 cannot find `(A, missing)` in the staged lookup;
 //! - returns `MissingStageDeclaration`.
 //!
+//! ## Cross-stage aliasing
+//!
+//! A third declaration, `use @B fn @bar from @A;`, lets a `specialize @B fn
+//! @bar ...` body exist without its own `stage @B fn @bar(...) -> ...;`
+//! header, by aliasing the one already declared at stage `A`. Pass 1 only
+//! validates that `(A, bar)` exists and records the alias; it cannot eagerly
+//! create `(B, bar)`'s staged function the way a real `stage` header would,
+//! because stage dispatch has no way to hand a dialect-typed `Signature<L>`
+//! back across two different stages in one call (see
+//! [`apply_specialize_declaration`] for where it's actually created). So the
+//! staged function is materialized lazily, in pass 2, by the first
+//! `specialize @B fn @bar ...` that claims the alias, using that
+//! declaration's own signature.
+//!
+//! ## Signature shape DSL
+//!
+//! A header's return type may be followed by an optional `[...]` clause
+//! declaring the *shape* repeated `stage` headers for the same staged
+//! function are allowed to differ by, instead of requiring
+//! [`Header::signature`] to be byte-identical:
+//!
+//! ```text
+//! stage @A fn @opt(()) -> () [unroll(u): int, target: symbol];
+//! stage @A fn @opt(()) -> () [unroll(u): int, target: symbol, hint: type];
+//! ```
+//!
+//! - a bare shape keyword (`int`, `symbol`, `type`) is a positional slot,
+//!   optionally suffixed `?` or given a `= default` to mark it optional;
+//! - `name: shape` (with an optional short alias `name(n): shape`) is a
+//!   named flag, matched across declarations by name rather than position.
+//!
+//! Two declarations with shape clauses are compatible if their positional
+//! slots agree in shape at every index both declare (a slot only one side
+//! has must be optional there), and any flag named on both sides agrees in
+//! shape — so a later header that only adds an optional param or a new
+//! named flag is accepted. A mismatch is reported as
+//! [`FunctionParseErrorKind::ShapeMismatch`](super::error::FunctionParseErrorKind::ShapeMismatch),
+//! labelled at the offending slot.
+//!
 //! ## Data flow summary
 //!
 //! - `staged_lookup`: stable key map for staged-function resolution across passes.
-//! - `function_lookup`: name-to-function cache to avoid repeated arena scans.
+//! - `function_lookup`: interned-name-to-function cache to avoid repeated arena scans.
+//! - `interner`: assigns each function name a `Copy` `SymId`, shared by `function_lookup`
+//!   and every name lookup, so re-parsing doesn't re-hash/re-allocate `String`s per declaration.
+//! - `use_aliases`: `(stage, function)` pairs allowed to materialize without their own `stage` header.
+//! - `declaration_spans`: the source span each staged function was first declared at, so
+//!   "missing"/"conflicting" errors can point back at the original declaration, not just name it.
+//! - `declared_shapes`: the `[...]` shape clause (if any) a `stage` header first declared for a
+//!   staged function, so a later repeated header with its own shape clause can be compared
+//!   structurally instead of requiring a byte-identical `Signature` (see
+//!   `ensure_staged_signature_matches`).
 //! - `pending_specializations`: source offsets to re-dispatch specialize bodies.
 //! - `ParseState`: deduplicated set of touched abstract functions returned to caller.
+//! - `debug_asks`/`debug_collector`: opt-in, stage-keyed snapshot capture (see
+//!   [`ParsePipelineText::parse_with_debug_asks`]); `&[]`/`None` on the plain
+//!   [`ParsePipelineText::parse`] path, so it costs nothing when unused.
+//!
+//! ## Error recovery
+//!
+//! A declaration that fails to parse in either pass does not abort the
+//! whole file: the failure is recorded and parsing resynchronizes at the
+//! next top-level `stage`/`specialize`/`use` keyword (tracking brace depth so a
+//! malformed declaration's own body is skipped rather than mistaken for a
+//! resync point), then continues with the remaining declarations.
+//! `parse` succeeds only once every declaration parsed cleanly; otherwise
+//! it returns every recorded error combined via [`FunctionParseError::many`].
 //!
 use std::collections::{HashMap, HashSet};
 
@@ -90,13 +151,45 @@ use strsim::levenshtein;
 
 use crate::{EmitContext, EmitIR, HasParser};
 
+use super::diagnostic::{Applicability, Diagnostic, Label, Suggestion};
 use super::error::{DiagnosticError, FunctionParseError, FunctionParseErrorKind};
-use super::syntax::{ChumskyError, Declaration, Header, parse_one_declaration, tokenize};
+use super::syntax::{
+    ChumskyError, Declaration, Header, SignatureShape, needs_more_input, parse_one_declaration,
+    tokenize,
+};
 use crate::ast::SymbolName;
 
 /// Parse function text into a pipeline using stage-driven dialect dispatch.
 pub trait ParsePipelineText {
     fn parse(&mut self, src: &str) -> Result<Vec<Function>, FunctionParseError>;
+
+    /// Like [`Self::parse`], but also snapshots a [`StageArtifact`] into
+    /// `collector` every time a `stage`/`specialize` declaration for a stage
+    /// matching one of `asks` is processed — the staged function's
+    /// signature, and (once pass 2 emits one) its body, or the conflicting
+    /// existing signature if the declaration was about to be rejected by
+    /// [`ensure_staged_signature_matches`]. Lets a caller debug exactly
+    /// which stage a `StagedFunction` signature diverged at without
+    /// instrumenting the parser itself.
+    fn parse_with_debug_asks(
+        &mut self,
+        src: &str,
+        asks: &[DebugAsk],
+        collector: &mut DebugArtifactCollector,
+    ) -> Result<Vec<Function>, FunctionParseError>;
+
+    /// Like [`Self::parse`], but reports failure as the stream of
+    /// [`JsonDiagnostic`](super::json::JsonDiagnostic)s a test harness or
+    /// editor would want, rather than a single [`FunctionParseError`] that
+    /// may itself bundle several underlying failures.
+    #[cfg(feature = "json-diagnostics")]
+    fn parse_json_diagnostics(
+        &mut self,
+        src: &str,
+    ) -> Result<Vec<Function>, Vec<super::json::JsonDiagnostic>> {
+        self.parse(src)
+            .map_err(|error| super::json::to_json_diagnostics(&error))
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -105,6 +198,48 @@ struct StagedKey {
     function: Function,
 }
 
+/// A cheap, `Copy` key for an interned function name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct SymId(u32);
+
+/// Interns function names so `function_lookup` can key on a `Copy` integer
+/// instead of hashing and allocating a `String` for every declaration in the
+/// file. Seeded once from the pipeline's existing functions and reused
+/// across both passes.
+#[derive(Default)]
+struct SymbolInterner {
+    names: Vec<String>,
+    ids: HashMap<String, SymId>,
+}
+
+impl SymbolInterner {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern `name`, allocating a new [`SymId`] the first time it's seen.
+    fn intern(&mut self, name: &str) -> SymId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = SymId(self.names.len() as u32);
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Look up an already-interned name without interning it, so resolving a
+    /// `specialize`/`use` reference to a name that was never declared costs
+    /// a hash lookup, not an allocation.
+    fn get(&self, name: &str) -> Option<SymId> {
+        self.ids.get(name).copied()
+    }
+
+    fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum DeclKeyword {
     Stage,
@@ -118,6 +253,14 @@ struct DeclarationHead<'src> {
     function: SymbolName<'src>,
 }
 
+/// Head of a `use @target fn @name from @source;` alias declaration.
+#[derive(Debug, Clone)]
+struct UseDeclarationHead<'src> {
+    target: SymbolName<'src>,
+    function: SymbolName<'src>,
+    source: SymbolName<'src>,
+}
+
 #[derive(Clone, Copy, Debug)]
 struct FirstPassOutcome {
     keyword: DeclKeyword,
@@ -130,6 +273,16 @@ struct FirstPassDispatchResult {
     link: Option<(Function, StagedFunction)>,
 }
 
+/// The result of dispatching one pass-2 `specialize` declaration: where to
+/// resume from, and the `(function, staged_function)` pair to link when the
+/// declaration materialized a `use`-aliased staged function that didn't
+/// exist yet.
+#[derive(Clone, Copy, Debug)]
+struct SecondPassDispatchResult {
+    next_index: usize,
+    link: Option<(Function, StagedFunction)>,
+}
+
 /// Shared mutable state threaded through both parse passes.
 struct ParseState {
     touched_functions: Vec<Function>,
@@ -151,13 +304,80 @@ impl ParseState {
     }
 }
 
+/// Identifies a stage to capture debug artifacts from, the same two ways a
+/// declaration can reference a stage in source text: by its symbolic name
+/// (`@A`) or by its raw numeric id (`@1`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DebugAsk {
+    ByStage { stage_name: String },
+    ById { id: usize },
+}
+
+impl DebugAsk {
+    fn matches(&self, stage_id: CompileStage, stage_name: &str) -> bool {
+        match self {
+            DebugAsk::ByStage { stage_name: asked } => asked == stage_name,
+            DebugAsk::ById { id } => Id::from(stage_id).raw() == *id,
+        }
+    }
+}
+
+fn stage_matches_any_ask(stage_id: CompileStage, stage_name: &str, asks: &[DebugAsk]) -> bool {
+    asks.iter().any(|ask| ask.matches(stage_id, stage_name))
+}
+
+/// A staged-function snapshot captured because its stage matched a
+/// [`DebugAsk`]: the signature that was parsed (and, once pass 2 emits a
+/// body for it, the body's `Statement`). `signature`/`conflicting_with` are
+/// rendered with `Debug` rather than kept as typed `Signature<L>` values, so
+/// one collector can hold artifacts captured across every dialect a
+/// heterogeneous pipeline might dispatch to.
+#[derive(Clone, Debug)]
+pub struct StageArtifact {
+    pub stage_id: CompileStage,
+    pub function: Function,
+    pub signature: String,
+    pub body: Option<Statement>,
+    /// Set only when this artifact was captured at the moment
+    /// [`ensure_staged_signature_matches`] was about to reject a repeated
+    /// `stage` header: the signature already on file, to compare side by
+    /// side with `signature` (the incoming header's).
+    pub conflicting_with: Option<String>,
+}
+
+/// Collects [`StageArtifact`]s captured during a parse for stages matching
+/// a caller-supplied set of [`DebugAsk`]s (see
+/// [`ParsePipelineText::parse_with_debug_asks`]).
+#[derive(Debug, Default)]
+pub struct DebugArtifactCollector {
+    artifacts: Vec<StageArtifact>,
+}
+
+impl DebugArtifactCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn artifacts(&self) -> &[StageArtifact] {
+        &self.artifacts
+    }
+
+    fn record(&mut self, artifact: StageArtifact) {
+        self.artifacts.push(artifact);
+    }
+}
+
 struct FirstPassAction<'a, 'src> {
     tokens: &'a [(Token<'src>, SimpleSpan)],
     start_index: usize,
     function: Option<Function>,
     function_symbol: Option<GlobalSymbol>,
     staged_lookup: &'a mut HashMap<StagedKey, StagedFunction>,
+    declaration_spans: &'a mut HashMap<StagedKey, SimpleSpan>,
+    declared_shapes: &'a mut HashMap<StagedKey, SignatureShape>,
     state: &'a mut ParseState,
+    debug_asks: &'a [DebugAsk],
+    debug_collector: Option<&'a mut DebugArtifactCollector>,
 }
 
 impl<'src, S, L> StageActionMut<S, L> for FirstPassAction<'_, 'src>
@@ -199,7 +419,11 @@ where
                     function_symbol,
                     &header,
                     self.staged_lookup,
+                    self.declaration_spans,
+                    self.declared_shapes,
                     self.state,
+                    self.debug_asks,
+                    self.debug_collector.as_deref_mut(),
                 )?;
                 Ok(FirstPassDispatchResult {
                     outcome: FirstPassOutcome {
@@ -223,9 +447,14 @@ where
 struct SecondPassSpecializeAction<'a, 'src> {
     tokens: &'a [(Token<'src>, SimpleSpan)],
     start_index: usize,
-    function_lookup: &'a HashMap<String, Function>,
-    staged_lookup: &'a HashMap<StagedKey, StagedFunction>,
+    function_lookup: &'a HashMap<SymId, Function>,
+    interner: &'a SymbolInterner,
+    staged_lookup: &'a mut HashMap<StagedKey, StagedFunction>,
+    declaration_spans: &'a mut HashMap<StagedKey, SimpleSpan>,
+    use_aliases: &'a HashMap<StagedKey, GlobalSymbol>,
     state: &'a mut ParseState,
+    debug_asks: &'a [DebugAsk],
+    debug_collector: Option<&'a mut DebugArtifactCollector>,
 }
 
 impl<'src, S, L> StageActionMut<S, L> for SecondPassSpecializeAction<'_, 'src>
@@ -235,7 +464,7 @@ where
     L::Type: HasParser<'src, 'src, Output = L::Type>,
     <L as HasParser<'src, 'src>>::Output: EmitIR<L, Output = Statement>,
 {
-    type Output = usize;
+    type Output = SecondPassDispatchResult;
     type Error = FunctionParseError;
 
     fn run(
@@ -258,18 +487,23 @@ where
             ));
         };
 
-        apply_specialize_declaration::<L>(
+        let link = apply_specialize_declaration::<L>(
             stage,
             stage_id,
             &header,
             &body,
             span,
             self.function_lookup,
+            self.interner,
             self.staged_lookup,
+            self.declaration_spans,
+            self.use_aliases,
             self.state,
+            self.debug_asks,
+            self.debug_collector.as_deref_mut(),
         )?;
 
-        Ok(next_index)
+        Ok(SecondPassDispatchResult { next_index, link })
     }
 }
 
@@ -281,10 +515,50 @@ where
             FirstPassDispatchResult,
             FunctionParseError,
         >,
-    for<'a, 'src> S:
-        SupportsStageDispatchMut<SecondPassSpecializeAction<'a, 'src>, usize, FunctionParseError>,
+    for<'a, 'src> S: SupportsStageDispatchMut<
+            SecondPassSpecializeAction<'a, 'src>,
+            SecondPassDispatchResult,
+            FunctionParseError,
+        >,
 {
     fn parse(&mut self, src: &str) -> Result<Vec<Function>, FunctionParseError> {
+        self.parse_impl(src, &[], None)
+    }
+
+    fn parse_with_debug_asks(
+        &mut self,
+        src: &str,
+        asks: &[DebugAsk],
+        collector: &mut DebugArtifactCollector,
+    ) -> Result<Vec<Function>, FunctionParseError> {
+        self.parse_impl(src, asks, Some(collector))
+    }
+}
+
+impl<S> Pipeline<S>
+where
+    S: StageMeta,
+    for<'a, 'src> S: SupportsStageDispatchMut<
+            FirstPassAction<'a, 'src>,
+            FirstPassDispatchResult,
+            FunctionParseError,
+        >,
+    for<'a, 'src> S: SupportsStageDispatchMut<
+            SecondPassSpecializeAction<'a, 'src>,
+            SecondPassDispatchResult,
+            FunctionParseError,
+        >,
+{
+    /// Shared implementation behind [`ParsePipelineText::parse`] and
+    /// [`ParsePipelineText::parse_with_debug_asks`]: `asks`/`collector` are
+    /// empty/`None` on the plain path, so debug capture costs nothing beyond
+    /// an `Option` check when the caller isn't using it.
+    fn parse_impl(
+        &mut self,
+        src: &str,
+        debug_asks: &[DebugAsk],
+        mut debug_collector: Option<&mut DebugArtifactCollector>,
+    ) -> Result<Vec<Function>, FunctionParseError> {
         let tokens = tokenize(src);
         if tokens.is_empty() {
             return Err(FunctionParseError::new(
@@ -295,59 +569,75 @@ where
         }
 
         let mut staged_lookup = collect_staged_lookup(self);
-        let mut function_lookup = collect_function_lookup(self);
+        // Source span of the `stage`/`specialize` declaration that first
+        // materialized each staged function, so later errors can point back
+        // at it instead of only naming it.
+        let mut declaration_spans: HashMap<StagedKey, SimpleSpan> = HashMap::new();
+        // The `[...]` shape clause (if any) a staged function's first `stage`
+        // header declared, consulted by `ensure_staged_signature_matches` when
+        // a later repeated header also carries one.
+        let mut declared_shapes: HashMap<StagedKey, SignatureShape> = HashMap::new();
+        let mut interner = SymbolInterner::new();
+        let mut function_lookup = collect_function_lookup(self, &mut interner);
+        // `(stage, function)` pairs declared via `use ... from ...;` that may
+        // materialize their own staged function without a `stage` header.
+        let mut use_aliases: HashMap<StagedKey, GlobalSymbol> = HashMap::new();
         let mut state = ParseState::new();
         // We intentionally defer specialize bodies to pass 2 so forward
         // references like `specialize @A fn @foo ...` before `stage @A fn @foo`
         // are validated against the full header set.
         let mut pending_specializations: Vec<(usize, CompileStage, SymbolName<'_>)> = Vec::new();
+        // Declarations that fail to parse are recorded here and skipped by
+        // resynchronizing at the next top-level declaration, so one mistake
+        // doesn't prevent every other declaration in the file from being
+        // reported too.
+        let mut errors: Vec<FunctionParseError> = Vec::new();
 
         let mut index = 0;
         while index < tokens.len() {
-            let head = parse_declaration_head(&tokens, index)?;
-            let stage_id = resolve_or_create_stage_symbol(self, &head.stage)?;
-            let (function, function_symbol) = if matches!(head.keyword, DeclKeyword::Stage) {
-                let function =
-                    get_or_create_function_by_name(self, &mut function_lookup, head.function.name);
-                (Some(function), Some(function_symbol(self, function)))
-            } else {
-                (None, None)
-            };
-
-            let mut action = FirstPassAction {
-                tokens: &tokens,
-                start_index: index,
-                function,
-                function_symbol,
-                staged_lookup: &mut staged_lookup,
-                state: &mut state,
-            };
-            let dispatch =
-                dispatch_stage_action_required(self, stage_id, &head.stage, &mut action)?;
-            let outcome = dispatch.outcome;
-            if let Some((function, staged_function)) = dispatch.link {
-                self.link(function, stage_id, staged_function);
+            if matches!(tokens[index].0, Token::Identifier("use")) {
+                match run_use_declaration(
+                    self,
+                    &tokens,
+                    index,
+                    &function_lookup,
+                    &staged_lookup,
+                    &mut use_aliases,
+                    &interner,
+                ) {
+                    Ok(next_index) => index = next_index,
+                    Err(error) => {
+                        errors.push(error);
+                        index = recover_to_next_declaration(&tokens, index);
+                    }
+                }
+                continue;
             }
 
-            if outcome.keyword != head.keyword {
-                return Err(FunctionParseError::new(
-                    FunctionParseErrorKind::InvalidHeader,
-                    Some(head.stage.span),
-                    "declaration keyword mismatch while parsing",
-                ));
-            }
-            ensure_forward_progress(
-                outcome.next_index,
+            match run_first_pass_declaration(
+                self,
+                &tokens,
                 index,
-                head.stage.span,
-                "failed to advance while parsing declaration",
-            )?;
-
-            if matches!(outcome.keyword, DeclKeyword::Specialize) {
-                pending_specializations.push((index, stage_id, head.stage));
+                &mut function_lookup,
+                &mut interner,
+                &mut staged_lookup,
+                &mut declaration_spans,
+                &mut declared_shapes,
+                &mut state,
+                debug_asks,
+                debug_collector.as_deref_mut(),
+            ) {
+                Ok(step) => {
+                    if step.is_specialize {
+                        pending_specializations.push((index, step.stage_id, step.stage));
+                    }
+                    index = step.next_index;
+                }
+                Err(error) => {
+                    errors.push(error);
+                    index = recover_to_next_declaration(&tokens, index);
+                }
             }
-
-            index = outcome.next_index;
         }
 
         for (start_index, stage_id, stage_symbol) in pending_specializations {
@@ -355,23 +645,127 @@ where
                 tokens: &tokens,
                 start_index,
                 function_lookup: &function_lookup,
-                staged_lookup: &staged_lookup,
+                interner: &interner,
+                staged_lookup: &mut staged_lookup,
+                declaration_spans: &mut declaration_spans,
+                use_aliases: &use_aliases,
                 state: &mut state,
+                debug_asks,
+                debug_collector: debug_collector.as_deref_mut(),
             };
-            let next_index =
-                dispatch_stage_action_required(self, stage_id, &stage_symbol, &mut action)?;
-            ensure_forward_progress(
-                next_index,
-                start_index,
-                stage_symbol.span,
-                "failed to advance while parsing specialize declaration",
-            )?;
+            let result = dispatch_stage_action_required(self, stage_id, &stage_symbol, &mut action)
+                .and_then(|dispatch| {
+                    ensure_forward_progress(
+                        dispatch.next_index,
+                        start_index,
+                        stage_symbol.span,
+                        "failed to advance while parsing specialize declaration",
+                    )?;
+                    Ok(dispatch.link)
+                });
+            match result {
+                Ok(link) => {
+                    if let Some((function, staged_function)) = link {
+                        self.link(function, stage_id, staged_function);
+                    }
+                }
+                Err(error) => errors.push(error),
+            }
         }
 
-        Ok(state.touched_functions)
+        if errors.is_empty() {
+            Ok(state.touched_functions)
+        } else {
+            Err(FunctionParseError::many(errors))
+        }
     }
 }
 
+/// The result of successfully parsing one pass-1 declaration: where to
+/// resume from, and whether it was a `specialize` declaration pass 2 still
+/// needs to revisit.
+struct FirstPassStep<'src> {
+    stage_id: CompileStage,
+    stage: SymbolName<'src>,
+    is_specialize: bool,
+    next_index: usize,
+}
+
+/// Parse and apply a single pass-1 declaration starting at `index`. Kept as
+/// a standalone function (rather than inlined in `parse`) so its error can
+/// be caught and recovered from instead of aborting the whole file.
+#[allow(clippy::too_many_arguments)]
+fn run_first_pass_declaration<'src, S>(
+    pipeline: &mut Pipeline<S>,
+    tokens: &[(Token<'src>, SimpleSpan)],
+    index: usize,
+    function_lookup: &mut HashMap<SymId, Function>,
+    interner: &mut SymbolInterner,
+    staged_lookup: &mut HashMap<StagedKey, StagedFunction>,
+    declaration_spans: &mut HashMap<StagedKey, SimpleSpan>,
+    declared_shapes: &mut HashMap<StagedKey, SignatureShape>,
+    state: &mut ParseState,
+    debug_asks: &[DebugAsk],
+    debug_collector: Option<&mut DebugArtifactCollector>,
+) -> Result<FirstPassStep<'src>, FunctionParseError>
+where
+    S: StageMeta,
+    for<'a, 'tsrc> S: SupportsStageDispatchMut<
+            FirstPassAction<'a, 'tsrc>,
+            FirstPassDispatchResult,
+            FunctionParseError,
+        >,
+{
+    let head = parse_declaration_head(tokens, index)?;
+    let stage_id = resolve_or_create_stage_symbol(pipeline, &head.stage)?;
+    let (function, function_symbol) = if matches!(head.keyword, DeclKeyword::Stage) {
+        let function =
+            get_or_create_function_by_name(pipeline, function_lookup, interner, head.function.name);
+        (Some(function), Some(function_symbol(pipeline, function)))
+    } else {
+        (None, None)
+    };
+
+    let mut action = FirstPassAction {
+        tokens,
+        start_index: index,
+        function,
+        function_symbol,
+        staged_lookup,
+        declaration_spans,
+        declared_shapes,
+        state,
+        debug_asks,
+        debug_collector,
+    };
+    let dispatch = dispatch_stage_action_required(pipeline, stage_id, &head.stage, &mut action)?;
+    let outcome = dispatch.outcome;
+    if let Some((function, staged_function)) = dispatch.link {
+        pipeline.link(function, stage_id, staged_function);
+    }
+
+    if outcome.keyword != head.keyword {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(head.stage.span),
+            "declaration keyword mismatch while parsing",
+        ));
+    }
+    ensure_forward_progress(
+        outcome.next_index,
+        index,
+        head.stage.span,
+        "failed to advance while parsing declaration",
+    )?;
+
+    Ok(FirstPassStep {
+        stage_id,
+        stage: head.stage,
+        is_specialize: matches!(outcome.keyword, DeclKeyword::Specialize),
+        next_index: outcome.next_index,
+    })
+}
+
 fn parse_declaration_head<'src>(
     tokens: &[(Token<'src>, SimpleSpan)],
     start_index: usize,
@@ -391,8 +785,12 @@ fn parse_declaration_head<'src>(
             return Err(FunctionParseError::new(
                 FunctionParseErrorKind::InvalidHeader,
                 Some(*keyword_span),
-                "expected declaration starting with 'stage' or 'specialize'",
-            ));
+                "expected declaration starting with 'stage', 'specialize', or 'use'",
+            )
+            .with_diagnostic(Diagnostic::new(Label::new(
+                *keyword_span,
+                "expected 'stage', 'specialize', or 'use' here",
+            ))));
         }
     };
 
@@ -401,7 +799,11 @@ fn parse_declaration_head<'src>(
             FunctionParseErrorKind::InvalidHeader,
             Some(*keyword_span),
             "expected stage symbol after declaration keyword",
-        ));
+        )
+        .with_diagnostic(Diagnostic::new(Label::new(
+            *keyword_span,
+            "expected a stage symbol (e.g., @A) after this",
+        ))));
     };
 
     let Token::Symbol(stage_name) = stage_symbol else {
@@ -409,6 +811,10 @@ fn parse_declaration_head<'src>(
             FunctionParseErrorKind::InvalidHeader,
             Some(*stage_span),
             "stage names must use global-symbol syntax (e.g., @A)",
+        )
+        .with_diagnostic(
+            Diagnostic::new(Label::new(*stage_span, "expected a symbol like '@A' here"))
+                .with_secondary(Label::new(*keyword_span, "for this declaration")),
         ));
     };
 
@@ -417,6 +823,10 @@ fn parse_declaration_head<'src>(
             FunctionParseErrorKind::InvalidHeader,
             Some(*stage_span),
             "expected 'fn' after stage symbol",
+        )
+        .with_diagnostic(
+            Diagnostic::new(Label::new(*stage_span, "expected 'fn' after this"))
+                .with_secondary(Label::new(*keyword_span, "for this declaration")),
         ));
     };
     let Token::Identifier("fn") = fn_keyword else {
@@ -424,6 +834,10 @@ fn parse_declaration_head<'src>(
             FunctionParseErrorKind::InvalidHeader,
             Some(*fn_span),
             "expected 'fn' before function symbol",
+        )
+        .with_diagnostic(
+            Diagnostic::new(Label::new(*fn_span, "expected 'fn' here"))
+                .with_secondary(Label::new(*keyword_span, "for this declaration")),
         ));
     };
 
@@ -432,6 +846,13 @@ fn parse_declaration_head<'src>(
             FunctionParseErrorKind::InvalidHeader,
             Some(*fn_span),
             "expected function symbol after 'fn'",
+        )
+        .with_diagnostic(
+            Diagnostic::new(Label::new(
+                *fn_span,
+                "expected a function symbol (e.g., @foo) after this",
+            ))
+            .with_secondary(Label::new(*keyword_span, "for this declaration")),
         ));
     };
     let Token::Symbol(function_name) = function_symbol else {
@@ -439,6 +860,13 @@ fn parse_declaration_head<'src>(
             FunctionParseErrorKind::InvalidHeader,
             Some(*function_span),
             "function names must use global-symbol syntax (e.g., @foo)",
+        )
+        .with_diagnostic(
+            Diagnostic::new(Label::new(
+                *function_span,
+                "expected a symbol like '@foo' here",
+            ))
+            .with_secondary(Label::new(*keyword_span, "for this declaration")),
         ));
     };
 
@@ -455,6 +883,209 @@ fn parse_declaration_head<'src>(
     })
 }
 
+/// Parse and apply a single `use @target fn @name from @source;` alias
+/// declaration. Unlike `stage`/`specialize`, this never dispatches to a
+/// per-dialect parser: it only records that `(target, name)` may be
+/// materialized without its own `stage` header once a matching `specialize`
+/// supplies a signature (see the module doc for why it can't be created
+/// eagerly here).
+fn run_use_declaration<'src, S>(
+    pipeline: &mut Pipeline<S>,
+    tokens: &[(Token<'src>, SimpleSpan)],
+    index: usize,
+    function_lookup: &HashMap<SymId, Function>,
+    staged_lookup: &HashMap<StagedKey, StagedFunction>,
+    use_aliases: &mut HashMap<StagedKey, GlobalSymbol>,
+    interner: &SymbolInterner,
+) -> Result<usize, FunctionParseError>
+where
+    S: StageMeta,
+{
+    let (head, next_index) = parse_use_declaration_head(tokens, index)?;
+
+    let target_id = resolve_or_create_stage_symbol(pipeline, &head.target)?;
+    let Some(source_id) = find_stage_symbol(pipeline, head.source.name) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::UnknownStage,
+            Some(head.source.span),
+            format!("stage '@{}' does not exist in the pipeline", head.source.name),
+        ));
+    };
+
+    let Some(function) = interner
+        .get(head.function.name)
+        .and_then(|id| function_lookup.get(&id).copied())
+    else {
+        let suggestion = best_function_suggestion(head.function.name, interner);
+        return Err(missing_use_source_error(&head.function, &head.source, suggestion));
+    };
+
+    let source_key = StagedKey {
+        stage: source_id,
+        function,
+    };
+    if !staged_lookup.contains_key(&source_key) {
+        return Err(missing_use_source_error(&head.function, &head.source, None));
+    }
+
+    let target_key = StagedKey {
+        stage: target_id,
+        function,
+    };
+    use_aliases.insert(target_key, function_symbol(pipeline, function));
+
+    Ok(next_index)
+}
+
+fn parse_use_declaration_head<'src>(
+    tokens: &[(Token<'src>, SimpleSpan)],
+    start_index: usize,
+) -> Result<(UseDeclarationHead<'src>, usize), FunctionParseError> {
+    let Some((_, use_span)) = tokens.get(start_index) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            None,
+            "expected declaration",
+        ));
+    };
+
+    let Some((target_symbol, target_span)) = tokens.get(start_index + 1) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*use_span),
+            "expected a target stage symbol after 'use'",
+        )
+        .with_diagnostic(Diagnostic::new(Label::new(
+            *use_span,
+            "expected a stage symbol (e.g., @B) after this",
+        ))));
+    };
+    let Token::Symbol(target_name) = target_symbol else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*target_span),
+            "stage names must use global-symbol syntax (e.g., @B)",
+        ));
+    };
+
+    let Some((fn_keyword, fn_span)) = tokens.get(start_index + 2) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*target_span),
+            "expected 'fn' after stage symbol",
+        ));
+    };
+    let Token::Identifier("fn") = fn_keyword else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*fn_span),
+            "expected 'fn' before function symbol",
+        ));
+    };
+
+    let Some((function_symbol, function_span)) = tokens.get(start_index + 3) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*fn_span),
+            "expected function symbol after 'fn'",
+        ));
+    };
+    let Token::Symbol(function_name) = function_symbol else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*function_span),
+            "function names must use global-symbol syntax (e.g., @bar)",
+        ));
+    };
+
+    let Some((from_keyword, from_span)) = tokens.get(start_index + 4) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*function_span),
+            "expected 'from' after function symbol",
+        ));
+    };
+    let Token::Identifier("from") = from_keyword else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*from_span),
+            "expected 'from' before source stage symbol",
+        ));
+    };
+
+    let Some((source_symbol, source_span)) = tokens.get(start_index + 5) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*from_span),
+            "expected a source stage symbol after 'from'",
+        ));
+    };
+    let Token::Symbol(source_name) = source_symbol else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*source_span),
+            "stage names must use global-symbol syntax (e.g., @A)",
+        ));
+    };
+
+    let Some((semicolon, semicolon_span)) = tokens.get(start_index + 6) else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*source_span),
+            "expected ';' after use declaration",
+        ));
+    };
+    let Token::Semicolon = semicolon else {
+        return Err(FunctionParseError::new(
+            FunctionParseErrorKind::InvalidHeader,
+            Some(*semicolon_span),
+            "expected ';' after use declaration",
+        ));
+    };
+
+    Ok((
+        UseDeclarationHead {
+            target: SymbolName {
+                name: target_name,
+                span: *target_span,
+            },
+            function: SymbolName {
+                name: function_name,
+                span: *function_span,
+            },
+            source: SymbolName {
+                name: source_name,
+                span: *source_span,
+            },
+        },
+        start_index + 7,
+    ))
+}
+
+/// Synchronize after a declaration fails to parse: scan forward from
+/// `start_index` to the next top-level `stage`/`specialize`/`use` keyword,
+/// tracking brace depth so a malformed declaration's own `{ ... }` body (or
+/// any nested group inside it) is skipped rather than mistaken for a
+/// resync point.
+fn recover_to_next_declaration<'src>(tokens: &[(Token<'src>, SimpleSpan)], start_index: usize) -> usize {
+    let mut index = start_index.saturating_add(1);
+    let mut depth = 0usize;
+    while index < tokens.len() {
+        match &tokens[index].0 {
+            Token::LBrace => depth += 1,
+            Token::RBrace => depth = depth.saturating_sub(1),
+            Token::Identifier("stage") | Token::Identifier("specialize") | Token::Identifier("use")
+                if depth == 0 =>
+            {
+                break;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    index
+}
+
 fn advance_to_next_declaration<'src>(
     tokens: &[(Token<'src>, SimpleSpan)],
     start_index: usize,
@@ -470,6 +1101,7 @@ fn advance_to_next_declaration<'src>(
     index
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_stage_declaration<'src, L>(
     stage: &mut StageInfo<L>,
     stage_id: CompileStage,
@@ -477,19 +1109,40 @@ fn apply_stage_declaration<'src, L>(
     function_symbol: GlobalSymbol,
     header: &Header<'src, L::Type>,
     staged_lookup: &mut HashMap<StagedKey, StagedFunction>,
+    declaration_spans: &mut HashMap<StagedKey, SimpleSpan>,
+    declared_shapes: &mut HashMap<StagedKey, SignatureShape>,
     state: &mut ParseState,
+    debug_asks: &[DebugAsk],
+    mut debug_collector: Option<&mut DebugArtifactCollector>,
 ) -> Result<Option<StagedFunction>, FunctionParseError>
 where
     L: Dialect,
 {
     state.record(function);
+    let watched = stage_matches_any_ask(stage_id, header.stage.name, debug_asks);
 
     let key = StagedKey {
         stage: stage_id,
         function,
     };
     if let Some(existing) = staged_lookup.get(&key).copied() {
-        ensure_staged_signature_matches::<L>(stage, existing, header)?;
+        let existing_span = declaration_spans.get(&key).copied();
+        let existing_shape = declared_shapes.get(&key).cloned().unwrap_or_default();
+        let checked =
+            ensure_staged_signature_matches::<L>(stage, existing, header, &existing_shape, existing_span);
+        if watched && checked.is_err() {
+            if let Some(collector) = debug_collector.as_deref_mut() {
+                let existing_signature = existing.expect_info(stage).signature();
+                collector.record(StageArtifact {
+                    stage_id,
+                    function,
+                    signature: format!("{:?}", header.signature),
+                    body: None,
+                    conflicting_with: Some(format!("{existing_signature:?}")),
+                });
+            }
+        }
+        checked?;
         return Ok(None);
     }
 
@@ -506,27 +1159,84 @@ where
             )
         })?;
     staged_lookup.insert(key, staged_function);
+    declaration_spans.insert(key, header.span);
+    declared_shapes.insert(key, header.shape.clone());
+
+    if watched {
+        if let Some(collector) = debug_collector {
+            collector.record(StageArtifact {
+                stage_id,
+                function,
+                signature: format!("{:?}", header.signature),
+                body: None,
+                conflicting_with: None,
+            });
+        }
+    }
 
     Ok(Some(staged_function))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_specialize_declaration<'src, L>(
     stage: &mut StageInfo<L>,
     stage_id: CompileStage,
     header: &Header<'src, L::Type>,
     body: &<L as HasParser<'src, 'src>>::Output,
     span: SimpleSpan,
-    function_lookup: &HashMap<String, Function>,
-    staged_lookup: &HashMap<StagedKey, StagedFunction>,
+    function_lookup: &HashMap<SymId, Function>,
+    interner: &SymbolInterner,
+    staged_lookup: &mut HashMap<StagedKey, StagedFunction>,
+    declaration_spans: &mut HashMap<StagedKey, SimpleSpan>,
+    use_aliases: &HashMap<StagedKey, GlobalSymbol>,
     state: &mut ParseState,
-) -> Result<(), FunctionParseError>
+    debug_asks: &[DebugAsk],
+    debug_collector: Option<&mut DebugArtifactCollector>,
+) -> Result<Option<(Function, StagedFunction)>, FunctionParseError>
 where
     L: Dialect + HasParser<'src, 'src>,
     L::Type: HasParser<'src, 'src, Output = L::Type>,
     <L as HasParser<'src, 'src>>::Output: EmitIR<L, Output = Statement>,
 {
-    let (function, staged_function) =
-        resolve_specialize_target::<L>(stage_id, header, span, function_lookup, staged_lookup)?;
+    let (function, target) = resolve_specialize_target::<L>(
+        stage_id,
+        header,
+        span,
+        function_lookup,
+        interner,
+        staged_lookup,
+        declaration_spans,
+        use_aliases,
+    )?;
+
+    let key = StagedKey {
+        stage: stage_id,
+        function,
+    };
+    let (staged_function, link) = match target {
+        SpecializeTarget::Staged(staged_function) => (staged_function, None),
+        SpecializeTarget::Alias(function_symbol) => {
+            // The `use` declaration that aliased this pair only validated its
+            // source existed; it couldn't create this staged function itself
+            // (see the module doc), so the first specialize to claim it
+            // creates it now, from its own already-`L`-typed signature.
+            let staged_function = stage
+                .staged_function()
+                .name(function_symbol)
+                .signature(header.signature.clone())
+                .new()
+                .map_err(|err| {
+                    FunctionParseError::new(
+                        FunctionParseErrorKind::EmitFailed,
+                        Some(header.span),
+                        err.to_string(),
+                    )
+                })?;
+            staged_lookup.insert(key, staged_function);
+            declaration_spans.insert(key, header.span);
+            (staged_function, Some((function, staged_function)))
+        }
+    };
 
     let body_statement = {
         let mut emit_ctx = EmitContext::new(stage);
@@ -547,31 +1257,98 @@ where
             )
         })?;
 
+    if stage_matches_any_ask(stage_id, header.stage.name, debug_asks) {
+        if let Some(collector) = debug_collector {
+            collector.record(StageArtifact {
+                stage_id,
+                function,
+                signature: format!("{:?}", header.signature),
+                body: Some(body_statement),
+                conflicting_with: None,
+            });
+        }
+    }
+
     state.record(function);
-    Ok(())
+    Ok(link)
+}
+
+/// Where a `specialize` declaration's target staged function comes from:
+/// either it already exists (from a `stage` header), or it's a `use` alias
+/// waiting to be materialized from this declaration's own signature.
+enum SpecializeTarget {
+    Staged(StagedFunction),
+    Alias(GlobalSymbol),
 }
 
 fn resolve_specialize_target<'src, L>(
     stage_id: CompileStage,
     header: &Header<'src, L::Type>,
     span: SimpleSpan,
-    function_lookup: &HashMap<String, Function>,
+    function_lookup: &HashMap<SymId, Function>,
+    interner: &SymbolInterner,
     staged_lookup: &HashMap<StagedKey, StagedFunction>,
-) -> Result<(Function, StagedFunction), FunctionParseError>
+    declaration_spans: &HashMap<StagedKey, SimpleSpan>,
+    use_aliases: &HashMap<StagedKey, GlobalSymbol>,
+) -> Result<(Function, SpecializeTarget), FunctionParseError>
 where
     L: Dialect,
 {
-    let Some(function) = function_lookup.get(header.function.name).copied() else {
-        return Err(missing_stage_declaration_error(header, Some(span)));
+    let Some(function) = interner
+        .get(header.function.name)
+        .and_then(|id| function_lookup.get(&id).copied())
+    else {
+        let suggestion = best_function_suggestion(header.function.name, interner);
+        return Err(missing_stage_declaration_error(
+            header,
+            Some(span),
+            suggestion,
+            None,
+        ));
     };
     let key = StagedKey {
         stage: stage_id,
         function,
     };
-    let Some(staged_function) = staged_lookup.get(&key).copied() else {
-        return Err(missing_stage_declaration_error(header, Some(span)));
-    };
-    Ok((function, staged_function))
+    if let Some(staged_function) = staged_lookup.get(&key).copied() {
+        return Ok((function, SpecializeTarget::Staged(staged_function)));
+    }
+    if let Some(&function_symbol) = use_aliases.get(&key) {
+        return Ok((function, SpecializeTarget::Alias(function_symbol)));
+    }
+    let nearest = nearest_declaration_for_function(declaration_spans, function, span);
+    Err(missing_stage_declaration_error(
+        header,
+        Some(span),
+        None,
+        nearest,
+    ))
+}
+
+/// Find the declared stage header closest to `near` for the same `function`,
+/// so a "no stage declaration" error can point at the stage it *was*
+/// declared for instead of only naming the function.
+fn nearest_declaration_for_function(
+    declaration_spans: &HashMap<StagedKey, SimpleSpan>,
+    function: Function,
+    near: SimpleSpan,
+) -> Option<SimpleSpan> {
+    declaration_spans
+        .iter()
+        .filter(|(key, _)| key.function == function)
+        .map(|(_, declared_span)| *declared_span)
+        .min_by_key(|declared_span| declared_span.start.abs_diff(near.start))
+}
+
+/// Find the closest existing function name to `name`, for "did you mean" suggestions.
+fn best_function_suggestion(name: &str, interner: &SymbolInterner) -> Option<String> {
+    let mut candidates: Vec<&String> = interner.names().iter().collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .min_by_key(|candidate| levenshtein(name, candidate))
+        .filter(|candidate| levenshtein(name, candidate) <= 3)
+        .cloned()
 }
 
 fn dispatch_stage_action_required<'src, S, A, R>(
@@ -583,9 +1360,10 @@ fn dispatch_stage_action_required<'src, S, A, R>(
 where
     S: StageMeta + SupportsStageDispatchMut<A, R, FunctionParseError>,
 {
-    pipeline
-        .dispatch_stage_mut_required(stage_id, action)
-        .map_err(|error| stage_dispatch_error(error, stage_symbol.name, Some(stage_symbol.span)))
+    let result = pipeline.dispatch_stage_mut_required(stage_id, action);
+    result.map_err(|error| {
+        stage_dispatch_error(pipeline, error, stage_symbol.name, Some(stage_symbol.span))
+    })
 }
 
 fn ensure_forward_progress(
@@ -605,6 +1383,7 @@ fn ensure_forward_progress(
 }
 
 fn parse_error_from_chumsky(errors: Vec<ChumskyError<'_>>) -> FunctionParseError {
+    let incomplete = needs_more_input(&errors);
     let diagnostics: Vec<String> = errors.iter().map(ToString::to_string).collect();
     let span = errors.first().map(|error| *error.span());
     let message = diagnostics
@@ -613,10 +1392,15 @@ fn parse_error_from_chumsky(errors: Vec<ChumskyError<'_>>) -> FunctionParseError
         .unwrap_or_else(|| "failed to parse declarations".to_string());
     FunctionParseError::new(FunctionParseErrorKind::InvalidHeader, span, message)
         .with_source(DiagnosticError::new(diagnostics))
+        .with_incomplete(incomplete)
 }
 
-/// Build a `function-name -> function` lookup from existing pipeline state.
-fn collect_function_lookup<S>(pipeline: &Pipeline<S>) -> HashMap<String, Function> {
+/// Build an `interned-name -> function` lookup from existing pipeline state,
+/// seeding `interner` with every existing function's name along the way.
+fn collect_function_lookup<S>(
+    pipeline: &Pipeline<S>,
+    interner: &mut SymbolInterner,
+) -> HashMap<SymId, Function> {
     let mut lookup = HashMap::new();
     for info in pipeline.function_arena().iter() {
         let function = Function::from(info.clone().unwrap());
@@ -626,7 +1410,7 @@ fn collect_function_lookup<S>(pipeline: &Pipeline<S>) -> HashMap<String, Functio
         let Some(name) = pipeline.resolve(symbol) else {
             continue;
         };
-        lookup.insert(name.to_string(), function);
+        lookup.insert(interner.intern(name), function);
     }
     lookup
 }
@@ -634,14 +1418,16 @@ fn collect_function_lookup<S>(pipeline: &Pipeline<S>) -> HashMap<String, Functio
 /// Resolve an abstract function by name, creating it if it does not exist.
 fn get_or_create_function_by_name<S>(
     pipeline: &mut Pipeline<S>,
-    function_lookup: &mut HashMap<String, Function>,
+    function_lookup: &mut HashMap<SymId, Function>,
+    interner: &mut SymbolInterner,
     name: &str,
 ) -> Function {
-    if let Some(existing) = function_lookup.get(name).copied() {
+    let id = interner.intern(name);
+    if let Some(existing) = function_lookup.get(&id).copied() {
         return existing;
     }
     let function = pipeline.function().name(name.to_string()).new();
-    function_lookup.insert(name.to_string(), function);
+    function_lookup.insert(id, function);
     function
 }
 
@@ -700,12 +1486,7 @@ where
     S: StageMeta,
 {
     let mut output = message;
-    let mut candidates = stage_candidates(pipeline);
-    for name in S::declared_stage_names() {
-        candidates.push((*name).to_string());
-    }
-    candidates.sort();
-    candidates.dedup();
+    let candidates = all_stage_candidates(pipeline);
 
     if let Some(suggestion) = best_stage_suggestion(stage_symbol, &candidates) {
         if !output.contains(&suggestion) {
@@ -715,6 +1496,22 @@ where
     output
 }
 
+/// Collect every stage name a "did you mean" suggestion could draw from:
+/// stages already added to the pipeline, plus every dialect `S` knows how to
+/// construct by name.
+fn all_stage_candidates<S>(pipeline: &Pipeline<S>) -> Vec<String>
+where
+    S: StageMeta,
+{
+    let mut candidates = stage_candidates(pipeline);
+    for name in S::declared_stage_names() {
+        candidates.push((*name).to_string());
+    }
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
 /// Lookup a stage by symbolic name (`@A`) or numeric symbol (`@1`).
 fn find_stage_symbol<S>(pipeline: &Pipeline<S>, stage_symbol: &str) -> Option<CompileStage>
 where
@@ -783,6 +1580,8 @@ fn ensure_staged_signature_matches<L>(
     stage: &StageInfo<L>,
     staged_function: StagedFunction,
     header: &Header<'_, L::Type>,
+    existing_shape: &SignatureShape,
+    existing_span: Option<SimpleSpan>,
 ) -> Result<(), FunctionParseError>
 where
     L: Dialect,
@@ -791,65 +1590,297 @@ where
     if staged_info.signature() == &header.signature {
         return Ok(());
     }
+
+    // A `[...]` shape clause opts a repeated `stage` header out of requiring
+    // a byte-identical `Signature`: as long as the incoming header's shape
+    // agrees structurally with whatever shape the first declaration had (or
+    // with "no shape at all" if it had none), a later header that only adds
+    // an optional/defaulted param or a named flag is accepted.
+    if !header.shape.is_empty() {
+        return match shape_mismatch(existing_shape, &header.shape) {
+            None => Ok(()),
+            Some(mismatch) => Err(shape_mismatch_error(header, mismatch)),
+        };
+    }
+
+    let mut diagnostic = Diagnostic::new(Label::new(
+        header.span,
+        format!(
+            "conflicts with the existing signature of '@{}'",
+            header.function.name
+        ),
+    ));
+    if let Some(existing_span) = existing_span {
+        diagnostic = diagnostic.with_secondary(Label::new(existing_span, "previously declared here"));
+    }
+
     Err(FunctionParseError::new(
         FunctionParseErrorKind::EmitFailed,
         Some(header.span),
         "stage declaration signature does not match existing staged function",
-    ))
+    )
+    .with_diagnostic(diagnostic))
+}
+
+/// A single point of structural disagreement between two declarations'
+/// shape clauses: where to point the primary label, its message, and
+/// (when the conflicting slot's span from the other declaration is known)
+/// a secondary label to show it alongside.
+struct ShapeMismatch {
+    span: SimpleSpan,
+    message: String,
+    secondary: Option<(SimpleSpan, String)>,
+}
+
+/// Compares two shape clauses the way a repeated `stage` header is allowed
+/// to differ from the first: positional slots must agree in shape kind at
+/// every index both declare, and a slot present on only one side must be
+/// optional; named flags must agree in shape kind when both declarations
+/// name the same flag, but a flag present on only one side is always fine.
+fn shape_mismatch(existing: &SignatureShape, incoming: &SignatureShape) -> Option<ShapeMismatch> {
+    let slot_count = existing.params.len().max(incoming.params.len());
+    for index in 0..slot_count {
+        match (existing.params.get(index), incoming.params.get(index)) {
+            (Some(existing_slot), Some(incoming_slot)) => {
+                if existing_slot.shape != incoming_slot.shape {
+                    return Some(ShapeMismatch {
+                        span: incoming_slot.span,
+                        message: format!(
+                            "parameter {index} is declared as `{}` here, but `{}` in the existing declaration",
+                            incoming_slot.shape, existing_slot.shape
+                        ),
+                        secondary: Some((existing_slot.span, "existing declaration".to_string())),
+                    });
+                }
+            }
+            (Some(only), None) | (None, Some(only)) if !only.optional => {
+                return Some(ShapeMismatch {
+                    span: only.span,
+                    message: format!(
+                        "parameter {index} is required here but missing from the other declaration"
+                    ),
+                    secondary: None,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    for incoming_flag in &incoming.flags {
+        let Some(existing_flag) = existing
+            .flags
+            .iter()
+            .find(|flag| flag.name == incoming_flag.name)
+        else {
+            continue;
+        };
+        if existing_flag.shape != incoming_flag.shape {
+            return Some(ShapeMismatch {
+                span: incoming_flag.span,
+                message: format!(
+                    "flag '{}' is declared as `{}` here, but `{}` in the existing declaration",
+                    incoming_flag.name, incoming_flag.shape, existing_flag.shape
+                ),
+                secondary: Some((existing_flag.span, "existing declaration".to_string())),
+            });
+        }
+    }
+
+    None
+}
+
+fn shape_mismatch_error<T>(header: &Header<'_, T>, mismatch: ShapeMismatch) -> FunctionParseError {
+    let mut diagnostic = Diagnostic::new(Label::new(mismatch.span, mismatch.message.clone()));
+    if let Some((secondary_span, secondary_message)) = mismatch.secondary {
+        diagnostic = diagnostic.with_secondary(Label::new(secondary_span, secondary_message));
+    }
+    FunctionParseError::new(
+        FunctionParseErrorKind::ShapeMismatch,
+        Some(header.span),
+        mismatch.message,
+    )
+    .with_diagnostic(diagnostic)
 }
 
 fn stage_dialect_mismatch_error(
     stage_symbol: &str,
     span: Option<SimpleSpan>,
 ) -> FunctionParseError {
-    FunctionParseError::new(
+    let error = FunctionParseError::new(
         FunctionParseErrorKind::EmitFailed,
         span,
         format!(
             "stage '@{stage_symbol}' has no registered parser dialect in this compile-stage container"
         ),
-    )
+    );
+    match span {
+        Some(span) => error.with_diagnostic(Diagnostic::new(Label::new(
+            span,
+            "no parser dialect registered for this stage",
+        ))),
+        None => error,
+    }
 }
 
-fn stage_dispatch_miss_error(
+fn stage_dispatch_miss_error<S>(
+    pipeline: &Pipeline<S>,
     miss: StageDispatchMiss,
     stage_symbol: &str,
     span: Option<SimpleSpan>,
-) -> FunctionParseError {
+) -> FunctionParseError
+where
+    S: StageMeta,
+{
     match miss {
         StageDispatchMiss::MissingDialect => stage_dialect_mismatch_error(stage_symbol, span),
-        StageDispatchMiss::MissingStage => FunctionParseError::new(
-            FunctionParseErrorKind::EmitFailed,
-            span,
-            format!("stage '@{stage_symbol}' does not exist in the pipeline"),
-        ),
+        StageDispatchMiss::MissingStage => {
+            let error = FunctionParseError::new(
+                FunctionParseErrorKind::EmitFailed,
+                span,
+                format!("stage '@{stage_symbol}' does not exist in the pipeline"),
+            );
+            let Some(span) = span else {
+                return error;
+            };
+            let mut diagnostic = Diagnostic::new(Label::new(
+                span,
+                "this stage does not exist in the pipeline",
+            ));
+            if let Some(suggestion) =
+                best_stage_suggestion(stage_symbol, &all_stage_candidates(pipeline))
+            {
+                // A suggestion that only differs from what was written by
+                // case is, for all practical purposes, a typo in casing
+                // rather than a guess, so it's safe to apply without asking;
+                // anything else (a real edit) should be confirmed first.
+                let applicability = if suggestion.eq_ignore_ascii_case(stage_symbol) {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+                diagnostic = diagnostic.with_suggestion(Suggestion::new(
+                    span,
+                    format!("@{suggestion}"),
+                    applicability,
+                ));
+            }
+            error.with_diagnostic(diagnostic)
+        }
     }
 }
 
-fn stage_dispatch_error(
+fn stage_dispatch_error<S>(
+    pipeline: &Pipeline<S>,
     error: StageDispatchRequiredError<FunctionParseError>,
     stage_symbol: &str,
     span: Option<SimpleSpan>,
-) -> FunctionParseError {
+) -> FunctionParseError
+where
+    S: StageMeta,
+{
     match error {
         StageDispatchRequiredError::Action(error) => error,
         StageDispatchRequiredError::Miss(miss) => {
-            stage_dispatch_miss_error(miss, stage_symbol, span)
+            stage_dispatch_miss_error(pipeline, miss, stage_symbol, span)
         }
     }
 }
 
 /// Build a standardized error for `specialize` declarations without a matching stage header.
+/// `nearest_declaration`, when present, is the span of an existing `stage`
+/// header declared for the same function (just for a different stage), so
+/// the diagnostic can point at it instead of only naming the function.
 fn missing_stage_declaration_error<L>(
     header: &Header<'_, L>,
     span: Option<SimpleSpan>,
+    function_suggestion: Option<String>,
+    nearest_declaration: Option<SimpleSpan>,
 ) -> FunctionParseError {
+    let mut diagnostic = Diagnostic::new(Label::new(
+        header.span,
+        format!(
+            "no stage declaration for function '@{}'",
+            header.function.name
+        ),
+    ))
+    .with_secondary(Label::new(
+        header.stage.span,
+        format!(
+            "in specialize declaration for stage '@{}'",
+            header.stage.name
+        ),
+    ));
+    if let Some(nearest) = nearest_declaration {
+        diagnostic = diagnostic.with_secondary(Label::new(
+            nearest,
+            format!(
+                "'@{}' is declared here, for a different stage",
+                header.function.name
+            ),
+        ));
+    }
+    if let Some(suggestion) = &function_suggestion {
+        diagnostic = diagnostic.with_suggestion(Suggestion::new(
+            header.function.span,
+            format!("@{suggestion}"),
+            Applicability::MaybeIncorrect,
+        ));
+    }
+
+    let mut message = format!(
+        "specialize declaration for stage '@{}' and function '@{}' has no matching stage declaration",
+        header.stage.name, header.function.name
+    );
+    if let Some(suggestion) = function_suggestion {
+        message.push_str(&format!(", did you mean '@{suggestion}'?"));
+    }
+
     FunctionParseError::new(
         FunctionParseErrorKind::MissingStageDeclaration,
         span.or(Some(header.span)),
+        message,
+    )
+    .with_diagnostic(diagnostic)
+}
+
+/// Build a standardized error for `use ... from @source;` declarations whose
+/// source stage has no staged function for `@name`.
+fn missing_use_source_error(
+    function: &SymbolName<'_>,
+    source: &SymbolName<'_>,
+    function_suggestion: Option<String>,
+) -> FunctionParseError {
+    let mut diagnostic = Diagnostic::new(Label::new(
+        function.span,
         format!(
-            "specialize declaration for stage '@{}' and function '@{}' has no matching stage declaration",
-            header.stage.name, header.function.name
+            "no staged function '@{}' declared in stage '@{}'",
+            function.name, source.name
         ),
+    ))
+    .with_secondary(Label::new(
+        source.span,
+        format!("while resolving 'use ... from @{}'", source.name),
+    ));
+    if let Some(suggestion) = &function_suggestion {
+        diagnostic = diagnostic.with_suggestion(Suggestion::new(
+            function.span,
+            format!("@{suggestion}"),
+            Applicability::MaybeIncorrect,
+        ));
+    }
+
+    let mut message = format!(
+        "'use' declaration references '@{}' from stage '@{}', but no such staged function exists",
+        function.name, source.name
+    );
+    if let Some(suggestion) = function_suggestion {
+        message.push_str(&format!(", did you mean '@{suggestion}'?"));
+    }
+
+    FunctionParseError::new(
+        FunctionParseErrorKind::MissingStageDeclaration,
+        Some(function.span),
+        message,
     )
+    .with_diagnostic(diagnostic)
 }
@@ -0,0 +1,76 @@
+//! Source-anchored caret/underline rendering for [`FunctionParseError`].
+//!
+//! [`render_report`] is the rich counterpart to the terse `Display` impl:
+//! given the original source text it draws the offending line(s) with a
+//! `^^^^` underline under the primary span, `----` underlines under any
+//! secondary labels, and trailing `note:` lines, in the style compiler
+//! diagnostics use. Callers that don't have the source text on hand (or
+//! don't want to pay for locating lines) can keep using `Display`, which
+//! stays a single `kind at start..end: message` line.
+
+use super::diagnostic::Label;
+use super::error::FunctionParseError;
+use crate::diagnostics::{line_starts, render_span_line};
+
+/// Renders `error` against its original `source` text as a caret/underline
+/// report. A [`FunctionParseErrorKind::Multiple`](super::error::FunctionParseErrorKind::Multiple)
+/// error renders one report per underlying failure (via
+/// [`FunctionParseError::flatten`]), separated by a blank line.
+pub fn render_report(error: &FunctionParseError, source: &str) -> String {
+    error
+        .flatten()
+        .into_iter()
+        .map(|error| render_one(error, source))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_one(error: &FunctionParseError, source: &str) -> String {
+    let line_starts = line_starts(source);
+
+    // Fall back to the terse `Display` line when there's no span to anchor
+    // a report to at all (e.g. an `EmitFailed` wrapping an opaque IR error).
+    let primary = error
+        .diagnostic
+        .as_ref()
+        .and_then(|diagnostic| diagnostic.primary.clone())
+        .or_else(|| error.span.map(|span| Label::new(span, error.message.clone())));
+    let Some(primary) = primary else {
+        return error.to_string();
+    };
+
+    let mut out = format!("error: {}\n", error.message);
+    render_label(&mut out, source, &line_starts, &primary, '^');
+
+    if let Some(diagnostic) = &error.diagnostic {
+        for label in &diagnostic.secondary {
+            render_label(&mut out, source, &line_starts, label, '-');
+        }
+        for note in &diagnostic.notes {
+            out.push_str(&format!("  = note: {note}\n"));
+        }
+    }
+
+    // Drop the trailing newline so callers can freely wrap the report (e.g.
+    // joining several with blank lines) without doubling them up.
+    out.truncate(out.trim_end_matches('\n').len());
+    out
+}
+
+/// Writes one `--> line | text` / underline block for `label` into `out`.
+fn render_label(
+    out: &mut String,
+    source: &str,
+    line_starts: &[usize],
+    label: &Label,
+    underline: char,
+) {
+    render_span_line(
+        out,
+        source,
+        line_starts,
+        label.span,
+        underline,
+        Some(&label.message),
+    );
+}
@@ -4,7 +4,7 @@ use kirin_ir::{Dialect, Signature};
 use kirin_lexer::{Logos, Token};
 
 use crate::ast::SymbolName;
-use crate::parsers::{identifier, symbol};
+use crate::parsers::{any_identifier, identifier, needs_more_input, symbol};
 use crate::traits::{HasParser, ParserError, TokenInput};
 
 pub(super) type ChumskyError<'src> = Rich<'src, Token<'src>, SimpleSpan>;
@@ -14,9 +14,73 @@ pub(super) struct Header<'src, T> {
     pub stage: SymbolName<'src>,
     pub function: SymbolName<'src>,
     pub signature: Signature<T>,
+    /// Optional structural shape annotation (`[...]` after the return type):
+    /// empty unless the declaration opted in, in which case it's consulted
+    /// by `ensure_staged_signature_matches` instead of requiring
+    /// `signature` to be byte-identical across repeated `stage` headers.
+    pub shape: SignatureShape,
     pub span: SimpleSpan,
 }
 
+/// The structural shape a declared parameter slot is expected to have.
+/// Distinct from `T` (the dialect's concrete type): `int`/`symbol` describe
+/// a slot's kind without pinning it to one dialect type, the way a concrete
+/// type list entry does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ParamShape {
+    Int,
+    Symbol,
+    TypeRef,
+}
+
+impl std::fmt::Display for ParamShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamShape::Int => write!(f, "int"),
+            ParamShape::Symbol => write!(f, "symbol"),
+            ParamShape::TypeRef => write!(f, "type"),
+        }
+    }
+}
+
+/// A positional slot in a `[...]` shape clause: a [`ParamShape`], optionally
+/// marked `?` or given a `= default` (either makes it optional), plus the
+/// span of just this slot so a shape mismatch can be labelled precisely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ParamSlot {
+    pub shape: ParamShape,
+    pub optional: bool,
+    pub default: Option<String>,
+    pub span: SimpleSpan,
+}
+
+/// A named flag in a `[...]` shape clause, e.g. `unroll: int` or
+/// `unroll(u): int` for a flag with the short alias `u`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FlagSlot {
+    pub name: String,
+    pub short: Option<char>,
+    pub shape: ParamShape,
+    pub default: Option<String>,
+    pub span: SimpleSpan,
+}
+
+/// Positional and named-flag shape slots declared in a `[...]` clause after
+/// a `stage`/`specialize` header's return type. Empty (the default) when the
+/// declaration has no such clause, in which case header comparison behaves
+/// exactly as it did before this existed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct SignatureShape {
+    pub params: Vec<ParamSlot>,
+    pub flags: Vec<FlagSlot>,
+}
+
+impl SignatureShape {
+    pub(super) fn is_empty(&self) -> bool {
+        self.params.is_empty() && self.flags.is_empty()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(super) enum Declaration<'src, T, B> {
     Stage(Header<'src, T>),
@@ -31,6 +95,7 @@ pub(super) enum Declaration<'src, T, B> {
 struct ParsedFnSignature<'src, T> {
     function: SymbolName<'src>,
     signature: Signature<T>,
+    shape: SignatureShape,
 }
 
 fn type_list_parser<'src, I, L>() -> impl Parser<'src, I, Vec<L::Type>, ParserError<'src, 'src>>
@@ -46,6 +111,135 @@ where
         .labelled("type list")
 }
 
+/// One of the three shape keywords a `[...]` slot can declare.
+fn param_shape_parser<'src, I>() -> impl Parser<'src, I, ParamShape, ParserError<'src, 'src>>
+where
+    I: TokenInput<'src, 'src>,
+{
+    choice((
+        identifier("int").to(ParamShape::Int),
+        identifier("symbol").to(ParamShape::Symbol),
+        identifier("type").to(ParamShape::TypeRef),
+    ))
+    .labelled("parameter shape")
+}
+
+/// The raw source text of a `= default` value: kept as text rather than
+/// evaluated, since a shape slot isn't tied to a dialect's own literal
+/// parser and exists purely for structural (arity + kind) comparison.
+fn default_literal_parser<'src, I>() -> impl Parser<'src, I, String, ParserError<'src, 'src>>
+where
+    I: TokenInput<'src, 'src>,
+{
+    select! {
+        Token::Int(value) => value.to_string(),
+        Token::Unsigned(value) => value.to_string(),
+        Token::Float(value) => value.to_string(),
+        Token::Symbol(value) => format!("@{value}"),
+        Token::StringLit(value) => value,
+        Token::Identifier(value) => value.to_string(),
+    }
+    .labelled("default value")
+}
+
+fn param_slot_parser<'src, I>() -> impl Parser<'src, I, ParamSlot, ParserError<'src, 'src>>
+where
+    I: TokenInput<'src, 'src>,
+{
+    param_shape_parser()
+        .then(just(Token::Question).or_not())
+        .then(
+            just(Token::Equal)
+                .ignore_then(default_literal_parser())
+                .or_not(),
+        )
+        .map_with(|((shape, question), default), extra| ParamSlot {
+            shape,
+            optional: question.is_some() || default.is_some(),
+            default,
+            span: extra.span(),
+        })
+}
+
+fn flag_slot_parser<'src, I>() -> impl Parser<'src, I, FlagSlot, ParserError<'src, 'src>>
+where
+    I: TokenInput<'src, 'src>,
+{
+    any_identifier()
+        .then(
+            just(Token::LParen)
+                .ignore_then(any_identifier())
+                .then_ignore(just(Token::RParen))
+                .or_not(),
+        )
+        .then_ignore(just(Token::Colon))
+        .then(param_shape_parser())
+        .then(
+            just(Token::Equal)
+                .ignore_then(default_literal_parser())
+                .or_not(),
+        )
+        .map_with(|(((name, short), shape), default), extra| {
+            (name, short, shape, default, extra.span())
+        })
+        .try_map(|(name, short, shape, default, span), _| {
+            let short = short
+                .map(|short| {
+                    let mut chars = short.value.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Ok(c),
+                        _ => Err(Rich::custom(
+                            span,
+                            format!(
+                                "short flag alias '{}' must be a single character",
+                                short.value
+                            ),
+                        )),
+                    }
+                })
+                .transpose()?;
+            Ok(FlagSlot {
+                name: name.value.to_string(),
+                short,
+                shape,
+                default,
+                span,
+            })
+        })
+}
+
+enum ShapeEntry {
+    Param(ParamSlot),
+    Flag(FlagSlot),
+}
+
+/// The optional `[...]` clause after a header's return type: a mix of
+/// positional shape slots and named flags, in any order.
+fn shape_clause_parser<'src, I>() -> impl Parser<'src, I, SignatureShape, ParserError<'src, 'src>>
+where
+    I: TokenInput<'src, 'src>,
+{
+    choice((
+        flag_slot_parser().map(ShapeEntry::Flag),
+        param_slot_parser().map(ShapeEntry::Param),
+    ))
+    .separated_by(just(Token::Comma))
+    .allow_trailing()
+    .collect::<Vec<_>>()
+    .delimited_by(just(Token::LBracket), just(Token::RBracket))
+    .map(|entries| {
+        let mut shape = SignatureShape::default();
+        for entry in entries {
+            match entry {
+                ShapeEntry::Param(slot) => shape.params.push(slot),
+                ShapeEntry::Flag(flag) => shape.flags.push(flag),
+            }
+        }
+        shape
+    })
+    .labelled("signature shape clause")
+}
+
 fn fn_signature_parser<'src, I, L>()
 -> impl Parser<'src, I, ParsedFnSignature<'src, L::Type>, ParserError<'src, 'src>>
 where
@@ -58,13 +252,15 @@ where
         .then(type_list_parser::<I, L>())
         .then_ignore(just(Token::Arrow))
         .then(L::Type::parser())
-        .map(|((function, params), ret)| ParsedFnSignature {
+        .then(shape_clause_parser().or_not())
+        .map(|(((function, params), ret), shape)| ParsedFnSignature {
             function,
             signature: Signature {
                 params,
                 ret,
                 constraints: (),
             },
+            shape: shape.unwrap_or_default(),
         })
         .labelled("function signature")
 }
@@ -89,6 +285,7 @@ where
                 stage,
                 function: sig.function,
                 signature: sig.signature,
+                shape: sig.shape,
                 span: extra.span(),
             })
         });
@@ -102,6 +299,7 @@ where
                 stage,
                 function: sig.function,
                 signature: sig.signature,
+                shape: sig.shape,
                 span: extra.span(),
             },
             body,
@@ -141,3 +339,41 @@ where
         .parse(stream)
         .into_result()
 }
+
+/// Outcome of [`parse_one_declaration_incremental`]: lets a REPL distinguish
+/// "not done typing yet" from "that's just wrong".
+#[derive(Debug)]
+pub(super) enum IncrementalParse<'src, T> {
+    /// The fragment parsed to completion.
+    Complete(T),
+    /// The token stream ran out while the parser still expected more (e.g. an
+    /// unclosed `{` or a format placeholder awaiting an operand) — the caller
+    /// should buffer another line and retry rather than reporting an error.
+    NeedMore,
+    /// The fragment is definitely invalid.
+    Error(Vec<ChumskyError<'src>>),
+}
+
+/// Like [`parse_one_declaration`], but distinguishes "syntactically
+/// incomplete but not yet wrong" input from input that is definitely
+/// invalid, so an interactive caller can buffer another line instead of
+/// rejecting the first line of a multi-line `fn ... { ... }` definition.
+pub(super) fn parse_one_declaration_incremental<'src, L>(
+    tokens: &[(Token<'src>, SimpleSpan)],
+) -> IncrementalParse<
+    'src,
+    (
+        Declaration<'src, L::Type, <L as HasParser<'src, 'src>>::Output>,
+        SimpleSpan,
+    ),
+>
+where
+    L: Dialect + HasParser<'src, 'src>,
+    L::Type: HasParser<'src, 'src, Output = L::Type>,
+{
+    match parse_one_declaration::<L>(tokens) {
+        Ok(result) => IncrementalParse::Complete(result),
+        Err(errors) if needs_more_input(&errors) => IncrementalParse::NeedMore,
+        Err(errors) => IncrementalParse::Error(errors),
+    }
+}
@@ -0,0 +1,521 @@
+//! Lattice-driven type inference for `ResultValue`/`Operand` holes a
+//! dialect's surface syntax leaves unspecified.
+//!
+//! A format string like `UnaryLang::Neg`'s `"{res:name} = neg {arg}"` omits
+//! `-> ty`, so parsing alone leaves `res.ty == None` -- there's nowhere in
+//! the grammar for the type to come from. This module fills those holes
+//! after the fact: given a per-statement [`TypingRule`] (how a statement's
+//! result type follows from its operands' types, once those are known), it
+//! threads an SSA-name -> type environment through a block's statements in
+//! order and iterates to a fixpoint, the same way a single-block dataflow
+//! analysis would be written for an IR whose back-edges live one level up,
+//! in [`Region`](crate::ast::Region) rather than within a block itself.
+//!
+//! [`TypingRule`] is implemented once per dialect on the generated `*AST`
+//! type, the same way [`WithPrinter`](crate::WithPrinter) and
+//! [`StructEq`](crate::StructEq) are: there's no way to know which fields of
+//! an arbitrary statement are "operands" and which are "the result" other
+//! than asking the dialect author, so this is registered by hand rather
+//! than derived. [`meet_of`] provides the default rule most variants want --
+//! "result type = meet of operand types" -- built on the same
+//! [`Lattice::meet`] `kirin_ir` uses for the IR, so dialect authors aren't
+//! reinventing it at the syntax level.
+//!
+//! [`infer_block`]/[`infer_region`] only resolve holes within the
+//! block/region they're given directly; a statement whose own body is
+//! another nested `Block`/`Region` (e.g. `BlockRegionLang::Loop`/`Scope`)
+//! needs its own `infer_block`/`infer_region` call against that nested body
+//! -- only the dialect knows where those live, so recursing into them is the
+//! caller's responsibility, seeding the nested call's environment from the
+//! outer one if the dialect's scoping rules call for that.
+
+use std::collections::HashMap;
+
+use kirin_ir::{Dialect, HasBottom, Lattice};
+
+use crate::ast::{Block, Region, Spanned};
+use crate::diagnostics::ParseReport;
+use crate::traits::HasParser;
+
+/// One dialect statement's contribution to type inference.
+///
+/// Implemented on the generated `*AST` type for dialects that want
+/// [`infer_block`]/[`infer_region`] to fill in `ty: None` holes left by the
+/// surface syntax.
+pub trait TypingRule<'tokens, 'src, L, T>
+where
+    L: Dialect + HasParser<'tokens, 'src, L>,
+{
+    /// Names of the SSA values this statement reads, in the order its
+    /// operands appear.
+    fn reads(&self) -> Vec<&'src str>;
+
+    /// Name of the SSA value this statement defines, if it produces a
+    /// result at all (a terminator like a bare `ret` does not).
+    fn defines(&self) -> Option<&'src str>;
+
+    /// This statement's result type as already known -- either given
+    /// explicitly by the syntax (`-> ty`) or filled in by an earlier
+    /// [`infer_block`] pass -- without consulting operand types at all.
+    fn known_type(&self) -> Option<T>;
+
+    /// Writes a freshly-inferred result type back into this statement. Only
+    /// called when [`known_type`](Self::known_type) returned `None` and
+    /// [`infer`](Self::infer) produced something; a no-op impl is correct
+    /// for a statement [`defines`](Self::defines) says has no result.
+    fn set_inferred_type(&mut self, ty: T);
+
+    /// Computes this statement's result type from its operands' types
+    /// (`None` at a position whose type is still unresolved), or `None` if
+    /// there isn't enough information yet to say.
+    fn infer(&self, operand_types: &[Option<T>]) -> Option<T>;
+}
+
+/// The "result type = meet of operand types" rule most statements want:
+/// `None` if any operand's type is still unresolved, otherwise the
+/// pairwise [`Lattice::meet`] of all of them.
+pub fn meet_of<T: Lattice + Clone>(operand_types: &[Option<T>]) -> Option<T> {
+    let mut known = operand_types.iter().cloned();
+    let first = known.next()??;
+    known.try_fold(first, |acc, ty| ty.map(|ty| acc.meet(&ty)))
+}
+
+/// Whether [`meet_of`]'s fold over `operand_types` hides a genuine conflict
+/// rather than a legitimate common type: a pairwise [`Lattice::meet`] that
+/// degenerates to [`HasBottom::bottom`] even though neither side being
+/// combined was itself `bottom()` means those two operand types aren't
+/// related by the lattice at all (e.g. `i32` and `bool`), not that the
+/// lattice agrees on some lesser-but-still-meaningful common type. Returns
+/// the first such conflicting pair found, folding left-to-right the same
+/// way [`meet_of`] does.
+pub fn meet_conflict<T: Lattice + HasBottom + PartialEq + Clone>(
+    operand_types: &[Option<T>],
+) -> Option<(T, T)> {
+    let mut known = operand_types.iter().flatten().cloned();
+    let mut acc = known.next()?;
+    for ty in known {
+        let meet = acc.meet(&ty);
+        if meet == T::bottom() && acc != T::bottom() && ty != T::bottom() {
+            return Some((acc, ty));
+        }
+        acc = meet;
+    }
+    None
+}
+
+/// Runs [`TypingRule`] to a fixpoint over one block's statements, threading
+/// `env` (seeded with the block's already-typed arguments, and left with
+/// every name this block resolved) through them in order.
+///
+/// Returns one [`ParseReport`] per statement whose result type is still
+/// unresolved once no further progress can be made, anchored at that
+/// statement's span, plus one per statement whose operand types turned out
+/// to be unrelated under the lattice (see [`meet_conflict`]) -- a statement
+/// `infer` resolved to `bottom()` by folding two operands that don't agree
+/// is not "resolved", it's silently wrong, so it gets a report of its own
+/// rather than passing [`unresolved_reports`] by virtue of `known_type()`
+/// being `Some`.
+pub fn infer_block<'tokens, 'src: 'tokens, L, T>(
+    block: &mut Block<'tokens, 'src, L>,
+    env: &mut HashMap<&'src str, T>,
+) -> Vec<ParseReport>
+where
+    L: Dialect + HasParser<'tokens, 'src, L>,
+    L::TypeLattice: HasParser<'tokens, 'src, L>,
+    L::Output: TypingRule<'tokens, 'src, L, T>,
+    T: Lattice + HasBottom + PartialEq + Clone + std::fmt::Display,
+{
+    for argument in &block.header.value.arguments {
+        env.insert(argument.value.name.value, argument.value.ty.value.clone());
+    }
+
+    // A single pass can only propagate a type one statement further than
+    // where it became known, so re-run until nothing changes; capping at
+    // one pass per statement is enough since every name is defined once.
+    for _ in 0..=block.statements.len() {
+        let mut changed = false;
+        for statement in &mut block.statements {
+            changed |= resolve_statement(&mut statement.value, env);
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    unresolved_reports(&block.statements)
+        .into_iter()
+        .chain(conflicting_reports::<L, T>(&block.statements, env))
+        .collect()
+}
+
+/// [`infer_block`] applied to every block in a region, in order. Each
+/// block's environment starts as a clone of `env` (the outer scope's
+/// already-resolved names stay visible) but resolutions made inside one
+/// block are not fed back into `env` or the blocks after it -- `kirin`'s
+/// block arguments, not fallthrough, are how a dialect threads a value
+/// between blocks, so there's no def/use edge here for inference to follow.
+pub fn infer_region<'tokens, 'src: 'tokens, L, T>(
+    region: &mut Region<'tokens, 'src, L>,
+    env: &HashMap<&'src str, T>,
+) -> Vec<ParseReport>
+where
+    L: Dialect + HasParser<'tokens, 'src, L>,
+    L::TypeLattice: HasParser<'tokens, 'src, L>,
+    L::Output: TypingRule<'tokens, 'src, L, T>,
+    T: Lattice + HasBottom + PartialEq + Clone + std::fmt::Display,
+{
+    region
+        .blocks
+        .iter_mut()
+        .flat_map(|block| infer_block(&mut block.value, &mut env.clone()))
+        .collect()
+}
+
+fn resolve_statement<'tokens, 'src, L, T>(
+    statement: &mut L::Output,
+    env: &mut HashMap<&'src str, T>,
+) -> bool
+where
+    L: Dialect + HasParser<'tokens, 'src, L>,
+    L::Output: TypingRule<'tokens, 'src, L, T>,
+    T: Lattice + Clone,
+{
+    if statement.known_type().is_none() {
+        let operand_types: Vec<Option<T>> = statement
+            .reads()
+            .iter()
+            .map(|name| env.get(name).cloned())
+            .collect();
+        if let Some(ty) = statement.infer(&operand_types) {
+            statement.set_inferred_type(ty);
+        }
+    }
+
+    match (statement.defines(), statement.known_type()) {
+        (Some(name), Some(ty)) if env.get(name).is_none() => {
+            env.insert(name, ty);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn unresolved_reports<'tokens, 'src, L, T>(
+    statements: &[Spanned<L::Output>],
+) -> Vec<ParseReport>
+where
+    L: Dialect + HasParser<'tokens, 'src, L>,
+    L::Output: TypingRule<'tokens, 'src, L, T>,
+{
+    statements
+        .iter()
+        .filter(|statement| {
+            statement.value.defines().is_some() && statement.value.known_type().is_none()
+        })
+        .map(|statement| ParseReport {
+            span: statement.span,
+            message: "could not infer a result type for this statement".to_string(),
+            expected: Vec::new(),
+            found: None,
+        })
+        .collect()
+}
+
+/// One [`ParseReport`] per statement whose operands' types, once resolved
+/// through `env`, turn out not to agree under the lattice (see
+/// [`meet_conflict`]) -- re-derived from the final environment rather than
+/// tracked during [`resolve_statement`]'s fixpoint loop, since a conflicting
+/// statement's `known_type()` is already `Some(bottom())` by the time the
+/// loop settles and so won't show up in [`unresolved_reports`].
+fn conflicting_reports<'tokens, 'src, L, T>(
+    statements: &[Spanned<L::Output>],
+    env: &HashMap<&'src str, T>,
+) -> Vec<ParseReport>
+where
+    L: Dialect + HasParser<'tokens, 'src, L>,
+    L::Output: TypingRule<'tokens, 'src, L, T>,
+    T: Lattice + HasBottom + PartialEq + Clone + std::fmt::Display,
+{
+    statements
+        .iter()
+        .filter_map(|statement| {
+            let operand_types: Vec<Option<T>> = statement
+                .value
+                .reads()
+                .iter()
+                .map(|name| env.get(name).cloned())
+                .collect();
+            let (expected, found) = meet_conflict(&operand_types)?;
+            Some(ParseReport {
+                span: statement.span,
+                message: format!(
+                    "conflicting operand types for this statement: `{expected}` and `{found}` \
+                     have no type in common"
+                ),
+                expected: Vec::new(),
+                found: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::prelude::*;
+    use chumsky::span::SimpleSpan;
+
+    use super::*;
+    use crate::ast::{BlockArgument, BlockHeader, BlockLabel};
+    use crate::traits::{ParserError, TokenInput};
+    use kirin_ir::{
+        CompileTimeValue, HasArguments, HasArgumentsMut, HasRegionsMut, HasResultsMut, HasSuccessors,
+        HasSuccessorsMut, IsConstant,
+    };
+
+    /// A minimal four-point lattice (`Bottom < {Int, Bool} < Top`) standing
+    /// in for a dialect's real `TypeLattice` output: just enough for
+    /// [`meet`](Lattice::meet) of two unrelated concrete types to land on
+    /// `Bottom` without either side having started there.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum SimpleType {
+        Bottom,
+        Int,
+        Bool,
+        Top,
+    }
+
+    impl std::fmt::Display for SimpleType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SimpleType::Bottom => write!(f, "bottom"),
+                SimpleType::Int => write!(f, "int"),
+                SimpleType::Bool => write!(f, "bool"),
+                SimpleType::Top => write!(f, "top"),
+            }
+        }
+    }
+
+    impl Lattice for SimpleType {
+        fn join(&self, other: &Self) -> Self {
+            match (self, other) {
+                (a, b) if a == b => *a,
+                (SimpleType::Bottom, other) | (other, SimpleType::Bottom) => *other,
+                _ => SimpleType::Top,
+            }
+        }
+
+        fn meet(&self, other: &Self) -> Self {
+            match (self, other) {
+                (a, b) if a == b => *a,
+                (SimpleType::Top, other) | (other, SimpleType::Top) => *other,
+                _ => SimpleType::Bottom,
+            }
+        }
+
+        fn is_subseteq(&self, other: &Self) -> bool {
+            self == other || *self == SimpleType::Bottom || *other == SimpleType::Top
+        }
+    }
+
+    impl HasBottom for SimpleType {
+        fn bottom() -> Self {
+            SimpleType::Bottom
+        }
+    }
+
+    /// Stands in for a dialect's real `TypeLattice` type -- a single
+    /// compile-time value, parsed (in a real dialect) to a [`SimpleType`].
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    struct TestTypeLattice;
+
+    impl Lattice for TestTypeLattice {
+        fn join(&self, _other: &Self) -> Self {
+            TestTypeLattice
+        }
+        fn meet(&self, _other: &Self) -> Self {
+            TestTypeLattice
+        }
+        fn is_subseteq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl HasBottom for TestTypeLattice {
+        fn bottom() -> Self {
+            TestTypeLattice
+        }
+    }
+
+    impl kirin_ir::HasTop for TestTypeLattice {
+        fn top() -> Self {
+            TestTypeLattice
+        }
+    }
+
+    impl CompileTimeValue for TestTypeLattice {}
+    impl kirin_ir::TypeLattice for TestTypeLattice {}
+
+    /// A dialect tag with no statements of its own -- [`TestStmt`] (this
+    /// dialect's [`HasParser::Output`]) is what [`TypingRule`] is actually
+    /// implemented on.
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestLang;
+
+    impl<'a> HasArguments<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a kirin_ir::SSAValue>;
+        fn arguments(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasArgumentsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut kirin_ir::SSAValue>;
+        fn arguments_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> kirin_ir::HasResults<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a kirin_ir::ResultValue>;
+        fn results(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasResultsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut kirin_ir::ResultValue>;
+        fn results_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasSuccessors<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a kirin_ir::Block>;
+        fn successors(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasSuccessorsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut kirin_ir::Block>;
+        fn successors_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> kirin_ir::HasRegions<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a kirin_ir::Region>;
+        fn regions(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasRegionsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut kirin_ir::Region>;
+        fn regions_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl kirin_ir::IsTerminator for TestLang {
+        fn is_terminator(&self) -> bool {
+            false
+        }
+    }
+    impl IsConstant for TestLang {
+        fn is_constant(&self) -> bool {
+            false
+        }
+    }
+    impl kirin_ir::IsPure for TestLang {
+        fn is_pure(&self) -> bool {
+            true
+        }
+    }
+    impl Dialect for TestLang {
+        type TypeLattice = TestTypeLattice;
+    }
+
+    impl<'tokens, 'src: 'tokens> HasParser<'tokens, 'src, TestLang> for TestTypeLattice {
+        type Output = SimpleType;
+        fn parser<I: TokenInput<'tokens, 'src>>()
+        -> Boxed<'tokens, 'tokens, I, Self::Output, ParserError<'tokens, 'src>> {
+            unimplemented!("fixture only satisfies HasParser's bound; infer_block never parses")
+        }
+    }
+
+    /// A single binary statement: reads `lhs`/`rhs`, defines `result`, and
+    /// defers to [`meet_of`] the same way a real dialect's default
+    /// `TypingRule` impl would.
+    #[derive(Clone, Debug)]
+    enum TestStmt<'src> {
+        BinOp {
+            lhs: &'src str,
+            rhs: &'src str,
+            result: &'src str,
+            ty: Option<SimpleType>,
+        },
+    }
+
+    impl<'tokens, 'src: 'tokens> HasParser<'tokens, 'src, TestLang> for TestLang {
+        type Output = TestStmt<'src>;
+        fn parser<I: TokenInput<'tokens, 'src>>()
+        -> Boxed<'tokens, 'tokens, I, Self::Output, ParserError<'tokens, 'src>> {
+            unimplemented!("fixture only satisfies HasParser's bound; infer_block never parses")
+        }
+    }
+
+    impl<'tokens, 'src: 'tokens> TypingRule<'tokens, 'src, TestLang, SimpleType> for TestStmt<'src> {
+        fn reads(&self) -> Vec<&'src str> {
+            match self {
+                TestStmt::BinOp { lhs, rhs, .. } => vec![*lhs, *rhs],
+            }
+        }
+
+        fn defines(&self) -> Option<&'src str> {
+            match self {
+                TestStmt::BinOp { result, .. } => Some(*result),
+            }
+        }
+
+        fn known_type(&self) -> Option<SimpleType> {
+            match self {
+                TestStmt::BinOp { ty, .. } => *ty,
+            }
+        }
+
+        fn set_inferred_type(&mut self, ty: SimpleType) {
+            if let TestStmt::BinOp { ty: slot, .. } = self {
+                *slot = Some(ty);
+            }
+        }
+
+        fn infer(&self, operand_types: &[Option<SimpleType>]) -> Option<SimpleType> {
+            meet_of(operand_types)
+        }
+    }
+
+    fn spanned<T>(value: T) -> Spanned<T> {
+        Spanned { value, span: SimpleSpan::from(0..0) }
+    }
+
+    #[test]
+    fn conflicting_operand_types_produce_a_report_instead_of_silent_bottom() {
+        let header: Spanned<BlockHeader<'_, '_, TestLang>> = spanned(BlockHeader {
+            label: BlockLabel { name: spanned("bb0") },
+            arguments: vec![
+                spanned(BlockArgument { name: spanned("x"), ty: spanned(SimpleType::Int) }),
+                spanned(BlockArgument { name: spanned("y"), ty: spanned(SimpleType::Bool) }),
+            ],
+        });
+        let statement: Spanned<TestStmt<'_>> =
+            spanned(TestStmt::BinOp { lhs: "x", rhs: "y", result: "z", ty: None });
+        let mut block: Block<'_, '_, TestLang> = Block { header, statements: vec![statement] };
+        let mut env: HashMap<&str, SimpleType> = HashMap::new();
+
+        let reports = infer_block(&mut block, &mut env);
+
+        // The statement's own type did resolve -- to `bottom()`, by folding
+        // two operands the lattice doesn't relate -- so it must not also
+        // show up in `unresolved_reports`.
+        assert_eq!(env.get("z"), Some(&SimpleType::Bottom));
+        assert_eq!(reports.len(), 1);
+        assert!(
+            reports[0].message.contains("conflicting operand types"),
+            "expected a conflict report, got: {}",
+            reports[0].message
+        );
+    }
+}
@@ -1,26 +1,73 @@
 pub mod ast;
+/// Rich, source-anchored diagnostics for raw chumsky parse failures.
+pub mod diagnostics;
+/// Stage-dispatched pipeline text parser and the REPL driver built on it.
+pub mod function_text;
+/// Lattice-driven inference for `ty: None` holes a dialect's surface syntax
+/// leaves unspecified.
+pub mod inference;
+/// Whole-program parsing and a line-by-line REPL driver for a single
+/// dialect's surface syntax.
+pub mod module;
 mod parsers;
 mod traits;
+/// Emitting a tree-sitter `grammar.js` from a dialect's format strings.
+pub mod treesitter;
 
 /// Re-export chumsky for parser implementations
 pub use chumsky;
+pub use diagnostics::{
+    ParseReport, parse_dialect_recovering, parse_recovering, render_diagnostics, render_reports,
+    write_diagnostics, write_reports,
+};
+pub use function_text::{
+    Applicability, Diagnostic, FunctionParseError, FunctionParseErrorKind, Label,
+    ParsePipelineText, PipelineRepl, ReplOutcome, ReplStep, Suggestion, render_report,
+};
+pub use inference::{TypingRule, infer_block, infer_region, meet_of};
 pub use kirin_lexer::Token;
+pub use module::{
+    ModuleRepl, ModuleReplOutcome, SessionItem, SessionRepl, SessionReplOutcome, module_parser,
+    parse_session,
+};
 pub use parsers::*;
-pub use traits::{HasParser, ParserError, TokenInput, WithAbstractSyntaxTree};
+pub use traits::{
+    Fold, FoldChildren, HasParser, HasPrinter, ParserError, StructEq, TokenInput, Visit,
+    VisitChildren, VisitMut, VisitMutChildren, WithAbstractSyntaxTree, WithPrinter,
+};
+pub use treesitter::{HasTreeSitterGrammar, TreeSitterRule, emit_grammar};
 
 pub mod prelude {
     pub use crate::ast;
+    pub use crate::diagnostics::{
+        ParseReport, parse_dialect_recovering, parse_recovering, render_diagnostics,
+        render_reports, write_diagnostics, write_reports,
+    };
+    pub use crate::function_text::{
+        Applicability, Diagnostic, FunctionParseError, FunctionParseErrorKind, Label,
+        ParsePipelineText, PipelineRepl, ReplOutcome, ReplStep, Suggestion, render_report,
+    };
+    pub use crate::inference::{TypingRule, infer_block, infer_region, meet_of};
+    pub use crate::module::{
+        ModuleRepl, ModuleReplOutcome, SessionItem, SessionRepl, SessionReplOutcome, module_parser,
+        parse_session,
+    };
     pub use crate::parsers::*;
-    pub use crate::traits::{HasParser, ParserError, TokenInput, WithAbstractSyntaxTree};
+    pub use crate::traits::{
+        Fold, FoldChildren, HasParser, HasPrinter, ParserError, StructEq, TokenInput, Visit,
+        VisitChildren, VisitMut, VisitMutChildren, WithAbstractSyntaxTree, WithPrinter,
+    };
+    pub use crate::treesitter::{HasTreeSitterGrammar, TreeSitterRule, emit_grammar};
+    pub use crate::assert_struct_eq;
     pub use chumsky::prelude::*;
     pub use kirin_lexer::Token;
 
     #[cfg(feature = "derive")]
-    pub use kirin_derive::WithAbstractSyntaxTree;
+    pub use kirin_derive::{StructEq, Visitor, WithAbstractSyntaxTree};
 }
 
 #[cfg(test)]
 mod tests;
 
 #[cfg(feature = "derive")]
-pub use kirin_derive::WithAbstractSyntaxTree;
+pub use kirin_derive::{StructEq, Visitor, WithAbstractSyntaxTree};
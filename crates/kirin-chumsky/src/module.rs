@@ -0,0 +1,292 @@
+//! A module-level parser and REPL-oriented incremental driver for a single
+//! derived dialect.
+//!
+//! Every generated `HasParser` impl parses exactly one operation;
+//! [`module_parser`] wraps it to parse a whole program instead: a
+//! `;`-or-newline-separated sequence of operations, each wrapped in a
+//! [`Spanned`] so a caller can still locate it within the original buffer.
+//! [`ModuleRepl`] builds on top of that for line-by-line interactive use,
+//! buffering input until [`crate::parsers::needs_more_input`] says chumsky
+//! merely ran out of tokens rather than hit a genuine mismatch -- the same
+//! multi-line continuation signal used one layer down for a single
+//! declaration in `function_text`, applied here across a whole buffered
+//! program.
+//!
+//! [`parse_session`]/[`SessionRepl`] are the same idea with a different
+//! shape of result: instead of flattening to the operations that parsed
+//! and silently dropping the ones that didn't, [`SessionItem`] keeps every
+//! statement's slot -- output, diagnostics, and span -- so a session/REPL
+//! history view can show which line failed and why.
+
+use chumsky::error::Rich;
+use chumsky::input::Stream;
+use chumsky::prelude::*;
+use chumsky::span::SimpleSpan;
+
+use kirin_ir::Dialect;
+use kirin_lexer::{Logos, Token};
+
+use crate::ast::Spanned;
+use crate::diagnostics::ParseReport;
+use crate::parsers::{needs_more_input, recovering_top_level};
+use crate::traits::{HasParser, ParserError, TokenInput};
+
+/// Parses a whole program for dialect `L`: zero or more operations, each
+/// optionally terminated by `;` (so a trailing newline alone closes out the
+/// last one), wrapped in a [`Spanned`] so a caller can locate each top-level
+/// item in the original buffer.
+///
+/// A malformed item doesn't abort the rest of the module: like
+/// [`block`](crate::block)'s statement sequence, a failing item is skipped up
+/// to the next `;`/end-of-input via [`recovering_top_level`] and dropped from
+/// the result, rather than stopping the whole parse at the first mistake.
+/// Unlike `block`'s statement sequence, there's no enclosing `{`/`}` to
+/// resync against, so a stray `}` is not treated as a recovery point here.
+pub fn module_parser<'tokens, 'src, I, L>()
+-> impl Parser<'tokens, I, Vec<Spanned<L::Output>>, ParserError<'tokens, 'src>>
+where
+    I: TokenInput<'tokens, 'src>,
+    L: Dialect + HasParser<'tokens, 'src> + 'tokens,
+{
+    let item = L::parser()
+        .map_with(|op, e| Spanned {
+            value: op,
+            span: e.span(),
+        })
+        .then_ignore(just(Token::Semicolon).or_not());
+
+    recovering_top_level(item)
+        .repeated()
+        .collect::<Vec<_>>()
+        .map(|items| items.into_iter().flatten().collect())
+}
+
+/// Outcome of feeding one line to [`ModuleRepl::feed_line`].
+#[derive(Debug)]
+pub enum ModuleReplOutcome<'tokens, 'src, L: HasParser<'tokens, 'src>> {
+    /// The buffered input is syntactically incomplete (e.g. an unclosed `{`
+    /// from a multi-line block/region) -- the caller should read another
+    /// line and feed it in rather than reporting an error.
+    NeedsMore,
+    /// The buffered input parsed into a complete program. The buffer is
+    /// left in place until [`ModuleRepl::reset`] is called, so this borrows
+    /// from it.
+    Complete(Vec<Spanned<L::Output>>),
+    /// The buffered input is complete but definitely invalid.
+    Error(Vec<Rich<'tokens, Token<'src>, SimpleSpan>>),
+}
+
+/// Line-by-line REPL driver over [`module_parser`] for a single dialect `L`.
+///
+/// Lines accumulate in an internal buffer until they parse as a complete
+/// program; [`feed_line`](Self::feed_line) reports [`ModuleReplOutcome::NeedsMore`]
+/// in the meantime so a front-end can keep reading without surfacing a hard
+/// error for every partial line, the way
+/// [`kirin_interpreter::repl::ReplSession`] does for a lowered IR pipeline --
+/// this one stays entirely at the surface syntax level, with no `Pipeline`
+/// or dialect registration required.
+#[derive(Debug, Default)]
+pub struct ModuleRepl {
+    buffer: String,
+}
+
+impl ModuleRepl {
+    /// Starts an empty session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The input buffered so far, including lines that haven't parsed as a
+    /// complete program yet.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Discards the buffered input, e.g. after consuming a
+    /// [`ModuleReplOutcome::Complete`] result or giving up on a
+    /// [`ModuleReplOutcome::Error`].
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Feeds one more line of input, re-parsing the whole buffer (including
+    /// every line fed in since the last [`reset`](Self::reset)) as a
+    /// program for dialect `L`.
+    pub fn feed_line<'a, L>(&'a mut self, line: &str) -> ModuleReplOutcome<'a, 'a, L>
+    where
+        L: Dialect + HasParser<'a, 'a> + 'a,
+    {
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+
+        let stream = tokenize_buffer(&self.buffer);
+        let (output, errors) = module_parser::<_, L>().parse(stream).into_output_errors();
+        match output {
+            Some(items) if errors.is_empty() => ModuleReplOutcome::Complete(items),
+            _ if needs_more_input(&errors) => ModuleReplOutcome::NeedsMore,
+            _ => ModuleReplOutcome::Error(errors),
+        }
+    }
+}
+
+/// Lexes `buffer` and wraps it as a [`Stream`] the way [`ModuleRepl`]'s and
+/// [`SessionRepl`]'s `feed_line` both need to, re-tokenizing from scratch
+/// since the whole buffered program gets re-parsed on every line fed in.
+fn tokenize_buffer(buffer: &str) -> impl TokenInput<'_, '_> {
+    let tokens: Vec<_> = Token::lexer(buffer)
+        .spanned()
+        .map(|(tok, span)| (tok.unwrap_or(Token::Error), SimpleSpan::from(span)))
+        .collect();
+    Stream::from_iter(tokens).map((0..buffer.len()).into(), |(t, s)| (t, s))
+}
+
+/// One statement parsed by [`parse_session`]: its AST (`None` if recovery
+/// never produced one at all), every [`ParseReport`] whose span falls
+/// within this statement, and the statement's own byte-offset span in the
+/// original buffer.
+///
+/// Unlike [`module_parser`]'s flattened `Vec<Spanned<L::Output>>`, a failed
+/// statement still gets an entry here -- a session/REPL history view wants
+/// to say *which* statement failed and why, not just how many survived.
+#[derive(Debug)]
+pub struct SessionItem<'tokens, 'src, L: HasParser<'tokens, 'src>> {
+    pub output: Option<L::Output>,
+    pub diagnostics: Vec<ParseReport>,
+    pub span: SimpleSpan,
+}
+
+/// Parses a whole source buffer as a sequence of dialect statements, one
+/// [`SessionItem`] per statement (successes and failures alike), splitting
+/// on the same `;`/end-of-input boundary [`module_parser`] uses.
+///
+/// A statement's [`ParseReport`]s are every diagnostic [`recovering_top_level`]'s
+/// recovery raised with a span inside that statement's own extent, so one
+/// malformed statement's errors stay attached to its entry instead of
+/// leaking into its neighbours'.
+pub fn parse_session<'tokens, 'src, I, L>(tokens: I) -> Vec<SessionItem<'tokens, 'src, L>>
+where
+    I: TokenInput<'tokens, 'src>,
+    L: Dialect + HasParser<'tokens, 'src> + 'tokens,
+{
+    let (slots, errors) = parse_session_slots::<_, L>(tokens);
+    slots_to_items(slots, &errors)
+}
+
+type SessionSlot<'tokens, 'src, L> =
+    (Option<(<L as HasParser<'tokens, 'src>>::Output, SimpleSpan)>, SimpleSpan);
+
+/// Shared by [`parse_session`] and [`SessionRepl::feed_line`]: the latter
+/// needs the raw `Rich` errors (not yet turned into [`ParseReport`]s) to run
+/// [`needs_more_input`] on, on top of the same per-slot spans `parse_session`
+/// turns into [`SessionItem`]s.
+fn parse_session_slots<'tokens, 'src, I, L>(
+    tokens: I,
+) -> (Vec<SessionSlot<'tokens, 'src, L>>, Vec<Rich<'tokens, Token<'src>, SimpleSpan>>)
+where
+    I: TokenInput<'tokens, 'src>,
+    L: Dialect + HasParser<'tokens, 'src> + 'tokens,
+{
+    let item = L::parser()
+        .map_with(|op, e| (op, e.span()))
+        .then_ignore(just(Token::Semicolon).or_not());
+    let slot = recovering_top_level(item).map_with(|item, e| (item, e.span()));
+
+    let (slots, errors) = slot.repeated().collect::<Vec<_>>().parse(tokens).into_output_errors();
+    (slots.unwrap_or_default(), errors)
+}
+
+/// Pairs each slot [`parse_session_slots`] produced with the diagnostics
+/// whose span falls inside it, turning raw `Rich` errors into the
+/// [`ParseReport`]s a [`SessionItem`] carries.
+///
+/// A successful slot's `item_span` is narrower than its `slot_span` (the
+/// latter also covers the statement's trailing `;`, consumed after the
+/// inner `map_with` that records `item_span`) -- `item_span` is what we
+/// want here, so a [`SessionItem`]'s span doesn't include separator
+/// punctuation that isn't really part of the statement.
+fn slots_to_items<'tokens, 'src, L>(
+    slots: Vec<SessionSlot<'tokens, 'src, L>>,
+    errors: &[Rich<'tokens, Token<'src>, SimpleSpan>],
+) -> Vec<SessionItem<'tokens, 'src, L>>
+where
+    L: Dialect + HasParser<'tokens, 'src> + 'tokens,
+{
+    slots
+        .into_iter()
+        .map(|(parsed, slot_span)| {
+            let (output, span) = match parsed {
+                Some((output, item_span)) => (Some(output), item_span),
+                None => (None, slot_span),
+            };
+            let diagnostics = errors
+                .iter()
+                .filter(|error| span.start <= error.span().start && error.span().start < span.end)
+                .map(ParseReport::from)
+                .collect();
+            SessionItem { output, diagnostics, span }
+        })
+        .collect()
+}
+
+/// Outcome of feeding one line to [`SessionRepl::feed_line`].
+#[derive(Debug)]
+pub enum SessionReplOutcome<'tokens, 'src, L: HasParser<'tokens, 'src>> {
+    /// The buffered input is syntactically incomplete (e.g. an unclosed `{`
+    /// from a multi-line block/region) -- the caller should read another
+    /// line and feed it in rather than reporting an error.
+    NeedsMore,
+    /// The buffered input parsed into a complete sequence of statements, one
+    /// [`SessionItem`] per statement. The buffer is left in place until
+    /// [`SessionRepl::reset`] is called, so this borrows from it.
+    Complete(Vec<SessionItem<'tokens, 'src, L>>),
+}
+
+/// Like [`ModuleRepl`], but built on [`parse_session`] instead of
+/// [`module_parser`]: every [`feed_line`](Self::feed_line) call still
+/// re-parses the whole buffered program (there is no incremental re-parse
+/// here, the same tradeoff `ModuleRepl` makes), but the result is one
+/// [`SessionItem`] per statement rather than a flattened list of survivors,
+/// so a REPL history view can show *which* line in the session failed
+/// instead of only how many of them parsed.
+#[derive(Debug, Default)]
+pub struct SessionRepl {
+    buffer: String,
+}
+
+impl SessionRepl {
+    /// Starts an empty session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The input buffered so far, including lines that haven't parsed as a
+    /// complete statement yet.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Discards the buffered input, e.g. after consuming a
+    /// [`SessionReplOutcome::Complete`] result.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Feeds one more line of input, re-parsing the whole buffer (including
+    /// every line fed in since the last [`reset`](Self::reset)) as a
+    /// sequence of statements for dialect `L`.
+    pub fn feed_line<'a, L>(&'a mut self, line: &str) -> SessionReplOutcome<'a, 'a, L>
+    where
+        L: Dialect + HasParser<'a, 'a> + 'a,
+    {
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+
+        let stream = tokenize_buffer(&self.buffer);
+        let (slots, errors) = parse_session_slots::<_, L>(stream);
+        if needs_more_input(&errors) {
+            return SessionReplOutcome::NeedsMore;
+        }
+
+        SessionReplOutcome::Complete(slots_to_items(slots, &errors))
+    }
+}
@@ -338,6 +338,101 @@ where
         .labelled("block header")
 }
 
+/// Skips exactly one "unit" while scanning for a statement-recovery sync
+/// point: either a single non-brace token, or a whole `{ ... }` group
+/// (itself made of further such units, so nested groups nest correctly).
+///
+/// Used by [`recovering`] so that a malformed statement containing its own
+/// nested block/region (e.g. an `if`/`else` whose body fields are `Region`s,
+/// per `kirin-chumsky-format`'s `field_kind`) has its inner `}`s swallowed
+/// as part of skipping the whole statement, rather than being mistaken for
+/// the enclosing block's own closing brace.
+fn skip_balanced<'tokens, 'src, I>()
+-> impl Parser<'tokens, I, (), ParserError<'tokens, 'src>> + Clone
+where
+    I: TokenInput<'tokens, 'src>,
+{
+    recursive(|skip_unit| {
+        let group = just(Token::LBrace)
+            .then(skip_unit.repeated())
+            .then(just(Token::RBrace))
+            .ignored();
+        let non_brace = any()
+            .filter(|tok| !matches!(tok, Token::LBrace | Token::RBrace))
+            .ignored();
+        choice((group, non_brace))
+    })
+}
+
+/// Wraps a single parsed unit (typically one `statement;` inside a block
+/// body, i.e. `stmt` already includes its own trailing `;`) so that a
+/// failure to parse it doesn't abort the rest of the sequence it's
+/// `.repeated()` in: on failure, [`skip_balanced`] units are skipped one at
+/// a time until a `;` is found (and consumed, standing in for the `;` the
+/// failed `stmt` would otherwise have consumed itself), the block's closing
+/// `}` is seen (left unconsumed, for the enclosing `delimited_by` to
+/// match), or end-of-input is reached, and `None` is produced in place of
+/// the failed statement instead of retrying it.
+///
+/// This mirrors the per-variant `#[chumsky(recover)]` skip-to-sync-token
+/// strategy in the derive (see `recover_for_statement` in
+/// `kirin-chumsky-format`), but at the level of the block's statement
+/// *sequence* rather than inside a single dialect's own `.or()` chain of
+/// alternatives, and without needing an `Error` variant on `StmtOutput<L>`:
+/// the caller just filters the `None`s back out. The skipped span is still
+/// recorded as a parse error, so callers that want every mistake in a block
+/// rather than just the first should read the errors back out via
+/// [`parse_recovering`](crate::diagnostics::parse_recovering) instead of
+/// `.into_result()`.
+fn recovering<'tokens, 'src, I, O>(
+    stmt: impl Parser<'tokens, I, O, ParserError<'tokens, 'src>> + Clone + 'tokens,
+) -> impl Parser<'tokens, I, Option<O>, ParserError<'tokens, 'src>>
+where
+    I: TokenInput<'tokens, 'src>,
+{
+    let sync = choice((
+        just(Token::Semicolon).ignored(),
+        just(Token::RBrace).rewind().ignored(),
+        end(),
+    ));
+    stmt.map(Some)
+        .recover_with(via_parser(skip_then_retry_until(skip_balanced(), sync).to(None)))
+}
+
+/// Like [`recovering`], but for a sequence with no enclosing
+/// `delimited_by(LBrace, RBrace)` to hand an unconsumed `}` back to (e.g.
+/// [`crate::module`]'s top-level item sequence): the sync set only accepts
+/// `;` or end-of-input, so a stray unmatched `}` is left for [`skip_balanced`]
+/// to fail on rather than being mistaken for a delimiter that doesn't exist
+/// at this level.
+pub(crate) fn recovering_top_level<'tokens, 'src, I, O>(
+    item: impl Parser<'tokens, I, O, ParserError<'tokens, 'src>> + Clone + 'tokens,
+) -> impl Parser<'tokens, I, Option<O>, ParserError<'tokens, 'src>>
+where
+    I: TokenInput<'tokens, 'src>,
+{
+    let sync = choice((just(Token::Semicolon).ignored(), end()));
+    item.map(Some)
+        .recover_with(via_parser(skip_then_retry_until(skip_balanced(), sync).to(None)))
+}
+
+/// An error set means "just needs more input" only if every error in it
+/// failed by running out of tokens (`found` is `None`) while still expecting
+/// at least one token. A genuine mismatch mid-stream always counts as a hard
+/// error, even if other errors in the same batch were end-of-input misses.
+///
+/// Shared by [`function_text::syntax`](crate::function_text) (one
+/// declaration at a time) and [`crate::module`] (a whole buffered program),
+/// which both need this same incremental-parse heuristic.
+pub(crate) fn needs_more_input<'tokens, 'src>(
+    errors: &[chumsky::error::Rich<'tokens, Token<'src>, chumsky::span::SimpleSpan>],
+) -> bool {
+    !errors.is_empty()
+        && errors
+            .iter()
+            .all(|error| error.found().is_none() && error.expected().next().is_some())
+}
+
 /// Parses a complete block with header and statements.
 ///
 /// Requires a parser for the language/dialect statements.
@@ -357,15 +452,17 @@ where
     T: HasParser<'tokens, 'src, Output = T>,
 {
     let header = block_header::<_, L, T>();
-    let statements = language
+    let statement = language
         .clone()
         .map_with(|stmt, e| Spanned {
             value: stmt,
             span: e.span(),
         })
-        .then_ignore(just(Token::Semicolon))
+        .then_ignore(just(Token::Semicolon));
+    let statements = recovering(statement)
         .repeated()
         .collect::<Vec<_>>()
+        .map(|statements| statements.into_iter().flatten().collect::<Vec<_>>())
         .or(empty().to(Vec::new()))
         .delimited_by(just(Token::LBrace), just(Token::RBrace))
         .labelled("block statements");
@@ -390,6 +487,10 @@ where
 /// }
 /// ```
 ///
+/// Inherits [`block`]'s statement-level error recovery: a malformed
+/// statement in any of the region's blocks is skipped rather than aborting
+/// the whole region.
+///
 /// The type parameter `T` specifies the type annotation type (typically `L::TypeLattice`).
 pub fn region<'tokens, 'src: 'tokens, I, L, T>(
     language: RecursiveParser<
@@ -434,6 +535,60 @@ where
     region::<_, L, T>(language).boxed()
 }
 
+/// Parses a bare (unlabeled) block body: `{ stmt; stmt; }` with no
+/// `^label(...)` header.
+///
+/// This is for `Block`/`Region` fields marked `#[kirin(bare)]`. The parser
+/// consumes the `{`/`}` delimiters itself, so the format string names only
+/// the field (e.g. `"{res} = if {cond} {body}"` parses
+/// `%r = if %c { stmt; }`), and there is no separate label to parse.
+///
+/// Like [`block`], a malformed statement is skipped (via [`recovering`])
+/// rather than aborting the rest of the body.
+pub fn bare_block<'tokens, 'src: 'tokens, I, L>(
+    language: RecursiveParser<'tokens, 'src, I, StmtOutput<'tokens, 'src, L>>,
+) -> impl Parser<'tokens, I, BareBlock<'src, StmtOutput<'tokens, 'src, L>>, ParserError<'tokens, 'src>>
+where
+    I: TokenInput<'tokens, 'src>,
+    L: HasDialectParser<'tokens, 'src, L> + Dialect + 'tokens,
+{
+    let statement = language
+        .clone()
+        .map_with(|stmt, e| Spanned {
+            value: stmt,
+            span: e.span(),
+        })
+        .then_ignore(just(Token::Semicolon));
+    recovering(statement)
+        .repeated()
+        .collect::<Vec<_>>()
+        .map(|statements| statements.into_iter().flatten().collect::<Vec<_>>())
+        .or(empty().to(Vec::new()))
+        .delimited_by(just(Token::LBrace), just(Token::RBrace))
+        .map(|statements| BareBlock { statements })
+        .labelled("bare block")
+}
+
+/// Parses a bare region: a sequence of [`bare_block`]s with no labels,
+/// delimited by `{`/`}`.
+///
+/// Used for `Region` fields marked `#[kirin(bare)]`. Inherits
+/// [`bare_block`]'s statement-level error recovery.
+pub fn bare_region<'tokens, 'src: 'tokens, I, L>(
+    language: RecursiveParser<'tokens, 'src, I, StmtOutput<'tokens, 'src, L>>,
+) -> impl Parser<'tokens, I, Vec<BareBlock<'src, StmtOutput<'tokens, 'src, L>>>, ParserError<'tokens, 'src>>
+where
+    I: TokenInput<'tokens, 'src>,
+    L: HasDialectParser<'tokens, 'src, L> + Dialect + 'tokens,
+{
+    bare_block::<_, L>(language)
+        .then_ignore(just(Token::Semicolon).or_not())
+        .repeated()
+        .collect::<Vec<_>>()
+        .delimited_by(just(Token::LBrace), just(Token::RBrace))
+        .labelled("bare region")
+}
+
 /// Parses a function type signature.
 ///
 /// Matches: `(i32, f64) -> bool` or `(i32) -> (bool, i32)` or `-> i32`
@@ -4,6 +4,8 @@ use kirin_lexer::Token;
 use kirin_test_utils::*;
 use logos::Logos;
 
+use crate::diagnostics::render_diagnostics;
+
 #[derive(Debug, Clone)]
 pub enum SimpleAST<'tokens, 'src: 'tokens> {
     Add {
@@ -157,3 +159,64 @@ fn test_simple_language_parser() {
         }
     }
 }
+
+#[test]
+fn test_render_diagnostics_underlines_offending_token() {
+    const BAD_SRC: &str = "%r = add %a";
+
+    let token_iter = Token::lexer(BAD_SRC).spanned().map(|(tok, span)| match tok {
+        Ok(tok) => (tok, span.into()),
+        Err(()) => (Token::Error, span.into()),
+    });
+    let token_stream =
+        Stream::from_iter(token_iter).map((0..BAD_SRC.len()).into(), |(t, s): (_, _)| (t, s));
+
+    let errors = match SimpleLanguage::parser().parse(token_stream).into_result() {
+        Ok(_) => panic!("expected a parse error for a missing operand"),
+        Err(errors) => errors,
+    };
+
+    let report = render_diagnostics(BAD_SRC, &errors, false);
+    assert!(report.starts_with("error:"));
+    assert!(report.contains(BAD_SRC));
+    assert!(report.contains('^'));
+}
+
+#[test]
+fn test_parse_recovering_skips_bad_statement() {
+    use crate::diagnostics::parse_recovering;
+
+    // `%bad ???` isn't a valid statement, but the two surrounding statements
+    // are: recovery should skip it and still parse the rest of the block.
+    const SRC: &str = "
+%f = fn @main() -> i64 {
+    ^bb0() {
+        %a = constant 1.2;
+        %bad ???;
+        %r = add %a, %a;
+        return %r;
+    }
+}
+";
+
+    let token_iter = Token::lexer(SRC).spanned().map(|(tok, span)| match tok {
+        Ok(tok) => (tok, span.into()),
+        Err(()) => (Token::Error, span.into()),
+    });
+    let token_stream =
+        Stream::from_iter(token_iter).map((0..SRC.len()).into(), |(t, s): (_, _)| (t, s));
+
+    let (ast, errors) = parse_recovering(SimpleLanguage::parser(), token_stream);
+
+    assert!(!errors.is_empty(), "the bad statement should be reported");
+    let SimpleAST::Function { body, .. } = ast.expect("recovery should still produce an AST") else {
+        panic!("expected a parsed function");
+    };
+    let statements = &body.value.statements;
+    assert_eq!(
+        statements.len(),
+        3,
+        "the 3 good statements (`constant`, `add`, `return`) around the \
+         bad one should all still be present: {statements:?}"
+    );
+}
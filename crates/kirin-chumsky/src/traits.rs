@@ -43,6 +43,183 @@ pub trait WithAbstractSyntaxTree<'tokens, 'src: 'tokens, L: Dialect + HasParser<
     type AbstractSyntaxTreeNode;
 }
 
+/// Mirrors [`HasParser`] in the opposite direction: writes a value of type
+/// `Self::Output` back out as the surface syntax `HasParser::parser()`
+/// would accept for it.
+///
+/// Implemented for primitive types via [`impl_has_printer!`] below; dialect
+/// authors implement it for their `TypeLattice` (e.g. `SimpleType::I32`
+/// prints as `"i32"`) the same way they implement [`HasParser`] for it, for
+/// use in hand-written, dialect-aware printers.
+///
+/// The `WithPrinter` derive below does *not* go through this trait: the
+/// generated `*AST` type is generic over `TypeOutput`/`LanguageOutput`
+/// rather than a concrete `Dialect`/`L` (see `GenerateAST`'s doc comment),
+/// so it has no `L` to name this trait's bound with and instead requires
+/// `TypeOutput: Display`.
+pub trait HasPrinter<'tokens, 'src: 'tokens, L: Dialect + HasParser<'tokens, 'src, L>>:
+    HasParser<'tokens, 'src, L>
+{
+    /// Appends this value's surface syntax to `out`.
+    fn print(value: &Self::Output, out: &mut String);
+}
+
+/// Renders a parsed `*AST` node back to the surface syntax its
+/// `#[chumsky(format = "...")]` attribute describes, so parse -> print ->
+/// parse is a stable round-trip.
+///
+/// Unlike [`HasPrinter`], which mirrors [`HasParser`] for a single type
+/// lattice value, this mirrors [`crate::EmitIR`]: it's implemented once per
+/// dialect on the generated `*AST` type (by the `WithPrinter` derive) and
+/// has no `Dialect`/lattice parameter of its own, since printing a parsed
+/// tree back to text needs no IR context. A `Block`/`Region` field recurses
+/// into its nested statements' own `WithPrinter` impl via `LanguageOutput`.
+pub trait WithPrinter {
+    /// Appends this node's surface syntax to `out`.
+    fn print_into(&self, out: &mut String);
+
+    /// Renders this node's surface syntax as a new `String`.
+    fn print(&self) -> String {
+        let mut out = String::new();
+        self.print_into(&mut out);
+        out
+    }
+}
+
+/// Read-only traversal over a derived `*AST` node's `Block`/`Region`
+/// children, generated once per dialect by the `Visitor` derive.
+///
+/// Mirrors [`WithPrinter`]: implemented on the generated `*AST` type itself
+/// (no `Dialect`/lattice parameter), recursing into nested statements via
+/// `V`'s own [`Visit`] impl for that same `*AST` type rather than a
+/// hand-rolled `match`. Field kinds that don't nest another AST node
+/// (SSA/result values, successors, `Value` fields) have nothing to recurse
+/// into, so they're skipped — see [`Visit`] for observing the node itself.
+pub trait VisitChildren<V: ?Sized> {
+    /// Visits every nested statement reachable through this node's
+    /// `Block`/`Region` fields.
+    fn visit_children(&self, visitor: &mut V);
+}
+
+/// A read-only pass over a dialect's `*AST` tree.
+///
+/// Unlike swc's visitor (one method per distinct node *type*), this
+/// codebase gives every variant of a dialect the same Rust type (the
+/// generated `*AST` enum), so there's a single `visit` method per dialect
+/// rather than per variant; match on `node` inside an override to special-case
+/// individual variants. The default falls through to
+/// [`VisitChildren::visit_children`], so an override only needs to handle
+/// the variants it cares about and call `node.visit_children(self)` itself
+/// if it still wants the rest walked.
+pub trait Visit<Node: VisitChildren<Self>>: Sized {
+    /// Visits `node`, descending into its children by default.
+    fn visit(&mut self, node: &Node) {
+        node.visit_children(self);
+    }
+}
+
+/// The in-place-mutation counterpart to [`VisitChildren`], generated by the
+/// same `Visitor` derive.
+pub trait VisitMutChildren<V: ?Sized> {
+    /// Visits every nested statement reachable through this node's
+    /// `Block`/`Region` fields, by mutable reference.
+    fn visit_children_mut(&mut self, visitor: &mut V);
+}
+
+/// The in-place-mutation counterpart to [`Visit`]: a pass that rewrites
+/// nodes by reference instead of producing a new tree. See [`Fold`] for the
+/// owning alternative.
+pub trait VisitMut<Node: VisitMutChildren<Self>>: Sized {
+    /// Visits `node` by mutable reference, descending into its children by
+    /// default.
+    fn visit_mut(&mut self, node: &mut Node) {
+        node.visit_children_mut(self);
+    }
+}
+
+/// The owning, tree-rebuilding counterpart to [`VisitChildren`], generated
+/// by the same `Visitor` derive.
+pub trait FoldChildren<F: ?Sized> {
+    /// Rebuilds every nested statement reachable through this node's
+    /// `Block`/`Region` fields, consuming `self`.
+    fn fold_children(self, folder: &mut F) -> Self;
+}
+
+/// An owning pass over a dialect's `*AST` tree that rebuilds a new tree
+/// (e.g. constant folding, SSA renaming) rather than mutating in place. See
+/// [`VisitMut`] for the in-place alternative.
+pub trait Fold<Node: FoldChildren<Self>>: Sized {
+    /// Folds `node` into a (possibly rebuilt) replacement, descending into
+    /// its children by default.
+    fn fold(&mut self, node: Node) -> Node {
+        node.fold_children(self)
+    }
+}
+
+/// Compares two derived `*AST` nodes for structural equality while ignoring
+/// the byte offsets carried by every `Spanned<_>` field, so two parses of
+/// differently-formatted-but-equivalent source (e.g. differing only in
+/// whitespace) compare equal.
+///
+/// Implemented once per dialect on the generated `*AST` type by the
+/// `StructEq` derive, mirroring [`WithPrinter`]: it has no `Dialect`/lattice
+/// parameter of its own, and a `Block`/`Region` field recurses into its
+/// nested statements' own `StructEq` impl via `LanguageOutput`. Use
+/// [`assert_struct_eq!`] in tests for a panic message that prints both sides
+/// on mismatch.
+pub trait StructEq {
+    /// Returns whether `self` and `other` have the same structure, names,
+    /// and types, ignoring `Spanned` source spans.
+    fn struct_eq(&self, other: &Self) -> bool;
+}
+
+/// Asserts that two derived `*AST` nodes are structurally equal via
+/// [`StructEq::struct_eq`], the `Spanned`-ignoring counterpart to
+/// `assert_eq!`. Panics with both sides rendered via `{:?}` on mismatch.
+#[macro_export]
+macro_rules! assert_struct_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if !$crate::StructEq::struct_eq(left_val, right_val) {
+                    panic!(
+                        "assertion `left.struct_eq(right)` failed\n  left: {:?}\n right: {:?}",
+                        left_val, right_val
+                    );
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if !$crate::StructEq::struct_eq(left_val, right_val) {
+                    panic!(
+                        "assertion `left.struct_eq(right)` failed: {}\n  left: {:?}\n right: {:?}",
+                        format_args!($($arg)+),
+                        left_val,
+                        right_val
+                    );
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_has_printer {
+    ($name:ident) => {
+        impl<'tokens, 'src, L> HasPrinter<'tokens, 'src, L> for $name
+        where
+            'src: 'tokens,
+            L: Dialect + HasParser<'tokens, 'src, L> + 'tokens,
+        {
+            fn print(value: &Self::Output, out: &mut String) {
+                out.push_str(&value.to_string());
+            }
+        }
+    };
+}
+
 impl<'tokens, 'src, L, T> WithAbstractSyntaxTree<'tokens, 'src, L> for std::marker::PhantomData<T>
 where
     'src: 'tokens,
@@ -141,3 +318,16 @@ impl_with_abstract_syntax_tree!(f32);
 impl_with_abstract_syntax_tree!(f64);
 impl_with_abstract_syntax_tree!(bool);
 impl_with_abstract_syntax_tree!(String);
+
+impl_has_printer!(u8);
+impl_has_printer!(u16);
+impl_has_printer!(u32);
+impl_has_printer!(u64);
+impl_has_printer!(i8);
+impl_has_printer!(i16);
+impl_has_printer!(i32);
+impl_has_printer!(i64);
+impl_has_printer!(f32);
+impl_has_printer!(f64);
+impl_has_printer!(bool);
+impl_has_printer!(String);
@@ -0,0 +1,77 @@
+//! Emits an external [tree-sitter](https://tree-sitter.github.io/tree-sitter/)
+//! `grammar.js` from a dialect's `#[chumsky(format = "...")]` definitions, so
+//! editor support (highlighting, folding, structural selection) stays in
+//! sync with the Rust grammar instead of being hand-maintained separately.
+//!
+//! [`HasTreeSitterGrammar`] is implemented by the `TreeSitterGrammar` derive
+//! macro, which walks each variant's format string once at macro-expansion
+//! time and bakes the resulting rule bodies into `RULES`; [`emit_grammar`]
+//! only has to stitch those per-variant bodies together with the handful of
+//! rules every dialect shares (`ssa_value`, `result_value`, `label`, `type`,
+//! and the recursive `block`/`region` bodies).
+
+/// One named tree-sitter rule, generated from a single dialect variant (or
+/// struct) by the `TreeSitterGrammar` derive macro.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeSitterRule {
+    /// The rule's name in `grammar.js` (the variant name, `snake_case`d).
+    pub name: &'static str,
+    /// The rule's `$ => ...` body, already rendered as tree-sitter DSL
+    /// source (e.g. `seq($.result_value, '=', 'add', $.ssa_value)`).
+    pub body: &'static str,
+}
+
+/// Implemented by dialects with a `#[derive(TreeSitterGrammar)]`: exposes
+/// one [`TreeSitterRule`] per `#[chumsky(format = "...")]` variant/struct,
+/// in declaration order.
+pub trait HasTreeSitterGrammar {
+    /// This dialect's rules, one per format-string-bearing variant/struct.
+    const RULES: &'static [TreeSitterRule];
+
+    /// Renders this dialect's complete `grammar.js` via [`emit_grammar`], so
+    /// e.g. `TupleLang::tree_sitter_grammar("tuple_lang")` doesn't need the
+    /// caller to name the free function and turbofish the dialect itself.
+    fn tree_sitter_grammar(grammar_name: &str) -> String
+    where
+        Self: Sized,
+    {
+        emit_grammar::<Self>(grammar_name)
+    }
+}
+
+/// Renders a complete `grammar.js` for dialect `L`: a top-level `_item`
+/// choice over `L::RULES`, then the rules shared by every dialect -- SSA
+/// values, result values, successor labels, types, values, and recursive
+/// `block`/`region` bodies delimited by `{ }` and separated by `;` --
+/// followed by the `L::RULES` definitions themselves.
+///
+/// `grammar_name` becomes the tree-sitter grammar's `name` field, which also
+/// names the generated parser (e.g. `tree-sitter-<grammar_name>`).
+pub fn emit_grammar<L: HasTreeSitterGrammar>(grammar_name: &str) -> String {
+    let mut item_choices = String::new();
+    let mut rule_defs = String::new();
+    for rule in L::RULES {
+        item_choices.push_str(&format!("      $.{},\n", rule.name));
+        rule_defs.push_str(&format!("    {}: $ => {},\n\n", rule.name, rule.body));
+    }
+
+    let mut out = String::new();
+    out.push_str("module.exports = grammar({\n");
+    out.push_str(&format!("  name: '{grammar_name}',\n"));
+    out.push_str("  rules: {\n");
+    out.push_str("    source_file: $ => repeat(seq($._item, optional(';'))),\n\n");
+    out.push_str("    _item: $ => choice(\n");
+    out.push_str(&item_choices);
+    out.push_str("    ),\n\n");
+    out.push_str("    block: $ => seq('{', repeat(seq($._item, optional(';'))), '}'),\n");
+    out.push_str("    region: $ => seq('{', repeat($.block), '}'),\n");
+    out.push_str("    label: $ => /\\^[A-Za-z_][A-Za-z0-9_]*/,\n");
+    out.push_str("    ssa_value: $ => /%[A-Za-z_][A-Za-z0-9_]*/,\n");
+    out.push_str("    result_value: $ => /%[A-Za-z_][A-Za-z0-9_]*/,\n");
+    out.push_str("    type: $ => /[A-Za-z_][A-Za-z0-9_]*/,\n");
+    out.push_str("    value: $ => /[A-Za-z0-9_.+-]+/,\n\n");
+    out.push_str(&rule_defs);
+    out.push_str("  }\n");
+    out.push_str("});\n");
+    out
+}
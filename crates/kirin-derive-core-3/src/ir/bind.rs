@@ -0,0 +1,213 @@
+//! synstructure-inspired field-binding codegen on top of [`ScanInto`].
+//!
+//! Where synstructure walks raw `syn::Fields` and re-derives which fields
+//! matter from attributes on every call site, [`each`] walks the
+//! already-[`scan`](super::ScanInto::scan)ned [`Input`] tree, so a closure
+//! over a [`BoundField`] gets the field's [`Layout::FieldExtra`] (e.g. "is
+//! this an SSA operand or result?") for free instead of re-walking
+//! `syn::Fields` by hand in every derive that needs it.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use super::{Field, Fields, HasFields, Input, Layout};
+
+/// One field of a match arm, bound to a fresh `__binding_N` identifier.
+pub struct BoundField<'a, 'src, L: Layout> {
+    field: Field<'a, 'src, L>,
+    binding: syn::Ident,
+}
+
+impl<'a, 'src, L: Layout> BoundField<'a, 'src, L> {
+    /// The fresh identifier this field's value is bound to inside the match arm.
+    #[must_use]
+    pub fn binding(&self) -> &syn::Ident {
+        &self.binding
+    }
+
+    /// The field's name, or `None` for a tuple field.
+    #[must_use]
+    pub fn ident(&self) -> Option<&syn::Ident> {
+        self.field.src.ident.as_ref()
+    }
+
+    /// The field's declared type.
+    #[must_use]
+    pub fn ty(&self) -> &syn::Type {
+        &self.field.src.ty
+    }
+
+    /// The field's scanned [`Layout::FieldExtra`] (e.g. whether it's marked
+    /// as an SSA operand/result via `#[wraps]`/attribute machinery).
+    #[must_use]
+    pub fn extra(&self) -> &L::FieldExtra {
+        self.field.extra()
+    }
+
+    #[must_use]
+    pub fn is_wrapper(&self) -> bool {
+        self.field.is_wrapper()
+    }
+}
+
+/// Keeps only the bindings whose [`Layout::FieldExtra`] satisfies `predicate`,
+/// e.g. `only_matching(&bindings, |extra| extra.is_ssa_value)` to narrow a
+/// closure's bindings down to SSA operand/result fields.
+#[must_use]
+pub fn only_matching<'a, 'b, 'src, L: Layout>(
+    bindings: &'a [BoundField<'b, 'src, L>],
+    predicate: impl Fn(&L::FieldExtra) -> bool,
+) -> Vec<&'a BoundField<'b, 'src, L>> {
+    bindings.iter().filter(|b| predicate(b.extra())).collect()
+}
+
+/// Binds every field in `fields` to a fresh `__binding_N` and returns the
+/// destructuring pattern for them alongside the bindings themselves.
+fn bind_fields<'a, 'src, L: Layout>(
+    fields: &'a Fields<'a, 'src, L>,
+) -> (TokenStream, Vec<BoundField<'a, 'src, L>>) {
+    let bindings: Vec<BoundField<'a, 'src, L>> = fields
+        .iter()
+        .map(|field| {
+            let binding = format_ident!("__binding_{}", field.index);
+            BoundField { field, binding }
+        })
+        .collect();
+
+    let pattern = match fields.src {
+        syn::Fields::Named(_) => {
+            let entries = bindings.iter().map(|b| {
+                let name = b.ident().expect("named fields always have an ident");
+                let binding = &b.binding;
+                quote! { #name: #binding }
+            });
+            quote! { { #(#entries),* } }
+        }
+        syn::Fields::Unnamed(_) => {
+            let entries = bindings.iter().map(|b| &b.binding);
+            quote! { ( #(#entries),* ) }
+        }
+        syn::Fields::Unit => TokenStream::new(),
+    };
+
+    (pattern, bindings)
+}
+
+/// Builds the full `match self { ... }` skeleton for `input`: one arm per
+/// struct/variant, each field bound to a fresh `__binding_N`. `bind_field` is
+/// called once per bound field to splice its per-field code; `fold_arm`
+/// combines one arm's per-field tokens into that arm's body (analogous to
+/// synstructure's `Structure::each`/`.fold`).
+pub fn each<'src, L, F, G>(input: &Input<'src, L>, bind_field: F, fold_arm: G) -> TokenStream
+where
+    L: Layout,
+    F: Fn(&BoundField<'_, 'src, L>) -> TokenStream,
+    G: Fn(Vec<TokenStream>) -> TokenStream,
+{
+    match input {
+        Input::Struct(s) => {
+            let fields = s.fields();
+            let (pattern, bindings) = bind_fields(&fields);
+            let body = fold_arm(bindings.iter().map(&bind_field).collect());
+            quote! {
+                match self {
+                    Self #pattern => { #body }
+                }
+            }
+        }
+        Input::Enum(e) => {
+            let arms: Vec<TokenStream> = e
+                .variants()
+                .map(|variant| {
+                    let variant_ident = &variant.src.ident;
+                    let fields = variant.fields();
+                    let (pattern, bindings) = bind_fields(&fields);
+                    let body = fold_arm(bindings.iter().map(&bind_field).collect());
+                    quote! { Self::#variant_ident #pattern => { #body } }
+                })
+                .collect();
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{EmptyLayoutImpl, ScanInto};
+
+    #[test]
+    fn test_each_struct_named_fields() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct MyStruct {
+                a: u32,
+                b: String,
+            }
+        };
+        let node = EmptyLayoutImpl.scan(&input).unwrap();
+
+        let tokens = each(
+            &node,
+            |bound| {
+                let binding = bound.binding();
+                quote! { ::std::mem::drop(#binding); }
+            },
+            |per_field| quote! { #(#per_field)* },
+        );
+
+        let rendered = tokens.to_string();
+        assert!(rendered.contains("__binding_0"));
+        assert!(rendered.contains("__binding_1"));
+        assert!(rendered.contains("a : __binding_0"));
+    }
+
+    #[test]
+    fn test_each_enum_tuple_variant() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum MyEnum {
+                A(u32),
+                B { x: String },
+            }
+        };
+        let node = EmptyLayoutImpl.scan(&input).unwrap();
+
+        let tokens = each(
+            &node,
+            |bound| {
+                let binding = bound.binding();
+                quote! { ::std::mem::drop(#binding); }
+            },
+            |per_field| quote! { #(#per_field)* },
+        );
+
+        let rendered = tokens.to_string();
+        assert!(rendered.contains("Self :: A (__binding_0)"));
+        assert!(rendered.contains("Self :: B { x : __binding_0 }"));
+    }
+
+    #[test]
+    fn test_only_matching_filters_bindings() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct MyStruct {
+                a: u32,
+                b: String,
+            }
+        };
+        let node = EmptyLayoutImpl.scan(&input).unwrap();
+
+        if let Input::Struct(s) = &node {
+            let fields = s.fields();
+            let (_, bindings) = bind_fields(&fields);
+            // `EmptyLayoutImpl`'s `FieldExtra` is `()`, so no binding matches
+            // a predicate that rejects everything.
+            assert!(only_matching(&bindings, |_: &()| false).is_empty());
+            assert_eq!(only_matching(&bindings, |_: &()| true).len(), 2);
+        } else {
+            panic!("expected a struct");
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod attrs;
+mod bind;
 mod definition;
 mod fields;
 mod generics;
@@ -8,6 +9,7 @@ mod to_tokens;
 mod wrapper;
 
 pub use attrs::Attrs;
+pub use bind::{BoundField, each, only_matching};
 pub use definition::*;
 pub use fields::HasFields;
 pub use generics::WithGenerics;
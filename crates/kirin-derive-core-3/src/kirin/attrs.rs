@@ -9,6 +9,17 @@ pub struct KirinFieldOptions {
     pub default: Option<syn::Expr>,
     #[darling(rename = "type")]
     pub ssa_ty: Option<syn::Expr>,
+
+    /// Call this `fn(&Field, &mut Formatter) -> fmt::Result` instead of the
+    /// default formatting when this field is printed in an IR dump, e.g.
+    /// `#[kirin(print_with = my_mod::print_radians)]` (derivative's
+    /// `debug_format_with`).
+    pub print_with: Option<syn::Path>,
+
+    /// Omit this field entirely from the default IR print, e.g.
+    /// `#[kirin(skip_print)]` (derivative's `Debug(ignore)`).
+    #[darling(default)]
+    pub skip_print: bool,
 }
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(kirin))]
@@ -9,7 +9,7 @@ use super::{
     initialization::Initialization,
     input::{InputSignature, LetNameEqInput},
     name::{BuildFnName, StatementIdName},
-    result::LetNameEqResultValue,
+    result::{result_shape_params, LetNameEqResultValue},
 };
 
 target! {
@@ -88,13 +88,34 @@ target! {
 
 impl<'src> Compile<'src, Fields<'_, 'src, Builder>, Inputs> for Builder {
     fn compile(&self, node: &Fields<'_, 'src, Builder>) -> Inputs {
-        let inputs: Vec<InputSignature> = node
+        let mut inputs: Vec<TokenStream> = node
             .iter()
             .filter(|f| {
                 f.attrs().default.is_none() && !matches!(&f.extra().kind, FieldKind::ResultValue)
             })
-            .map(|f| self.compile(&f))
+            .map(|f| {
+                let sig: InputSignature = self.compile(&f);
+                quote! { #sig }
+            })
             .collect();
+
+        // A `Vec<ResultValue>`/`Option<ResultValue>` field (however deeply
+        // nested, e.g. `Option<Vec<ResultValue>>`) is allocated by the
+        // builder rather than taken directly, so instead of the field
+        // itself it takes how many (or whether) to allocate at each
+        // `Vec`/`Option` level of its shape.
+        inputs.extend(node.iter().filter_map(|f| {
+            if !matches!(f.extra().kind, FieldKind::ResultValue) {
+                return None;
+            }
+            let params = result_shape_params(&f.extra().collection, &f.source_ident());
+            if params.is_empty() {
+                None
+            } else {
+                Some(quote! { #(#params),* })
+            }
+        }));
+
         quote! { #(#inputs),* }.into()
     }
 }
@@ -40,7 +40,7 @@ impl Emit<'_> for Builder {
 #[derive(Debug, Clone)]
 pub struct FieldExtra {
     pub(super) kind: FieldKind,
-    pub(super) collection: FieldCollectionKind,
+    pub(super) collection: Shape,
 }
 
 #[derive(Debug, Clone)]
@@ -53,11 +53,44 @@ pub(super) enum FieldKind {
     Other,
 }
 
+/// How a field's declared type wraps its [`FieldKind`] leaf, e.g. the
+/// `Vec<Option<_>>` in `Vec<Option<ResultValue>>`.
+///
+/// Only `Vec`/`Option` nesting is recognized (recursively, to any depth);
+/// other containers (tuples, maps, `Box<[_]>`, ...) don't have an obvious
+/// builder-parameter shape and still classify as `FieldKind::Other`, same
+/// as before this type became recursive.
 #[derive(Debug, Clone)]
-pub(super) enum FieldCollectionKind {
-    Vec,
-    Option,
-    None,
+pub(super) enum Shape {
+    /// The field holds the value directly, with no wrapping container.
+    Leaf,
+    Vec(Box<Shape>),
+    Option(Box<Shape>),
+}
+
+/// Recursively matches `ty` against `name`, descending through any number
+/// of `Vec`/`Option` wrappers to build the [`Shape`] of the match, e.g.
+/// `Option<Vec<ResultValue>>` classifies as `Shape::Option(Shape::Vec(Shape::Leaf))`.
+fn classify_shape(ty: &syn::Type, name: &str) -> Option<Shape> {
+    if is_type(ty, name) {
+        return Some(Shape::Leaf);
+    }
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let seg = type_path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    let inner_ty = args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    })?;
+    match seg.ident.to_string().as_str() {
+        "Vec" => classify_shape(inner_ty, name).map(|s| Shape::Vec(Box::new(s))),
+        "Option" => classify_shape(inner_ty, name).map(|s| Shape::Option(Box::new(s))),
+        _ => None,
+    }
 }
 
 macro_rules! impl_from_context {
@@ -66,26 +99,16 @@ macro_rules! impl_from_context {
             fn scan_extra(&self, node: &'src syn::Field) -> syn::Result<FieldExtra> {
                 let ty = &node.ty;
                 let extra = $(
-                    if is_type(ty, stringify!($name)) {
-                        FieldExtra {
-                            kind: FieldKind::$name,
-                            collection: FieldCollectionKind::None,
-                        }
-                    } else if is_type_in(ty, stringify!($name), |seg| seg.ident == "Vec") {
-                        FieldExtra {
-                            kind: FieldKind::$name,
-                            collection: FieldCollectionKind::Vec,
-                        }
-                    } else if is_type_in(ty, stringify!($name), |seg| seg.ident == "Option") {
+                    if let Some(shape) = classify_shape(ty, stringify!($name)) {
                         FieldExtra {
                             kind: FieldKind::$name,
-                            collection: FieldCollectionKind::Option,
+                            collection: shape,
                         }
                     } else
                 )* {
                     FieldExtra {
                         kind: FieldKind::Other,
-                        collection: FieldCollectionKind::None,
+                        collection: Shape::Leaf,
                     }
                 };
                 Ok(extra)
@@ -1,62 +1,116 @@
-use super::context::{Builder, FieldKind};
-use crate::{
-    kirin::builder::{context::FieldCollectionKind, name::StatementIdName},
-    prelude::*,
-};
-use quote::quote;
+use super::context::{Builder, FieldKind, Shape};
+use crate::{kirin::builder::name::StatementIdName, prelude::*};
+use quote::{format_ident, quote};
 
 target! {
     pub struct LetNameEqResultValue
 }
 
+/// Builder input name for the count of a `Vec<..>` level of a field's shape.
+pub(super) fn count_param_ident(name: &syn::Ident) -> syn::Ident {
+    format_ident!("{}_count", name)
+}
+
+/// Builder input name for whether an `Option<..>` level of a field's shape
+/// is allocated.
+pub(super) fn enabled_param_ident(name: &syn::Ident) -> syn::Ident {
+    format_ident!("{}_enabled", name)
+}
+
+/// Collects the builder parameters needed to allocate a field's result
+/// value(s), one `_count: usize`/`_enabled: bool` per `Vec`/`Option` level
+/// of its (possibly nested) [`Shape`], e.g. `Option<Vec<ResultValue>>`
+/// needs both `..._enabled: bool` and `..._count: usize`.
+pub(super) fn result_shape_params(shape: &Shape, name: &syn::Ident) -> Vec<TokenStream> {
+    match shape {
+        Shape::Leaf => vec![],
+        Shape::Vec(inner) => {
+            let count = count_param_ident(name);
+            let mut params = vec![quote! { #count: usize }];
+            params.extend(result_shape_params(inner, name));
+            params
+        }
+        Shape::Option(inner) => {
+            let enabled = enabled_param_ident(name);
+            let mut params = vec![quote! { #enabled: bool }];
+            params.extend(result_shape_params(inner, name));
+            params
+        }
+    }
+}
+
+/// Builds the expression that allocates this field's result value(s)
+/// according to its (possibly nested) `Vec`/`Option` [`Shape`], advancing
+/// the shared `__kirin_result_index` counter once per leaf allocated.
+fn alloc_result_expr(
+    shape: &Shape,
+    name: &syn::Ident,
+    statement_id: &StatementIdName,
+    ssa_ty: &syn::Expr,
+) -> TokenStream {
+    match shape {
+        Shape::Leaf => quote! {{
+            let __kirin_result = context
+                .ssa()
+                .kind(SSAKind::Result(#statement_id, __kirin_result_index))
+                .ty(Lang::TypeLattice::from(#ssa_ty))
+                .new()
+                .into();
+            __kirin_result_index += 1;
+            __kirin_result
+        }},
+        Shape::Vec(inner) => {
+            let count = count_param_ident(name);
+            let inner_expr = alloc_result_expr(inner, name, statement_id, ssa_ty);
+            quote! { (0..#count).map(|_| #inner_expr).collect() }
+        }
+        Shape::Option(inner) => {
+            let enabled = enabled_param_ident(name);
+            let inner_expr = alloc_result_expr(inner, name, statement_id, ssa_ty);
+            quote! { if #enabled { Some(#inner_expr) } else { None } }
+        }
+    }
+}
+
 impl<'src> Compile<'src, Fields<'_, 'src, Builder>, LetNameEqResultValue> for Builder {
     fn compile(&self, node: &Fields<'_, 'src, Builder>) -> LetNameEqResultValue {
+        let statement_id: StatementIdName = self.compile(node);
+        let mut has_result = false;
+
         let results: Vec<TokenStream> = node
             .iter()
             .filter(|f| matches!(f.extra().kind, FieldKind::ResultValue))
-            .enumerate()
-            .map(|(index, f)| {
+            .map(|f| {
+                has_result = true;
                 let name = f.source_ident();
                 let ty = &f.source().ty;
-                let statement_id: StatementIdName = self.compile(node);
 
                 let Some(ssa_ty) = &f.attrs().ssa_ty else {
                     return syn::Error::new_spanned(
                         &f.source_ident(),
                         "expect #[kirin(type = ...)] attribute for ResultValue field",
                     )
-                    .to_compile_error()
-                    .into();
+                    .to_compile_error();
                 };
-                if matches!(f.extra().collection, FieldCollectionKind::Vec) {
-                    return syn::Error::new_spanned(
-                        &f.source_ident(),
-                        "ResultValue field cannot be a Vec, consider implementing the builder manually",
-                    )
-                    .to_compile_error()
-                    .into();
-                } else if matches!(f.extra().collection, FieldCollectionKind::Option) {
-                    return syn::Error::new_spanned(
-                        &f.source_ident(),
-                        "ResultValue field cannot be an Option, consider implementing the builder manually",
-                    )
-                    .to_compile_error()
-                    .into();
-                }
 
+                // Index numbering must stay consistent across scalar and
+                // collection result fields in the same statement, so the
+                // generated allocation expression advances the same
+                // runtime counter once per leaf it allocates, however
+                // deeply its `Vec`/`Option` shape is nested.
+                let alloc = alloc_result_expr(&f.extra().collection, &name, &statement_id, ssa_ty);
                 quote! {
-                    let #name: #ty = context
-                        .ssa()
-                        .kind(SSAKind::Result(#statement_id, #index))
-                        .ty(Lang::TypeLattice::from(#ssa_ty))
-                        .new()
-                        .into();
+                    let #name: #ty = #alloc;
                 }
-                .into()
             })
             .collect();
 
+        if !has_result {
+            return quote! {}.into();
+        }
+
         quote! {
+            let mut __kirin_result_index: usize = 0;
             #(#results)*
         }
         .into()
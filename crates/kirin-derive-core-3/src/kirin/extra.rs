@@ -1,9 +1,18 @@
+use darling::FromField;
+
+use crate::kirin::attrs::KirinFieldOptions;
 use crate::prelude::*;
 
 #[derive(Debug, Clone)]
 pub struct FieldMeta {
     pub(super) kind: FieldKind,
     pub(super) collection: FieldCollectionKind,
+    /// `#[kirin(print_with = path)]` override for this field's IR-dump
+    /// formatting, if any. See [`KirinFieldOptions::print_with`].
+    pub(super) print_with: Option<syn::Path>,
+    /// Whether `#[kirin(skip_print)]` was set on this field. See
+    /// [`KirinFieldOptions::skip_print`].
+    pub(super) skip_print: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -27,31 +36,28 @@ macro_rules! impl_from_context {
     ($($name:ident),*) => {
         impl<'src, T: Layout> ScanExtra<'src, syn::Field, FieldMeta> for T {
             fn scan_extra(&self, node: &'src syn::Field) -> syn::Result<FieldMeta> {
+                let options = KirinFieldOptions::from_field(node)
+                    .map_err(|e| syn::Error::new_spanned(node, e))?;
+
                 let ty = &node.ty;
-                let extra = $(
+                let (kind, collection) = $(
                     if is_type(ty, stringify!($name)) {
-                        FieldMeta {
-                            kind: FieldKind::$name,
-                            collection: FieldCollectionKind::None,
-                        }
+                        (FieldKind::$name, FieldCollectionKind::None)
                     } else if is_type_in(ty, stringify!($name), |seg| seg.ident == "Vec") {
-                        FieldMeta {
-                            kind: FieldKind::$name,
-                            collection: FieldCollectionKind::Vec,
-                        }
+                        (FieldKind::$name, FieldCollectionKind::Vec)
                     } else if is_type_in(ty, stringify!($name), |seg| seg.ident == "Option") {
-                        FieldMeta {
-                            kind: FieldKind::$name,
-                            collection: FieldCollectionKind::Option,
-                        }
+                        (FieldKind::$name, FieldCollectionKind::Option)
                     } else
                 )* {
-                    FieldMeta {
-                        kind: FieldKind::Other,
-                        collection: FieldCollectionKind::None,
-                    }
+                    (FieldKind::Other, FieldCollectionKind::None)
                 };
-                Ok(extra)
+
+                Ok(FieldMeta {
+                    kind,
+                    collection,
+                    print_with: options.print_with,
+                    skip_print: options.skip_print,
+                })
             }
         }
     }
@@ -98,3 +98,70 @@ fn default_format<'src>(node: &Struct<'src, Format>) -> Vec<kirin_lexer::Token<'
     // }
     tokens
 }
+
+/// Per-field print codegen consulted when generating a statement's IR-dump
+/// formatting: `#[kirin(print_with = path)]` overrides the default `Debug`
+/// rendering with a call to `path(value, f)`, `#[kirin(skip_print)]` omits
+/// the field entirely (returns `None`), and otherwise the field falls
+/// through to `Debug::fmt`. `value` is the expression referring to the
+/// field (e.g. `&self.operand` or `&self.0`).
+///
+/// A `#[wraps]` field should bypass this and forward straight to the inner
+/// value's own printer instead, so the wrapper statement as a whole reads as
+/// its wrapped value — see `Field::is_wrapper`.
+fn field_print_tokens(meta: &FieldMeta, value: &syn::Expr) -> Option<proc_macro2::TokenStream> {
+    if meta.skip_print {
+        return None;
+    }
+
+    Some(match &meta.print_with {
+        Some(print_with) => quote! { #print_with(#value, f)?; },
+        None => quote! { ::std::fmt::Debug::fmt(#value, f)?; },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_print_tokens_skip_print() {
+        let meta = FieldMeta {
+            kind: FieldKind::Other,
+            collection: crate::kirin::extra::FieldCollectionKind::None,
+            print_with: None,
+            skip_print: true,
+        };
+        let value: syn::Expr = syn::parse_quote!(&self.operand);
+
+        assert!(field_print_tokens(&meta, &value).is_none());
+    }
+
+    #[test]
+    fn test_field_print_tokens_print_with_override() {
+        let meta = FieldMeta {
+            kind: FieldKind::Other,
+            collection: crate::kirin::extra::FieldCollectionKind::None,
+            print_with: Some(syn::parse_quote!(my_mod::print_radians)),
+            skip_print: false,
+        };
+        let value: syn::Expr = syn::parse_quote!(&self.angle);
+
+        let rendered = field_print_tokens(&meta, &value).unwrap().to_string();
+        assert!(rendered.contains("my_mod :: print_radians"));
+    }
+
+    #[test]
+    fn test_field_print_tokens_default_debug_fmt() {
+        let meta = FieldMeta {
+            kind: FieldKind::Other,
+            collection: crate::kirin::extra::FieldCollectionKind::None,
+            print_with: None,
+            skip_print: false,
+        };
+        let value: syn::Expr = syn::parse_quote!(&self.operand);
+
+        let rendered = field_print_tokens(&meta, &value).unwrap().to_string();
+        assert!(rendered.contains("Debug :: fmt"));
+    }
+}
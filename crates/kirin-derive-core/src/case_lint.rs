@@ -0,0 +1,108 @@
+//! Naming-convention lint for dialect definitions, modeled on rust-analyzer's
+//! `decl_check`/`case_conv`: round-trip an identifier through [`to_camel_case`]
+//! or [`to_snake_case`] and flag it when the result doesn't match.
+//!
+//! Enum variants (used as `statement.name` throughout the derive/interpreter
+//! plumbing) are expected to be UpperCamelCase, matching ordinary Rust type
+//! naming; statement/op keywords derived from them (e.g. builder function
+//! names) are expected to be snake_case.
+
+use crate::misc::{to_camel_case, to_snake_case};
+
+/// A single naming-convention violation, with a suggested fix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseViolation {
+    pub found: String,
+    pub suggested: String,
+}
+
+impl CaseViolation {
+    pub fn message(&self, what: &str) -> String {
+        format!(
+            "{what} `{}` is not named consistently with the rest of the dialect; rename it to `{}`",
+            self.found, self.suggested
+        )
+    }
+}
+
+/// Check that `name` is already UpperCamelCase, as expected of an enum variant
+/// (or single-statement struct) identifier.
+pub fn check_upper_camel_case(name: &str) -> Option<CaseViolation> {
+    if looks_like_acronym(name) {
+        return None;
+    }
+    let suggested = to_camel_case(name);
+    (suggested != name).then(|| CaseViolation {
+        found: name.to_string(),
+        suggested,
+    })
+}
+
+/// Check that `name` is already snake_case, as expected of a statement's
+/// textual op keyword (e.g. the name plugged into a generated `op_*` builder).
+pub fn check_snake_case(name: &str) -> Option<CaseViolation> {
+    if looks_like_acronym(name) {
+        return None;
+    }
+    let suggested = to_snake_case(name);
+    (suggested != name).then(|| CaseViolation {
+        found: name.to_string(),
+        suggested,
+    })
+}
+
+/// Heuristically recognize identifiers that shouldn't be flagged even though
+/// they don't round-trip cleanly through case conversion: acronyms made of a
+/// run of two or more consecutive uppercase letters (`HTTPServer`, `IOError`).
+/// Plain digits (`op_add2`, `I64`) never break the round trip in the first
+/// place, so they need no special casing here.
+fn looks_like_acronym(name: &str) -> bool {
+    let mut consecutive_upper = 0usize;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            consecutive_upper += 1;
+            if consecutive_upper >= 2 {
+                return true;
+            }
+        } else {
+            consecutive_upper = 0;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upper_camel_case_accepts_compliant_names() {
+        assert_eq!(check_upper_camel_case("Add"), None);
+        assert_eq!(check_upper_camel_case("ControlFlow"), None);
+        assert_eq!(check_upper_camel_case("I64"), None);
+    }
+
+    #[test]
+    fn test_upper_camel_case_flags_snake_case_variant() {
+        let violation = check_upper_camel_case("op_add").unwrap();
+        assert_eq!(violation.suggested, "OpAdd");
+    }
+
+    #[test]
+    fn test_snake_case_accepts_compliant_names() {
+        assert_eq!(check_snake_case("op_add"), None);
+        assert_eq!(check_snake_case("op_add2"), None);
+    }
+
+    #[test]
+    fn test_snake_case_flags_camel_case_name() {
+        let violation = check_snake_case("OpAdd").unwrap();
+        assert_eq!(violation.suggested, "op_add");
+    }
+
+    #[test]
+    fn test_acronym_guard_suppresses_false_positive() {
+        assert_eq!(check_snake_case("HTTPServer"), None);
+        assert_eq!(check_upper_camel_case("HTTPServer"), None);
+    }
+}
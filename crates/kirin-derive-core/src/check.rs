@@ -279,7 +279,11 @@ impl FromVariantGenerate for RegularVariant<'_, CheckInfo> {
         _trait_path: &syn::Path,
     ) -> proc_macro2::TokenStream {
         let variant_name = &self.variant_name;
-        let value = global_value || self.fields.unwrap_or(false);
+        // The variant's own value, if set explicitly, overrides the enum-level
+        // value even to flip a global `true` down to `false`; an unset variant
+        // value inherits the enum-level one. A plain `||` here would make an
+        // explicit per-variant `false` unable to ever override a global `true`.
+        let value = self.fields.unwrap_or(global_value);
         match &self.variant.fields {
             syn::Fields::Named(_) => {
                 quote! {
@@ -428,6 +432,21 @@ mod tests {
         insta::assert_snapshot!(generate(input));
     }
 
+    #[test]
+    fn test_variant_overrides_global() {
+        // A per-variant `pure = false` must override the enum-level `pure`,
+        // not just OR against it.
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[kirin(pure)]
+            enum MixedEnum {
+                PureOp { field: u32 },
+                #[kirin(pure = false)]
+                NotPureOp { field: u32 },
+            }
+        };
+        insta::assert_snapshot!(rustfmt(derive_check!(&input, is_pure, IsPure)));
+    }
+
     fn generate(input: syn::DeriveInput) -> String {
         rustfmt(derive_check!(&input, is_constant, IsConstant))
     }
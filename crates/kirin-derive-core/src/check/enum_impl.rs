@@ -10,21 +10,28 @@ impl<'input> EnumChecker<'input> {
         checker: &'input CheckerInfo,
         input: &'input syn::DeriveInput,
         data: &'input syn::DataEnum,
-    ) -> Self {
+    ) -> darling::Result<Self> {
+        // Collect every variant's diagnostics before bailing, so a typo in
+        // one variant doesn't hide mistakes in the rest.
+        let mut errors = darling::Error::accumulator();
         if has_attr(input.attrs, "kirin", "wraps") {
             let variants = data
                 .variants
                 .iter()
-                .map(|variant| EnumVariantWrapper::scan(checker, input, variant))
+                .filter_map(|variant| {
+                    errors.handle(EnumVariantWrapper::scan(checker, input, variant))
+                })
                 .collect();
-            Self::GlobalWrapper(variants)
+            errors.finish_with(Self::GlobalWrapper(variants))
         } else {
             let variants = data
                 .variants
                 .iter()
-                .map(|variant| EnumVariantChecker::scan(checker, input, variant))
+                .filter_map(|variant| {
+                    errors.handle(EnumVariantChecker::scan(checker, input, variant))
+                })
                 .collect();
-            Self::Regular(variants)
+            errors.finish_with(Self::Regular(variants))
         }
     }
 }
@@ -39,15 +46,19 @@ impl<'input> EnumVariantWrapper<'input> {
         checker: &'input CheckerInfo,
         input: &'input syn::DeriveInput,
         variant: &'input syn::Variant,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         match &variant.fields {
-            syn::Fields::Named(fields) => EnumVariantWrapper::Named(NamedVariantWrapper::scan(
-                checker, input, variant, fields,
-            )),
-            syn::Fields::Unnamed(fields) => EnumVariantWrapper::Unnamed(
+            syn::Fields::Named(fields) => {
+                NamedVariantWrapper::scan(checker, input, variant, fields)
+                    .map(EnumVariantWrapper::Named)
+            }
+            syn::Fields::Unnamed(fields) => Ok(EnumVariantWrapper::Unnamed(
                 UnnamedVariantWrapper::scan(checker, input, variant, fields),
-            ),
-            _ => panic!("wrapper variants must have named or unnamed fields"),
+            )),
+            syn::Fields::Unit => Err(darling::Error::custom(
+                "wrapper variants must have named or unnamed fields",
+            )
+            .with_span(&variant.ident)),
         }
     }
 }
@@ -63,15 +74,15 @@ impl<'input> EnumVariantChecker<'input> {
         checker: &'input CheckerInfo,
         input: &'input syn::DeriveInput,
         variant: &'input syn::Variant,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         match &variant.fields {
-            syn::Fields::Named(fields) => EnumVariantChecker::Named(NamedVariantChecker::scan(
-                checker, input, variant, fields,
-            )),
-            syn::Fields::Unnamed(fields) => EnumVariantChecker::Unnamed(
-                UnnamedVariantChecker::scan(checker, input, variant, fields),
-            ),
-            syn::Fields::Unit => EnumVariantChecker::Unit,
+            syn::Fields::Named(fields) => NamedVariantChecker::scan(checker, input, variant, fields)
+                .map(EnumVariantChecker::Named),
+            syn::Fields::Unnamed(fields) => {
+                UnnamedVariantChecker::scan(checker, input, variant, fields)
+                    .map(EnumVariantChecker::Unnamed)
+            }
+            syn::Fields::Unit => Ok(EnumVariantChecker::Unit),
         }
     }
 }
@@ -87,15 +98,16 @@ impl<'input> NamedVariantChecker<'input> {
         input: &'input syn::DeriveInput,
         variant: &'input syn::Variant,
         fields: &'input syn::FieldsNamed,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         if has_attr(&variant.attrs, "kirin", "wraps") {
-            NamedVariantChecker::Wrapper(NamedVariantWrapper::scan(checker, input, variant, fields))
+            NamedVariantWrapper::scan(checker, input, variant, fields)
+                .map(NamedVariantChecker::Wrapper)
         } else {
-            NamedVariantChecker::Regular(RegularVariantChecker {
+            Ok(NamedVariantChecker::Regular(RegularVariantChecker {
                 checker,
                 variant_name: &variant.ident,
                 value: is_attr_option_true(&variant.attrs, &checker.option),
-            })
+            }))
         }
     }
 }
@@ -124,32 +136,32 @@ impl<'input> NamedVariantWrapper<'input> {
         input: &'input syn::DeriveInput,
         variant: &'input syn::Variant,
         fields_named: &'input syn::FieldsNamed,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         if fields_named.named.len() == 1 {
             let f = fields_named.named.first().unwrap();
-            Self {
+            Ok(Self {
                 checker,
                 variant_name: &variant.ident,
                 wraps: f.ident.clone().unwrap(),
                 wraps_type: f.ty.clone(),
-            }
+            })
         } else if let Some(f) = fields_named
             .named
             .iter()
             .find(|f| has_attr(&f.attrs, "kirin", "wraps"))
         {
-            Self {
+            Ok(Self {
                 checker,
                 variant_name: &variant.ident,
                 wraps: f.ident.clone().unwrap(),
                 wraps_type: f.ty.clone(),
-            }
+            })
         } else {
-            panic!(
-                "variant #[kirin(wraps)] attribute can only be used \
-on wrapper variants with a single field,\
-consider adding #[kirin(wraps)] to the specific field instead"
-            );
+            Err(darling::Error::custom(
+                "variant #[kirin(wraps)] attribute can only be used on wrapper variants with \
+                 a single field; consider adding #[kirin(wraps)] to the specific field instead",
+            )
+            .with_span(&variant.ident))
         }
     }
 }
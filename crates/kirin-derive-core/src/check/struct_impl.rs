@@ -12,15 +12,18 @@ impl<'input> StructChecker<'input> {
         checker: &'input CheckerInfo,
         input: &'input syn::DeriveInput,
         data: &'input syn::DataStruct,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         match &data.fields {
             syn::Fields::Named(fields) => {
-                StructChecker::Named(NamedStructChecker::scan(checker, input, fields))
+                NamedStructChecker::scan(checker, input, fields).map(StructChecker::Named)
             }
             syn::Fields::Unnamed(fields) => {
-                StructChecker::Unnamed(UnnamedStructChecker::scan(checker, input, fields))
+                UnnamedStructChecker::scan(checker, input, fields).map(StructChecker::Unnamed)
             }
-            _ => panic!("only named and unnamed fields are supported"),
+            syn::Fields::Unit => Err(darling::Error::custom(
+                "only named and unnamed fields are supported; a unit struct has no field to check",
+            )
+            .with_span(&input.ident)),
         }
     }
 
@@ -42,31 +45,32 @@ impl<'input> NamedStructChecker<'input> {
         checker: &'input CheckerInfo,
         input: &'input syn::DeriveInput,
         fields: &'input syn::FieldsNamed,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         if has_attr(&input.attrs, "kirin", "wraps") {
             if fields.named.len() != 1 {
-                panic!(
-                    "global #[kirin(wraps)] attribute can only be used \
-on wrapper structs with a single field,\
-consider adding #[kirin(wraps)] to the specific field instead"
-                );
+                return Err(darling::Error::custom(
+                    "global #[kirin(wraps)] attribute can only be used on wrapper structs \
+                     with a single field; consider adding #[kirin(wraps)] to the specific \
+                     field instead",
+                )
+                .with_span(&input.ident));
             }
             let wraps = fields.named.first().unwrap().ident.clone().unwrap();
             let wraps_type = fields.named.first().unwrap().ty.clone();
-            NamedStructChecker::Wrapper(NamedStructWrapperChecker {
+            Ok(NamedStructChecker::Wrapper(NamedStructWrapperChecker {
                 checker,
                 name: &input.ident,
                 generics: &input.generics,
                 wraps,
                 wraps_type,
-            })
+            }))
         } else {
-            NamedStructChecker::Regular(StructRegularChecker {
+            Ok(NamedStructChecker::Regular(StructRegularChecker {
                 checker,
                 name: &input.ident,
                 generics: &input.generics,
                 value: is_attr_option_true(&input.attrs, &checker.option),
-            })
+            }))
         }
     }
 
@@ -88,31 +92,32 @@ impl<'input> UnnamedStructChecker<'input> {
         checker: &'input CheckerInfo,
         input: &'input syn::DeriveInput,
         fields: &'input syn::FieldsUnnamed,
-    ) -> Self {
+    ) -> darling::Result<Self> {
         if has_attr(&input.attrs, "kirin", "wraps") {
             if fields.unnamed.len() != 1 {
-                panic!(
-                    "global #[kirin(wraps)] attribute can only be used \
-on wrapper structs with a single field,\
-consider adding #[kirin(wraps)] to the specific field instead"
-                );
+                return Err(darling::Error::custom(
+                    "global #[kirin(wraps)] attribute can only be used on wrapper structs \
+                     with a single field; consider adding #[kirin(wraps)] to the specific \
+                     field instead",
+                )
+                .with_span(&input.ident));
             }
             let wraps = 0;
             let wraps_type = fields.unnamed.first().unwrap().ty.clone();
-            UnnamedStructChecker::Wrapper(UnnamedStructWrapperChecker {
+            Ok(UnnamedStructChecker::Wrapper(UnnamedStructWrapperChecker {
                 checker,
                 name: &input.ident,
                 generics: &input.generics,
                 wraps,
                 wraps_type,
-            })
+            }))
         } else {
-            UnnamedStructChecker::Regular(StructRegularChecker {
+            Ok(UnnamedStructChecker::Regular(StructRegularChecker {
                 checker,
                 name: &input.ident,
                 generics: &input.generics,
                 value: is_attr_option_true(&input.attrs, &checker.option),
-            })
+            }))
         }
     }
 
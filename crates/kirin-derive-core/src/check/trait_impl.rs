@@ -17,15 +17,19 @@ pub enum DataChecker<'input> {
 }
 
 impl<'input> DataChecker<'input> {
-    pub fn scan(checker: &'input CheckerInfo, input: &'input syn::DeriveInput) -> Self {
+    pub fn scan(
+        checker: &'input CheckerInfo,
+        input: &'input syn::DeriveInput,
+    ) -> darling::Result<Self> {
         match &input.data {
             syn::Data::Struct(data) => {
-                DataChecker::Struct(StructChecker::scan(checker, input, data))
+                StructChecker::scan(checker, input, data).map(DataChecker::Struct)
             }
-            syn::Data::Enum(data) => {
-                DataChecker::Enum(EnumChecker::scan(checker, input, data))
-            }
-            _ => panic!("only structs and enums are supported"),
+            syn::Data::Enum(data) => EnumChecker::scan(checker, input, data).map(DataChecker::Enum),
+            syn::Data::Union(_) => Err(darling::Error::custom(
+                "union types are not supported; use a struct or enum",
+            )
+            .with_span(&input.ident)),
         }
     }
 }
@@ -1,3 +1,4 @@
+use darling::{Error, FromMeta};
 use quote::ToTokens;
 
 #[derive(Clone)]
@@ -13,6 +14,33 @@ impl Default for Builder {
     }
 }
 
+/// `#[kirin(fn)]` enables a builder with the default name; `#[kirin(fn = name)]`
+/// (identifier or string literal) enables it under an explicit name.
+impl FromMeta for Builder {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Builder::Enabled)
+    }
+
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Path(syn::ExprPath { path, .. }) => match path.get_ident() {
+                Some(ident) => Ok(Builder::EnabledWithName(ident.clone())),
+                None => Err(Error::custom("expected identifier for builder name")),
+            },
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            }) => Ok(Builder::EnabledWithName(syn::Ident::new(
+                &s.value(),
+                s.span(),
+            ))),
+            _ => Err(Error::custom(
+                "expected identifier or string for builder name",
+            )),
+        }
+    }
+}
+
 impl Builder {
     pub fn is_enabled(&self) -> bool {
         match self {
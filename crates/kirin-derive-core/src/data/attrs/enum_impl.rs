@@ -1,72 +1,41 @@
+use darling::{FromAttributes, FromDeriveInput};
+use quote::ToTokens;
+
 use crate::data::PropertyAttribute;
-use crate::data::attrs::utils::{error_unknown_attribute, parse_kirin_attributes};
 
 use super::builder::Builder;
 use super::field_impl::FieldAttribute;
 
-#[derive(Clone, Default)]
+/// Parsed `#[kirin(...)]` attributes on a statement enum (a dialect).
+///
+/// Takes `wraps`, `crate`, `type_lattice`, `constant`, `pure`, `terminator` and
+/// `fn` as [`super::StructAttribute`] does, plus `name = <expr>` to name the
+/// dialect. The wrapper delegations (`deref`, `deref_mut`, `as_ref`, `as_mut`,
+/// `from`, `into`) are struct-only. Each variant's own attributes are parsed
+/// into [`VariantAttribute`].
+#[derive(Clone, Default, FromDeriveInput)]
+#[darling(attributes(kirin), supports(enum_any))]
 pub struct EnumAttribute {
     /// name for the enum statement, e.g a dialect name
     pub name: Option<syn::Expr>,
-    /// whether the instruction wraps another instruction
+    #[darling(default)]
     pub wraps: bool,
-    /// path to the kirin crate
+    #[darling(rename = "crate")]
     pub crate_path: Option<syn::Path>,
-    /// type lattice for the dialect's SSAValue/ResultValue to use
-    /// this is only allowed on the type level attribute
-    /// e.g #[kirin(type_lattice = ...)]
-    /// this is different from the field-level `type` attribute
-    /// e.g #[kirin(type = ...)]
-    /// which specifies the type of the SSAValue/ResultValue field
     pub type_lattice: Option<syn::Type>,
-    /// whether the instruction is constant
+    #[darling(rename = "constant")]
     pub is_constant: Option<bool>,
-    /// whether the instruction is pure
+    #[darling(rename = "pure")]
     pub is_pure: Option<bool>,
-    /// whether the instruction is a terminator
+    #[darling(rename = "terminator")]
     pub is_terminator: Option<bool>,
-    /// options for the builder method to generate
+    #[darling(rename = "fn", default)]
     pub builder: Builder,
 }
 
 impl EnumAttribute {
-    pub fn new<'a>(input: &'a syn::DeriveInput) -> syn::Result<Self> {
-        let mut enum_attr = Self::default();
-        parse_kirin_attributes(&input.attrs, |meta| {
-            if meta.path.is_ident("wraps") {
-                enum_attr.wraps = true;
-            } else if meta.path.is_ident("name") {
-                let expr: syn::Expr = meta.value()?.parse()?;
-                enum_attr.name = Some(expr);
-            } else if meta.path.is_ident("crate") {
-                let path: syn::Path = meta.value()?.parse()?;
-                enum_attr.crate_path = Some(path);
-            } else if meta.path.is_ident("type_lattice") {
-                let ty: syn::Type = meta.value()?.parse()?;
-                enum_attr.type_lattice = Some(ty);
-            } else if meta.path.is_ident("fn") {
-                match meta.value() {
-                    Ok(v) => {
-                        let ident: syn::Ident = v.parse()?;
-                        enum_attr.builder = Builder::EnabledWithName(ident);
-                    }
-                    Err(_) => {
-                        // just pass through, #[kirin(fn)] means enable default builder name
-                        enum_attr.builder = Builder::Enabled;
-                    }
-                }
-            } else if meta.path.is_ident("constant") {
-                enum_attr.is_constant = Some(true);
-            } else if meta.path.is_ident("pure") {
-                enum_attr.is_pure = Some(true);
-            } else if meta.path.is_ident("terminator") {
-                enum_attr.is_terminator = Some(true);
-            } else {
-                return Err(error_unknown_attribute(&meta));
-            }
-            Ok(())
-        })?;
-        Ok(enum_attr)
+    pub fn new(input: &syn::DeriveInput) -> syn::Result<Self> {
+        Ok(Self::from_derive_input(input)?)
     }
 }
 
@@ -84,6 +53,45 @@ impl PropertyAttribute for EnumAttribute {
     }
 }
 
+impl std::fmt::Debug for EnumAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnumAttribute")
+            .field("name", &self.name.as_ref().map(|n| n.to_token_stream()))
+            .field("wraps", &self.wraps)
+            .field(
+                "crate_path",
+                &self.crate_path.as_ref().map(|p| p.to_token_stream()),
+            )
+            .field(
+                "type_lattice",
+                &self.type_lattice.as_ref().map(|t| t.to_token_stream()),
+            )
+            .field("is_constant", &self.is_constant)
+            .field("is_pure", &self.is_pure)
+            .field("is_terminator", &self.is_terminator)
+            .field("builder", &self.builder)
+            .finish()
+    }
+}
+
+/// Raw darling view of a single variant's `#[kirin(...)]` attributes; merged
+/// into [`VariantAttribute`] below alongside that variant's field attributes.
+#[derive(Clone, Default, Debug, FromAttributes)]
+#[darling(attributes(kirin))]
+struct RawVariantAttribute {
+    name: Option<syn::Expr>,
+    #[darling(default)]
+    wraps: bool,
+    #[darling(rename = "constant")]
+    is_constant: Option<bool>,
+    #[darling(rename = "pure")]
+    is_pure: Option<bool>,
+    #[darling(rename = "terminator")]
+    is_terminator: Option<bool>,
+    #[darling(rename = "fn", default)]
+    builder: Builder,
+}
+
 #[derive(Clone, Default)]
 pub struct VariantAttribute {
     /// name for the variant statement
@@ -105,61 +113,47 @@ pub struct VariantAttribute {
 impl VariantAttribute {
     pub fn is_wrapper(&self) -> bool {
         self.wraps
-            || self.fields.as_ref().map_or(false, |fields| {
-                fields.iter().any(|f_attr_opt| {
-                    if let Some(f_attr) = f_attr_opt {
-                        f_attr.wraps
-                    } else {
-                        false
-                    }
-                })
+            || self.fields.as_ref().is_some_and(|fields| {
+                fields
+                    .iter()
+                    .any(|f| f.as_ref().is_some_and(|fa| fa.wraps))
             })
     }
 
     pub fn get_field_attribute(&self, index: usize) -> Option<&FieldAttribute> {
-        if let Some(fields) = &self.fields {
-            return fields.get(index).and_then(|f_attr_opt| f_attr_opt.as_ref());
-        }
-        None
+        self.fields
+            .as_ref()
+            .and_then(|fields| fields.get(index).and_then(|f| f.as_ref()))
     }
 
     pub fn new(variant: &syn::Variant) -> syn::Result<Self> {
-        let mut variant_attr = Self::default();
-        parse_kirin_attributes(&variant.attrs, |meta| {
-            if meta.path.is_ident("wraps") {
-                variant_attr.wraps = true;
-            } else if meta.path.is_ident("name") {
-                let expr: syn::Expr = meta.value()?.parse()?;
-                variant_attr.name = Some(expr);
-            } else if meta.path.is_ident("fn") {
-                match meta.value() {
-                    Ok(v) => {
-                        let ident: syn::Ident = v.parse()?;
-                        variant_attr.builder = Builder::EnabledWithName(ident);
-                    }
-                    Err(_) => {
-                        // just pass through, #[kirin(fn)] means enable default builder name
-                        variant_attr.builder = Builder::Enabled;
-                    }
-                }
-            } else if meta.path.is_ident("constant") {
-                variant_attr.is_constant = Some(true);
-            } else if meta.path.is_ident("pure") {
-                variant_attr.is_pure = Some(true);
-            } else if meta.path.is_ident("terminator") {
-                variant_attr.is_terminator = Some(true);
-            } else {
-                return Err(error_unknown_attribute(&meta));
-            }
-            Ok(())
-        })?;
-        let fields = variant
+        let raw = RawVariantAttribute::from_attributes(&variant.attrs)?;
+        let fields: Vec<Option<FieldAttribute>> = variant
             .fields
             .iter()
             .map(|field| FieldAttribute::from_field_attrs(&field.attrs))
             .collect::<syn::Result<Vec<_>>>()?;
-        variant_attr.fields = Some(fields);
-        Ok(variant_attr)
+
+        Ok(VariantAttribute {
+            name: raw.name,
+            wraps: raw.wraps,
+            is_constant: raw.is_constant,
+            is_pure: raw.is_pure,
+            is_terminator: raw.is_terminator,
+            builder: raw.builder,
+            fields: Some(fields),
+        })
+    }
+
+    /// Resolves a property (`is_constant`/`is_pure`/`is_terminator`) by
+    /// inheritance: the variant's own value wins if set explicitly, even to
+    /// override an enum-level `true` down to `false`; otherwise the
+    /// enum-level value is inherited, defaulting to `false` if neither sets it.
+    pub fn resolve(
+        variant_value: Option<bool>,
+        global_value: Option<bool>,
+    ) -> bool {
+        variant_value.or(global_value).unwrap_or(false)
     }
 }
 
@@ -1,7 +1,24 @@
-use crate::data::attrs::utils::{error_unknown_attribute, parse_kirin_attributes};
+use darling::FromAttributes;
 
 use super::builder::FieldBuilder;
 
+/// Declarative `#[kirin(...)]` schema for a single field's attributes.
+///
+/// This is only the raw darling view; [`FieldAttribute::from_field_attrs`]
+/// merges it into the nested `builder: Option<FieldBuilder>` shape that the
+/// rest of the crate (e.g. `builder::field::FieldInfo`) already matches on.
+#[derive(Clone, Default, Debug, FromAttributes)]
+#[darling(attributes(kirin))]
+struct RawFieldAttribute {
+    #[darling(default)]
+    wraps: bool,
+    #[darling(default)]
+    into: bool,
+    init: Option<syn::Expr>,
+    #[darling(rename = "type")]
+    ty: Option<syn::Expr>,
+}
+
 #[derive(Clone, Default)]
 pub struct FieldAttribute {
     /// whether the field wraps another instruction
@@ -11,29 +28,30 @@ pub struct FieldAttribute {
 }
 
 impl FieldAttribute {
-    pub fn from_field_attrs(attrs: &Vec<syn::Attribute>) -> Option<Self> {
+    pub fn from_field_attrs(attrs: &[syn::Attribute]) -> syn::Result<Option<Self>> {
         if !attrs.iter().any(|attr| attr.path().is_ident("kirin")) {
-            return None;
+            return Ok(None);
         }
 
-        let mut field_attr = FieldAttribute::default();
-        parse_kirin_attributes(attrs, |meta| {
-            if meta.path.is_ident("wraps") {
-                field_attr.wraps = true;
-            } else if meta.path.is_ident("into") {
-                field_attr.builder.get_or_insert_with(Default::default).into = true;
-            } else if meta.path.is_ident("init") {
-                let expr: syn::Expr = meta.value()?.parse()?;
-                field_attr.builder.get_or_insert_with(Default::default).init = Some(expr);
-            } else if meta.path.is_ident("type") {
-                let expr: syn::Expr = meta.value()?.parse()?;
-                field_attr.builder.get_or_insert_with(Default::default).ty = Some(expr);
-            } else {
-                return Err(error_unknown_attribute(&meta));
-            }
-            Ok(())
-        })
-        .unwrap();
-        Some(field_attr)
+        let raw = RawFieldAttribute::from_attributes(attrs)?;
+        let builder = (raw.into || raw.init.is_some() || raw.ty.is_some()).then(|| FieldBuilder {
+            into: raw.into,
+            default: raw.init,
+            ty: raw.ty,
+        });
+
+        Ok(Some(FieldAttribute {
+            wraps: raw.wraps,
+            builder,
+        }))
+    }
+}
+
+impl std::fmt::Debug for FieldAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldAttribute")
+            .field("wraps", &self.wraps)
+            .field("builder", &self.builder)
+            .finish()
     }
 }
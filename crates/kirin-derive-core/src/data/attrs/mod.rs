@@ -8,7 +8,6 @@ mod builder;
 mod enum_impl;
 mod field_impl;
 mod struct_impl;
-mod utils;
 
 pub use builder::{Builder, FieldBuilder};
 pub use enum_impl::{EnumAttribute, VariantAttribute};
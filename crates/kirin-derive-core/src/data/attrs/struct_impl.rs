@@ -1,77 +1,71 @@
+use darling::FromDeriveInput;
 use quote::ToTokens;
 
 use crate::data::PropertyAttribute;
-use crate::data::attrs::utils::parse_kirin_attributes;
 
 use super::builder::Builder;
 use super::field_impl::FieldAttribute;
-use super::utils::error_unknown_attribute;
 
-#[derive(Clone, Default)]
+/// Parsed `#[kirin(...)]` attributes on a statement struct.
+///
+/// # Attributes
+///
+/// - `wraps` — the struct wraps another instruction.
+/// - `crate = <Path>` — override the default path to the kirin crate.
+/// - `type_lattice = <Type>` — type lattice for the dialect's SSAValue/ResultValue fields.
+///   This is distinct from the field-level `type = ...` attribute, which specifies the
+///   type of an individual SSAValue/ResultValue field.
+/// - `constant` / `constant = <bool>` — whether the instruction is constant.
+/// - `pure` / `pure = <bool>` — whether the instruction is pure.
+/// - `terminator` / `terminator = <bool>` — whether the instruction is a terminator.
+/// - `fn` / `fn = <ident>` — generate a builder method (auto-named or with the given name).
+/// - `deref` / `deref_mut` — for a wrapper struct, delegate `Deref`/`DerefMut` to the
+///   wrapped field.
+/// - `as_ref` / `as_mut` — for a wrapper struct, delegate `AsRef`/`AsMut` to the wrapped
+///   field.
+/// - `from` / `into` — for a wrapper struct, generate `From<wraps_type>` / the reverse
+///   `From<Self> for wraps_type` (i.e. `Into`) conversion.
+#[derive(Clone, Default, FromDeriveInput)]
+#[darling(attributes(kirin), supports(struct_any))]
 pub struct StructAttribute {
-    /// whether the instruction wraps another instruction
+    #[darling(default)]
     pub wraps: bool,
-    /// path to the kirin crate
+    #[darling(rename = "crate")]
     pub crate_path: Option<syn::Path>,
-    /// type lattice for the dialect's SSAValue/ResultValue to use
-    /// this is only allowed on the type level attribute
-    /// e.g #[kirin(type_lattice = ...)]
-    /// this is different from the field-level `type` attribute
-    /// e.g #[kirin(type = ...)]
-    /// which specifies the type of the SSAValue/ResultValue field
     pub type_lattice: Option<syn::Type>,
-    /// whether the instruction is constant
+    #[darling(rename = "constant")]
     pub is_constant: Option<bool>,
-    /// whether the instruction is pure
+    #[darling(rename = "pure")]
     pub is_pure: Option<bool>,
-    /// whether the instruction is a terminator
+    #[darling(rename = "terminator")]
     pub is_terminator: Option<bool>,
-    /// options for the builder method to generate
+    #[darling(rename = "fn", default)]
     pub builder: Builder,
-    /// attributes for each field in the struct
+    #[darling(default)]
+    pub deref: bool,
+    #[darling(default)]
+    pub deref_mut: bool,
+    #[darling(default)]
+    pub as_ref: bool,
+    #[darling(default)]
+    pub as_mut: bool,
+    #[darling(default)]
+    pub from: bool,
+    #[darling(default)]
+    pub into: bool,
+    /// attributes for each field in the struct, filled in by [`StructAttribute::new`]
+    /// after the darling pass above: `FromDeriveInput` has no view of sibling
+    /// field attributes, only the top-level `#[kirin(...)]` on the struct itself.
+    #[darling(skip)]
     pub fields: Option<Vec<Option<FieldAttribute>>>,
 }
 
 impl StructAttribute {
-    pub fn new<'a>(input: &'a syn::DeriveInput) -> syn::Result<Self> {
-        let mut struct_attr = Self::default();
-        parse_kirin_attributes(&input.attrs, |meta| {
-            if meta.path.is_ident("wraps") {
-                struct_attr.wraps = true;
-            } else if meta.path.is_ident("crate") {
-                let path: syn::Path = meta.value()?.parse()?;
-                struct_attr.crate_path = Some(path);
-            } else if meta.path.is_ident("type_lattice") {
-                let ty: syn::Type = meta.value()?.parse()?;
-                struct_attr.type_lattice = Some(ty);
-            } else if meta.path.is_ident("fn") {
-                match meta.value() {
-                    Ok(v) => {
-                        let ident: syn::Ident = v.parse()?;
-                        struct_attr.builder = Builder::EnabledWithName(ident);
-                    }
-                    Err(_) => {
-                        // just pass through, #[kirin(fn)] means enable default builder name
-                        struct_attr.builder = Builder::Enabled;
-                    }
-                }
-            } else if meta.path.is_ident("constant") {
-                struct_attr.is_constant = Some(true);
-            } else if meta.path.is_ident("pure") {
-                struct_attr.is_pure = Some(true);
-            } else if meta.path.is_ident("terminator") {
-                struct_attr.is_terminator = Some(true);
-            } else {
-                return Err(error_unknown_attribute(&meta));
-            }
-            Ok(())
-        })?;
+    pub fn new(input: &syn::DeriveInput) -> syn::Result<Self> {
+        let mut struct_attr = Self::from_derive_input(input)?;
 
         let syn::Data::Struct(data) = &input.data else {
-            return Err(syn::Error::new_spanned(
-                input,
-                "StructAttribute can only be created from struct data",
-            ));
+            unreachable!("`supports(struct_any)` above guarantees struct data");
         };
 
         let fields: Vec<Option<FieldAttribute>> = data
@@ -80,20 +74,19 @@ impl StructAttribute {
             .map(|field| FieldAttribute::from_field_attrs(&field.attrs))
             .collect::<syn::Result<Vec<_>>>()?;
 
-        // if all fields are None, set to None
-        if fields.iter().all(|f| f.is_none()) {
-            return Ok(struct_attr);
+        // if all fields are None, leave it as None
+        if fields.iter().any(Option::is_some) {
+            struct_attr.fields = Some(fields);
         }
-        struct_attr.fields = Some(fields);
         Ok(struct_attr)
     }
 
     pub fn is_wrapper(&self) -> bool {
         self.wraps
-            || self.fields.as_ref().map_or(false, |fields| {
+            || self.fields.as_ref().is_some_and(|fields| {
                 fields
                     .iter()
-                    .any(|f| f.as_ref().map_or(false, |fa| fa.wraps))
+                    .any(|f| f.as_ref().is_some_and(|fa| fa.wraps))
             })
     }
 
@@ -134,6 +127,12 @@ impl std::fmt::Debug for StructAttribute {
             .field("is_pure", &self.is_pure)
             .field("is_terminator", &self.is_terminator)
             .field("builder", &self.builder)
+            .field("deref", &self.deref)
+            .field("deref_mut", &self.deref_mut)
+            .field("as_ref", &self.as_ref)
+            .field("as_mut", &self.as_mut)
+            .field("from", &self.from)
+            .field("into", &self.into)
             .field("fields", &self.fields)
             .finish()
     }
@@ -150,24 +150,47 @@ impl<'input, T: CombineGenerics> NamedWrapperStruct<'input, T> {
         }
 
         if let Some(field_attrs) = &attrs.fields {
-            for (f, f_attr) in fields.named.iter().zip(field_attrs.iter()) {
-                if let Some(f_attr) = f_attr {
-                    if f_attr.wraps {
-                        return Ok(NamedWrapperStruct {
-                            input,
-                            combined_generics,
-                            attrs,
-                            wraps: f.ident.clone().unwrap(),
-                            wraps_type: f.ty.clone(),
-                            _marker: std::marker::PhantomData,
-                        });
-                    }
+            let mut marked = fields
+                .named
+                .iter()
+                .zip(field_attrs.iter())
+                .filter_map(|(f, f_attr)| f_attr.as_ref().filter(|a| a.wraps).map(|_| f));
+
+            if let Some(first) = marked.next() {
+                if let Some(second) = marked.next() {
+                    return Err(syn::Error::new_spanned(
+                        second,
+                        format!(
+                            "field `{}` is also marked `#[kirin(wraps)]`, but `{}` already is; \
+                             only one field may be `#[kirin(wraps)]`",
+                            second.ident.as_ref().unwrap(),
+                            first.ident.as_ref().unwrap(),
+                        ),
+                    ));
                 }
+                return Ok(NamedWrapperStruct {
+                    input,
+                    combined_generics,
+                    attrs,
+                    wraps: first.ident.clone().unwrap(),
+                    wraps_type: first.ty.clone(),
+                    _marker: std::marker::PhantomData,
+                });
             }
         }
+
+        let candidates = fields
+            .named
+            .iter()
+            .map(|f| f.ident.as_ref().unwrap().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
         Err(syn::Error::new_spanned(
             input,
-            "Struct is marked as wrapper but no field marked as wrapper or no single field present",
+            format!(
+                "struct is marked as a wrapper but has multiple fields ({candidates}) and none \
+                 is marked `#[kirin(wraps)]`; add `#[kirin(wraps)]` to exactly one of them",
+            ),
         ))
     }
 
@@ -259,24 +282,44 @@ impl<'input, T: CombineGenerics> UnnamedWrapperStruct<'input, T> {
         }
 
         if let Some(field_attrs) = &attrs.fields {
-            for (i, (f, f_attr)) in fields.unnamed.iter().zip(field_attrs.iter()).enumerate() {
-                if let Some(f_attr) = f_attr {
-                    if f_attr.wraps {
-                        return Ok(Self {
-                            input,
-                            combined_generics,
-                            attrs,
-                            wraps: i,
-                            wraps_type: f.ty.clone(),
-                            _marker: std::marker::PhantomData,
-                        });
-                    }
+            let mut marked = fields
+                .unnamed
+                .iter()
+                .zip(field_attrs.iter())
+                .enumerate()
+                .filter_map(|(i, (f, f_attr))| f_attr.as_ref().filter(|a| a.wraps).map(|_| (i, f)));
+
+            if let Some((index, f)) = marked.next() {
+                if let Some((second_index, second_f)) = marked.next() {
+                    return Err(syn::Error::new_spanned(
+                        second_f,
+                        format!(
+                            "field {second_index} is also marked `#[kirin(wraps)]`, but field \
+                             {index} already is; only one field may be `#[kirin(wraps)]`",
+                        ),
+                    ));
                 }
+                return Ok(Self {
+                    input,
+                    combined_generics,
+                    attrs,
+                    wraps: index,
+                    wraps_type: f.ty.clone(),
+                    _marker: std::marker::PhantomData,
+                });
             }
         }
+
+        let candidates = (0..fields.unnamed.len())
+            .map(|i| format!("field {i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
         Err(syn::Error::new_spanned(
             input,
-            "Struct is marked as wrapper but no field marked as wrapper or no single field present",
+            format!(
+                "struct is marked as a wrapper but has multiple fields ({candidates}) and none \
+                 is marked `#[kirin(wraps)]`; add `#[kirin(wraps)]` to exactly one of them",
+            ),
         ))
     }
 
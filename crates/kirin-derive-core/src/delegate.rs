@@ -0,0 +1,279 @@
+use quote::quote;
+
+use crate::data::*;
+
+/// Generates `derive_more`-style delegations (`Deref`, `DerefMut`, `AsRef`,
+/// `AsMut`, `From`, `Into`) for a `#[kirin(wraps)]` wrapper struct, gated by
+/// the corresponding `StructAttribute` flag so a dialect author opts in with
+/// e.g. `#[kirin(deref, from)]` instead of getting every delegation for
+/// free.
+#[macro_export]
+macro_rules! derive_delegate {
+    ($input:expr) => {{
+        let trait_info = DelegateInfo::default();
+        let data = Data::builder()
+            .trait_info(&trait_info)
+            .input($input)
+            .build();
+        trait_info.generate_from(&data)
+    }};
+}
+
+#[derive(Clone, Default)]
+pub struct DelegateInfo(syn::Generics);
+
+impl StatementFields<'_> for DelegateInfo {
+    type FieldsType = ();
+    type InfoType = ();
+}
+
+impl HasGenerics for DelegateInfo {
+    fn generics(&self) -> &syn::Generics {
+        &self.0
+    }
+}
+
+/// The subset of `StructAttribute`'s delegation flags, plus the accessor
+/// (`self.<wraps>`) and wrapped type each wrapper shape resolves them to.
+///
+/// `construct` builds a `Self { .. }` or `Self( .. )` literal from the
+/// wrapped value, since `from`/`into` need to initialize the struct and
+/// named vs. tuple structs spell that differently.
+struct Delegations<'a> {
+    attrs: &'a StructAttribute,
+    accessor: proc_macro2::TokenStream,
+    wraps_type: &'a syn::Type,
+    construct: Box<dyn Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream + 'a>,
+}
+
+impl Delegations<'_> {
+    fn generate(&self, name: &syn::Ident, split: &SplitForImpl<'_>) -> proc_macro2::TokenStream {
+        let SplitForImpl {
+            impl_generics,
+            input_ty_generics,
+            where_clause,
+            ..
+        } = split;
+        let accessor = &self.accessor;
+        let wraps_type = self.wraps_type;
+
+        let deref = self.attrs.deref.then(|| {
+            quote! {
+                impl #impl_generics ::core::ops::Deref for #name #input_ty_generics #where_clause {
+                    type Target = #wraps_type;
+
+                    fn deref(&self) -> &Self::Target {
+                        &self.#accessor
+                    }
+                }
+            }
+        });
+
+        let deref_mut = self.attrs.deref_mut.then(|| {
+            quote! {
+                impl #impl_generics ::core::ops::DerefMut for #name #input_ty_generics #where_clause {
+                    fn deref_mut(&mut self) -> &mut Self::Target {
+                        &mut self.#accessor
+                    }
+                }
+            }
+        });
+
+        let as_ref = self.attrs.as_ref.then(|| {
+            quote! {
+                impl #impl_generics ::core::convert::AsRef<#wraps_type> for #name #input_ty_generics #where_clause {
+                    fn as_ref(&self) -> &#wraps_type {
+                        &self.#accessor
+                    }
+                }
+            }
+        });
+
+        let as_mut = self.attrs.as_mut.then(|| {
+            quote! {
+                impl #impl_generics ::core::convert::AsMut<#wraps_type> for #name #input_ty_generics #where_clause {
+                    fn as_mut(&mut self) -> &mut #wraps_type {
+                        &mut self.#accessor
+                    }
+                }
+            }
+        });
+
+        let from = self.attrs.from.then(|| {
+            let value = syn::Ident::new("value", proc_macro2::Span::call_site());
+            let construct = (self.construct)(quote! { #value });
+            quote! {
+                impl #impl_generics ::core::convert::From<#wraps_type> for #name #input_ty_generics #where_clause {
+                    fn from(#value: #wraps_type) -> Self {
+                        #construct
+                    }
+                }
+            }
+        });
+
+        let into = self.attrs.into.then(|| {
+            quote! {
+                impl #impl_generics ::core::convert::From<#name #input_ty_generics> for #wraps_type #where_clause {
+                    fn from(value: #name #input_ty_generics) -> Self {
+                        value.#accessor
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #deref
+            #deref_mut
+            #as_ref
+            #as_mut
+            #from
+            #into
+        }
+    }
+}
+
+impl GenerateFrom<'_, NamedWrapperStruct<'_, DelegateInfo>> for DelegateInfo {
+    fn generate_from(
+        &self,
+        data: &NamedWrapperStruct<'_, DelegateInfo>,
+    ) -> proc_macro2::TokenStream {
+        let name = &data.input.ident;
+        let wraps = &data.wraps;
+        let split = data.split_for_impl(self);
+
+        let syn::Data::Struct(struct_data) = &data.input.data else {
+            unreachable!("NamedWrapperStruct is only ever built from struct data");
+        };
+        let syn::Fields::Named(fields) = &struct_data.fields else {
+            unreachable!("NamedWrapperStruct is only ever built from named fields");
+        };
+
+        // other fields (if any) fall back to `Default::default()`, the same
+        // as the sibling `From` derivation in `from.rs`.
+        let other_fields = fields
+            .named
+            .iter()
+            .map(|f| f.ident.as_ref().expect("named field has an ident"))
+            .filter(|ident| *ident != wraps)
+            .collect::<Vec<_>>();
+
+        let delegations = Delegations {
+            attrs: &data.attrs,
+            accessor: quote! { #wraps },
+            wraps_type: &data.wraps_type,
+            construct: Box::new(move |value| {
+                quote! { Self { #wraps: #value, #(#other_fields: ::core::default::Default::default()),* } }
+            }),
+        };
+        delegations.generate(name, &split)
+    }
+}
+
+impl GenerateFrom<'_, UnnamedWrapperStruct<'_, DelegateInfo>> for DelegateInfo {
+    fn generate_from(
+        &self,
+        data: &UnnamedWrapperStruct<'_, DelegateInfo>,
+    ) -> proc_macro2::TokenStream {
+        let name = &data.input.ident;
+        let wraps_index = data.wraps;
+        let wraps = syn::Index::from(wraps_index);
+        let split = data.split_for_impl(self);
+
+        let syn::Data::Struct(struct_data) = &data.input.data else {
+            unreachable!("UnnamedWrapperStruct is only ever built from struct data");
+        };
+        let syn::Fields::Unnamed(fields) = &struct_data.fields else {
+            unreachable!("UnnamedWrapperStruct is only ever built from unnamed fields");
+        };
+        let field_count = fields.unnamed.len();
+
+        let delegations = Delegations {
+            attrs: &data.attrs,
+            accessor: quote! { #wraps },
+            wraps_type: &data.wraps_type,
+            construct: Box::new(move |value| {
+                let positions = (0..field_count).map(|i| {
+                    if i == wraps_index {
+                        value.clone()
+                    } else {
+                        quote! { ::core::default::Default::default() }
+                    }
+                });
+                quote! { Self(#(#positions),*) }
+            }),
+        };
+        delegations.generate(name, &split)
+    }
+}
+
+impl GenerateFrom<'_, RegularStruct<'_, DelegateInfo>> for DelegateInfo {
+    fn generate_from(&self, _data: &RegularStruct<'_, DelegateInfo>) -> proc_macro2::TokenStream {
+        quote! {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::rustfmt;
+
+    #[test]
+    fn test_named_wrapper_struct_deref() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[kirin(deref, deref_mut)]
+            struct Wrapper {
+                #[kirin(wraps)]
+                inner: Inner,
+            }
+        };
+        insta::assert_snapshot!(generate(input));
+    }
+
+    #[test]
+    fn test_named_wrapper_struct_as_ref_from_into() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[kirin(as_ref, as_mut, from, into)]
+            struct Wrapper {
+                #[kirin(wraps)]
+                inner: Inner,
+            }
+        };
+        insta::assert_snapshot!(generate(input));
+    }
+
+    #[test]
+    fn test_unnamed_wrapper_struct_deref() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[kirin(deref, deref_mut, from, into)]
+            struct Wrapper(#[kirin(wraps)] Inner);
+        };
+        insta::assert_snapshot!(generate(input));
+    }
+
+    #[test]
+    fn test_wrapper_struct_without_flags_generates_nothing() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Wrapper {
+                #[kirin(wraps)]
+                inner: Inner,
+            }
+        };
+        insta::assert_snapshot!(generate(input));
+    }
+
+    #[test]
+    fn test_regular_struct_generates_nothing() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[kirin(deref, from)]
+            struct Regular {
+                a: i32,
+                b: f64,
+            }
+        };
+        insta::assert_snapshot!(generate(input));
+    }
+
+    fn generate(input: syn::DeriveInput) -> String {
+        rustfmt(derive_delegate!(&input))
+    }
+}
@@ -0,0 +1,265 @@
+//! An in-place rewriting counterpart to [`Emit`](crate::emit::Emit): where
+//! `Emit::emit_*` methods read an `ir` node and produce a `TokenStream`,
+//! [`Fold::fold_*`] methods consume an `ir` node and return a transformed
+//! one, so passes over a dialect's own parsed definition (e.g.
+//! canonicalizing a `#[kirin(default = ...)]` expression, or dropping a
+//! field a prior pass determined was dead) can be written once and
+//! composed, the same way `syn::fold::Fold`/fayalite's `Fold` are. Override
+//! only the node kinds a pass cares about; every other `fold_*` method's
+//! default body recurses into its children via the free `fold_*` function
+//! of the same name, so overrides compose instead of needing to
+//! reimplement traversal.
+//!
+//! [`VisitMut`] is the same traversal over `&mut` references instead of by
+//! value, for passes that only mutate nodes in place and don't need to
+//! reconstruct them.
+
+use darling::Result;
+
+use crate::ir::fields::{FieldCategory, FieldInfo, Wrapper};
+use crate::ir::{Data, DataEnum, DataStruct, Input, Layout, Statement};
+
+pub trait Fold<L: Layout> {
+    fn fold_input(&mut self, input: Input<L>) -> Result<Input<L>> {
+        fold_input(self, input)
+    }
+
+    fn fold_data(&mut self, data: Data<L>) -> Result<Data<L>> {
+        fold_data(self, data)
+    }
+
+    fn fold_struct(&mut self, data: DataStruct<L>) -> Result<DataStruct<L>> {
+        fold_struct(self, data)
+    }
+
+    fn fold_enum(&mut self, data: DataEnum<L>) -> Result<DataEnum<L>> {
+        fold_enum(self, data)
+    }
+
+    fn fold_statement(&mut self, statement: Statement<L>) -> Result<Statement<L>> {
+        fold_statement(self, statement)
+    }
+
+    fn fold_wrapper(&mut self, wrapper: Wrapper) -> Result<Wrapper> {
+        Ok(wrapper)
+    }
+
+    /// Dispatches to the category-specific `fold_*` method below. Override
+    /// this instead when a pass needs to touch every field regardless of
+    /// category.
+    fn fold_field(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        fold_field(self, field)
+    }
+
+    fn fold_argument(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        Ok(field)
+    }
+
+    fn fold_result(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        Ok(field)
+    }
+
+    fn fold_block(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        Ok(field)
+    }
+
+    fn fold_successor(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        Ok(field)
+    }
+
+    fn fold_region(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        Ok(field)
+    }
+
+    fn fold_value(&mut self, field: FieldInfo<L>) -> Result<FieldInfo<L>> {
+        Ok(field)
+    }
+}
+
+pub fn fold_input<L: Layout, F: Fold<L> + ?Sized>(folder: &mut F, input: Input<L>) -> Result<Input<L>> {
+    let data = folder.fold_data(input.data)?;
+    Ok(Input { data, ..input })
+}
+
+pub fn fold_data<L: Layout, F: Fold<L> + ?Sized>(folder: &mut F, data: Data<L>) -> Result<Data<L>> {
+    match data {
+        Data::Struct(data) => Ok(Data::Struct(folder.fold_struct(data)?)),
+        Data::Enum(data) => Ok(Data::Enum(folder.fold_enum(data)?)),
+    }
+}
+
+pub fn fold_struct<L: Layout, F: Fold<L> + ?Sized>(
+    folder: &mut F,
+    data: DataStruct<L>,
+) -> Result<DataStruct<L>> {
+    Ok(DataStruct(folder.fold_statement(data.0)?))
+}
+
+pub fn fold_enum<L: Layout, F: Fold<L> + ?Sized>(folder: &mut F, data: DataEnum<L>) -> Result<DataEnum<L>> {
+    let mut errors = darling::Error::accumulator();
+    let mut variants = Vec::with_capacity(data.variants.len());
+    for variant in data.variants {
+        if let Some(variant) = errors.handle(folder.fold_statement(variant)) {
+            variants.push(variant);
+        }
+    }
+    errors.finish()?;
+    Ok(DataEnum { variants })
+}
+
+pub fn fold_statement<L: Layout, F: Fold<L> + ?Sized>(
+    folder: &mut F,
+    mut statement: Statement<L>,
+) -> Result<Statement<L>> {
+    let mut errors = darling::Error::accumulator();
+
+    if let Some(wrapper) = statement.wraps.take() {
+        statement.wraps = errors.handle(folder.fold_wrapper(wrapper));
+    }
+
+    let mut fields = Vec::with_capacity(statement.fields.len());
+    for field in std::mem::take(&mut statement.fields) {
+        if let Some(field) = errors.handle(folder.fold_field(field)) {
+            fields.push(field);
+        }
+    }
+    statement.fields = fields;
+
+    errors.finish()?;
+    Ok(statement)
+}
+
+pub fn fold_field<L: Layout, F: Fold<L> + ?Sized>(
+    folder: &mut F,
+    field: FieldInfo<L>,
+) -> Result<FieldInfo<L>> {
+    match field.category() {
+        FieldCategory::Argument => folder.fold_argument(field),
+        FieldCategory::Result => folder.fold_result(field),
+        FieldCategory::Block => folder.fold_block(field),
+        FieldCategory::Successor => folder.fold_successor(field),
+        FieldCategory::Region => folder.fold_region(field),
+        FieldCategory::Value => folder.fold_value(field),
+    }
+}
+
+/// [`Fold`]'s by-reference counterpart: the same traversal, but mutating a
+/// node in place (`&mut X`, `Result<()>`) instead of consuming and
+/// reconstructing it. Pick this when a pass only needs to tweak a field in
+/// place (e.g. flip a `bare` flag) and has no use for owning the node.
+pub trait VisitMut<L: Layout> {
+    fn visit_input_mut(&mut self, input: &mut Input<L>) -> Result<()> {
+        visit_input_mut(self, input)
+    }
+
+    fn visit_data_mut(&mut self, data: &mut Data<L>) -> Result<()> {
+        visit_data_mut(self, data)
+    }
+
+    fn visit_struct_mut(&mut self, data: &mut DataStruct<L>) -> Result<()> {
+        visit_struct_mut(self, data)
+    }
+
+    fn visit_enum_mut(&mut self, data: &mut DataEnum<L>) -> Result<()> {
+        visit_enum_mut(self, data)
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement<L>) -> Result<()> {
+        visit_statement_mut(self, statement)
+    }
+
+    fn visit_wrapper_mut(&mut self, _wrapper: &mut Wrapper) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_field_mut(&mut self, field: &mut FieldInfo<L>) -> Result<()> {
+        visit_field_mut(self, field)
+    }
+
+    fn visit_argument_mut(&mut self, _field: &mut FieldInfo<L>) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_result_mut(&mut self, _field: &mut FieldInfo<L>) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_block_mut(&mut self, _field: &mut FieldInfo<L>) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_successor_mut(&mut self, _field: &mut FieldInfo<L>) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_region_mut(&mut self, _field: &mut FieldInfo<L>) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_value_mut(&mut self, _field: &mut FieldInfo<L>) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub fn visit_input_mut<L: Layout, V: VisitMut<L> + ?Sized>(
+    visitor: &mut V,
+    input: &mut Input<L>,
+) -> Result<()> {
+    visitor.visit_data_mut(&mut input.data)
+}
+
+pub fn visit_data_mut<L: Layout, V: VisitMut<L> + ?Sized>(
+    visitor: &mut V,
+    data: &mut Data<L>,
+) -> Result<()> {
+    match data {
+        Data::Struct(data) => visitor.visit_struct_mut(data),
+        Data::Enum(data) => visitor.visit_enum_mut(data),
+    }
+}
+
+pub fn visit_struct_mut<L: Layout, V: VisitMut<L> + ?Sized>(
+    visitor: &mut V,
+    data: &mut DataStruct<L>,
+) -> Result<()> {
+    visitor.visit_statement_mut(&mut data.0)
+}
+
+pub fn visit_enum_mut<L: Layout, V: VisitMut<L> + ?Sized>(
+    visitor: &mut V,
+    data: &mut DataEnum<L>,
+) -> Result<()> {
+    let mut errors = darling::Error::accumulator();
+    for variant in &mut data.variants {
+        errors.handle(visitor.visit_statement_mut(variant));
+    }
+    errors.finish()
+}
+
+pub fn visit_statement_mut<L: Layout, V: VisitMut<L> + ?Sized>(
+    visitor: &mut V,
+    statement: &mut Statement<L>,
+) -> Result<()> {
+    let mut errors = darling::Error::accumulator();
+    if let Some(wrapper) = &mut statement.wraps {
+        errors.handle(visitor.visit_wrapper_mut(wrapper));
+    }
+    for field in &mut statement.fields {
+        errors.handle(visitor.visit_field_mut(field));
+    }
+    errors.finish()
+}
+
+pub fn visit_field_mut<L: Layout, V: VisitMut<L> + ?Sized>(
+    visitor: &mut V,
+    field: &mut FieldInfo<L>,
+) -> Result<()> {
+    match field.category() {
+        FieldCategory::Argument => visitor.visit_argument_mut(field),
+        FieldCategory::Result => visitor.visit_result_mut(field),
+        FieldCategory::Block => visitor.visit_block_mut(field),
+        FieldCategory::Successor => visitor.visit_successor_mut(field),
+        FieldCategory::Region => visitor.visit_region_mut(field),
+        FieldCategory::Value => visitor.visit_value_mut(field),
+    }
+}
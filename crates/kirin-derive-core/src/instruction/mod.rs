@@ -1,4 +1,5 @@
 mod attribute;
+mod variant_accessors;
 
 use proc_macro2::TokenStream;
 
@@ -7,6 +8,7 @@ use crate::{
     accessor::Config,
     instruction::attribute::{AttributeInfo, DeriveAttribute},
 };
+use variant_accessors::DeriveVariantAccessors;
 
 pub struct DeriveInstruction;
 
@@ -32,6 +34,7 @@ impl DeriveTrait for DeriveInstruction {
         ctx.write_helper_impl(DeriveIsTerminator::generate(input.clone()));
         ctx.write_helper_impl(DeriveIsConstant::generate(input.clone()));
         ctx.write_helper_impl(DeriveIsPure::generate(input.clone()));
+        ctx.write_helper_impl(DeriveVariantAccessors::generate(input.clone()));
         ctx.generate()
     }
 }
@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    instruction::attribute::AttributeInfo, misc::to_snake_case, DeriveHelperAttribute,
+};
+
+/// Generates `is_foo`/`as_foo` ergonomic accessors for each variant of an
+/// `#[derive(Instruction)]` enum, plus `From<Inner> for Self` impls for
+/// `#[kirin(wraps)]` variants. A no-op for struct/wrapper-struct input, since
+/// those forms have only one shape and no variants to distinguish between.
+///
+/// Unlike the other helpers in this module, these are plain inherent methods
+/// and free-standing `From` impls rather than an implementation of some
+/// shared trait, so this bypasses the `DeriveTrait`/`WriteTokenStream`
+/// machinery (which always wraps its output in `impl SomeTrait for Name`)
+/// and just returns the tokens directly.
+pub struct DeriveVariantAccessors;
+
+impl DeriveVariantAccessors {
+    pub fn generate(input: syn::DeriveInput) -> TokenStream {
+        let syn::Data::Enum(data) = &input.data else {
+            return quote! {};
+        };
+
+        let attribute_info = AttributeInfo::scan(&input).unwrap();
+        let name = &input.ident;
+
+        let predicates = data.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let is_method = format_ident!("is_{}", to_snake_case(variant_ident.to_string()));
+            let pat = match &variant.fields {
+                syn::Fields::Named(_) => quote! { Self::#variant_ident { .. } },
+                syn::Fields::Unnamed(_) => quote! { Self::#variant_ident(..) },
+                syn::Fields::Unit => quote! { Self::#variant_ident },
+            };
+            quote! {
+                pub fn #is_method(&self) -> bool {
+                    matches!(self, #pat)
+                }
+            }
+        });
+
+        // `as_foo` only makes sense for a single-field tuple variant, which is
+        // exactly the shape `#[kirin(wraps)]` variants always have.
+        let accessors = data.variants.iter().filter_map(|variant| {
+            let inner_ty = single_unnamed_field_ty(variant)?;
+            let variant_ident = &variant.ident;
+            let as_method = format_ident!("as_{}", to_snake_case(variant_ident.to_string()));
+            Some(quote! {
+                pub fn #as_method(&self) -> Option<&#inner_ty> {
+                    match self {
+                        Self::#variant_ident(inner) => Some(inner),
+                        _ => None,
+                    }
+                }
+            })
+        });
+
+        let from_impls = data.variants.iter().filter_map(|variant| {
+            if !attribute_info.variant_wraps(&variant.ident) {
+                return None;
+            }
+            let inner_ty = single_unnamed_field_ty(variant)?;
+            let variant_ident = &variant.ident;
+            Some(quote! {
+                #[automatically_derived]
+                impl ::core::convert::From<#inner_ty> for #name {
+                    fn from(value: #inner_ty) -> Self {
+                        Self::#variant_ident(value)
+                    }
+                }
+            })
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl #name {
+                #(#predicates)*
+                #(#accessors)*
+            }
+            #(#from_impls)*
+        }
+    }
+}
+
+fn single_unnamed_field_ty(variant: &syn::Variant) -> Option<&syn::Type> {
+    let syn::Fields::Unnamed(fields) = &variant.fields else {
+        return None;
+    };
+    match fields.unnamed.len() {
+        1 => Some(&fields.unnamed[0].ty),
+        _ => None,
+    }
+}
@@ -25,6 +25,8 @@ use darling::{Error, FromDeriveInput, FromField, FromMeta, FromVariant};
 /// - `crate = <Path>` — override the default IR crate path (`::kirin::ir`).
 /// - `format = "<string>"` — custom format string for pretty-printing.
 /// - `fn` / `fn = <ident>` — generate a builder function (auto-named or with the given name).
+/// - `builder` — generate a staged (type-state) builder type instead of a
+///   single positional constructor; see [`crate::kirin::staged_builder`].
 /// - `constant` — mark as a constant-foldable operation.
 /// - `pure` — mark as a pure (side-effect-free) operation.
 /// - `speculatable` — mark as safe to speculatively execute.
@@ -39,6 +41,8 @@ pub struct KirinStructOptions {
     pub format: Option<String>,
     #[darling(rename = "fn")]
     pub builder: Option<BuilderOptions>,
+    #[darling(default, rename = "builder")]
+    pub staged_builder: bool,
     #[darling(default)]
     pub constant: bool,
     #[darling(default)]
@@ -62,6 +66,8 @@ pub struct KirinEnumOptions {
     pub ir_type: syn::Path,
     #[darling(rename = "fn")]
     pub builder: Option<BuilderOptions>,
+    #[darling(default, rename = "builder")]
+    pub staged_builder: bool,
     #[darling(default)]
     pub constant: bool,
     #[darling(default)]
@@ -109,6 +115,13 @@ pub struct StatementOptions {
 ///   given expression.
 /// - `type = <expr>` — the SSA type expression for this field's IR value
 ///   (e.g. `ArithType::Float`, `SimpleIRType::default()`).
+/// - `builder(type = "<Type>", build = "<expr>")` — customize the generated
+///   builder function for this field; see [`FieldBuilderOptions`].
+/// - `bare` — for `Block`/`Region` fields, parse (and print) the field's
+///   nested body as a plain brace-delimited statement list (`{ stmt; stmt; }`)
+///   instead of an MLIR-style labeled block (`^label(...) { ... }`). The
+///   field's own parser and printer supply the `{`/`}`, so the format string
+///   names only the field, e.g. `"{res} = if {cond} {body}"`.
 #[derive(Debug, Clone, FromField)]
 #[darling(attributes(kirin))]
 pub struct KirinFieldOptions {
@@ -117,6 +130,29 @@ pub struct KirinFieldOptions {
     pub default: Option<DefaultValue>,
     #[darling(rename = "type")]
     pub ssa_ty: Option<syn::Expr>,
+    pub builder: Option<FieldBuilderOptions>,
+    #[darling(default)]
+    pub bare: bool,
+}
+
+/// Parsed `#[kirin(builder(...))]` sub-attributes on a field, in the spirit
+/// of derive_builder's `field(type = ..., build = ...)`.
+///
+/// - `type = "<Type>"` — the builder function's argument type for this
+///   field, used in place of the field's own type (e.g. `impl Into<SSAValue>`,
+///   or a slice type that gets interned).
+/// - `build = "<expr>"` — expression that produces the stored value from the
+///   builder argument (bound under the field's own name). Runs instead of
+///   the plain `#[kirin(into)]` conversion when present.
+///
+/// Both are independent of `into`/`default` on the same field: `default`
+/// still wins (the field is omitted from the builder signature entirely),
+/// and `into` is only consulted when no `build` expression is given.
+#[derive(Debug, Clone, FromMeta)]
+pub struct FieldBuilderOptions {
+    #[darling(rename = "type")]
+    pub ty: Option<syn::Type>,
+    pub build: Option<syn::Expr>,
 }
 
 /// Default value specification for a field.
@@ -191,6 +227,7 @@ pub struct GlobalOptions {
     pub crate_path: Option<syn::Path>,
     pub ir_type: syn::Path,
     pub builder: Option<BuilderOptions>,
+    pub staged_builder: bool,
     pub constant: bool,
     pub pure: bool,
     pub speculatable: bool,
@@ -228,6 +265,7 @@ impl From<KirinStructOptions> for GlobalOptions {
             crate_path: opts.crate_path,
             ir_type: opts.ir_type,
             builder: opts.builder,
+            staged_builder: opts.staged_builder,
             constant: opts.constant,
             pure: opts.pure,
             speculatable: opts.speculatable,
@@ -255,6 +293,7 @@ impl From<KirinEnumOptions> for GlobalOptions {
             crate_path: value.crate_path,
             ir_type: value.ir_type,
             builder: value.builder,
+            staged_builder: value.staged_builder,
             constant: value.constant,
             pure: value.pure,
             speculatable: value.speculatable,
@@ -10,7 +10,7 @@ pub use index::FieldIndex;
 pub use value::{Argument, Arguments, Result, Results, Value};
 pub use wrapper::Wrapper;
 
-use crate::ir::{DefaultValue, Layout};
+use crate::ir::{DefaultValue, FieldBuilderOptions, Layout};
 use proc_macro2::Span;
 
 /// Macro to define a simple IR field collection type.
@@ -78,7 +78,8 @@ pub enum FieldCategory {
 /// This enum stores the data that varies by field category:
 /// - `Argument` and `Result`: SSA type expression
 /// - `Value`: type, default, into flag, and layout-specific extra data
-/// - `Block`, `Successor`, `Region`: no additional data
+/// - `Block`, `Region`: whether the field uses bare (unlabeled) body syntax
+/// - `Successor`: no additional data
 #[derive(Debug, Clone)]
 pub enum FieldData<L: Layout> {
     /// SSAValue argument field
@@ -92,11 +93,20 @@ pub enum FieldData<L: Layout> {
         ssa_type: syn::Expr,
     },
     /// Block field (owned control flow block)
-    Block,
+    Block {
+        /// Whether the field was marked `#[kirin(bare)]`: its body is parsed
+        /// as a plain `{ stmt; stmt; }` list rather than an MLIR-style
+        /// `^label(...) { ... }` labeled block.
+        bare: bool,
+    },
     /// Successor field (branch target)
     Successor,
     /// Region field (nested scope)
-    Region,
+    Region {
+        /// Whether the field was marked `#[kirin(bare)]`; see the `Block`
+        /// variant's `bare` field.
+        bare: bool,
+    },
     /// Compile-time value field
     Value {
         /// The type of the compile-time value
@@ -105,6 +115,8 @@ pub enum FieldData<L: Layout> {
         default: Option<DefaultValue>,
         /// Whether the `#[kirin(into)]` attribute is specified
         into: bool,
+        /// Custom builder argument type/conversion from `#[kirin(builder(...))]`
+        builder: Option<FieldBuilderOptions>,
         /// Layout-specific extra data from field attributes
         extra: L::ExtraFieldAttrs,
     },
@@ -133,9 +145,9 @@ impl<L: Layout> FieldInfo<L> {
         match &self.data {
             FieldData::Argument { .. } => FieldCategory::Argument,
             FieldData::Result { .. } => FieldCategory::Result,
-            FieldData::Block => FieldCategory::Block,
+            FieldData::Block { .. } => FieldCategory::Block,
             FieldData::Successor => FieldCategory::Successor,
-            FieldData::Region => FieldCategory::Region,
+            FieldData::Region { .. } => FieldCategory::Region,
             FieldData::Value { .. } => FieldCategory::Value,
         }
     }
@@ -193,6 +205,36 @@ impl<L: Layout> FieldInfo<L> {
         matches!(&self.data, FieldData::Value { into: true, .. })
     }
 
+    /// Returns the custom builder-argument type for this field, if any
+    /// (`#[kirin(builder(type = ...))]`), used in place of the field's own
+    /// type in the generated builder function's signature.
+    pub fn builder_type(&self) -> Option<&syn::Type> {
+        match &self.data {
+            FieldData::Value { builder, .. } => builder.as_ref()?.ty.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the expression that converts the builder argument into this
+    /// field's stored value, if any (`#[kirin(builder(build = ...))]`).
+    pub fn builder_build_expr(&self) -> Option<&syn::Expr> {
+        match &self.data {
+            FieldData::Value { builder, .. } => builder.as_ref()?.build.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this Block or Region field was marked `#[kirin(bare)]`,
+    /// i.e. its nested body is parsed as a plain brace-delimited statement
+    /// list instead of an MLIR-style labeled block. Always false for other
+    /// field categories.
+    pub fn is_bare(&self) -> bool {
+        matches!(
+            &self.data,
+            FieldData::Block { bare: true } | FieldData::Region { bare: true }
+        )
+    }
+
     /// Returns the extra field attributes for Value fields.
     pub fn extra(&self) -> Option<&L::ExtraFieldAttrs> {
         match &self.data {
@@ -0,0 +1,115 @@
+//! Parses `#[kirin(format = "...")]` template strings into a sequence of
+//! literal text and field placeholders for the format-driven pretty-printer
+//! derive (see [`crate::kirin::format`]).
+
+/// One piece of a parsed format template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatSegment {
+    /// Literal text to emit as-is, with `{{`/`}}` already unescaped.
+    Literal(String),
+    /// A `{name}` placeholder, resolved against a field's own identifier.
+    Named(String),
+    /// A `{0}`, `{1}`, ... placeholder, resolved by declaration order among
+    /// a tuple-style variant's fields.
+    Positional(usize),
+}
+
+/// Parses a `#[kirin(format = "...")]` template into its segments.
+///
+/// `{{` and `}}` are literal braces; any other `{` opens a placeholder,
+/// closed by the next unescaped `}`. A placeholder whose contents parse as a
+/// plain integer is [`FormatSegment::Positional`]; everything else is
+/// [`FormatSegment::Named`].
+pub fn parse_format_template(template: &str) -> Result<Vec<FormatSegment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                if !literal.is_empty() {
+                    segments.push(FormatSegment::Literal(std::mem::take(&mut literal)));
+                }
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder in format template {template:?}")),
+                    }
+                }
+                segments.push(match name.parse::<usize>() {
+                    Ok(index) => FormatSegment::Positional(index),
+                    Err(_) => FormatSegment::Named(name),
+                });
+            }
+            '}' => return Err(format!("unescaped `}}` in format template {template:?}")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(FormatSegment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_only() {
+        assert_eq!(
+            parse_format_template("add").unwrap(),
+            vec![FormatSegment::Literal("add".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_named_and_positional_placeholders() {
+        let segments = parse_format_template("{lhs} = add {rhs0}, {1} : {ty}").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                FormatSegment::Named("lhs".to_string()),
+                FormatSegment::Literal(" = add ".to_string()),
+                FormatSegment::Named("rhs0".to_string()),
+                FormatSegment::Literal(", ".to_string()),
+                FormatSegment::Positional(1),
+                FormatSegment::Literal(" : ".to_string()),
+                FormatSegment::Named("ty".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        assert_eq!(
+            parse_format_template("{{{name}}}").unwrap(),
+            vec![
+                FormatSegment::Literal("{".to_string()),
+                FormatSegment::Named("name".to_string()),
+                FormatSegment::Literal("}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_placeholder_errors() {
+        assert!(parse_format_template("{lhs").is_err());
+    }
+
+    #[test]
+    fn test_unescaped_closing_brace_errors() {
+        assert!(parse_format_template("oops}").is_err());
+    }
+}
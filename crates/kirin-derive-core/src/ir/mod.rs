@@ -1,13 +1,17 @@
 mod attrs;
 pub mod fields;
+mod format_template;
 mod input;
 mod layout;
 mod statement;
+mod structure;
 
-pub use attrs::{BuilderOptions, DefaultValue};
+pub use attrs::{BuilderOptions, DefaultValue, FieldBuilderOptions};
+pub use format_template::{parse_format_template, FormatSegment};
 pub use input::{Data, DataEnum, DataStruct, Input};
 pub use layout::{Layout, StandardLayout};
 pub use statement::Statement;
+pub use structure::{BindingInfo, Structure};
 
 #[cfg(test)]
 mod tests {
@@ -149,7 +149,9 @@ impl<L: Layout> Statement<L> {
                 index,
                 ident,
                 collection,
-                data: FieldData::Block,
+                data: FieldData::Block {
+                    bare: kirin_opts.bare,
+                },
             });
         }
 
@@ -169,7 +171,9 @@ impl<L: Layout> Statement<L> {
                 index,
                 ident,
                 collection,
-                data: FieldData::Region,
+                data: FieldData::Region {
+                    bare: kirin_opts.bare,
+                },
             });
         }
 
@@ -192,6 +196,7 @@ impl<L: Layout> Statement<L> {
                 ty: ty.clone(),
                 default: kirin_opts.default,
                 into: kirin_opts.into,
+                builder: kirin_opts.builder,
                 extra,
             },
         })
@@ -0,0 +1,1839 @@
+//! synstructure-style binding/traversal helper over [`Input`].
+//!
+//! [`Structure`] enumerates every variant of a derive input (a struct counts
+//! as a single variant), assigns each field a fresh binding identifier, and
+//! exposes [`Structure::each`]/[`Structure::fold`] so a derived trait can be
+//! written as one closure over [`BindingInfo`] instead of a hand-rolled
+//! `match self { ... }` per trait. [`Structure::generate_visit`]/
+//! [`Structure::generate_visit_mut`]/[`Structure::generate_fold`] build on the
+//! same binding machinery to emit whole `Visit`/`VisitMut`/`Fold` trait
+//! bodies directly.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::misc::to_snake_case;
+
+use super::{
+    fields::{Collection, FieldCategory, FieldInfo},
+    format_template::FormatSegment,
+    input::{Data, Input},
+    layout::Layout,
+    statement::Statement,
+};
+
+/// One field binding produced while walking a [`Structure`].
+pub struct BindingInfo<'a, L: Layout> {
+    /// The fresh identifier bound to this field (`__kirin_binding_<index>`).
+    pub binding: syn::Ident,
+    /// The field's parsed declaration (index, collection kind, category, ...).
+    pub field: &'a FieldInfo<L>,
+    /// The variant this field belongs to (the struct itself, for structs).
+    pub variant: &'a Statement<L>,
+}
+
+/// A synstructure-style view over a derive input's variants and fields.
+///
+/// Every variant (or the single struct body) is assigned fresh bindings via
+/// [`Structure::each`]/[`Structure::fold`], so callers never write match arms
+/// by hand; they only supply a closure that runs per field.
+pub struct Structure<'a, L: Layout> {
+    input: &'a Input<L>,
+}
+
+impl<'a, L: Layout> Structure<'a, L> {
+    /// Creates a new structure view over `input`.
+    pub fn new(input: &'a Input<L>) -> Self {
+        Self { input }
+    }
+
+    /// All variants, treating a struct as a single variant.
+    fn variants(&self) -> Vec<&'a Statement<L>> {
+        match &self.input.data {
+            Data::Struct(s) => vec![&s.0],
+            Data::Enum(e) => e.variants.iter().collect(),
+        }
+    }
+
+    /// Builds a `match self { ... }` body: each variant's fields are bound
+    /// by reference (`ref`, or `ref mut` when `mutable` is set) to fresh
+    /// `__kirin_binding_<index>` identifiers, and `each` is called once per
+    /// field in declaration order; its output is spliced into the arm in
+    /// that same order.
+    pub fn each(
+        &self,
+        mutable: bool,
+        mut each: impl FnMut(&BindingInfo<'_, L>) -> TokenStream,
+    ) -> TokenStream {
+        let binder = if mutable { Binder::RefMut } else { Binder::Ref };
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, binder);
+            let body = stmt
+                .iter_all_fields()
+                .map(|field| each(&self.binding_info(stmt, field)));
+            quote! { #pat => { #(#body)* } }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Like [`Structure::each`], but threads an accumulator through every
+    /// field instead of emitting one statement per field. `init` is
+    /// re-evaluated fresh in each match arm.
+    pub fn fold(
+        &self,
+        mutable: bool,
+        init: impl Fn() -> TokenStream,
+        mut fold: impl FnMut(TokenStream, &BindingInfo<'_, L>) -> TokenStream,
+    ) -> TokenStream {
+        let binder = if mutable { Binder::RefMut } else { Binder::Ref };
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, binder);
+            let body = stmt
+                .iter_all_fields()
+                .fold(init(), |acc, field| fold(acc, &self.binding_info(stmt, field)));
+            quote! { #pat => #body }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    fn binding_info(&self, stmt: &'a Statement<L>, field: &'a FieldInfo<L>) -> BindingInfo<'a, L> {
+        BindingInfo {
+            binding: binding_ident(field),
+            field,
+            variant: stmt,
+        }
+    }
+
+    /// The pattern for one match arm, e.g. `Name::Variant { a: ref __kirin_binding_0, .. }`.
+    ///
+    /// A `#[wraps]` field is excluded from `stmt.iter_all_fields()`, but its
+    /// original index still occupies a slot in the source struct/variant, so
+    /// tuple-style patterns (which are positional) must still account for it
+    /// or every field after it would bind to the wrong position; it is bound
+    /// under [`wraps_binding_ident`] whenever present.
+    fn arm_pattern(&self, stmt: &Statement<L>, binder: Binder) -> TokenStream {
+        let name = &self.input.name;
+        let path = match &self.input.data {
+            Data::Struct(_) => quote! { #name },
+            Data::Enum(_) => {
+                let variant_name = &stmt.name;
+                quote! { #name::#variant_name }
+            }
+        };
+        let binder_tokens = binder.tokens();
+
+        if stmt.is_tuple_style() {
+            let total = stmt.field_count() + stmt.wraps.is_some() as usize;
+            let mut slots = vec![quote! { _ }; total];
+            for field in stmt.iter_all_fields() {
+                let binding = binding_ident(field);
+                slots[field.index] = quote! { #binder_tokens #binding };
+            }
+            if let Some(wrapper) = &stmt.wraps {
+                let binding = wraps_binding_ident();
+                slots[wrapper.field.index] = quote! { #binder_tokens #binding };
+            }
+            quote! { #path( #(#slots),* ) }
+        } else {
+            let mut pats: Vec<TokenStream> = stmt
+                .iter_all_fields()
+                .map(|field| {
+                    let ident = field
+                        .ident
+                        .as_ref()
+                        .expect("named variant/struct fields always have an ident");
+                    let binding = binding_ident(field);
+                    quote! { #ident: #binder_tokens #binding, }
+                })
+                .collect();
+            if let Some(wrapper) = &stmt.wraps {
+                let ident = wrapper
+                    .field
+                    .ident
+                    .as_ref()
+                    .expect("named wrapper fields always have an ident");
+                let binding = wraps_binding_ident();
+                pats.push(quote! { #ident: #binder_tokens #binding, });
+            }
+            quote! { #path { #(#pats)* .. } }
+        }
+    }
+
+    /// The constructor dual of [`Structure::arm_pattern`]: rebuilds one
+    /// variant from scratch, e.g. `Name::Variant { a: <value>, .. }`. Used by
+    /// [`Structure::generate_fold`] to reassemble a statement after its
+    /// fields have been run through a folder.
+    ///
+    /// `wraps_value` supplies the rebuilt wrapped field for a `#[wraps]`
+    /// variant (required whenever `stmt.wraps` is `Some`); `field_value`
+    /// supplies every other field's rebuilt value.
+    fn construct(
+        &self,
+        stmt: &Statement<L>,
+        wraps_value: Option<TokenStream>,
+        mut field_value: impl FnMut(&FieldInfo<L>) -> TokenStream,
+    ) -> TokenStream {
+        let name = &self.input.name;
+        let path = match &self.input.data {
+            Data::Struct(_) => quote! { #name },
+            Data::Enum(_) => {
+                let variant_name = &stmt.name;
+                quote! { #name::#variant_name }
+            }
+        };
+
+        if stmt.is_tuple_style() {
+            let total = stmt.field_count() + stmt.wraps.is_some() as usize;
+            let mut slots = vec![TokenStream::new(); total];
+            for field in stmt.iter_all_fields() {
+                slots[field.index] = field_value(field);
+            }
+            if let Some(wrapper) = &stmt.wraps {
+                slots[wrapper.field.index] =
+                    wraps_value.expect("wraps_value is required for a #[wraps] variant");
+            }
+            quote! { #path( #(#slots),* ) }
+        } else {
+            let mut fields: Vec<TokenStream> = stmt
+                .iter_all_fields()
+                .map(|field| {
+                    let ident = field
+                        .ident
+                        .as_ref()
+                        .expect("named variant/struct fields always have an ident");
+                    let value = field_value(field);
+                    quote! { #ident: #value }
+                })
+                .collect();
+            if let Some(wrapper) = &stmt.wraps {
+                let ident = wrapper
+                    .field
+                    .ident
+                    .as_ref()
+                    .expect("named wrapper fields always have an ident");
+                let value = wraps_value.expect("wraps_value is required for a #[wraps] variant");
+                fields.push(quote! { #ident: #value });
+            }
+            quote! { #path { #(#fields),* } }
+        }
+    }
+
+    /// Builds a `match self { ... }` body for a generated `Visit`-style
+    /// trait impl: every `Argument`/`Block`/`Region` field is passed to the
+    /// matching `visit_*` hook on `visitor`, in declaration order. A
+    /// `#[wraps]` variant instead delegates straight to the wrapped field's
+    /// own `walk`, skipping its own fields entirely.
+    pub fn generate_visit(&self) -> TokenStream {
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, Binder::Ref);
+            if stmt.wraps.is_some() {
+                let binding = wraps_binding_ident();
+                quote! { #pat => #binding.walk(visitor), }
+            } else {
+                let calls = stmt
+                    .iter_all_fields()
+                    .filter_map(|field| visit_call(field, &binding_ident(field)));
+                quote! { #pat => { #(#calls)* } }
+            }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Builds a `match self { ... }` body for a generated `VisitMut`-style
+    /// trait impl: the `&mut self` counterpart of [`Structure::generate_visit`]
+    /// — every `Argument`/`Block`/`Region` field is passed to the matching
+    /// `visit_*_mut` hook on `visitor`. A `#[wraps]` variant instead delegates
+    /// straight to the wrapped field's own `walk_mut`, skipping its own fields
+    /// entirely.
+    pub fn generate_visit_mut(&self) -> TokenStream {
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, Binder::RefMut);
+            if stmt.wraps.is_some() {
+                let binding = wraps_binding_ident();
+                quote! { #pat => #binding.walk_mut(visitor), }
+            } else {
+                let calls = stmt
+                    .iter_all_fields()
+                    .filter_map(|field| visit_call_mut(field, &binding_ident(field)));
+                quote! { #pat => { #(#calls)* } }
+            }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Builds a `match self { ... }` body for a generated `Fold`-style trait
+    /// impl: every `Argument`/`Block`/`Region` field is passed through the
+    /// matching `fold_*` hook on `folder` and the variant is rebuilt from the
+    /// results; other fields pass through unchanged. A `#[wraps]` variant
+    /// instead folds the wrapped field via its own `fold_with` and rewraps it.
+    pub fn generate_fold(&self) -> TokenStream {
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, Binder::Move);
+            let wraps_value = stmt.wraps.is_some().then(|| {
+                let binding = wraps_binding_ident();
+                quote! { #binding.fold_with(folder) }
+            });
+            let ctor = self.construct(stmt, wraps_value, |field| {
+                fold_call(field, &binding_ident(field))
+            });
+            quote! { #pat => #ctor, }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Builds a `match self { ... }` body for a generated `ToValue` trait
+    /// impl: every variant becomes a `Document::Node` tagged with the
+    /// variant's name, whose fields are each field's own `to_value()`, in
+    /// declaration order. A `#[wraps]` variant's single child is the wrapped
+    /// field's own document, so its other fields (if any) are not written —
+    /// the same trade-off [`Structure::generate_visit`] makes for traversal.
+    pub fn generate_to_value(&self, crate_path: &syn::Path) -> TokenStream {
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, Binder::Ref);
+            let tag = stmt.name.to_string();
+            let fields = if stmt.wraps.is_some() {
+                let binding = wraps_binding_ident();
+                vec![quote! { #crate_path::ToValue::to_value(#binding) }]
+            } else {
+                stmt.iter_all_fields()
+                    .map(|field| {
+                        let binding = binding_ident(field);
+                        quote! { #crate_path::ToValue::to_value(#binding) }
+                    })
+                    .collect()
+            };
+            quote! {
+                #pat => #crate_path::Document::Node {
+                    tag: #tag.to_string(),
+                    fields: ::std::vec![#(#fields),*],
+                },
+            }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// The deserializing counterpart of [`Structure::generate_to_value`]:
+    /// given `tag` and `fields` unpacked from a `Document::Node`, rebuilds
+    /// whichever variant `tag` names, reconstructing each field through its
+    /// own `FromValue::from_value`. A `#[wraps]` variant is rebuilt from its
+    /// document's lone field via the wrapped field's own `FromValue`.
+    pub fn generate_from_value(&self, crate_path: &syn::Path) -> TokenStream {
+        let arms = self.variants().into_iter().map(|stmt| {
+            let tag = stmt.name.to_string();
+            if stmt.wraps.is_some() {
+                let ctor = self.construct(
+                    stmt,
+                    Some(quote! { #crate_path::FromValue::from_value(&fields[0], resolver)? }),
+                    |_field| unreachable!("a #[wraps] variant has no other fields to reconstruct"),
+                );
+                quote! { #tag => #ctor, }
+            } else {
+                let mut values = stmt.iter_all_fields().enumerate().map(|(i, _)| {
+                    quote! { #crate_path::FromValue::from_value(&fields[#i], resolver)? }
+                });
+                let ctor = self.construct(stmt, None, |_field| values.next().unwrap());
+                quote! { #tag => #ctor, }
+            }
+        });
+        quote! {
+            match tag.as_str() {
+                #(#arms)*
+                other => return ::std::result::Result::Err(
+                    #crate_path::FromValueError::UnknownVariant(other.to_string()),
+                ),
+            }
+        }
+    }
+
+    /// Builds a `match self { ... }` body for one [`Structure::generate_walk`]
+    /// method: only fields of `category` are passed to `visitor`, in
+    /// declaration order. A `#[wraps]` variant forwards to the wrapped
+    /// field's own `walk_*` method of the same name instead of inspecting its
+    /// own fields.
+    fn generate_walk_category(&self, category: FieldCategory, method: &str, mutable: bool) -> TokenStream {
+        let method = format_ident!("{method}");
+        let binder = if mutable { Binder::RefMut } else { Binder::Ref };
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, binder);
+            if stmt.wraps.is_some() {
+                let binding = wraps_binding_ident();
+                quote! { #pat => #binding.#method(visitor), }
+            } else {
+                let calls = stmt
+                    .iter_all_fields()
+                    .filter(|field| field.category() == category)
+                    .map(|field| walk_call(field, &binding_ident(field), mutable));
+                quote! { #pat => { #(#calls)* } }
+            }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Builds a `match self { ... }` body for a generated `PrettyPrint`
+    /// impl: a variant with a `#[kirin(format = "...")]` template renders
+    /// each `{name}`/`{0}` placeholder from [`parse_format_template`] with
+    /// the named (or, for tuple-style variants, positional) field's own
+    /// `Display` rendering, joined with the template's literal text. A
+    /// variant with no template falls back to the same `{:?}`-based
+    /// rendering [`Printer::print_statement_default`](kirin_prettyless) uses.
+    pub fn generate_pretty_print(&self) -> TokenStream {
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, Binder::Ref);
+            let body = match &stmt.attrs.format {
+                Some(template) => self.generate_format_body(stmt, template),
+                None => quote! { printer.arena.text(::std::format!("statement {:?}", self)) },
+            };
+            quote! { #pat => #body, }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Builds the `ArenaDoc`-producing expression for one
+    /// `#[kirin(format = "...")]` template, resolving each placeholder
+    /// against `stmt`'s own fields. An unparseable template becomes a
+    /// `compile_error!`, surfaced at the derive's call site.
+    fn generate_format_body(&self, stmt: &Statement<L>, template: &str) -> TokenStream {
+        let segments = match super::format_template::parse_format_template(template) {
+            Ok(segments) => segments,
+            Err(message) => return quote! { ::std::compile_error!(#message) },
+        };
+        if segments.is_empty() {
+            return quote! { printer.arena.nil() };
+        }
+        let pieces = segments.into_iter().map(|segment| match segment {
+            FormatSegment::Literal(text) => quote! { printer.arena.text(#text) },
+            FormatSegment::Named(name) => {
+                let field = stmt
+                    .iter_all_fields()
+                    .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name.as_str()));
+                render_placeholder(field, &name)
+            }
+            FormatSegment::Positional(index) => {
+                let field = stmt.iter_all_fields().find(|field| field.index == index);
+                render_placeholder(field, &index.to_string())
+            }
+        });
+        quote! { #(#pieces)+* }
+    }
+
+    /// Builds the five `match self { ... }` bodies for a generated `Walk`
+    /// trait impl: `walk_operands`/`walk_results`/`walk_blocks`/
+    /// `walk_successors`/`walk_regions`, each dispatching only its own
+    /// [`FieldCategory`] to `visitor`, in declaration order.
+    pub fn generate_walk(&self) -> [TokenStream; 5] {
+        [
+            self.generate_walk_category(FieldCategory::Argument, "walk_operands", false),
+            self.generate_walk_category(FieldCategory::Result, "walk_results", false),
+            self.generate_walk_category(FieldCategory::Block, "walk_blocks", false),
+            self.generate_walk_category(FieldCategory::Successor, "walk_successors", false),
+            self.generate_walk_category(FieldCategory::Region, "walk_regions", false),
+        ]
+    }
+
+    /// The `&mut self` counterpart of [`Structure::generate_walk`]: the five
+    /// match bodies for a generated `WalkMut` trait impl.
+    pub fn generate_walk_mut(&self) -> [TokenStream; 5] {
+        [
+            self.generate_walk_category(FieldCategory::Argument, "walk_operands_mut", true),
+            self.generate_walk_category(FieldCategory::Result, "walk_results_mut", true),
+            self.generate_walk_category(FieldCategory::Block, "walk_blocks_mut", true),
+            self.generate_walk_category(FieldCategory::Successor, "walk_successors_mut", true),
+            self.generate_walk_category(FieldCategory::Region, "walk_regions_mut", true),
+        ]
+    }
+
+    /// Builds a `match self { ... }` body for one [`Structure::generate_map`]
+    /// method: every field of `category` is passed through `f` and the
+    /// variant is rebuilt from the results; other fields are moved through
+    /// unchanged. A `#[wraps]` variant instead maps the wrapped field via its
+    /// own method of the same name and rewraps it.
+    fn generate_map_category(&self, category: FieldCategory, method: &str) -> TokenStream {
+        let method = format_ident!("{method}");
+        let arms = self.variants().into_iter().map(|stmt| {
+            let pat = self.arm_pattern(stmt, Binder::Move);
+            let wraps_value = stmt.wraps.is_some().then(|| {
+                let binding = wraps_binding_ident();
+                quote! { #binding.#method(f) }
+            });
+            let ctor = self.construct(stmt, wraps_value, |field| {
+                map_call(field, &binding_ident(field), category)
+            });
+            quote! { #pat => #ctor, }
+        });
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// Builds the five `match self { ... }` bodies for a generated `Map`
+    /// trait impl: `map_operands`/`map_results`/`map_blocks`/
+    /// `map_successors`/`map_regions`, each rebuilding the variant with only
+    /// its own [`FieldCategory`] passed through the supplied closure. Unlike
+    /// [`Structure::generate_walk`] (which borrows), this consumes `self` and
+    /// returns the rebuilt value — the shape SSA renaming and inlining need,
+    /// since they replace values rather than visit them.
+    pub fn generate_map(&self) -> [TokenStream; 5] {
+        [
+            self.generate_map_category(FieldCategory::Argument, "map_operands"),
+            self.generate_map_category(FieldCategory::Result, "map_results"),
+            self.generate_map_category(FieldCategory::Block, "map_blocks"),
+            self.generate_map_category(FieldCategory::Successor, "map_successors"),
+            self.generate_map_category(FieldCategory::Region, "map_regions"),
+        ]
+    }
+
+    /// Builds `is_*`/`as_*`/`as_*_mut`/`into_*` inherent methods plus
+    /// `TryFrom<Self>` conversions, one set per variant (a struct input has
+    /// nothing to discriminate, so this is a no-op for it).
+    ///
+    /// Every variant gets `is_<snake_name>(&self) -> bool`. Every variant
+    /// with at least one field (a `#[wraps]` variant counts as exactly one,
+    /// its wrapped value) also gets `as_<snake_name>`/`as_<snake_name>_mut`/
+    /// `into_<snake_name>`, uniformly across unit, tuple, and named variants:
+    /// a single-field variant's trio is typed as that one field, a
+    /// multi-field variant's is typed as a tuple of all of them (borrowed,
+    /// mutably borrowed, and owned respectively). Only the single-field case
+    /// additionally gets a `TryFrom<Self>` impl, since a multi-field variant
+    /// has no single destination type two different variants could collide
+    /// on the way [`Structure::generate_accessors`]'s single-field one
+    /// intentionally risks.
+    pub fn generate_accessors(&self, crate_path: &syn::Path) -> TokenStream {
+        if !matches!(self.input.data, Data::Enum(_)) {
+            return TokenStream::new();
+        }
+
+        let name = &self.input.name;
+        let (impl_generics, ty_generics, where_clause) = self.input.generics.split_for_impl();
+
+        let mut methods = TokenStream::new();
+        let mut conversions = TokenStream::new();
+
+        for stmt in self.variants() {
+            let snake_name = to_snake_case(stmt.name.to_string());
+            let is_ident = format_ident!("is_{snake_name}");
+            let wildcard_pat = self.wildcard_arm_pattern(stmt);
+
+            methods.extend(quote! {
+                pub fn #is_ident(&self) -> bool {
+                    match self {
+                        #wildcard_pat => true,
+                        #[allow(unreachable_patterns)]
+                        _ => false,
+                    }
+                }
+            });
+
+            let Some(fields) = self.extractable_fields(stmt, crate_path) else {
+                continue;
+            };
+
+            let as_ident = format_ident!("as_{snake_name}");
+            let as_mut_ident = format_ident!("as_{snake_name}_mut");
+            let into_ident = format_ident!("into_{snake_name}");
+            let ref_pat = self.arm_pattern(stmt, Binder::Ref);
+            let ref_mut_pat = self.arm_pattern(stmt, Binder::RefMut);
+            let move_pat = self.arm_pattern(stmt, Binder::Move);
+
+            let bindings: Vec<&syn::Ident> = fields.iter().map(|(binding, _)| binding).collect();
+            let types: Vec<&TokenStream> = fields.iter().map(|(_, ty)| ty).collect();
+
+            let (value_expr, ref_ty, mut_ty, owned_ty) = if let [(binding, ty)] = fields.as_slice() {
+                (quote! { #binding }, quote! { &#ty }, quote! { &mut #ty }, quote! { #ty })
+            } else {
+                (
+                    quote! { ( #(#bindings),* ) },
+                    quote! { ( #(&#types),* ) },
+                    quote! { ( #(&mut #types),* ) },
+                    quote! { ( #(#types),* ) },
+                )
+            };
+
+            methods.extend(quote! {
+                pub fn #as_ident(&self) -> ::std::option::Option<#ref_ty> {
+                    match self {
+                        #ref_pat => ::std::option::Option::Some(#value_expr),
+                        #[allow(unreachable_patterns)]
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                pub fn #as_mut_ident(&mut self) -> ::std::option::Option<#mut_ty> {
+                    match self {
+                        #ref_mut_pat => ::std::option::Option::Some(#value_expr),
+                        #[allow(unreachable_patterns)]
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                pub fn #into_ident(self) -> ::std::option::Option<#owned_ty> {
+                    match self {
+                        #move_pat => ::std::option::Option::Some(#value_expr),
+                        #[allow(unreachable_patterns)]
+                        _ => ::std::option::Option::None,
+                    }
+                }
+            });
+
+            let [(binding, field_ty)] = fields.as_slice() else {
+                continue;
+            };
+
+            conversions.extend(quote! {
+                #[automatically_derived]
+                impl #impl_generics ::std::convert::TryFrom<#name #ty_generics> for #field_ty #where_clause {
+                    type Error = #name #ty_generics;
+
+                    fn try_from(value: #name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                        match value {
+                            #move_pat => ::std::result::Result::Ok(#binding),
+                            #[allow(unreachable_patterns)]
+                            other => ::std::result::Result::Err(other),
+                        }
+                    }
+                }
+            });
+        }
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #methods
+            }
+
+            #conversions
+        }
+    }
+
+    /// A wildcard pattern for one variant, with no field bindings at all —
+    /// `Name::Variant(..)` or `Name::Variant { .. }` — for accessors (like
+    /// [`Structure::generate_accessors`]'s `is_*` methods) that only need to
+    /// test the discriminant.
+    fn wildcard_arm_pattern(&self, stmt: &Statement<L>) -> TokenStream {
+        let name = &self.input.name;
+        let path = match &self.input.data {
+            Data::Struct(_) => quote! { #name },
+            Data::Enum(_) => {
+                let variant_name = &stmt.name;
+                quote! { #name::#variant_name }
+            }
+        };
+        if stmt.is_tuple_style() {
+            quote! { #path(..) }
+        } else {
+            quote! { #path { .. } }
+        }
+    }
+
+    /// Every field of `stmt`, bound and typed for
+    /// [`Structure::generate_accessors`]'s `as_*`/`as_*_mut`/`into_*` trio —
+    /// a `#[wraps]` variant yields its single wrapped field, a unit variant
+    /// yields nothing (there is no value to extract), and any other variant
+    /// yields all of its fields in declaration order.
+    fn extractable_fields(&self, stmt: &Statement<L>, crate_path: &syn::Path) -> Option<Vec<(syn::Ident, TokenStream)>> {
+        if let Some(wrapper) = &stmt.wraps {
+            let ty = &wrapper.ty;
+            return Some(vec![(wraps_binding_ident(), quote! { #ty })]);
+        }
+        let fields: Vec<_> = stmt
+            .iter_all_fields()
+            .map(|field| (binding_ident(field), field_type_tokens(field, crate_path)))
+            .collect();
+        if fields.is_empty() { None } else { Some(fields) }
+    }
+
+    /// Builds a checked `new[_<variant>]`/`unchecked_new[_<variant>]` pair of
+    /// inherent constructors per variant (one unsuffixed pair for a struct
+    /// input, matching how [`Structure::generate_accessors`] only suffixes
+    /// enum methods).
+    ///
+    /// `new_*` takes the same fields as the type itself and, before
+    /// building, checks every `Argument`/`Result` field's already-assigned
+    /// type (read from `context` via `GetInfo`) is a subset of that field's
+    /// declared `#[kirin(type = ...)]` expression under the dialect's
+    /// `Lattice`, returning a `ConstructError` naming the first offending
+    /// field. `unchecked_new_*` skips this and is infallible. A `#[wraps]`
+    /// field is taken at face value, with no type check of its own — the
+    /// same trade-off [`Structure::generate_visit`] makes.
+    pub fn generate_checked_constructor(&self, crate_path: &syn::Path, dialect_ty: &syn::Path) -> TokenStream {
+        let name = &self.input.name;
+        let (impl_generics, ty_generics, where_clause) = self.input.generics.split_for_impl();
+
+        let mut methods = TokenStream::new();
+        for stmt in self.variants() {
+            let suffix = match &self.input.data {
+                Data::Struct(_) => String::new(),
+                Data::Enum(_) => format!("_{}", to_snake_case(stmt.name.to_string())),
+            };
+            let new_ident = format_ident!("new{suffix}");
+            let unchecked_new_ident = format_ident!("unchecked_new{suffix}");
+
+            let params = self.constructor_params(stmt, crate_path);
+            let checks = stmt.iter_all_fields().filter_map(|field| constructor_check(field, crate_path));
+            let wraps_ctor_value = stmt.wraps.as_ref().map(|_| {
+                let binding = wraps_binding_ident();
+                quote! { #binding }
+            });
+            let ctor = self.construct(stmt, wraps_ctor_value, |field| {
+                let ident = field.name_ident(proc_macro2::Span::call_site());
+                quote! { #ident }
+            });
+
+            methods.extend(quote! {
+                pub fn #new_ident(
+                    context: &#crate_path::Context<#dialect_ty>,
+                    #(#params),*
+                ) -> ::std::result::Result<Self, #crate_path::ConstructError> {
+                    #(#checks)*
+                    ::std::result::Result::Ok(#ctor)
+                }
+
+                pub fn #unchecked_new_ident(#(#params),*) -> Self {
+                    #ctor
+                }
+            });
+        }
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #methods
+            }
+        }
+    }
+
+    /// Builds a staged (type-state) builder type per variant, as an
+    /// alternative to [`Structure::generate_checked_constructor`]'s single
+    /// positional constructor. Each *required* field (every `Argument`/
+    /// `Result`/`Block`/`Successor`/`Region` field, plus any `Value` field
+    /// with no `#[kirin(default = ...)]`) gets its own generic type-state
+    /// parameter that flips from a "missing" marker to a "set" marker once
+    /// its setter runs, so `build()` only exists on the builder type once
+    /// every required field has been supplied. A `Value` field with a
+    /// default is pre-populated by [`DefaultValue::to_expr`] and its setter
+    /// is unconditionally available, outside the type-state. A `#[wraps]`
+    /// variant is skipped entirely: its identity is the wrapped value, not a
+    /// field list to stage.
+    pub fn generate_staged_builder(&self, crate_path: &syn::Path) -> TokenStream {
+        let name = &self.input.name;
+        let missing_ident = format_ident!("__{}BuilderMissing", name);
+        let set_ident = format_ident!("__{}BuilderSet", name);
+
+        let bodies: Vec<TokenStream> = self
+            .variants()
+            .into_iter()
+            .filter(|stmt| stmt.wraps.is_none())
+            .map(|stmt| self.staged_builder_for_variant(stmt, crate_path, &missing_ident, &set_ident))
+            .collect();
+
+        if bodies.is_empty() {
+            return TokenStream::new();
+        }
+
+        quote! {
+            #[doc(hidden)]
+            #[derive(Debug, Clone, Copy)]
+            pub struct #missing_ident;
+            #[doc(hidden)]
+            #[derive(Debug, Clone, Copy)]
+            pub struct #set_ident<T>(T);
+
+            #(#bodies)*
+        }
+    }
+
+    /// One variant's builder type, entry point (`Name::builder[_<variant>]()`),
+    /// per-required-field setter impls, the shared defaulted-field setter
+    /// impl, and the gated `build()` impl. See [`Structure::generate_staged_builder`].
+    fn staged_builder_for_variant(
+        &self,
+        stmt: &Statement<L>,
+        crate_path: &syn::Path,
+        missing_ident: &syn::Ident,
+        set_ident: &syn::Ident,
+    ) -> TokenStream {
+        let name = &self.input.name;
+        let builder_ident = match &self.input.data {
+            Data::Struct(_) => format_ident!("{}Builder", name),
+            Data::Enum(_) => format_ident!("{}{}Builder", name, stmt.name),
+        };
+
+        let fields: Vec<&FieldInfo<L>> = stmt.iter_all_fields().collect();
+        let required: Vec<&FieldInfo<L>> = fields.iter().copied().filter(|f| !f.has_default()).collect();
+        let defaulted: Vec<&FieldInfo<L>> = fields.iter().copied().filter(|f| f.has_default()).collect();
+        let state_idents: Vec<syn::Ident> = required
+            .iter()
+            .map(|field| format_ident!("__State{}", field.index))
+            .collect();
+
+        let orig_generics = &self.input.generics;
+        let orig_args = generic_arg_tokens(orig_generics);
+        let orig_where = &orig_generics.where_clause;
+        let (orig_impl_generics, orig_ty_generics, _) = orig_generics.split_for_impl();
+
+        let mut struct_generics = orig_generics.clone();
+        for state in &state_idents {
+            struct_generics
+                .params
+                .push(syn::GenericParam::Type(syn::TypeParam::from(state.clone())));
+        }
+        let (struct_impl_generics, _, _) = struct_generics.split_for_impl();
+        let all_state_args: Vec<TokenStream> = state_idents.iter().map(|state| quote! { #state }).collect();
+
+        let required_struct_fields = required.iter().zip(state_idents.iter()).map(|(field, state)| {
+            let ident = field.name_ident(proc_macro2::Span::call_site());
+            quote! { #ident: #state }
+        });
+        let defaulted_struct_fields = defaulted.iter().map(|field| {
+            let ident = field.name_ident(proc_macro2::Span::call_site());
+            let ty = field_type_tokens(field, crate_path);
+            quote! { #ident: #ty }
+        });
+        let has_marker_field = !orig_args.is_empty();
+        let marker_field = has_marker_field
+            .then(|| quote! { __kirin_marker: ::std::marker::PhantomData<(#(#orig_args),*)>, })
+            .unwrap_or_default();
+
+        let struct_def = quote! {
+            #[doc(hidden)]
+            pub struct #builder_ident #struct_impl_generics #orig_where {
+                #(#required_struct_fields,)*
+                #(#defaulted_struct_fields,)*
+                #marker_field
+            }
+        };
+
+        let all_missing: Vec<TokenStream> = state_idents.iter().map(|_| quote! { #missing_ident }).collect();
+        let entry_suffix = match &self.input.data {
+            Data::Struct(_) => String::new(),
+            Data::Enum(_) => format!("_{}", to_snake_case(stmt.name.to_string())),
+        };
+        let builder_fn_ident = format_ident!("builder{entry_suffix}");
+        let required_inits = required.iter().map(|field| {
+            let ident = field.name_ident(proc_macro2::Span::call_site());
+            quote! { #ident: #missing_ident }
+        });
+        let defaulted_inits = defaulted.iter().map(|field| {
+            let ident = field.name_ident(proc_macro2::Span::call_site());
+            let expr = field
+                .default_value()
+                .expect("a defaulted field always carries a default expression")
+                .to_expr();
+            quote! { #ident: #expr }
+        });
+        let marker_init = has_marker_field
+            .then(|| quote! { __kirin_marker: ::std::marker::PhantomData, })
+            .unwrap_or_default();
+
+        let entry_point = quote! {
+            #[automatically_derived]
+            impl #orig_impl_generics #name #orig_ty_generics #orig_where {
+                pub fn #builder_fn_ident() -> #builder_ident<#(#orig_args,)* #(#all_missing),*> {
+                    #builder_ident {
+                        #(#required_inits,)*
+                        #(#defaulted_inits,)*
+                        #marker_init
+                    }
+                }
+            }
+        };
+
+        let setters: Vec<TokenStream> = required
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                self.staged_builder_setter(
+                    field,
+                    i,
+                    &builder_ident,
+                    &orig_args,
+                    &state_idents,
+                    missing_ident,
+                    set_ident,
+                    crate_path,
+                )
+            })
+            .collect();
+
+        let defaulted_setters = (!defaulted.is_empty()).then(|| {
+            let all_args: Vec<TokenStream> = orig_args.iter().cloned().chain(all_state_args.iter().cloned()).collect();
+            let methods = defaulted
+                .iter()
+                .map(|field| staged_builder_field_setter_body(field, crate_path));
+            quote! {
+                #[automatically_derived]
+                impl #struct_impl_generics #builder_ident<#(#all_args),*> {
+                    #(#methods)*
+                }
+            }
+        })
+        .unwrap_or_default();
+
+        let all_set_args: Vec<TokenStream> = required
+            .iter()
+            .map(|field| {
+                let ty = field_type_tokens(field, crate_path);
+                quote! { #set_ident<#ty> }
+            })
+            .collect();
+        let build_args: Vec<TokenStream> = orig_args.iter().cloned().chain(all_set_args).collect();
+        let is_tuple = stmt.is_tuple_style();
+        let ctor_builder = match &self.input.data {
+            Data::Struct(_) => crate::codegen::ConstructorBuilder::new_struct(name, is_tuple),
+            Data::Enum(_) => crate::codegen::ConstructorBuilder::new_variant(name, &stmt.name, is_tuple),
+        };
+        let owned_fields: Vec<FieldInfo<L>> = fields.into_iter().cloned().collect();
+        let build_expr = ctor_builder.build(&owned_fields, |field| {
+            let ident = field.name_ident(proc_macro2::Span::call_site());
+            if field.has_default() {
+                quote! { self.#ident }
+            } else {
+                quote! { self.#ident.0 }
+            }
+        });
+
+        let build_method = quote! {
+            #[automatically_derived]
+            impl #orig_impl_generics #builder_ident<#(#build_args),*> {
+                pub fn build(self) -> #name #orig_ty_generics {
+                    #build_expr
+                }
+            }
+        };
+
+        quote! {
+            #struct_def
+            #entry_point
+            #(#setters)*
+            #defaulted_setters
+            #build_method
+        }
+    }
+
+    /// One required field's setter: available only while that field's own
+    /// type-state is `Missing`, switching it to `Set<FieldTy>` in the return
+    /// type via struct-update syntax (`..self`) for every other field. The
+    /// parameter honors `#[kirin(into)]` (`impl Into<FieldTy>`) and
+    /// `#[kirin(builder(type = ..., build = ...))]` the same way
+    /// [`Structure::generate_checked_constructor`]'s sibling codegen doesn't
+    /// need to (that one takes every field positionally, unconditionally).
+    #[allow(clippy::too_many_arguments)]
+    fn staged_builder_setter(
+        &self,
+        field: &FieldInfo<L>,
+        index: usize,
+        builder_ident: &syn::Ident,
+        orig_args: &[TokenStream],
+        state_idents: &[syn::Ident],
+        missing_ident: &syn::Ident,
+        set_ident: &syn::Ident,
+        crate_path: &syn::Path,
+    ) -> TokenStream {
+        let field_ty = field_type_tokens(field, crate_path);
+        let param_ident = field.name_ident(proc_macro2::Span::call_site());
+        let (param_ty, value_expr) = staged_builder_setter_signature(field, &field_ty, &param_ident);
+
+        let before_args: Vec<TokenStream> = orig_args
+            .iter()
+            .cloned()
+            .chain(state_idents.iter().enumerate().map(|(i, state)| {
+                if i == index {
+                    quote! { #missing_ident }
+                } else {
+                    quote! { #state }
+                }
+            }))
+            .collect();
+        let after_args: Vec<TokenStream> = orig_args
+            .iter()
+            .cloned()
+            .chain(state_idents.iter().enumerate().map(|(i, state)| {
+                if i == index {
+                    quote! { #set_ident<#field_ty> }
+                } else {
+                    quote! { #state }
+                }
+            }))
+            .collect();
+        let other_state_idents = state_idents.iter().enumerate().filter_map(|(i, state)| (i != index).then_some(state));
+        let mut impl_generics = self.input.generics.clone();
+        for state in other_state_idents {
+            impl_generics
+                .params
+                .push(syn::GenericParam::Type(syn::TypeParam::from(state.clone())));
+        }
+        let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #builder_ident<#(#before_args),*> {
+                pub fn #param_ident(self, #param_ident: #param_ty) -> #builder_ident<#(#after_args),*> {
+                    #builder_ident {
+                        #param_ident: #set_ident(#value_expr),
+                        ..self
+                    }
+                }
+            }
+        }
+    }
+
+    /// The parameter list for [`Structure::generate_checked_constructor`]'s
+    /// `new_*`/`unchecked_new_*` pair: one `<name>: <type>` per field in
+    /// declaration order, `#[wraps]` field included.
+    fn constructor_params(&self, stmt: &Statement<L>, crate_path: &syn::Path) -> Vec<TokenStream> {
+        let mut params: Vec<(usize, TokenStream)> = stmt
+            .iter_all_fields()
+            .map(|field| {
+                let ident = field.name_ident(proc_macro2::Span::call_site());
+                let ty = field_type_tokens(field, crate_path);
+                (field.index, quote! { #ident: #ty })
+            })
+            .collect();
+        if let Some(wrapper) = &stmt.wraps {
+            let binding = wraps_binding_ident();
+            let ty = &wrapper.ty;
+            params.push((wrapper.field.index, quote! { #binding: #ty }));
+        }
+        params.sort_by_key(|(index, _)| *index);
+        params.into_iter().map(|(_, tokens)| tokens).collect()
+    }
+}
+
+/// How a field is bound in a generated match arm pattern.
+#[derive(Clone, Copy)]
+enum Binder {
+    /// `ref`, for `&self`-style traversal (e.g. [`Structure::generate_visit`]).
+    Ref,
+    /// `ref mut`, for in-place mutation.
+    RefMut,
+    /// No binding mode keyword: the field is moved out of `self` (e.g.
+    /// [`Structure::generate_fold`], which consumes and rebuilds `self`).
+    Move,
+}
+
+impl Binder {
+    fn tokens(self) -> TokenStream {
+        match self {
+            Binder::Ref => quote! { ref },
+            Binder::RefMut => quote! { ref mut },
+            Binder::Move => TokenStream::new(),
+        }
+    }
+}
+
+fn binding_ident<L: Layout>(field: &FieldInfo<L>) -> syn::Ident {
+    format_ident!("__kirin_binding_{}", field.index)
+}
+
+/// The binding identifier for a variant's `#[wraps]` field, which is excluded
+/// from `stmt.iter_all_fields()` and so needs a binding of its own.
+fn wraps_binding_ident() -> syn::Ident {
+    format_ident!("__kirin_wraps_binding")
+}
+
+/// The Rust type of one field, for [`Structure::generate_accessors`]'s
+/// `as_*`/`as_*_mut`/`TryFrom` methods: `#crate_path::SSAValue`/`ResultValue`/
+/// `Block`/`Successor`/`Region` for those categories (matching the types
+/// [`crate::kirin::walk`] already generates against), or the field's own
+/// declared Rust type for `Value`, wrapped in `Vec<...>`/`Option<...>`
+/// per its [`Collection`].
+fn field_type_tokens<L: Layout>(field: &FieldInfo<L>, crate_path: &syn::Path) -> TokenStream {
+    let base = match field.category() {
+        FieldCategory::Argument => quote! { #crate_path::SSAValue },
+        FieldCategory::Result => quote! { #crate_path::ResultValue },
+        FieldCategory::Block => quote! { #crate_path::Block },
+        FieldCategory::Successor => quote! { #crate_path::Successor },
+        FieldCategory::Region => quote! { #crate_path::Region },
+        FieldCategory::Value => {
+            let ty = field.value_type().expect("a Value field always carries its own type");
+            quote! { #ty }
+        }
+    };
+    field.collection.wrap_type(base)
+}
+
+/// The generic arguments `generics` itself would be instantiated with (just
+/// the bare idents/lifetimes, no bounds) — e.g. `<'a, T, const N: usize>`
+/// becomes `['a, T, N]`. Used by [`Structure::generate_staged_builder`] to
+/// carry the original type's generics through to its builder type, which
+/// needs the plain argument list in several spots a `syn::Generics`'s own
+/// `split_for_impl` doesn't directly hand back (the `ty_generics` half still
+/// carries the angle brackets and is meant for one specific splice point).
+fn generic_arg_tokens(generics: &syn::Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(lt) => {
+                let lt = &lt.lifetime;
+                quote! { #lt }
+            }
+            syn::GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                quote! { #ident }
+            }
+            syn::GenericParam::Const(c) => {
+                let ident = &c.ident;
+                quote! { #ident }
+            }
+        })
+        .collect()
+}
+
+/// The setter parameter type and the expression it's stored as, for one
+/// required field of [`Structure::generate_staged_builder`]: a
+/// `#[kirin(builder(build = ...))]` expression wins outright, taking a
+/// parameter of the `builder(type = ...)` type if one is given and of the
+/// field's own type otherwise; a `builder(type = ...)` alone sets the
+/// parameter type and stores it as-is, or `.into()`-converted under
+/// `#[kirin(into)]`; absent both, `#[kirin(into)]` widens the parameter to
+/// `impl Into<FieldTy>` and converts on store; absent all three, the field's
+/// own type is taken verbatim.
+fn staged_builder_setter_signature<L: Layout>(
+    field: &FieldInfo<L>,
+    field_ty: &TokenStream,
+    param_ident: &syn::Ident,
+) -> (TokenStream, TokenStream) {
+    if let Some(build) = field.builder_build_expr() {
+        let ty = field
+            .builder_type()
+            .map(|ty| quote! { #ty })
+            .unwrap_or_else(|| field_ty.clone());
+        (ty, quote! { #build })
+    } else if let Some(ty) = field.builder_type() {
+        if field.has_into() {
+            (quote! { #ty }, quote! { #param_ident.into() })
+        } else {
+            (quote! { #ty }, quote! { #param_ident })
+        }
+    } else if field.has_into() {
+        (quote! { impl ::std::convert::Into<#field_ty> }, quote! { #param_ident.into() })
+    } else {
+        (field_ty.clone(), quote! { #param_ident })
+    }
+}
+
+/// One defaulted field's always-available setter, for the shared impl block
+/// [`Structure::generate_staged_builder`] emits outside the type-state.
+/// Honors `#[kirin(into)]`/`#[kirin(builder(...))]` the same way
+/// [`staged_builder_setter_signature`] does for required fields.
+fn staged_builder_field_setter_body<L: Layout>(field: &FieldInfo<L>, crate_path: &syn::Path) -> TokenStream {
+    let field_ty = field_type_tokens(field, crate_path);
+    let param_ident = field.name_ident(proc_macro2::Span::call_site());
+    let (param_ty, value_expr) = staged_builder_setter_signature(field, &field_ty, &param_ident);
+    quote! {
+        pub fn #param_ident(mut self, #param_ident: #param_ty) -> Self {
+            self.#param_ident = #value_expr;
+            self
+        }
+    }
+}
+
+/// The type-check [`Structure::generate_checked_constructor`]'s `new_*` runs
+/// against one parameter before constructing, or `None` for field kinds that
+/// carry no `#[kirin(type = ...)]` expression (`Block`/`Successor`/`Region`/
+/// `Value`). A `Vec`/`Option` field checks each item it actually holds.
+fn constructor_check<L: Layout>(field: &FieldInfo<L>, crate_path: &syn::Path) -> Option<TokenStream> {
+    let expected = field.ssa_type()?;
+    let ident = field.name_ident(proc_macro2::Span::call_site());
+    let field_name = ident.to_string();
+    // `item` must already be a `&SSAValue`/`&ResultValue` expression.
+    let check_one = |item: TokenStream| {
+        quote! {
+            if !#crate_path::GetInfo::expect_info(#item, context).ty().is_subseteq(&(#expected)) {
+                return ::std::result::Result::Err(#crate_path::ConstructError {
+                    field: #field_name,
+                    message: ::std::string::String::from(
+                        "argument's type is not a subset of the field's declared type",
+                    ),
+                });
+            }
+        }
+    };
+    Some(match field.collection {
+        Collection::Single => check_one(quote! { &#ident }),
+        Collection::Vec => {
+            let check = check_one(quote! { __kirin_item });
+            quote! { for __kirin_item in #ident.iter() { #check } }
+        }
+        Collection::Option => {
+            let check = check_one(quote! { __kirin_item });
+            quote! { if let Some(__kirin_item) = &#ident { #check } }
+        }
+    })
+}
+
+/// The `visit_*` call for one field, or `None` for kinds [`Structure::generate_visit`]
+/// doesn't hook (`Result`/`Successor`/`Value`).
+fn visit_call<L: Layout>(field: &FieldInfo<L>, binding: &syn::Ident) -> Option<TokenStream> {
+    let method = match field.category() {
+        FieldCategory::Argument => format_ident!("visit_ssa_value"),
+        FieldCategory::Block => format_ident!("visit_block"),
+        FieldCategory::Region => format_ident!("visit_region"),
+        _ => return None,
+    };
+    Some(match field.collection {
+        Collection::Single => quote! { visitor.#method(#binding); },
+        Collection::Vec => {
+            quote! { for __kirin_item in #binding.iter() { visitor.#method(__kirin_item); } }
+        }
+        Collection::Option => {
+            quote! { if let Some(__kirin_item) = #binding { visitor.#method(__kirin_item); } }
+        }
+    })
+}
+
+/// The `visit_*_mut` call for one field, or `None` for kinds
+/// [`Structure::generate_visit_mut`] doesn't hook (`Result`/`Successor`/`Value`).
+fn visit_call_mut<L: Layout>(field: &FieldInfo<L>, binding: &syn::Ident) -> Option<TokenStream> {
+    let method = match field.category() {
+        FieldCategory::Argument => format_ident!("visit_ssa_value_mut"),
+        FieldCategory::Block => format_ident!("visit_block_mut"),
+        FieldCategory::Region => format_ident!("visit_region_mut"),
+        _ => return None,
+    };
+    Some(match field.collection {
+        Collection::Single => quote! { visitor.#method(#binding); },
+        Collection::Vec => {
+            quote! { for __kirin_item in #binding.iter_mut() { visitor.#method(__kirin_item); } }
+        }
+        Collection::Option => {
+            quote! { if let Some(__kirin_item) = #binding { visitor.#method(__kirin_item); } }
+        }
+    })
+}
+
+/// The `visitor` call for one field already known to match the category
+/// [`Structure::generate_walk_category`] is emitting for: a scalar field is a
+/// single callback, a `Vec<T>` field loops, and an `Option<T>` field is a
+/// zero-or-one call.
+fn walk_call<L: Layout>(field: &FieldInfo<L>, binding: &syn::Ident, mutable: bool) -> TokenStream {
+    if mutable {
+        match field.collection {
+            Collection::Single => quote! { visitor(#binding); },
+            Collection::Vec => quote! { for __kirin_item in #binding.iter_mut() { visitor(__kirin_item); } },
+            Collection::Option => {
+                quote! { if let Some(__kirin_item) = #binding { visitor(__kirin_item); } }
+            }
+        }
+    } else {
+        match field.collection {
+            Collection::Single => quote! { visitor(#binding); },
+            Collection::Vec => quote! { for __kirin_item in #binding.iter() { visitor(__kirin_item); } },
+            Collection::Option => {
+                quote! { if let Some(__kirin_item) = #binding { visitor(__kirin_item); } }
+            }
+        }
+    }
+}
+
+/// The rebuilt value for one field already known to belong to the category
+/// [`Structure::generate_map_category`] is emitting for: a scalar field is
+/// replaced by `f(binding)` directly, a `Vec<T>` field maps every element,
+/// and an `Option<T>` field maps its contained value if present. A field of
+/// any other category is moved through unchanged.
+fn map_call<L: Layout>(field: &FieldInfo<L>, binding: &syn::Ident, category: FieldCategory) -> TokenStream {
+    if field.category() != category {
+        return quote! { #binding };
+    }
+    match field.collection {
+        Collection::Single => quote! { f(#binding) },
+        Collection::Vec => quote! { #binding.into_iter().map(|__kirin_item| f(__kirin_item)).collect() },
+        Collection::Option => quote! { #binding.map(|__kirin_item| f(__kirin_item)) },
+    }
+}
+
+/// The rebuilt value for one field: run through the matching `fold_*` hook,
+/// or passed through unchanged for kinds [`Structure::generate_fold`] doesn't
+/// hook (`Result`/`Successor`/`Value`).
+fn fold_call<L: Layout>(field: &FieldInfo<L>, binding: &syn::Ident) -> TokenStream {
+    let method = match field.category() {
+        FieldCategory::Argument => format_ident!("fold_ssa_value"),
+        FieldCategory::Block => format_ident!("fold_block"),
+        FieldCategory::Region => format_ident!("fold_region"),
+        _ => return quote! { #binding },
+    };
+    match field.collection {
+        Collection::Single => quote! { folder.#method(#binding) },
+        Collection::Vec => {
+            quote! { #binding.into_iter().map(|__kirin_item| folder.#method(__kirin_item)).collect() }
+        }
+        Collection::Option => {
+            quote! { #binding.map(|__kirin_item| folder.#method(__kirin_item)) }
+        }
+    }
+}
+
+/// The `ArenaDoc`-producing expression for one resolved format placeholder.
+/// Every field category prints through its own `Display` (operands/results
+/// render as their SSA name, blocks/successors as their reference label,
+/// scalar fields as whatever `Display` their type provides); a `Region`
+/// field has no single-line label, so it renders via `Debug` instead. A
+/// placeholder that didn't resolve to any field becomes a `compile_error!`
+/// naming the bad index/identifier, surfaced at the derive's call site.
+fn render_placeholder<L: Layout>(field: Option<&FieldInfo<L>>, label: &str) -> TokenStream {
+    let Some(field) = field else {
+        let message = format!("format placeholder `{{{label}}}` does not match any field");
+        return quote! { ::std::compile_error!(#message) };
+    };
+    let binding = binding_ident(field);
+    match field.category() {
+        FieldCategory::Region => quote! { printer.arena.text(::std::format!("{:?}", #binding)) },
+        _ => quote! { printer.arena.text(::std::format!("{}", #binding)) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::StandardLayout;
+
+    fn parse(input: proc_macro2::TokenStream) -> Input<StandardLayout> {
+        let input: syn::DeriveInput = syn::parse2(input).expect("Failed to parse input");
+        Input::from_derive_input(&input).expect("Failed to create Input")
+    }
+
+    #[test]
+    fn test_each_struct() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+                #[kirin(type = "T")]
+                res: ResultValue,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let mut visited = Vec::new();
+        let body = structure.each(false, |binding| {
+            visited.push(binding.field.index);
+            quote::quote! {}
+        });
+
+        assert_eq!(visited, vec![0, 1]);
+        let rendered = body.to_string();
+        assert!(rendered.contains("__kirin_binding_0"));
+        assert!(rendered.contains("__kirin_binding_1"));
+    }
+
+    #[test]
+    fn test_each_enum_visits_every_variant() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                VariantA {
+                    #[kirin(type = "T")]
+                    arg: SSAValue,
+                },
+                VariantB(#[kirin(type = "T")] ResultValue),
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let mut seen_variants = Vec::new();
+        structure.each(false, |binding| {
+            seen_variants.push(binding.variant.name.to_string());
+            quote::quote! {}
+        });
+
+        assert_eq!(seen_variants, vec!["VariantA", "VariantB"]);
+    }
+
+    #[test]
+    fn test_fold_accumulates_across_fields() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                a: SSAValue,
+                #[kirin(type = "T")]
+                b: SSAValue,
+                #[kirin(type = "T")]
+                c: SSAValue,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let mut count = 0;
+        structure.fold(
+            false,
+            || quote::quote! { 0 },
+            |acc, _binding| {
+                count += 1;
+                quote::quote! { #acc + 1 }
+            },
+        );
+
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_arm_pattern_binds_wraps_field_at_its_own_position() {
+        // The wrapped field sits at tuple position 0, with a plain field
+        // after it at position 1; the plain field must bind to position 1,
+        // not be shifted into the wrapper's slot.
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                MultiField(#[wraps] InnerType, String),
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_visit().to_string();
+        assert!(rendered.contains("__kirin_wraps_binding"));
+        assert!(rendered.contains("MyEnum :: MultiField (ref __kirin_wraps_binding , ref __kirin_binding_1)"));
+    }
+
+    #[test]
+    fn test_generate_visit_dispatches_by_category() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+                block: Block,
+                region: Region,
+                value: String,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_visit().to_string();
+        assert!(rendered.contains("visit_ssa_value"));
+        assert!(rendered.contains("visit_block"));
+        assert!(rendered.contains("visit_region"));
+        // `value` isn't a hooked category and must be left alone.
+        assert!(!rendered.contains("visit_value"));
+    }
+
+    #[test]
+    fn test_generate_visit_wrapper_delegates() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                #[wraps]
+                WrapperVariant(InnerType),
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_visit().to_string();
+        assert!(rendered.contains("__kirin_wraps_binding . walk (visitor)"));
+    }
+
+    #[test]
+    fn test_generate_visit_mut_dispatches_by_category() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+                block: Block,
+                region: Region,
+                value: String,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_visit_mut().to_string();
+        assert!(rendered.contains("visit_ssa_value_mut"));
+        assert!(rendered.contains("visit_block_mut"));
+        assert!(rendered.contains("visit_region_mut"));
+        assert!(!rendered.contains("visit_value_mut"));
+    }
+
+    #[test]
+    fn test_generate_visit_mut_wrapper_delegates() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                #[wraps]
+                WrapperVariant(InnerType),
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_visit_mut().to_string();
+        assert!(rendered.contains("__kirin_wraps_binding . walk_mut (visitor)"));
+    }
+
+    #[test]
+    fn test_generate_fold_rebuilds_variant() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+                value: String,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_fold().to_string();
+        assert!(rendered.contains("fold_ssa_value"));
+        assert!(rendered.contains("MyStmt"));
+        // The non-hooked field passes through unchanged.
+        assert!(rendered.contains("value : __kirin_binding_1"));
+    }
+
+    #[test]
+    fn test_generate_fold_wrapper_rewraps() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                #[wraps]
+                WrapperVariant(InnerType),
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let rendered = structure.generate_fold().to_string();
+        assert!(rendered.contains("__kirin_wraps_binding . fold_with (folder)"));
+        assert!(rendered.contains("MyEnum :: WrapperVariant"));
+    }
+
+    #[test]
+    fn test_generate_map_dispatches_by_category() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+                block: Block,
+                region: Region,
+                value: String,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let [operands, _results, blocks, _successors, regions] = structure.generate_map();
+        let operands = operands.to_string();
+        let blocks = blocks.to_string();
+        let regions = regions.to_string();
+
+        // The `arg` field is mapped when generating `map_operands`...
+        assert!(operands.contains("f (__kirin_binding_0)"));
+        // ...but passed through unchanged for every other category.
+        assert!(blocks.contains("__kirin_binding_0"));
+        assert!(!blocks.contains("f (__kirin_binding_0)"));
+        assert!(regions.contains("f (__kirin_binding_2)"));
+        // The non-hooked `value` field is never mapped.
+        assert!(!operands.contains("f (__kirin_binding_3)"));
+    }
+
+    #[test]
+    fn test_generate_map_wrapper_delegates() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                #[wraps]
+                WrapperVariant(InnerType),
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let [operands, ..] = structure.generate_map();
+        let rendered = operands.to_string();
+        assert!(rendered.contains("__kirin_wraps_binding . map_operands (f)"));
+        assert!(rendered.contains("MyEnum :: WrapperVariant"));
+    }
+
+    #[test]
+    fn test_generate_map_vec_field_maps_each_element() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                args: Vec<SSAValue>,
+            }
+        });
+        let structure = Structure::new(&input);
+
+        let [operands, ..] = structure.generate_map();
+        let rendered = operands.to_string();
+        assert!(rendered.contains(". into_iter () . map"));
+        assert!(rendered.contains(". collect ()"));
+    }
+
+    #[test]
+    fn test_generate_accessors_struct_is_empty() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        assert!(structure.generate_accessors(&crate_path).is_empty());
+    }
+
+    #[test]
+    fn test_generate_accessors_single_field_variant_gets_as_and_try_from() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                VariantA {
+                    #[kirin(type = "T")]
+                    arg: SSAValue,
+                },
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_accessors(&crate_path).to_string();
+        assert!(rendered.contains("fn is_variant_a (& self) -> bool"));
+        assert!(rendered.contains("fn as_variant_a (& self) -> :: std :: option :: Option < & kirin :: SSAValue >"));
+        assert!(rendered.contains("fn as_variant_a_mut (& mut self)"));
+        assert!(rendered.contains("fn into_variant_a (self) -> :: std :: option :: Option < kirin :: SSAValue >"));
+        assert!(rendered.contains("TryFrom < MyEnum > for kirin :: SSAValue"));
+    }
+
+    #[test]
+    fn test_generate_accessors_wraps_variant_delegates_despite_sibling_field() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                WrapperVariant(#[wraps] InnerType, String),
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_accessors(&crate_path).to_string();
+        assert!(rendered.contains("fn as_wrapper_variant (& self) -> :: std :: option :: Option < & InnerType >"));
+        assert!(rendered.contains("fn into_wrapper_variant (self) -> :: std :: option :: Option < InnerType >"));
+        assert!(rendered.contains("TryFrom < MyEnum > for InnerType"));
+    }
+
+    #[test]
+    fn test_generate_accessors_multi_field_variant_gets_tuple_accessors_but_no_try_from() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                VariantA {
+                    #[kirin(type = "T")]
+                    a: SSAValue,
+                    #[kirin(type = "T")]
+                    b: ResultValue,
+                },
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_accessors(&crate_path).to_string();
+        assert!(rendered.contains("fn is_variant_a (& self) -> bool"));
+        assert!(rendered.contains("fn as_variant_a (& self) -> :: std :: option :: Option <"));
+        assert!(rendered.contains("fn as_variant_a_mut (& mut self)"));
+        assert!(rendered.contains("fn into_variant_a (self) -> :: std :: option :: Option <"));
+        // both fields show up, borrowed in as_*/as_*_mut and owned in into_*
+        assert!(rendered.contains("& kirin :: SSAValue"));
+        assert!(rendered.contains("& kirin :: ResultValue"));
+        assert!(rendered.contains("__kirin_binding_0"));
+        assert!(rendered.contains("__kirin_binding_1"));
+        assert!(!rendered.contains("TryFrom"));
+    }
+
+    #[test]
+    fn test_generate_accessors_unit_variant_gets_only_is() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                VariantA,
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_accessors(&crate_path).to_string();
+        assert!(rendered.contains("fn is_variant_a (& self) -> bool"));
+        assert!(!rendered.contains("fn as_variant_a"));
+        assert!(!rendered.contains("fn into_variant_a"));
+        assert!(!rendered.contains("TryFrom"));
+    }
+
+    #[test]
+    fn test_generate_checked_constructor_struct_is_unsuffixed() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct MyStmt {
+                #[kirin(type = "T")]
+                arg: SSAValue,
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+        let dialect_ty: syn::Path = syn::parse_quote! { MyDialect };
+
+        let rendered = structure
+            .generate_checked_constructor(&crate_path, &dialect_ty)
+            .to_string();
+        assert!(rendered.contains("fn new (context : & kirin :: Context < MyDialect >"));
+        assert!(rendered.contains("fn unchecked_new (arg : kirin :: SSAValue)"));
+        assert!(rendered.contains("is_subseteq"));
+        assert!(rendered.contains("kirin :: ConstructError"));
+    }
+
+    #[test]
+    fn test_generate_checked_constructor_enum_suffixes_by_variant() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                VariantA {
+                    #[kirin(type = "T")]
+                    arg: SSAValue,
+                },
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+        let dialect_ty: syn::Path = syn::parse_quote! { MyDialect };
+
+        let rendered = structure
+            .generate_checked_constructor(&crate_path, &dialect_ty)
+            .to_string();
+        assert!(rendered.contains("fn new_variant_a"));
+        assert!(rendered.contains("fn unchecked_new_variant_a"));
+    }
+
+    #[test]
+    fn test_generate_checked_constructor_wraps_variant_skips_type_check() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                WrapperVariant(#[wraps] InnerType),
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+        let dialect_ty: syn::Path = syn::parse_quote! { MyDialect };
+
+        let rendered = structure
+            .generate_checked_constructor(&crate_path, &dialect_ty)
+            .to_string();
+        assert!(rendered.contains("fn new_wrapper_variant"));
+        assert!(!rendered.contains("is_subseteq"));
+    }
+
+    #[test]
+    fn test_generate_staged_builder_struct_has_entry_point_and_build() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct Constant {
+                #[kirin(type = "T")]
+                result: ResultValue,
+                #[kirin(into)]
+                value: String,
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_staged_builder(&crate_path).to_string();
+        assert!(rendered.contains("struct ConstantBuilder"));
+        assert!(rendered.contains("fn builder ()"));
+        assert!(rendered.contains("fn result (self"));
+        assert!(rendered.contains("fn value (self"));
+        assert!(rendered.contains("impl :: std :: convert :: Into < String >"));
+        assert!(rendered.contains("fn build (self) -> Constant"));
+    }
+
+    #[test]
+    fn test_generate_staged_builder_defaulted_field_setter_is_ungated() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            struct Constant {
+                #[kirin(type = "T")]
+                result: ResultValue,
+                #[kirin(default)]
+                flag: bool,
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_staged_builder(&crate_path).to_string();
+        // The defaulted field is pre-populated at `builder()` time...
+        assert!(rendered.contains("flag : :: core :: default :: Default :: default ()"));
+        // ...and its setter is available in a single impl generic over every
+        // remaining type-state parameter, not gated behind one of its own.
+        assert!(rendered.contains("fn flag (mut self"));
+        // Only `result` is required, so there is exactly one state parameter.
+        assert!(rendered.contains("__State0"));
+        assert!(!rendered.contains("__State1"));
+    }
+
+    #[test]
+    fn test_generate_staged_builder_enum_suffixes_by_variant() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                VariantA {
+                    #[kirin(type = "T")]
+                    arg: SSAValue,
+                },
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        let rendered = structure.generate_staged_builder(&crate_path).to_string();
+        assert!(rendered.contains("struct MyEnumVariantABuilder"));
+        assert!(rendered.contains("fn builder_variant_a ()"));
+    }
+
+    #[test]
+    fn test_generate_staged_builder_wraps_variant_skipped() {
+        let input = parse(quote::quote! {
+            #[kirin(type_lattice = MyLattice)]
+            enum MyEnum {
+                #[wraps]
+                WrapperVariant(InnerType),
+            }
+        });
+        let structure = Structure::new(&input);
+        let crate_path: syn::Path = syn::parse_quote! { kirin };
+
+        assert!(structure.generate_staged_builder(&crate_path).is_empty());
+    }
+}
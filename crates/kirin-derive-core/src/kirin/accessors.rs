@@ -0,0 +1,24 @@
+//! `#[kirin(accessors)]`: generates `is_*`/`as_*`/`as_*_mut`/`into_*` inherent
+//! methods and `TryFrom<Self>` conversions for a dialect enum's variants.
+//!
+//! This is opt-in, the same way `#[kirin(serialize)]` (see
+//! [`crate::kirin::serialize`]) is: a dialect author adds `#[kirin(accessors)]`
+//! to the derive input to get `node.is_add()`/`node.as_add()`/
+//! `node.into_add()`/`Add::try_from(node)`-style ergonomics without
+//! hand-writing one `match` per variant. A multi-field variant gets the same
+//! `as_*`/`as_*_mut`/`into_*` trio typed as a tuple of its fields instead of
+//! just `is_*`. The actual per-variant method/impl bodies are built by
+//! [`Structure::generate_accessors`] — this module only wires that codegen in.
+
+use proc_macro2::TokenStream;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate the `is_*`/`as_*`/`as_*_mut`/`into_*` methods and `TryFrom<Self>`
+/// impls for `input`'s type. `dialect_ty` is accepted for consistency with
+/// the other `kirin::*::generate` entry points, but isn't needed here: these
+/// are plain inherent methods and free-standing conversions, not a
+/// dialect-generic trait impl.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, _dialect_ty: &syn::Path) -> TokenStream {
+    Structure::new(input).generate_accessors(crate_path)
+}
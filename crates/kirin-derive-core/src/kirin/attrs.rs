@@ -0,0 +1,97 @@
+//! Parsed `#[kirin(...)]` attributes for the smaller property/field-iterator
+//! derive family (`crate::kirin::property`, `crate::kirin::field`) -- a
+//! leaner sibling of [`crate::ir::attrs`]'s statement/builder-oriented
+//! options, since these derives only need a type-lattice path, the boolean
+//! property flags, and (since chunk112-5) keyed effect contributions for
+//! value-typed properties.
+
+use darling::{FromDeriveInput, FromField, FromMeta, FromVariant};
+
+/// Struct-level `#[kirin(...)]` options.
+///
+/// # Attributes
+///
+/// - `crate = <Path>` -- override the default IR crate path.
+/// - `type_lattice = <Path>` -- the dialect's type lattice, if this struct
+///   needs one (most property-only derives don't).
+/// - `constant` / `pure` / `terminator` / `speculatable` -- the bare
+///   boolean-property flags `boolean_property!` reads.
+/// - `effect(reads = <expr>, writes = <expr>)` -- this type's own
+///   contribution to a value-typed [`LatticeProperty`](super::property::LatticeProperty),
+///   e.g. the resources it reads from or writes to.
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(attributes(kirin), supports(struct_any))]
+pub struct KirinStructOptions {
+    #[darling(rename = "crate")]
+    pub crate_path: Option<syn::Path>,
+    pub type_lattice: Option<syn::Path>,
+    #[darling(default)]
+    pub constant: bool,
+    #[darling(default)]
+    pub pure: bool,
+    #[darling(default)]
+    pub terminator: bool,
+    #[darling(default)]
+    pub speculatable: bool,
+    pub effect: Option<EffectOptions>,
+}
+
+/// Enum-level `#[kirin(...)]` options -- the same shape as
+/// [`KirinStructOptions`], read as the enum-wide seed a non-wrapper
+/// variant's own [`KirinVariantOptions`] combines with.
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(attributes(kirin), supports(enum_any))]
+pub struct KirinEnumOptions {
+    #[darling(rename = "crate")]
+    pub crate_path: Option<syn::Path>,
+    pub type_lattice: Option<syn::Path>,
+    #[darling(default)]
+    pub constant: bool,
+    #[darling(default)]
+    pub pure: bool,
+    #[darling(default)]
+    pub terminator: bool,
+    #[darling(default)]
+    pub speculatable: bool,
+    pub effect: Option<EffectOptions>,
+}
+
+/// Per-variant `#[kirin(...)]` options.
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(kirin))]
+pub struct KirinVariantOptions {
+    pub format: Option<String>,
+    #[darling(default)]
+    pub constant: bool,
+    #[darling(default)]
+    pub pure: bool,
+    #[darling(default)]
+    pub terminator: bool,
+    #[darling(default)]
+    pub speculatable: bool,
+    pub effect: Option<EffectOptions>,
+}
+
+/// Per-field `#[kirin(...)]` options.
+#[derive(Debug, Clone, FromField)]
+#[darling(attributes(kirin))]
+pub struct KirinFieldOptions {
+    #[darling(default)]
+    pub into: bool,
+    /// This field's own contribution to a value-typed
+    /// [`LatticeProperty`](super::property::LatticeProperty), folded into
+    /// its owning statement's value before that statement joins its
+    /// siblings' (e.g. an operand field declaring the resource it reads).
+    pub effect: Option<EffectOptions>,
+}
+
+/// Parsed `#[kirin(effect(reads = ..., writes = ...))]`: a struct's,
+/// variant's, or field's declared contribution to a value-typed
+/// [`LatticeProperty`](super::property::LatticeProperty) -- e.g. the set of
+/// resources a statement reads from or writes to. Either key may be
+/// omitted; an absent key contributes nothing.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct EffectOptions {
+    pub reads: Option<syn::Expr>,
+    pub writes: Option<syn::Expr>,
+}
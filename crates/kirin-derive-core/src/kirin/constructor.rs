@@ -0,0 +1,24 @@
+//! `#[kirin(fn)]` (new, lattice-checked form): generates a `new[_<variant>]`/
+//! `unchecked_new[_<variant>]` pair of inherent constructors for a dialect
+//! enum or struct.
+//!
+//! `new_*` verifies every `SSAValue`/`ResultValue` argument's already-
+//! assigned type against the field's declared `#[kirin(type = ...)]`
+//! expression before building the statement, so a malformed operand is
+//! rejected at the one place a statement comes into existence rather than
+//! discovered later by [`kirin_ir::verify::Verifier`]. `unchecked_new_*`
+//! builds the same statement without checking, for callers that have
+//! already established well-typedness some other way. The actual per-field
+//! checks and construction are built by
+//! [`Structure::generate_checked_constructor`] — this module only wires that
+//! codegen in.
+
+use proc_macro2::TokenStream;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate the `new_*`/`unchecked_new_*` constructors for `input`'s type,
+/// against the dialect type named by `dialect_ty`.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, dialect_ty: &syn::Path) -> TokenStream {
+    Structure::new(input).generate_checked_constructor(crate_path, dialect_ty)
+}
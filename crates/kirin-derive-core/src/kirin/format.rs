@@ -0,0 +1,33 @@
+//! `#[kirin(format = "...")]`: drives a format-string-based `PrettyPrint`
+//! impl (see `kirin_prettyless::PrettyPrint`) for a dialect enum or struct.
+//!
+//! A variant's own template substitutes each `{name}`/`{0}` placeholder with
+//! the corresponding field's own rendering, in the spirit of a derived
+//! `Display` impl — see [`Structure::generate_pretty_print`] for exactly how
+//! each placeholder resolves. A variant with no template falls back to the
+//! same `{:?}`-based rendering `Printer::print_statement_default` uses, so
+//! dropping the attribute never leaves a statement unprintable.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate `impl PrettyPrint<L>` for `input`'s type, against the dialect
+/// type named by `dialect_ty`.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, dialect_ty: &syn::Path) -> TokenStream {
+    let structure = Structure::new(input);
+    let name = &input.name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let pretty_print_body = structure.generate_pretty_print();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_path::PrettyPrint<#dialect_ty> for #name #ty_generics #where_clause {
+            fn pretty_print<'a>(&self, printer: &'a #crate_path::Printer<'a, #dialect_ty>) -> #crate_path::ArenaDoc<'a> {
+                #pretty_print_body
+            }
+        }
+    }
+}
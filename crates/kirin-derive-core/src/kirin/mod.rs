@@ -1,24 +1,43 @@
+/// derive macro for the opt-in `#[kirin(accessors)]` is_*/as_*/TryFrom family
+pub mod accessors;
 /// Kirin's helper attribute definitions and property parsing
 pub mod attrs;
 
+/// derive macro for the opt-in lattice-checked `new`/`unchecked_new` constructors
+pub mod constructor;
 /// Common extra information for statement definitions
 pub mod extra;
 /// the `fn` builder options
 pub mod builder;
+/// derive macro for the opt-in `#[kirin(builder)]` staged/type-state builder
+pub mod staged_builder;
 /// derive macro for field iterators such as `HasArguments`, `HasArgumentMut` etc.
 pub mod field;
-// /// derive macro for setting statement text format
-// pub mod format;
+/// derive macro for the opt-in `#[kirin(format = "...")]` pretty-printer
+pub mod format;
 /// derive macro for marker traits such as `Dialect`
 pub mod marker;
 /// derive macro for getting the name of an instruction or dialect
 pub mod name;
 /// derive macro for accessing properties such as `IsConstant`, `IsPure` etc.
 pub mod property;
+/// derive macro for the opt-in `ToValue`/`FromValue` document serialization
+pub mod serialize;
+/// derive macro for the `Visitable`/`VisitableMut`/`Foldable` visitor family
+pub mod visit;
+/// derive macro for the `Walk`/`WalkMut` per-category visitor family
+pub mod walk;
 
 pub mod prelude {
+    pub use super::accessors::generate as generate_accessors_family;
     pub use super::builder::Builder;
+    pub use super::constructor::generate as generate_constructor_family;
     pub use super::field::FieldsIter;
+    pub use super::format::generate as generate_format_family;
     pub use super::marker::DialectMarker;
-    pub use super::property::{IsConstant, IsPure, IsTerminator, Property, SearchProperty};
+    pub use super::property::{IsConstant, IsPure, IsTerminator, LatticeProperty, Property, SearchProperty};
+    pub use super::serialize::generate as generate_serialize_family;
+    pub use super::staged_builder::generate as generate_staged_builder_family;
+    pub use super::visit::generate as generate_visit_family;
+    pub use super::walk::generate as generate_walk_family;
 }
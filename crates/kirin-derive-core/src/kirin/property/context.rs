@@ -0,0 +1,269 @@
+//! Shared types behind the `#[derive(...)]`-generated property traits
+//! (`IsConstant`, `IsPure`, `IsTerminator`, and -- since chunk112-5 --
+//! value-typed properties like an effects set) that `struct_impl`/
+//! `enum_impl` assemble into a full trait impl for a specific dialect type.
+//!
+//! This module deliberately does not go through `crate::derive::Compile` or
+//! the `ir::Structure`/`Statement` machinery: both assume a `Layout` shaped
+//! around a dialect's full statement definition (operands, results, blocks,
+//! regions), which a property derive has no need of -- it only needs a
+//! type's own `#[kirin(...)]` options and, for the `#[wraps]` fast path, the
+//! single field being forwarded to. `struct_impl`/`enum_impl` read
+//! `syn::DeriveInput` directly and emit the trait impl with `quote!`, the
+//! same way `ir::structure::Structure`'s `generate_*` methods do.
+
+use std::marker::PhantomData;
+
+use bon::Builder;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::kirin::attrs::{EffectOptions, KirinEnumOptions, KirinStructOptions, KirinVariantOptions};
+use crate::misc::{from_str, strip_path};
+
+use super::{enum_impl, struct_impl};
+
+/// The boolean `#[kirin(...)]` flags a [`SearchProperty`] reads off a
+/// struct's, enum's, or variant's own options.
+pub trait HasFlags {
+    fn constant(&self) -> bool;
+    fn pure(&self) -> bool;
+    fn terminator(&self) -> bool;
+    fn speculatable(&self) -> bool;
+}
+
+macro_rules! impl_has_flags {
+    ($ty:ty) => {
+        impl HasFlags for $ty {
+            fn constant(&self) -> bool {
+                self.constant
+            }
+
+            fn pure(&self) -> bool {
+                self.pure
+            }
+
+            fn terminator(&self) -> bool {
+                self.terminator
+            }
+
+            fn speculatable(&self) -> bool {
+                self.speculatable
+            }
+        }
+    };
+}
+
+impl_has_flags!(KirinStructOptions);
+impl_has_flags!(KirinEnumOptions);
+impl_has_flags!(KirinVariantOptions);
+
+/// A type's declared `#[kirin(effect(reads = ..., writes = ...))]`
+/// contribution, read by a [`LatticeProperty`].
+pub trait HasEffect {
+    fn effect(&self) -> Option<&EffectOptions>;
+}
+
+macro_rules! impl_has_effect {
+    ($ty:ty) => {
+        impl HasEffect for $ty {
+            fn effect(&self) -> Option<&EffectOptions> {
+                self.effect.as_ref()
+            }
+        }
+    };
+}
+
+impl_has_effect!(KirinStructOptions);
+impl_has_effect!(KirinEnumOptions);
+impl_has_effect!(KirinVariantOptions);
+impl_has_effect!(crate::kirin::attrs::KirinFieldOptions);
+
+/// A boolean effect/analysis property, combined across fields and variants
+/// with `||` -- `IsConstant`, `IsPure`, `IsTerminator` below.
+pub trait SearchProperty: Sized {
+    /// This flag as declared directly on `opts`, ignoring everything else
+    /// (wrapped fields, sibling variants, the enum-global default) --
+    /// callers fold those in with [`SearchProperty::combine`].
+    fn flag(opts: &impl HasFlags) -> bool;
+
+    /// Combines two already-computed flags. `||` for every boolean
+    /// property this crate defines; not customizable per-property since,
+    /// unlike [`LatticeProperty::join`], there's only one sensible monoid
+    /// for "is this true of any contributing part".
+    fn combine(a: bool, b: bool) -> bool {
+        a || b
+    }
+}
+
+/// Declares a boolean [`SearchProperty`] backed by one [`HasFlags`] flag,
+/// the same way `IsConstant`/`IsPure`/`IsTerminator` are below. Exported so
+/// a dialect crate can add its own boolean properties the same way.
+#[macro_export]
+macro_rules! boolean_property {
+    ($name:ident, $flag:ident) => {
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl $crate::kirin::property::SearchProperty for $name {
+            fn flag(opts: &impl $crate::kirin::property::HasFlags) -> bool {
+                opts.$flag()
+            }
+        }
+    };
+}
+
+boolean_property!(IsConstant, constant);
+boolean_property!(IsPure, pure);
+boolean_property!(IsTerminator, terminator);
+
+/// A value-typed property whose per-field and per-variant contributions are
+/// combined with a user-supplied lattice join instead of `||` -- e.g. an
+/// MLIR-style memory-effects set, or a cost estimate. See [`SearchProperty`]
+/// for this trait's boolean counterpart.
+pub trait LatticeProperty: Sized {
+    /// This type's own declared contribution (its `#[kirin(effect(...))]`
+    /// options, if any), as an expression of the property's `value_type`.
+    /// `opts` may declare neither `reads` nor `writes`, in which case this
+    /// contributes [`Property::identity`].
+    fn value(opts: &impl HasEffect, identity: TokenStream) -> TokenStream;
+
+    /// Joins two already-computed values of the property's `value_type` --
+    /// the lattice join a dialect supplies in place of [`SearchProperty`]'s
+    /// fixed `||`.
+    fn join(a: TokenStream, b: TokenStream) -> TokenStream;
+}
+
+/// Context for deriving a property trait impl (boolean, via
+/// [`Property::print`], or value-typed, via [`Property::print_lattice`])
+/// with the following generated signature:
+///
+/// ```ignore
+/// impl <TraitPath> for <Name> {
+///     fn <TraitMethod>(&self) -> <ValueType> { ... }
+/// }
+/// ```
+#[derive(Clone, Builder)]
+pub struct Property<S> {
+    #[builder(with = |s: impl Into<String>| from_str(s))]
+    pub default_crate_path: syn::Path,
+    #[builder(with = |s: impl Into<String>| from_str(s))]
+    pub trait_path: syn::Path,
+    #[builder(default = strip_path(&trait_path))]
+    pub trait_name: syn::Ident,
+    #[builder(with = |s: impl Into<String>| from_str(s))]
+    pub trait_method: syn::Ident,
+    #[builder(with = |s: impl Into<String>| from_str::<syn::Type>(s))]
+    pub value_type: syn::Type,
+    #[builder(default)]
+    _marker: PhantomData<S>,
+}
+
+impl<S> Property<S> {
+    /// The identity element a [`LatticeProperty::join`] folds field/variant
+    /// contributions from -- `value_type`'s own `Default`.
+    pub(super) fn identity(&self) -> TokenStream {
+        let value_type = &self.value_type;
+        quote! { <#value_type as ::core::default::Default>::default() }
+    }
+
+    /// Wraps `body` (an expression computing `self.value_type`) in
+    /// `impl trait_path for <name> { fn trait_method(&self) -> value_type { body } }`,
+    /// shared by `struct_impl`/`enum_impl`'s `compile`/`compile_lattice`.
+    pub(super) fn wrap_impl(&self, input: &syn::DeriveInput, body: TokenStream) -> TokenStream {
+        let name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let trait_path = &self.trait_path;
+        let trait_method = &self.trait_method;
+        let value_type = &self.value_type;
+        quote! {
+            impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+                fn #trait_method(&self) -> #value_type {
+                    #body
+                }
+            }
+        }
+    }
+}
+
+impl<S: SearchProperty> Property<S> {
+    /// Generates `impl trait_path for <struct/enum>`: a struct yields its own
+    /// flag, and an enum folds each variant's own flag into the enum-level
+    /// one with `S::combine`, preserving
+    /// the `#[wraps]` fast path (forward straight to the wrapped op's own
+    /// impl instead of searching).
+    pub fn print(&self, input: &syn::DeriveInput) -> TokenStream {
+        match &input.data {
+            syn::Data::Struct(data) => struct_impl::compile(self, input, data),
+            syn::Data::Enum(data) => enum_impl::compile(self, input, data),
+            syn::Data::Union(_) => panic!("{} cannot be derived for unions", self.trait_name),
+        }
+    }
+}
+
+impl<S: LatticeProperty> Property<S> {
+    /// [`Property::print`]'s value-typed counterpart: folds each field's
+    /// declared contribution into its statement's own value with
+    /// `S::join`, then joins that against the enum-global seed.
+    pub fn print_lattice(&self, input: &syn::DeriveInput) -> TokenStream {
+        match &input.data {
+            syn::Data::Struct(data) => struct_impl::compile_lattice(self, input, data),
+            syn::Data::Enum(data) => enum_impl::compile_lattice(self, input, data),
+            syn::Data::Union(_) => panic!("{} cannot be derived for unions", self.trait_name),
+        }
+    }
+}
+
+/// A `#[wraps]` field found by [`wrapped_field`]/[`wrapped_variant`]: its
+/// position among its siblings (for building a match-arm pattern that binds
+/// just this one), the token stream that accesses it on `self` (a bare
+/// ident for a named field, a `syn::Index` for a tuple field), and the
+/// field itself.
+pub(super) struct Wrapped<'a> {
+    pub index: usize,
+    pub accessor: TokenStream,
+    pub field: &'a syn::Field,
+}
+
+/// Finds a struct's or variant's `#[wraps]` field, if any. Mirrors
+/// `ir::statement::Statement::update_fields`'s own field-level `#[wraps]`
+/// detection, minus the enum/variant-level short-circuits `wrapped_variant`
+/// below handles separately.
+pub(super) fn wrapped_field(fields: &syn::Fields) -> Option<Wrapped<'_>> {
+    fields.iter().enumerate().find_map(|(index, field)| {
+        has_attr(&field.attrs, "wraps").then(|| Wrapped {
+            index,
+            accessor: field_accessor(index, field),
+            field,
+        })
+    })
+}
+
+/// A variant-level or enum-level `#[wraps]` fast path: the variant's single
+/// field is the wrapped value, whether or not that field itself also
+/// carries `#[wraps]`.
+pub(super) fn wrapped_variant(fields: &syn::Fields) -> Option<Wrapped<'_>> {
+    if fields.len() != 1 {
+        return None;
+    }
+    let field = fields.iter().next()?;
+    Some(Wrapped {
+        index: 0,
+        accessor: field_accessor(0, field),
+        field,
+    })
+}
+
+fn field_accessor(index: usize, field: &syn::Field) -> TokenStream {
+    match &field.ident {
+        Some(ident) => quote! { #ident },
+        None => {
+            let index = syn::Index::from(index);
+            quote! { #index }
+        }
+    }
+}
+
+pub(super) fn has_attr(attrs: &[syn::Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
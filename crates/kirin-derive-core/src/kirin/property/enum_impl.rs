@@ -1,52 +1,136 @@
-use super::context::{Property, SearchProperty};
-use crate::prelude::*;
-use quote::{ToTokens, quote};
+use darling::{FromDeriveInput, FromField, FromVariant};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
 
-target! {
-    pub struct EnumImpl
+use crate::kirin::attrs::{KirinEnumOptions, KirinVariantOptions};
+
+use super::context::{LatticeProperty, Property, SearchProperty, Wrapped, has_attr, wrapped_field, wrapped_variant};
+
+fn enum_options(input: &syn::DeriveInput) -> KirinEnumOptions {
+    KirinEnumOptions::from_derive_input(input)
+        .unwrap_or_else(|err| panic!("failed to parse `#[kirin(...)]` options: {err}"))
 }
 
-impl<'src, S: SearchProperty> Compile<'src, Property<S>, EnumImpl> for Enum<'src, Property<S>> {
-    fn compile(&self, ctx: &Property<S>) -> EnumImpl {
-        let value_type = &ctx.value_type;
-        let variant_ident = self.variant_names();
-        let unpacking = self.unpacking();
-        let glob = S::search_enum(self);
-        let action = self
-            .variants()
-            .map(|v| {
-                if let Some(wrapper) = v.wrapper() {
-                    let wrapper_type = &wrapper.source().ty;
-                    let trait_path = &ctx.trait_path;
-                    let trait_method = &ctx.trait_method;
-                    quote! {
-                        <#wrapper_type as #trait_path>::#trait_method(#wrapper)
-                    }
+fn variant_options(variant: &syn::Variant) -> KirinVariantOptions {
+    KirinVariantOptions::from_variant(variant)
+        .unwrap_or_else(|err| panic!("failed to parse `#[kirin(...)]` options: {err}"))
+}
+
+/// A variant is a wrapper if one of its own fields carries `#[wraps]`, or if
+/// either the variant itself or the whole enum does and it has exactly one
+/// field -- see `context::wrapped_field`/`context::wrapped_variant`.
+fn variant_wraps<'a>(input: &syn::DeriveInput, variant: &'a syn::Variant) -> Option<Wrapped<'a>> {
+    wrapped_field(&variant.fields).or_else(|| {
+        (has_attr(&input.attrs, "wraps") || has_attr(&variant.attrs, "wraps"))
+            .then(|| wrapped_variant(&variant.fields))
+            .flatten()
+    })
+}
+
+/// Builds the match-arm pattern for `variant`, binding its `#[wraps]` field
+/// (if any) to `binding` and discarding every other field.
+fn variant_pattern(variant: &syn::Variant, wrapped_index: Option<usize>, binding: &syn::Ident) -> TokenStream {
+    let name = &variant.ident;
+    match &variant.fields {
+        syn::Fields::Named(fields) => {
+            let members = fields.named.iter().enumerate().map(|(index, field)| {
+                let field_ident = field.ident.as_ref().expect("named field has an ident");
+                if Some(index) == wrapped_index {
+                    quote! { #field_ident: #binding }
+                } else {
+                    quote! { #field_ident: _ }
+                }
+            });
+            quote! { Self::#name { #(#members),* } }
+        }
+        syn::Fields::Unnamed(fields) => {
+            let members = fields.unnamed.iter().enumerate().map(|(index, _)| {
+                if Some(index) == wrapped_index {
+                    quote! { #binding }
                 } else {
-                    let value = S::search_variant(&v);
-                    let combined = S::combine(&glob, &value);
-                    combined
+                    quote! { _ }
                 }
-            })
-            .collect::<Vec<_>>();
-
-        let trait_path: TraitPath = self.compile(ctx);
-        let trait_impl = TraitImpl::default()
-            .input(self.source())
-            .trait_path(trait_path)
-            .add_method(
-                TraitItemFnImpl::new(&ctx.trait_method)
-                    .with_output(quote! {#value_type})
-                    .with_token_body(quote! {
-                        match self {
-                            #(
-                                Self::#variant_ident #unpacking => {
-                                    #action
-                                }
-                            ),*
-                        }
-                    }),
-            );
-        trait_impl.to_token_stream().into()
+            });
+            quote! { Self::#name ( #(#members),* ) }
+        }
+        syn::Fields::Unit => quote! { Self::#name },
     }
 }
+
+pub(super) fn compile<S: SearchProperty>(
+    ctx: &Property<S>,
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+) -> TokenStream {
+    let trait_path = &ctx.trait_path;
+    let trait_method = &ctx.trait_method;
+    let glob = S::flag(&enum_options(input));
+    let binding = format_ident!("__wrapped");
+
+    let arms = data.variants.iter().map(|variant| {
+        let wrapped = variant_wraps(input, variant);
+        let pattern = variant_pattern(variant, wrapped.as_ref().map(|w| w.index), &binding);
+        let action = match &wrapped {
+            Some(wrapped) => {
+                let field_ty = &wrapped.field.ty;
+                quote! { <#field_ty as #trait_path>::#trait_method(#binding) }
+            }
+            None => {
+                let value = S::combine(glob, S::flag(&variant_options(variant)));
+                quote! { #value }
+            }
+        };
+        quote! { #pattern => { #action } }
+    });
+
+    ctx.wrap_impl(
+        input,
+        quote! {
+            match self {
+                #(#arms),*
+            }
+        },
+    )
+}
+
+pub(super) fn compile_lattice<S: LatticeProperty>(
+    ctx: &Property<S>,
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+) -> TokenStream {
+    let trait_path = &ctx.trait_path;
+    let trait_method = &ctx.trait_method;
+    let global_seed = S::value(&enum_options(input), ctx.identity());
+    let binding = format_ident!("__wrapped");
+
+    let arms = data.variants.iter().map(|variant| {
+        let wrapped = variant_wraps(input, variant);
+        let pattern = variant_pattern(variant, wrapped.as_ref().map(|w| w.index), &binding);
+        let action = match &wrapped {
+            Some(wrapped) => {
+                let field_ty = &wrapped.field.ty;
+                quote! { <#field_ty as #trait_path>::#trait_method(#binding) }
+            }
+            None => {
+                let seed = S::value(&variant_options(variant), ctx.identity());
+                let value = variant.fields.iter().fold(seed, |acc, field| {
+                    match crate::kirin::attrs::KirinFieldOptions::from_field(field) {
+                        Ok(opts) => S::join(acc, S::value(&opts, ctx.identity())),
+                        Err(_) => acc,
+                    }
+                });
+                S::join(global_seed.clone(), value)
+            }
+        };
+        quote! { #pattern => { #action } }
+    });
+
+    ctx.wrap_impl(
+        input,
+        quote! {
+            match self {
+                #(#arms),*
+            }
+        },
+    )
+}
@@ -2,7 +2,7 @@ mod context;
 mod enum_impl;
 mod struct_impl;
 
-pub use context::{IsConstant, IsPure, IsTerminator, Property, SearchProperty};
+pub use context::{HasEffect, HasFlags, IsConstant, IsPure, IsTerminator, LatticeProperty, Property, SearchProperty};
 pub use crate::boolean_property;
 
 #[cfg(test)]
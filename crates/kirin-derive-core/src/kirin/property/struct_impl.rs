@@ -1,37 +1,49 @@
-use super::context::{Property, SearchProperty};
-use crate::prelude::*;
-use quote::{ToTokens, quote};
+use darling::{FromDeriveInput, FromField};
+use proc_macro2::TokenStream;
+use quote::quote;
 
-target! {
-    pub struct StructImpl
+use crate::kirin::attrs::{KirinFieldOptions, KirinStructOptions};
+
+use super::context::{LatticeProperty, Property, SearchProperty, wrapped_field};
+
+fn struct_options(input: &syn::DeriveInput) -> KirinStructOptions {
+    KirinStructOptions::from_derive_input(input)
+        .unwrap_or_else(|err| panic!("failed to parse `#[kirin(...)]` options: {err}"))
+}
+
+pub(super) fn compile<S: SearchProperty>(
+    ctx: &Property<S>,
+    input: &syn::DeriveInput,
+    data: &syn::DataStruct,
+) -> TokenStream {
+    let trait_path = &ctx.trait_path;
+    let trait_method = &ctx.trait_method;
+    let body = if let Some(wrapped) = wrapped_field(&data.fields) {
+        let (accessor, field_ty) = (&wrapped.accessor, &wrapped.field.ty);
+        quote! { <#field_ty as #trait_path>::#trait_method(&self.#accessor) }
+    } else {
+        let value = S::flag(&struct_options(input));
+        quote! { #value }
+    };
+    ctx.wrap_impl(input, body)
 }
 
-impl<'src, S: SearchProperty> Compile<'src, Struct<'src, Self>, StructImpl> for Property<S> {
-    fn compile(&self, node: &Struct<'src, Self>) -> StructImpl {
-        let trait_method = &self.trait_method;
-        let trait_path: TraitPath = self.compile(node);
-        let trait_fn_impl = if let Some(wrapper) = &node.wrapper() {
-            let wrapper_type = &wrapper.source().ty;
-            let unpacking = node.unpacking();
-            TraitItemFnImpl::new(&self.trait_method)
-                .with_output(&self.value_type)
-                .with_token_body(quote! {
-                    let Self #unpacking = self;
-                    <#wrapper_type as #trait_path>::#trait_method(#wrapper)
-                })
-        } else {
-            let value = S::search_struct(node);
-            TraitItemFnImpl::new(&self.trait_method)
-                .with_output(&self.value_type)
-                .with_token_body(quote! {
-                    #value
-                })
-        };
-        TraitImpl::default()
-            .input(node.source())
-            .trait_path(&self.trait_path)
-            .add_method(trait_fn_impl)
-            .to_token_stream()
-            .into()
-    }
+pub(super) fn compile_lattice<S: LatticeProperty>(
+    ctx: &Property<S>,
+    input: &syn::DeriveInput,
+    data: &syn::DataStruct,
+) -> TokenStream {
+    let trait_path = &ctx.trait_path;
+    let trait_method = &ctx.trait_method;
+    let body = if let Some(wrapped) = wrapped_field(&data.fields) {
+        let (accessor, field_ty) = (&wrapped.accessor, &wrapped.field.ty);
+        quote! { <#field_ty as #trait_path>::#trait_method(&self.#accessor) }
+    } else {
+        let seed = S::value(&struct_options(input), ctx.identity());
+        data.fields.iter().fold(seed, |acc, field| match KirinFieldOptions::from_field(field) {
+            Ok(opts) => S::join(acc, S::value(&opts, ctx.identity())),
+            Err(_) => acc,
+        })
+    };
+    ctx.wrap_impl(input, body)
 }
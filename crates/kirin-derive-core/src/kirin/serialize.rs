@@ -0,0 +1,55 @@
+//! `#[kirin(serialize)]`: implements the `ToValue`/`FromValue` family from
+//! `kirin_ir::serialize` for a dialect enum or struct.
+//!
+//! This is opt-in (unlike `#[derive(Visit)]`/`#[derive(Walk)]`, which are
+//! their own derive macros): a dialect author adds `#[kirin(serialize)]` to
+//! the same derive input used for `#[derive(Dialect)]` to also get a
+//! canonical, self-describing on-disk representation, built the same way a
+//! syn-serde layer mirrors an AST into a serde-friendly tree. The actual
+//! per-field/per-variant document shape is built by
+//! [`Structure::generate_to_value`]/[`Structure::generate_from_value`] —
+//! this module only wires that codegen into the two trait impls, generic
+//! over the dialect `L` the derived type is itself parameterized by (or, for
+//! a concrete dialect type, over whatever `L` its `Dialect` bound resolves
+//! to).
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate `impl ToValue<L>` and `impl FromValue<L>` for `input`'s type,
+/// against the dialect type named by `dialect_ty`.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, dialect_ty: &syn::Path) -> TokenStream {
+    let structure = Structure::new(input);
+    let name = &input.name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let to_value_body = structure.generate_to_value(crate_path);
+    let from_value_body = structure.generate_from_value(crate_path);
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_path::ToValue<#dialect_ty> for #name #ty_generics #where_clause {
+            fn to_value(&self) -> #crate_path::Document {
+                #to_value_body
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::FromValue<#dialect_ty> for #name #ty_generics #where_clause {
+            fn from_value(
+                value: &#crate_path::Document,
+                resolver: &mut impl #crate_path::HandleResolver<#dialect_ty>,
+            ) -> ::std::result::Result<Self, #crate_path::FromValueError> {
+                let (tag, fields) = match value {
+                    #crate_path::Document::Node { tag, fields } => (tag, fields),
+                    _ => return ::std::result::Result::Err(#crate_path::FromValueError::UnexpectedShape {
+                        expected: "Node",
+                    }),
+                };
+                ::std::result::Result::Ok(#from_value_body)
+            }
+        }
+    }
+}
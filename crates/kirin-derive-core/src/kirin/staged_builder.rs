@@ -0,0 +1,29 @@
+//! `#[kirin(builder)]`: generates a staged (type-state) builder type per
+//! variant, as an alternative to `#[kirin(fn = new)]`'s single positional
+//! constructor (see [`crate::kirin::constructor`]).
+//!
+//! Each required field (every `Argument`/`Result`/`Block`/`Successor`/
+//! `Region` field, plus any `Value` field with no `#[kirin(default = ...)]`)
+//! gets its own generic type-state parameter, so `Constant::builder().value(x)
+//! .result(r).build()` only compiles once every required setter has run —
+//! omitting one leaves the builder's type stuck one `Set<_>` short of
+//! `build()`'s own impl block. A `Value` field with a default is
+//! pre-populated and its setter is unconditionally available, outside the
+//! type-state. The actual per-field setter/`build()` codegen is built by
+//! [`Structure::generate_staged_builder`] — this module only wires that
+//! codegen in.
+
+use proc_macro2::TokenStream;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate the staged builder type(s) for `input`'s type. `dialect_ty` is
+/// accepted for consistency with the other `kirin::*::generate` entry
+/// points, but isn't needed here: a builder only ever touches its own
+/// type's fields, not a dialect-generic trait impl.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, _dialect_ty: &syn::Path) -> TokenStream {
+    if !input.attrs.staged_builder {
+        return TokenStream::new();
+    }
+    Structure::new(input).generate_staged_builder(crate_path)
+}
@@ -0,0 +1,48 @@
+//! `#[derive(Visit)]`: implements the `Visitable`/`VisitableMut`/`Foldable`
+//! family from `kirin_ir::visitor` for a dialect enum or struct.
+//!
+//! The actual per-field recursion is built by [`Structure::generate_visit`]/
+//! [`Structure::generate_visit_mut`]/[`Structure::generate_fold`] — this
+//! module only wires that codegen into the three trait impls, generic over
+//! the dialect `L` the derived type is itself parameterized by (or, for a
+//! concrete dialect type, over whatever `L` its `Dialect` bound resolves to).
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate `impl Visitable<L>`, `impl VisitableMut<L>`, and `impl Foldable<L>`
+/// for `input`'s type, against the dialect type named by `dialect_ty`.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, dialect_ty: &syn::Path) -> TokenStream {
+    let structure = Structure::new(input);
+    let name = &input.name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let visit_body = structure.generate_visit();
+    let visit_mut_body = structure.generate_visit_mut();
+    let fold_body = structure.generate_fold();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Visitable<#dialect_ty> for #name #ty_generics #where_clause {
+            fn walk(&self, visitor: &mut impl #crate_path::StatementVisitor<#dialect_ty>) {
+                #visit_body
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::VisitableMut<#dialect_ty> for #name #ty_generics #where_clause {
+            fn walk_mut(&mut self, visitor: &mut impl #crate_path::StatementVisitorMut<#dialect_ty>) {
+                #visit_mut_body
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Foldable<#dialect_ty> for #name #ty_generics #where_clause {
+            fn fold_with(self, folder: &mut impl #crate_path::StatementFolder<#dialect_ty>) -> Self {
+                #fold_body
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! `#[derive(Walk)]`: implements the `Walk`/`WalkMut`/`Map` family from
+//! `kirin_ir::visitor` for a dialect enum or struct.
+//!
+//! Unlike `#[derive(Visit)]` (see [`crate::kirin::visit`]), which lumps every
+//! `SSAValue`/`Block`/`Region` field into one `walk` callback, this derive
+//! keeps operands, results, blocks, successors, and regions apart so a pass
+//! can tell an operand use from a result definition without hand-writing a
+//! `match` over the dialect's variants. `Map` is the consuming counterpart of
+//! `Walk`/`WalkMut`: instead of visiting (or mutating in place), it rebuilds
+//! the value with one category's fields replaced by a closure's return
+//! value — the shape SSA renaming, inlining, and block-argument remapping
+//! actually need. The actual per-field recursion is built by
+//! [`Structure::generate_walk`]/[`Structure::generate_walk_mut`]/
+//! [`Structure::generate_map`] — this module only wires that codegen into
+//! the three trait impls, generic over the dialect `L` the derived type is
+//! itself parameterized by (or, for a concrete dialect type, over whatever
+//! `L` its `Dialect` bound resolves to).
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ir::{Input, Layout, Structure};
+
+/// Generate `impl Walk<L>`, `impl WalkMut<L>`, and `impl Map<L>` for
+/// `input`'s type, against the dialect type named by `dialect_ty`.
+pub fn generate<L: Layout>(input: &Input<L>, crate_path: &syn::Path, dialect_ty: &syn::Path) -> TokenStream {
+    let structure = Structure::new(input);
+    let name = &input.name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let [operands, results, blocks, successors, regions] = structure.generate_walk();
+    let [operands_mut, results_mut, blocks_mut, successors_mut, regions_mut] = structure.generate_walk_mut();
+    let [map_operands, map_results, map_blocks, map_successors, map_regions] = structure.generate_map();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Walk<#dialect_ty> for #name #ty_generics #where_clause {
+            fn walk_operands(&self, visitor: &mut impl FnMut(&#crate_path::SSAValue)) {
+                #operands
+            }
+
+            fn walk_results(&self, visitor: &mut impl FnMut(&#crate_path::ResultValue)) {
+                #results
+            }
+
+            fn walk_blocks(&self, visitor: &mut impl FnMut(&#crate_path::Block)) {
+                #blocks
+            }
+
+            fn walk_successors(&self, visitor: &mut impl FnMut(&#crate_path::Successor)) {
+                #successors
+            }
+
+            fn walk_regions(&self, visitor: &mut impl FnMut(&#crate_path::Region)) {
+                #regions
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::WalkMut<#dialect_ty> for #name #ty_generics #where_clause {
+            fn walk_operands_mut(&mut self, visitor: &mut impl FnMut(&mut #crate_path::SSAValue)) {
+                #operands_mut
+            }
+
+            fn walk_results_mut(&mut self, visitor: &mut impl FnMut(&mut #crate_path::ResultValue)) {
+                #results_mut
+            }
+
+            fn walk_blocks_mut(&mut self, visitor: &mut impl FnMut(&mut #crate_path::Block)) {
+                #blocks_mut
+            }
+
+            fn walk_successors_mut(&mut self, visitor: &mut impl FnMut(&mut #crate_path::Successor)) {
+                #successors_mut
+            }
+
+            fn walk_regions_mut(&mut self, visitor: &mut impl FnMut(&mut #crate_path::Region)) {
+                #regions_mut
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #crate_path::Map<#dialect_ty> for #name #ty_generics #where_clause {
+            fn map_operands(self, f: &mut impl FnMut(#crate_path::SSAValue) -> #crate_path::SSAValue) -> Self {
+                #map_operands
+            }
+
+            fn map_results(self, f: &mut impl FnMut(#crate_path::ResultValue) -> #crate_path::ResultValue) -> Self {
+                #map_results
+            }
+
+            fn map_blocks(self, f: &mut impl FnMut(#crate_path::Block) -> #crate_path::Block) -> Self {
+                #map_blocks
+            }
+
+            fn map_successors(self, f: &mut impl FnMut(#crate_path::Successor) -> #crate_path::Successor) -> Self {
+                #map_successors
+            }
+
+            fn map_regions(self, f: &mut impl FnMut(#crate_path::Region) -> #crate_path::Region) -> Self {
+                #map_regions
+            }
+        }
+    }
+}
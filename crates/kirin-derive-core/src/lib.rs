@@ -13,12 +13,21 @@
 //! Kirin's built-in derive macros are also implemented using this crate.
 //! Take a look at the [`kirin`](crate::kirin) module for more details.
 
+/// naming-convention lint for dialect definitions
+pub mod case_lint;
+/// common code-generation helpers (field bindings, generics, constructors)
+/// shared across derive macro implementations
+pub mod codegen;
 /// traits and tools for derive macro definitions
 pub mod derive;
 /// code generation gadgets for derive macros
 pub mod gadgets;
 /// intermediate representation for derive macros and code generation
 pub mod ir;
+/// read-only code-generation visitor over the `ir` node set
+pub mod emit;
+/// in-place IR rewriting over the same node set `emit` visits
+pub mod fold;
 /// miscellaneous utilities
 pub mod misc;
 /// Kirin's built-in derive macros.
@@ -34,6 +43,7 @@ pub mod debug;
 /// commonly used items from kirin-derive-core
 pub mod prelude {
     pub use crate::derive::*;
+    pub use crate::fold::*;
     pub use crate::gadgets::*;
     pub use crate::ir::*;
     pub use crate::misc::*;
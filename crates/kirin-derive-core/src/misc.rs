@@ -108,6 +108,42 @@ where
     false
 }
 
+/// Levenshtein edit distance between two strings, used to power "did you mean"
+/// suggestions when a lookup by name fails.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let tmp = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j]).min(row[j + 1])
+            };
+            prev_diag = tmp;
+        }
+    }
+    row[b.len()]
+}
+
+/// Find the closest match to `name` among `candidates`, if any lies within
+/// `max_distance` edits. Ties are broken by the order `candidates` is iterated in.
+pub fn did_you_mean<'a, I>(name: &str, candidates: I, max_distance: usize) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, levenshtein_distance(name, candidate)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
 pub fn parse_attribute(
     name: &str,
     attrs: &[syn::Attribute],
@@ -213,4 +249,22 @@ mod tests {
         assert!(is_type_in(&ty, "String", |seg| seg.ident == "Result"));
         assert!(!is_type_in(&ty, "f64", |seg| seg.ident == "Result"));
     }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("add", "add"), 0);
+        assert_eq!(levenshtein_distance("add", "adn"), 1);
+        assert_eq!(levenshtein_distance("add", "sub"), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let candidates = ["add", "sub", "mul"];
+        assert_eq!(
+            did_you_mean("adn", candidates.iter().copied(), 2),
+            Some("add")
+        );
+        assert_eq!(did_you_mean("xyz", candidates.iter().copied(), 2), None);
+    }
 }
@@ -54,7 +54,13 @@ fn build_fn_inputs(info: &StatementInfo) -> Vec<proc_macro2::TokenStream> {
                 }
                 let ty = field.value_type().expect("Value field must have type");
                 let name = field.name_ident(ty.span());
-                let sig = if field.has_into() {
+                let sig = if let Some(builder_ty) = field.builder_type() {
+                    // `#[kirin(builder(type = ...))]` overrides the
+                    // signature type entirely; it already spells out
+                    // whatever ergonomics (`impl Into<_>`, a slice, etc.)
+                    // the author wants.
+                    quote! { #name: #builder_ty }
+                } else if field.has_into() {
                     quote! { #name: impl Into<#ty> }
                 } else {
                     quote! { #name: #ty }
@@ -86,6 +92,11 @@ fn build_fn_let_inputs(info: &StatementInfo) -> Vec<proc_macro2::TokenStream> {
                 if let Some(default_value) = field.default_value() {
                     let expr = default_value.to_expr();
                     assigns.push(quote! { let #name: #ty = #expr; });
+                } else if let Some(build_expr) = field.builder_build_expr() {
+                    // `#[kirin(builder(build = ...))]` takes over the whole
+                    // conversion from the (possibly custom-typed) builder
+                    // argument, bound here under the field's own name.
+                    assigns.push(quote! { let #name: #ty = #build_expr; });
                 } else if field.has_into() {
                     assigns.push(quote! { let #name: #ty = #name.into(); });
                 } else if is_type(ty, "PhantomData") {
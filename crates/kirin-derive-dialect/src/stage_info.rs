@@ -30,6 +30,7 @@
 
 use std::collections::BTreeMap;
 
+use kirin_derive_core::misc::to_snake_case;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::{DeriveInput, Fields, GenericArgument, PathArguments, Type};
@@ -190,6 +191,39 @@ pub fn generate(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
     // declared_stage_names
     let stage_names: Vec<&str> = variants.iter().map(|v| v.stage_name.as_str()).collect();
 
+    // "did you mean" suggestion on an unrecognized stage name, via a plain
+    // Levenshtein distance routine emitted as a free function beside the
+    // generated impl so
+    // the derive doesn't need a runtime dependency (e.g. `strsim`) for it.
+    let distance_fn = format_ident!(
+        "__{}_stage_name_distance",
+        to_snake_case(enum_ident.to_string())
+    );
+
+    tokens.extend(quote! {
+        fn #distance_fn(a: &str, b: &str) -> usize {
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+            for (i, row) in d.iter_mut().enumerate() {
+                row[0] = i;
+            }
+            for j in 0..=b.len() {
+                d[0][j] = j;
+            }
+            for i in 1..=a.len() {
+                for j in 1..=b.len() {
+                    let cost = if a[i - 1] != b[j - 1] { 1 } else { 0 };
+                    d[i][j] = core::cmp::min(
+                        core::cmp::min(d[i - 1][j] + 1, d[i][j - 1] + 1),
+                        d[i - 1][j - 1] + cost,
+                    );
+                }
+            }
+            d[a.len()][b.len()]
+        }
+    });
+
     tokens.extend(quote! {
         impl #impl_generics #ir_crate::CompileStageInfo for #enum_ident #ty_generics #where_clause {
             type Languages = #languages_ty;
@@ -213,7 +247,25 @@ pub fn generate(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
             fn from_stage_name(stage_name: &str) -> Result<Self, String> {
                 match stage_name {
                     #( #from_name_arms )*
-                    _ => Err(format!("no stage variant mapping for '@{}'", stage_name)),
+                    _ => {
+                        let names = Self::declared_stage_names();
+                        let available = names.join(", ");
+                        let best = names
+                            .iter()
+                            .map(|candidate| (*candidate, #distance_fn(candidate, stage_name)))
+                            .min_by_key(|(_, dist)| *dist);
+                        let threshold = core::cmp::max(1, stage_name.len() / 3);
+                        match best {
+                            Some((candidate, dist)) if dist <= threshold => Err(format!(
+                                "unknown stage '@{}'; did you mean '@{}'? (available: {})",
+                                stage_name, candidate, available
+                            )),
+                            _ => Err(format!(
+                                "unknown stage '@{}' (available: {})",
+                                stage_name, available
+                            )),
+                        }
+                    }
                 }
             }
 
@@ -223,9 +275,84 @@ pub fn generate(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
         }
     });
 
+    // 3. Per-variant `is_foo`/`as_foo`/`as_foo_mut` accessors, so callers who
+    // already know which stage they want don't need a hand-written `match`
+    // or a detour through the type-indexed `HasStageInfo` trait.
+    tokens.extend(generate_variant_accessors(
+        enum_ident,
+        &impl_generics,
+        &ty_generics,
+        where_clause,
+        &ir_crate,
+        &variants,
+    )?);
+
     Ok(tokens)
 }
 
+/// Generate `is_<name>`/`as_<name>`/`as_<name>_mut` inherent methods, one
+/// triple per variant, named after its snake-cased `#[stage(name = "...")]`.
+fn generate_variant_accessors(
+    enum_ident: &syn::Ident,
+    impl_generics: &syn::ImplGenerics,
+    ty_generics: &syn::TypeGenerics,
+    where_clause: Option<&syn::WhereClause>,
+    ir_crate: &syn::Path,
+    variants: &[VariantInfo],
+) -> Result<TokenStream, syn::Error> {
+    let mut seen: BTreeMap<String, &syn::Ident> = BTreeMap::new();
+
+    let methods = variants
+        .iter()
+        .map(|v| {
+            let method_name = to_snake_case(&v.stage_name);
+
+            if let Some(prior_ident) = seen.insert(method_name.clone(), &v.ident) {
+                return Err(syn::Error::new_spanned(
+                    &v.ident,
+                    format!(
+                        "stage name '{}' collides with variant `{}`: both snake-case to `{}`, \
+                         which would generate duplicate `is_{}`/`as_{}`/`as_{}_mut` methods",
+                        v.stage_name, prior_ident, method_name, method_name, method_name, method_name
+                    ),
+                ));
+            }
+
+            let ident = &v.ident;
+            let dialect_ty = &v.dialect_ty;
+            let is_method = format_ident!("is_{}", method_name);
+            let as_method = format_ident!("as_{}", method_name);
+            let as_method_mut = format_ident!("as_{}_mut", method_name);
+
+            Ok(quote! {
+                pub fn #is_method(&self) -> bool {
+                    matches!(self, #enum_ident::#ident(_))
+                }
+
+                pub fn #as_method(&self) -> Option<&#ir_crate::StageInfo<#dialect_ty>> {
+                    match self {
+                        #enum_ident::#ident(s) => Some(s),
+                        _ => None,
+                    }
+                }
+
+                pub fn #as_method_mut(&mut self) -> Option<&mut #ir_crate::StageInfo<#dialect_ty>> {
+                    match self {
+                        #enum_ident::#ident(s) => Some(s),
+                        _ => None,
+                    }
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
 /// Parse the optional `#[stage(crate = ...)]` attribute on the enum.
 fn parse_crate_attr(input: &DeriveInput) -> Result<String, syn::Error> {
     for attr in &input.attrs {
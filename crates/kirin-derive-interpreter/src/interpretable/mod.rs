@@ -2,7 +2,7 @@ mod emit;
 mod scan;
 
 use kirin_derive_core::derive::InputMeta;
-use kirin_derive_core::misc::from_str;
+use kirin_derive_core::misc::{did_you_mean, from_str};
 use kirin_derive_core::prelude::*;
 use std::collections::HashMap;
 
@@ -57,12 +57,27 @@ impl DeriveInterpretable {
         statement: &ir::Statement<StandardLayout>,
     ) -> darling::Result<&StatementInfo> {
         let key = statement.name.to_string();
-        self.statements.get(&key).ok_or_else(|| {
-            darling::Error::custom(format!(
-                "Missing statement info for '{}', call scan_statement first",
-                key
-            ))
-        })
+        self.statements
+            .get(&key)
+            .ok_or_else(|| darling::Error::custom(self.missing_statement_message(&key)))
+    }
+
+    fn missing_statement_message(&self, key: &str) -> String {
+        let known = self.statements.keys().map(String::as_str);
+        match did_you_mean(key, known, 2) {
+            Some(suggestion) => format!(
+                "Missing statement info for '{key}', call scan_statement first. Did you mean '{suggestion}'?"
+            ),
+            None => {
+                let mut available: Vec<&str> =
+                    self.statements.keys().map(String::as_str).collect();
+                available.sort_unstable();
+                format!(
+                    "Missing statement info for '{key}', call scan_statement first. Available statements: {}",
+                    available.join(", ")
+                )
+            }
+        }
     }
 
     pub(crate) fn interpreter_crate_path(&self) -> syn::Path {
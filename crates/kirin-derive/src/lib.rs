@@ -1,5 +1,6 @@
 extern crate proc_macro;
 
+use kirin_derive_core::case_lint::check_upper_camel_case;
 use kirin_derive_core::kirin::prelude::*;
 use kirin_derive_core::chumsky::prelude::*;
 use kirin_derive_core::prelude::*;
@@ -11,6 +12,7 @@ pub fn derive_statement(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as syn::DeriveInput);
 
     let mut tokens = proc_macro2::TokenStream::new();
+    tokens.extend(lint_naming(&ast));
 
     for (mutable, trait_name, matching_type, trait_method, trait_type_iter) in [
         (false, "HasArguments", "SSAValue", "arguments", "Iter"),
@@ -55,6 +57,12 @@ pub fn derive_statement(input: TokenStream) -> TokenStream {
             .to_tokens(&mut tokens);
     }
 
+    // `Visitable`/`VisitableMut`/`Foldable` and `Walk`/`WalkMut`, so a pass
+    // can walk a dialect's statement tree without hand-writing the `match`
+    // over its variants; see `kirin_ir::visitor` for the trait family.
+    tokens.extend(emit_visit(&ast));
+    tokens.extend(emit_walk(&ast));
+
     Property::<IsTerminator>::builder()
         .default_crate_path("::kirin::ir")
         .trait_path("IsTerminator")
@@ -93,6 +101,66 @@ pub fn derive_statement(input: TokenStream) -> TokenStream {
     tokens.into()
 }
 
+/// Flag statement/op names (enum variants, or the struct itself) that aren't
+/// UpperCamelCase, via [`kirin_derive_core::case_lint`].
+fn lint_naming(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let mut errors = darling::Error::accumulator();
+
+    let idents: Vec<&syn::Ident> = match &ast.data {
+        syn::Data::Enum(data) => data.variants.iter().map(|v| &v.ident).collect(),
+        syn::Data::Struct(_) => vec![&ast.ident],
+        syn::Data::Union(_) => vec![],
+    };
+
+    for ident in idents {
+        if let Some(violation) = check_upper_camel_case(&ident.to_string()) {
+            errors.push(
+                darling::Error::custom(violation.message("statement")).with_span(ident),
+            );
+        }
+    }
+
+    match errors.finish() {
+        Ok(()) => proc_macro2::TokenStream::new(),
+        Err(err) => err.write_errors(),
+    }
+}
+
+/// Builds `impl Visitable<L>`/`impl VisitableMut<L>`/`impl Foldable<L>` for
+/// `ast`, with `ast` itself standing in for the `L: Dialect` type parameter:
+/// a dialect enum's own `Region`/`Block` fields are what its statements
+/// recurse through, so it's always its own dialect. Honors the same
+/// `#[kirin(crate = ...)]` override `FieldsIter`/`Property`/`Builder`/
+/// `DialectMarker` fall back to `default_crate_path` for above.
+fn emit_visit(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    match Input::<StandardLayout>::from_derive_input(ast) {
+        Ok(ir_input) => {
+            let default_crate_path: syn::Path = syn::parse_quote!(::kirin::ir);
+            let crate_path = ir_input.attrs.crate_path.clone().unwrap_or(default_crate_path);
+            let name = &ir_input.name;
+            let (_, ty_generics, _) = ir_input.generics.split_for_impl();
+            let dialect_ty: syn::Path = syn::parse_quote!(#name #ty_generics);
+            kirin_derive_core::kirin::visit::generate(&ir_input, &crate_path, &dialect_ty)
+        }
+        Err(err) => err.write_errors(),
+    }
+}
+
+/// [`emit_visit`]'s counterpart for `impl Walk<L>`/`impl WalkMut<L>`.
+fn emit_walk(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    match Input::<StandardLayout>::from_derive_input(ast) {
+        Ok(ir_input) => {
+            let default_crate_path: syn::Path = syn::parse_quote!(::kirin::ir);
+            let crate_path = ir_input.attrs.crate_path.clone().unwrap_or(default_crate_path);
+            let name = &ir_input.name;
+            let (_, ty_generics, _) = ir_input.generics.split_for_impl();
+            let dialect_ty: syn::Path = syn::parse_quote!(#name #ty_generics);
+            kirin_derive_core::kirin::walk::generate(&ir_input, &crate_path, &dialect_ty)
+        }
+        Err(err) => err.write_errors(),
+    }
+}
+
 macro_rules! derive_fields_iter {
     ($mutable:expr, $name:ident, $matching_type:ident, $trait_method:ident, $trait_type_iter:ident) => {
         paste::paste! {
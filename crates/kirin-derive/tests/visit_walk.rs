@@ -0,0 +1,72 @@
+//! Tests for the `Visitable`/`VisitableMut`/`Foldable` and `Walk`/`WalkMut`
+//! families `#[derive(Dialect)]` now emits alongside `HasArguments` et al.
+
+use kirin_derive::Dialect;
+use kirin_ir::*;
+use kirin_test_utils::*;
+
+#[derive(Dialect, Clone, Debug, PartialEq)]
+#[kirin(fn, type = SimpleIRType, crate = kirin_ir)]
+struct VisitOp {
+    arg: SSAValue,
+    res: ResultValue,
+}
+
+#[derive(Default)]
+struct RecordingVisitor {
+    ssa_values: Vec<SSAValue>,
+}
+
+impl StatementVisitor<VisitOp> for RecordingVisitor {
+    fn visit_ssa_value(&mut self, value: &SSAValue) {
+        self.ssa_values.push(*value);
+    }
+}
+
+#[test]
+fn test_visitable_walks_operand_fields_but_not_results() {
+    let op = VisitOp {
+        arg: TestSSAValue(1).into(),
+        res: TestSSAValue(2).into(),
+    };
+
+    let mut visitor = RecordingVisitor::default();
+    op.walk(&mut visitor);
+
+    assert_eq!(visitor.ssa_values, vec![TestSSAValue(1).into()]);
+}
+
+#[test]
+fn test_walk_separates_operands_from_results() {
+    let op = VisitOp {
+        arg: TestSSAValue(1).into(),
+        res: TestSSAValue(2).into(),
+    };
+
+    let mut operands = Vec::new();
+    op.walk_operands(&mut |v| operands.push(*v));
+    assert_eq!(operands, vec![TestSSAValue(1).into()]);
+
+    let mut results = Vec::new();
+    op.walk_results(&mut |v| results.push(*v));
+    assert_eq!(results, vec![TestSSAValue(2).into()]);
+}
+
+#[test]
+fn test_foldable_rewrites_operand_in_place() {
+    let op = VisitOp {
+        arg: TestSSAValue(1).into(),
+        res: TestSSAValue(2).into(),
+    };
+
+    struct Remap;
+    impl StatementFolder<VisitOp> for Remap {
+        fn fold_ssa_value(&mut self, _value: SSAValue) -> SSAValue {
+            TestSSAValue(99).into()
+        }
+    }
+
+    let folded = op.fold_with(&mut Remap);
+    assert_eq!(folded.arg, TestSSAValue(99).into());
+    assert_eq!(folded.res, TestSSAValue(2).into());
+}
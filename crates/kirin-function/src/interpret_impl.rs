@@ -78,6 +78,7 @@ where
             .ok_or_else(|| InterpreterError::UnknownFunctionTarget {
                 name: format!("{:?}", self.target()),
                 stage: stage_id,
+                suggestion: None,
             })?;
 
         let function = interp
@@ -93,9 +94,23 @@ where
                     None
                 }
             })
-            .ok_or_else(|| InterpreterError::UnknownFunctionTarget {
-                name: target_name.clone(),
-                stage: stage_id,
+            .ok_or_else(|| {
+                let defined_names: Vec<String> = interp
+                    .pipeline()
+                    .function_arena()
+                    .iter()
+                    .filter_map(|info| interp.pipeline().resolve(info.name()?).cloned())
+                    .collect();
+                InterpreterError::UnknownFunctionTarget {
+                    name: target_name.clone(),
+                    stage: stage_id,
+                    suggestion: kirin_interpreter::did_you_mean(
+                        &target_name,
+                        defined_names.iter().map(String::as_str),
+                        2,
+                    )
+                    .map(str::to_owned),
+                }
             })?;
 
         let function_info = interp.pipeline().function_info(function).ok_or(
@@ -40,8 +40,13 @@ pub enum InterpreterError {
         stage: CompileStage,
     },
     /// No abstract function with the requested symbolic name exists.
-    #[error("unknown function target '{name}' at stage {stage:?}")]
-    UnknownFunctionTarget { name: String, stage: CompileStage },
+    #[error("unknown function target '{name}' at stage {stage:?}{}", fmt_suggestion(suggestion))]
+    UnknownFunctionTarget {
+        name: String,
+        stage: CompileStage,
+        /// The closest known function name, if one is within a small edit distance.
+        suggestion: Option<String>,
+    },
     /// No live specialization exists for the requested staged function/stage pair.
     #[error("no live specialization for staged function {staged_function:?} at stage {stage:?}")]
     NoSpecializationAtStage {
@@ -80,3 +85,46 @@ impl InterpreterError {
         InterpreterError::Custom(Box::new(error))
     }
 }
+
+fn fmt_suggestion(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(name) => format!(" (did you mean '{name}'?)"),
+        None => String::new(),
+    }
+}
+
+/// Levenshtein edit distance between two strings, used to suggest a close
+/// match when a lookup by name fails (e.g. [`InterpreterError::UnknownFunctionTarget`]).
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let tmp = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j]).min(row[j + 1])
+            };
+            prev_diag = tmp;
+        }
+    }
+    row[b.len()]
+}
+
+/// Find the closest match to `name` among `candidates` within `max_distance` edits.
+pub fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    max_distance: usize,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, levenshtein_distance(name, candidate)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
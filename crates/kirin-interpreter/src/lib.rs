@@ -5,6 +5,7 @@ mod eval;
 mod frame;
 mod interpretable;
 mod interpreter;
+mod repl;
 mod result;
 mod stack;
 mod value;
@@ -12,11 +13,12 @@ mod widening;
 
 pub use abstract_interp::{AbstractInterpreter, FixpointState, SummaryCache, SummaryEntry};
 pub use control::{AbstractContinuation, Args, ConcreteContinuation, ConcreteExt, Continuation};
-pub use error::InterpreterError;
+pub use error::{InterpreterError, did_you_mean};
 pub use eval::{BlockExecutor, CallSemantics, SSACFGRegion};
 pub use frame::Frame;
 pub use interpretable::Interpretable;
 pub use interpreter::Interpreter;
+pub use repl::{ReplDialect, ReplOutcome, ReplSession};
 pub use result::AnalysisResult;
 pub use stack::StackInterpreter;
 pub use value::{AbstractValue, BranchCondition};
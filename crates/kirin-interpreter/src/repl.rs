@@ -0,0 +1,203 @@
+//! Interactive REPL driver for incrementally building and evaluating IR,
+//! in the style of Schala's cross-language REPL -- including its multiline
+//! continuation handling for input that hasn't balanced yet.
+//!
+//! A [`ReplSession`] keeps a [`Pipeline`] reference and the active
+//! [`CompileStage`] alive across calls to [`ReplSession::feed`], so SSA
+//! bindings made by one line of input stay referenceable by the next.
+//! Parsing and appending a line is dialect-specific (implement
+//! [`ReplDialect`] and register it with [`ReplSession::register_dialect`]);
+//! actually evaluating a terminating expression and pretty-printing the
+//! result is left to the caller, which already owns the concrete
+//! [`crate::StackInterpreter`] for the value type in play -- [`ReplSession`]
+//! only tells it *what* to call, via [`ReplOutcome::Evaluate`].
+
+use std::collections::HashMap;
+
+use kirin_ir::{CompileStage, Dialect, Function, HasStageInfo, Pipeline, SpecializedFunction, StageInfo};
+
+/// Dialect-specific hook that turns one line of REPL input into IR appended
+/// to the implicit entry block of `function`.
+pub trait ReplDialect<L: Dialect> {
+    /// Parse `line` and append it to `function`'s entry block within `stage`.
+    ///
+    /// Returns the [`SpecializedFunction`] to evaluate if `line` was a
+    /// terminating expression, or `None` if it only declared a binding.
+    fn repl_eval(
+        stage: &StageInfo<L>,
+        function: Function,
+        line: &str,
+    ) -> Result<Option<SpecializedFunction>, String>;
+}
+
+/// What happened after feeding a line to [`ReplSession::feed`].
+#[derive(Debug)]
+pub enum ReplOutcome {
+    /// The input so far has an unmatched `{`/`(`/`[`; prompt for another line.
+    NeedsMore,
+    /// A binding was appended but nothing needs evaluating yet.
+    Declared,
+    /// `line` completed a terminating expression; the caller should
+    /// evaluate `callee` (e.g. via `StackInterpreter::call`) and
+    /// pretty-print the result itself, since [`ReplSession`] doesn't know
+    /// the interpreter's value type.
+    Evaluate(SpecializedFunction),
+    /// The `:dump` command; the caller should render this function's IR
+    /// (e.g. via `kirin_prettyless::PipelineDocument::render_function`).
+    DumpRequested(Function),
+    /// The `:dialect <name>` command switched the active stage.
+    DialectSwitched(CompileStage),
+    /// Parsing, a command, or dialect dispatch failed.
+    Error(String),
+}
+
+type ReplHandler<S> =
+    Box<dyn Fn(&S, Function, &str) -> Result<Option<SpecializedFunction>, String>>;
+
+struct RegisteredDialect<S> {
+    stage: CompileStage,
+    handler: ReplHandler<S>,
+}
+
+/// Count of unmatched opening `{`/`(`/`[` across the buffered input so far.
+///
+/// A simplistic stand-in for real bracket-aware lexing -- good enough to
+/// tell the REPL "this looks unfinished" without a full parse.
+fn unmatched_brackets(text: &str) -> i64 {
+    let mut depth = 0i64;
+    for c in text.chars() {
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Interactive session over a single [`Pipeline`], dispatching each line of
+/// input to whichever dialect is registered for the active stage.
+pub struct ReplSession<'ir, S> {
+    pipeline: &'ir Pipeline<S>,
+    stage: CompileStage,
+    function: Function,
+    buffer: String,
+    dialects: HashMap<String, RegisteredDialect<S>>,
+}
+
+impl<'ir, S> ReplSession<'ir, S> {
+    /// Start a session rooted at `stage`, appending to `function`'s body.
+    pub fn new(pipeline: &'ir Pipeline<S>, stage: CompileStage, function: Function) -> Self {
+        Self {
+            pipeline,
+            stage,
+            function,
+            buffer: String::new(),
+            dialects: HashMap::new(),
+        }
+    }
+
+    /// The compilation stage currently active for parsing/evaluation.
+    pub fn stage(&self) -> CompileStage {
+        self.stage
+    }
+
+    /// The function new input is appended to.
+    pub fn function(&self) -> Function {
+        self.function
+    }
+
+    /// Register dialect `L`'s [`ReplDialect::repl_eval`] under `name`, for
+    /// use whenever `stage` is active or named by `:dialect <name>`.
+    pub fn register_dialect<L>(&mut self, name: impl Into<String>, stage: CompileStage)
+    where
+        L: Dialect + ReplDialect<L> + 'static,
+        S: HasStageInfo<L> + 'static,
+    {
+        let handler: ReplHandler<S> = Box::new(|stage_container, function, line| {
+            let stage_info = <S as HasStageInfo<L>>::try_stage_info(stage_container)
+                .expect("dialect registered against a stage that doesn't hold its StageInfo");
+            L::repl_eval(stage_info, function, line)
+        });
+        self.dialects.insert(
+            name.into(),
+            RegisteredDialect { stage, handler },
+        );
+    }
+
+    /// Feed one line of input.
+    ///
+    /// Lines starting with `:` are REPL commands (`:dump`, `:dialect
+    /// <name>`); anything else accumulates in an internal buffer until its
+    /// brackets balance, then is parsed and (if it's a terminating
+    /// expression) handed back to the caller to evaluate.
+    pub fn feed(&mut self, line: &str) -> ReplOutcome {
+        if let Some(command) = line.trim().strip_prefix(':') {
+            return self.command(command.trim());
+        }
+
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+        if unmatched_brackets(&self.buffer) > 0 {
+            return ReplOutcome::NeedsMore;
+        }
+
+        let input = std::mem::take(&mut self.buffer);
+        let Some(dialect) = self
+            .dialects
+            .values()
+            .find(|dialect| dialect.stage == self.stage)
+        else {
+            return ReplOutcome::Error("no dialect registered for the active stage".to_string());
+        };
+        let Some(stage_container) = self.pipeline.stage(self.stage) else {
+            return ReplOutcome::Error("active stage no longer exists in the pipeline".to_string());
+        };
+
+        match (dialect.handler)(stage_container, self.function, input.trim_end()) {
+            Ok(Some(callee)) => ReplOutcome::Evaluate(callee),
+            Ok(None) => ReplOutcome::Declared,
+            Err(message) => ReplOutcome::Error(message),
+        }
+    }
+
+    fn command(&mut self, command: &str) -> ReplOutcome {
+        let mut parts = command.split_whitespace();
+        match parts.next() {
+            Some("dump") => ReplOutcome::DumpRequested(self.function),
+            Some("dialect") => match parts.next() {
+                Some(name) => match self.dialects.get(name) {
+                    Some(dialect) => {
+                        self.stage = dialect.stage;
+                        ReplOutcome::DialectSwitched(self.stage)
+                    }
+                    None => ReplOutcome::Error(format!("unknown dialect `{name}`")),
+                },
+                None => ReplOutcome::Error("usage: :dialect <name>".to_string()),
+            },
+            Some(other) => ReplOutcome::Error(format!("unknown command `:{other}`")),
+            None => ReplOutcome::Error("empty command".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmatched_brackets_balanced() {
+        assert_eq!(unmatched_brackets("%a = add %b, %c"), 0);
+        assert_eq!(unmatched_brackets("{ %a = add %b, %c }"), 0);
+    }
+
+    #[test]
+    fn test_unmatched_brackets_open() {
+        assert_eq!(unmatched_brackets("region { block {"), 2);
+    }
+
+    #[test]
+    fn test_unmatched_brackets_closed_more_than_opened() {
+        assert_eq!(unmatched_brackets("} }"), -2);
+    }
+}
@@ -13,11 +13,31 @@ impl Id {
     pub fn raw(self) -> usize {
         self.0
     }
+
+    /// Reconstruct an `Id` from a raw `usize`, the inverse of [`Id::raw`].
+    ///
+    /// Unlike `arena.next_id()`/`arena.insert`, this does not allocate a slot
+    /// in any arena — it is meant for rebuilding an [`Identifier`] that was
+    /// previously written out as a raw handle (e.g. by `#[kirin(serialize)]`)
+    /// against whatever arena it is being reloaded into.
+    pub fn from_raw(raw: usize) -> Self {
+        Id(raw)
+    }
 }
 
 pub trait Identifier:
     Sized + Clone + Copy + Hash + std::fmt::Debug + PartialEq + Eq + From<Id> + Into<Id>
 {
+    /// The stable integer handle for this ID, the inverse of [`Identifier::from_handle`].
+    fn to_handle(self) -> usize {
+        self.into().raw()
+    }
+
+    /// Reconstructs an identifier from a handle previously produced by
+    /// [`Identifier::to_handle`], without allocating a slot in any arena.
+    fn from_handle(handle: usize) -> Self {
+        Id::from_raw(handle).into()
+    }
 }
 
 pub trait GetInfo<L: Dialect>: std::fmt::Debug {
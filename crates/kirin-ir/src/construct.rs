@@ -0,0 +1,142 @@
+//! Reconstructing a [`Context`] from a parsed surface-syntax tree -- the
+//! inverse of pretty-printing, mirroring how `rewrite`/`verify` walk an
+//! already-built tree but running bottom-up over an as-yet-unbuilt one.
+//!
+//! This module only establishes the extension point: [`ConstructFromAst`] is
+//! hand-implemented per dialect, the same way [`TypeSignature`](crate::verify::TypeSignature)
+//! and [`StructuralEq`](crate::cse::StructuralEq) are -- deriving it from a
+//! `#[kirin(format = "...")]` string the same way the printer side is
+//! generated is future work, not this chunk's. [`Context::insert_region`]/
+//! [`Context::insert_block`] extend [`Context::insert_statement`]'s raw
+//! arena-insertion path (see `crate::cse`) to regions and blocks, and
+//! [`Scope`] is the name -> value table a construction pass threads through
+//! a block body so that `%name` references resolve to the `SSAValue` that
+//! defined them, re-interning every name through [`Context::symbol_table`]
+//! so identity survives a print/parse round trip.
+//!
+//! A full `parse_function`/`parse_module` entry point additionally needs a
+//! concrete surface-syntax parser producing the `Ast` this module consumes.
+//! `kirin_chumsky`'s `region`/`block` combinators parse into their own
+//! `Region`/`Block` AST structs, but lowering those into a `Context` needs
+//! the `EmitIR`/`EmitContext` traits `kirin_chumsky::function_text` and
+//! `kirin_test_utils::roundtrip` already reference -- neither is defined
+//! anywhere in this tree, and the `Pipeline`/`StageInfo` types those call
+//! sites otherwise depend on (`kirin-ir/src/pipeline.rs`,
+//! `kirin-ir/src/stage_dispatch.rs`) exist on disk but aren't declared in
+//! this crate's `lib.rs`, and assume a `Context` shape (a `signature`
+//! module, a different `StageInfo`-based arena layout) that has since
+//! diverged from this tree's actual `Context`. Wiring a chumsky-backed
+//! `parse_function` through to this module is follow-up work once that's
+//! sorted out, not something this chunk can respray over without guessing
+//! at module layouts that don't exist here.
+
+use std::collections::HashMap;
+
+use crate::arena::GetInfo;
+use crate::node::region::RegionInfo;
+use crate::{
+    Block, BlockArgument, BlockInfo, Context, Dialect, LinkedListNode, Region, SSAInfo, SSAKind, SSAValue,
+    Statement, Symbol,
+};
+
+/// A located construction failure, reported the same way
+/// [`Verifier`](crate::verify::Verifier) reports type errors, but for a tree
+/// that doesn't have `Statement` handles yet to blame.
+pub use crate::verify::ConstructError;
+
+/// Per-block name resolution during construction: maps a surface-syntax
+/// `%name` to the [`SSAValue`] that binds it (a block argument or an
+/// earlier statement's result), re-interned through
+/// [`Context::symbol_table`] so a name round-trips to the same [`Symbol`]
+/// it printed as, not just an equal string.
+#[derive(Default)]
+pub struct Scope {
+    bindings: HashMap<Symbol, SSAValue>,
+}
+
+impl Scope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds `name` to `value`, re-interning `name` through `context`'s
+    /// symbol table first. Shadows any earlier binding of the same name,
+    /// matching how a re-declared SSA name shadows in the surface syntax.
+    pub fn bind<L: Dialect>(&mut self, context: &Context<L>, name: &str, value: SSAValue) {
+        let symbol = context.symbol_table().borrow_mut().intern(name.to_string());
+        self.bindings.insert(symbol, value);
+    }
+
+    /// Resolves `name` against the current scope, interning it the same way
+    /// [`Scope::bind`] does so a name that was never bound still compares
+    /// against the right [`Symbol`] key (and simply misses).
+    pub fn resolve<L: Dialect>(&self, context: &Context<L>, name: &str) -> Option<SSAValue> {
+        let symbol = context.symbol_table().borrow_mut().intern(name.to_string());
+        self.bindings.get(&symbol).copied()
+    }
+}
+
+/// Lowers a dialect's own parsed surface-syntax representation (`Self`) into
+/// a statement definition `L`, allocating into `context` as needed (e.g. via
+/// [`Context::insert_block`]/[`Context::insert_region`] for nested
+/// `Block`/`Region` fields, the same way a hand-rolled builder would).
+/// Implemented once per dialect on whatever AST type its parser produces;
+/// see this module's doc comment for why that parser isn't wired up yet.
+pub trait ConstructFromAst<L: Dialect> {
+    /// Construct `L`, resolving `%name` operand references against `scope`
+    /// and binding any names this node itself introduces (e.g. a block's
+    /// own arguments) before resolving its body.
+    fn construct(
+        self,
+        context: &mut Context<L>,
+        scope: &mut Scope,
+        parent: Option<Block>,
+    ) -> Result<L, ConstructError>;
+}
+
+impl<L: Dialect> Context<L> {
+    /// Allocates a new, empty region under `parent`. Mirrors
+    /// [`Context::insert_statement`]'s raw insertion path: callers are
+    /// responsible for pushing blocks into it with
+    /// [`Context::insert_block`], and for threading the returned handle into
+    /// whichever statement field owns it.
+    pub fn insert_region(&mut self, parent: Option<Statement>) -> Region {
+        let id = self.regions.next_id();
+        self.regions
+            .alloc(RegionInfo::builder().id(id).maybe_parent(parent).blocks(Default::default()).new());
+        id
+    }
+
+    /// Allocates a new block with `argument_types.len()` fresh
+    /// [`BlockArgument`] values, appended to the back of `parent`'s block
+    /// list.
+    pub fn insert_block(&mut self, parent: Region, argument_types: Vec<L::TypeLattice>) -> Block {
+        let id = self.blocks.next_id();
+        let arguments = argument_types
+            .into_iter()
+            .enumerate()
+            .map(|(index, ty)| {
+                let value: BlockArgument = self.ssas.next_id().into();
+                self.ssas
+                    .alloc(SSAInfo::new(value.into(), None, ty, SSAKind::BlockArgument(id, index)));
+                value
+            })
+            .collect();
+        self.blocks.alloc(
+            BlockInfo::builder()
+                .parent(parent)
+                .node(LinkedListNode::new(id))
+                .arguments(arguments)
+                .new(),
+        );
+
+        let mut blocks = {
+            let info = parent.expect_info_mut(self);
+            std::mem::take(&mut info.blocks)
+        };
+        blocks.push_back(self, id);
+        parent.expect_info_mut(self).blocks = blocks;
+
+        id
+    }
+}
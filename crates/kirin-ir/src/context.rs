@@ -1,7 +1,9 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::arena::Arena;
+use crate::cse::StructuralKey;
 use crate::node::region::RegionInfo;
 use crate::{Dialect, InternTable, node::*};
 
@@ -13,6 +15,11 @@ pub struct Context<L: Dialect> {
     pub(crate) statements: Arena<Statement, StatementInfo<L>>,
     pub(crate) ssas: Arena<SSAValue, SSAInfo<L>>,
     pub(crate) symbols: Arc<RefCell<InternTable<String, Symbol>>>,
+    /// Structural-interning table for [`Context::intern_statement`]; see
+    /// `crate::cse`. Keyed on a statement's shape, valued on the statement
+    /// it was first interned as plus its results, so a stale entry whose
+    /// statement has since been erased can be detected and evicted lazily.
+    pub(crate) cse_table: HashMap<StructuralKey<L>, (Statement, Vec<ResultValue>)>,
 }
 
 impl<L> Default for Context<L>
@@ -27,6 +34,7 @@ where
             statements: Arena::default(),
             ssas: Arena::default(),
             symbols: Arc::new(RefCell::new(InternTable::default())),
+            cse_table: HashMap::default(),
         }
     }
 }
@@ -36,6 +44,7 @@ where
     L: Dialect,
     StatementInfo<L>: Clone,
     SSAInfo<L>: Clone,
+    StructuralKey<L>: Clone,
 {
     fn clone(&self) -> Self {
         Self {
@@ -45,6 +54,7 @@ where
             statements: self.statements.clone(),
             ssas: self.ssas.clone(),
             symbols: self.symbols.clone(),
+            cse_table: self.cse_table.clone(),
         }
     }
 }
@@ -0,0 +1,372 @@
+//! Structural interning / CSE for pure statements, analogous to fayalite's
+//! `intern` module for structural types. `Context::symbols` already interns
+//! *names*; this interns *statement shapes* so a second structurally
+//! identical pure statement reuses the first one's results instead of
+//! allocating a new statement and new SSA values.
+
+use std::hash::{Hash, Hasher};
+
+use crate::arena::Item;
+use crate::{
+    Block, Context, Dialect, HasResultsMut, IsPure, IsTerminator, LinkedListNode, ResultValue, SSAInfo,
+    SSAKind, Statement, StatementInfo,
+};
+
+/// Structural equality over a statement's identity for CSE purposes: its own
+/// shape plus its *resolved* operand handles. Deliberately shallow -- it
+/// compares what an operand's `SSAValue` handle already is, not what
+/// produced it, so the check stays O(1) and stable across the arena instead
+/// of recursing through producers. Result fields are not part of a
+/// statement's identity and must be excluded. Implement by hand, or
+/// generate from `#[derive(Dialect)]` the same way `Visitable`/`Walk` are
+/// (see `kirin_derive_core::kirin::visit`).
+pub trait StructuralEq {
+    fn structural_eq(&self, other: &Self) -> bool;
+}
+
+/// [`StructuralEq`]'s hashing counterpart. Must agree with it: structurally
+/// equal statements must hash equal.
+pub trait StructuralHash {
+    fn structural_hash<H: Hasher>(&self, state: &mut H);
+}
+
+/// Wraps a dialect value so a [`HashMap`] can key on it by [`StructuralEq`]/
+/// [`StructuralHash`] instead of field-by-field `PartialEq`/`Hash` (which
+/// would also compare the statement's own result handles, defeating CSE).
+pub(crate) struct StructuralKey<L>(pub(crate) L);
+
+impl<L: StructuralHash> Hash for StructuralKey<L> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.structural_hash(state);
+    }
+}
+
+impl<L: StructuralEq> PartialEq for StructuralKey<L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.structural_eq(&other.0)
+    }
+}
+
+impl<L: StructuralEq> Eq for StructuralKey<L> {}
+
+impl<L: Clone> Clone for StructuralKey<L> {
+    fn clone(&self) -> Self {
+        StructuralKey(self.0.clone())
+    }
+}
+
+impl<L: std::fmt::Debug> std::fmt::Debug for StructuralKey<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StructuralKey").field(&self.0).finish()
+    }
+}
+
+/// What [`Context::intern_statement`] did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interned {
+    /// No structurally-identical pure statement existed yet; a new one was
+    /// allocated. Thread it into a block's statement list the same way
+    /// [`Context::insert_statement`]'s caller would.
+    Fresh(Statement),
+    /// A structurally-identical pure statement was already interned; no new
+    /// statement or SSA value was allocated.
+    Cached,
+}
+
+impl<L: Dialect> Context<L> {
+    /// Allocates `definition` as a new statement under `parent`, giving a
+    /// fresh SSA value to each of its result slots. Does not thread the
+    /// statement into `parent`'s block statement list, or set it as a
+    /// terminator -- callers splice it in with [`LinkedList`](crate::LinkedList)
+    /// the same way any other statement is. This is the raw insertion path;
+    /// see [`Context::intern_statement`] for the content-addressed one.
+    pub fn insert_statement(
+        &mut self,
+        parent: Option<Block>,
+        mut definition: L,
+        result_types: Vec<L::TypeLattice>,
+    ) -> (Statement, Vec<ResultValue>) {
+        let id = self.statements.next_id();
+        let mut result_types = result_types.into_iter();
+        let mut results = Vec::new();
+        for slot in definition.results_mut() {
+            let ty = result_types
+                .next()
+                .unwrap_or_else(|| panic!("insert_statement: fewer result types than {id:?} has results"));
+            let value: ResultValue = self.ssas.next_id().into();
+            self.ssas
+                .alloc(SSAInfo::new(value.into(), None, ty, SSAKind::Result(id, results.len())));
+            *slot = value;
+            results.push(value);
+        }
+        self.statements.alloc(StatementInfo {
+            node: LinkedListNode::new(id),
+            parent,
+            definition,
+        });
+        (id, results)
+    }
+}
+
+impl<L: Dialect + StructuralEq + StructuralHash> Context<L> {
+    /// [`Context::insert_statement`], but content-addressed: if a
+    /// structurally identical statement whose [`IsPure::is_pure`] holds was
+    /// already interned and hasn't since been erased, its existing results
+    /// are returned and nothing new is allocated.
+    ///
+    /// `definition`'s operand fields must already hold their final,
+    /// resolved `SSAValue` handles -- the key hashes those handles directly
+    /// rather than recursing into what produced them, so it can't see past
+    /// an operand that hasn't itself been interned yet. Statements for which
+    /// `is_pure()` is `false`, or that are terminators (mirroring the same
+    /// carve-out [`EraseDeadCode`](crate::rewrite::EraseDeadCode) makes --
+    /// every block needs its own), always fall back to a fresh insertion.
+    ///
+    /// The cache is scoped to the whole `Context`, not to `parent`, so it's
+    /// only safe to reuse a cached result where it's known to dominate the
+    /// new use -- e.g. repeated calls while building a single block
+    /// top-to-bottom, or interning truly global values (constants) in a
+    /// single-entry function. Interning structurally identical statements
+    /// built independently for sibling branches can hand back a result that
+    /// doesn't dominate one of them; this is a caller contract, not
+    /// something `intern_statement` can check from the definition alone.
+    pub fn intern_statement(
+        &mut self,
+        parent: Option<Block>,
+        definition: L,
+        result_types: Vec<L::TypeLattice>,
+    ) -> (Interned, Vec<ResultValue>) {
+        let cacheable = definition.is_pure() && !definition.is_terminator();
+        let key = cacheable.then(|| StructuralKey(definition.clone()));
+        if let Some(key) = &key {
+            if let Some((stmt, results)) = self.cse_table.get(key) {
+                if !self.statement_arena().get(*stmt).map(Item::deleted).unwrap_or(true) {
+                    return (Interned::Cached, results.clone());
+                }
+                self.cse_table.remove(key);
+            }
+        }
+        let (stmt, results) = self.insert_statement(parent, definition, result_types);
+        if let Some(key) = key {
+            self.cse_table.insert(key, (stmt, results.clone()));
+        }
+        (Interned::Fresh(stmt), results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena::Id;
+    use crate::comptime::CompileTimeValue;
+    use crate::lattice::{HasBottom, HasTop, Lattice, TypeLattice};
+    use crate::{HasArguments, HasArgumentsMut, HasRegions, HasRegionsMut, HasSuccessors, HasSuccessorsMut, IsConstant};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    struct TestType;
+
+    impl Lattice for TestType {
+        fn join(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn meet(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn is_subseteq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl HasBottom for TestType {
+        fn bottom() -> Self {
+            TestType
+        }
+    }
+
+    impl HasTop for TestType {
+        fn top() -> Self {
+            TestType
+        }
+    }
+
+    impl CompileTimeValue for TestType {}
+    impl TypeLattice for TestType {}
+
+    /// A statement shape with three variants exercising the three cache
+    /// outcomes: a pure, non-terminator op (cacheable), an impure op (never
+    /// cacheable), and a terminator (never cacheable regardless of purity).
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    enum TestOp {
+        Pure(i64, ResultValue),
+        Impure(i64, ResultValue),
+        Term(i64),
+    }
+
+    impl<'a> HasArguments<'a> for TestOp {
+        type Iter = std::iter::Empty<&'a crate::SSAValue>;
+        fn arguments(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasArgumentsMut<'a> for TestOp {
+        type IterMut = std::iter::Empty<&'a mut crate::SSAValue>;
+        fn arguments_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> crate::HasResults<'a> for TestOp {
+        type Iter = std::option::IntoIter<&'a ResultValue>;
+        fn results(&'a self) -> Self::Iter {
+            match self {
+                TestOp::Pure(_, r) | TestOp::Impure(_, r) => Some(r),
+                TestOp::Term(_) => None,
+            }
+            .into_iter()
+        }
+    }
+
+    impl<'a> HasResultsMut<'a> for TestOp {
+        type IterMut = std::option::IntoIter<&'a mut ResultValue>;
+        fn results_mut(&'a mut self) -> Self::IterMut {
+            match self {
+                TestOp::Pure(_, r) | TestOp::Impure(_, r) => Some(r),
+                TestOp::Term(_) => None,
+            }
+            .into_iter()
+        }
+    }
+
+    impl<'a> HasSuccessors<'a> for TestOp {
+        type Iter = std::iter::Empty<&'a Block>;
+        fn successors(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasSuccessorsMut<'a> for TestOp {
+        type IterMut = std::iter::Empty<&'a mut Block>;
+        fn successors_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasRegions<'a> for TestOp {
+        type Iter = std::iter::Empty<&'a crate::Region>;
+        fn regions(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasRegionsMut<'a> for TestOp {
+        type IterMut = std::iter::Empty<&'a mut crate::Region>;
+        fn regions_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+
+    impl IsTerminator for TestOp {
+        fn is_terminator(&self) -> bool {
+            matches!(self, TestOp::Term(_))
+        }
+    }
+
+    impl IsConstant for TestOp {
+        fn is_constant(&self) -> bool {
+            false
+        }
+    }
+
+    impl IsPure for TestOp {
+        fn is_pure(&self) -> bool {
+            !matches!(self, TestOp::Impure(_, _))
+        }
+    }
+
+    impl Dialect for TestOp {
+        type TypeLattice = TestType;
+    }
+
+    impl StructuralEq for TestOp {
+        fn structural_eq(&self, other: &Self) -> bool {
+            match (self, other) {
+                (TestOp::Pure(a, _), TestOp::Pure(b, _)) => a == b,
+                (TestOp::Impure(a, _), TestOp::Impure(b, _)) => a == b,
+                (TestOp::Term(a), TestOp::Term(b)) => a == b,
+                _ => false,
+            }
+        }
+    }
+
+    impl StructuralHash for TestOp {
+        fn structural_hash<H: Hasher>(&self, state: &mut H) {
+            match self {
+                TestOp::Pure(a, _) => {
+                    0u8.hash(state);
+                    a.hash(state);
+                }
+                TestOp::Impure(a, _) => {
+                    1u8.hash(state);
+                    a.hash(state);
+                }
+                TestOp::Term(a) => {
+                    2u8.hash(state);
+                    a.hash(state);
+                }
+            }
+        }
+    }
+
+    fn dummy_result() -> ResultValue {
+        ResultValue::from(Id::from_raw(0))
+    }
+
+    #[test]
+    fn fresh_insert_then_cache_hit() {
+        let mut context: Context<TestOp> = Context::default();
+        let (first, first_results) =
+            context.intern_statement(None, TestOp::Pure(1, dummy_result()), vec![TestType]);
+        assert!(matches!(first, Interned::Fresh(_)));
+
+        let (second, second_results) =
+            context.intern_statement(None, TestOp::Pure(1, dummy_result()), vec![TestType]);
+        assert_eq!(second, Interned::Cached);
+        assert_eq!(second_results, first_results);
+    }
+
+    #[test]
+    fn cache_entry_evicted_after_erase() {
+        let mut context: Context<TestOp> = Context::default();
+        let (first, _) =
+            context.intern_statement(None, TestOp::Pure(1, dummy_result()), vec![TestType]);
+        let Interned::Fresh(stmt) = first else {
+            panic!("expected a fresh statement");
+        };
+        context.statements.delete(stmt);
+
+        let (second, _) =
+            context.intern_statement(None, TestOp::Pure(1, dummy_result()), vec![TestType]);
+        assert!(matches!(second, Interned::Fresh(_)));
+    }
+
+    #[test]
+    fn impure_statements_bypass_cache() {
+        let mut context: Context<TestOp> = Context::default();
+        let (first, _) =
+            context.intern_statement(None, TestOp::Impure(1, dummy_result()), vec![TestType]);
+        let (second, _) =
+            context.intern_statement(None, TestOp::Impure(1, dummy_result()), vec![TestType]);
+        assert!(matches!(first, Interned::Fresh(_)));
+        assert!(matches!(second, Interned::Fresh(_)));
+    }
+
+    #[test]
+    fn terminators_bypass_cache() {
+        let mut context: Context<TestOp> = Context::default();
+        let (first, _) = context.intern_statement(None, TestOp::Term(1), vec![]);
+        let (second, _) = context.intern_statement(None, TestOp::Term(1), vec![]);
+        assert!(matches!(first, Interned::Fresh(_)));
+        assert!(matches!(second, Interned::Fresh(_)));
+    }
+}
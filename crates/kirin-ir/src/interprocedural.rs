@@ -0,0 +1,421 @@
+//! Interprocedural fixpoint analysis driven by [`SpecializedFunctionInfo::backedges`].
+//!
+//! [`analyze`] builds the call graph directly off each function's
+//! `backedges` (a callee's own list of its callers), partitions it into
+//! strongly connected components with Tarjan's algorithm (a single DFS
+//! maintaining per-node `index`/`lowlink`, an on-stack flag, and an explicit
+//! stack), and processes the components in the order Tarjan discovers them
+//! -- reverse-topological order of the condensation. An acyclic function
+//! gets exactly one [`Transfer::transfer`] call; a recursive SCC is driven
+//! to a fixpoint with a worklist seeded from its own members, re-enqueuing a
+//! function's callers (via `backedges`) whenever its summary changes. The
+//! lattice and the transfer function are both generic, so the same driver
+//! serves escape analysis, purity inference, return-type refinement, or
+//! anything else shaped like "a per-function summary that depends on its
+//! callees' summaries".
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::language::Dialect;
+use crate::lattice::{HasBottom, Lattice};
+use crate::node::function::{Signature, SpecializedFunction, SpecializedFunctionInfo};
+use crate::node::stmt::Statement;
+
+/// Computes a function's summary from its own signature/body and the
+/// current summaries of the functions it calls, keyed by [`SpecializedFunction`].
+/// A callee with no entry yet -- e.g. another member of the same recursive
+/// SCC, mid-fixpoint -- is simply absent from the map; `transfer` should
+/// treat a missing callee as [`HasBottom::bottom`].
+pub trait Transfer<L: Dialect> {
+    type Summary: Lattice + HasBottom + Clone + PartialEq;
+
+    fn transfer(
+        &mut self,
+        signature: &Signature<L>,
+        body: &Statement,
+        callee_summaries: &HashMap<SpecializedFunction, Self::Summary>,
+    ) -> Self::Summary;
+}
+
+/// Runs `transfer` to a fixpoint over `functions`, returning each function's
+/// final summary.
+pub fn analyze<L, T>(
+    functions: &[SpecializedFunctionInfo<L>],
+    mut transfer: T,
+) -> HashMap<SpecializedFunction, T::Summary>
+where
+    L: Dialect,
+    T: Transfer<L>,
+{
+    let by_id: HashMap<SpecializedFunction, &SpecializedFunctionInfo<L>> =
+        functions.iter().map(|info| (info.id(), info)).collect();
+
+    // callee -> callers, read straight off `backedges`; callers outside
+    // `functions` are dropped since the driver has no info to analyze them.
+    let graph: HashMap<SpecializedFunction, Vec<SpecializedFunction>> = by_id
+        .keys()
+        .map(|&callee| {
+            let callers = by_id[&callee]
+                .backedges()
+                .iter()
+                .filter(|caller| by_id.contains_key(caller))
+                .copied()
+                .collect();
+            (callee, callers)
+        })
+        .collect();
+
+    let mut summaries: HashMap<SpecializedFunction, T::Summary> = HashMap::new();
+
+    for scc in tarjan_scc(&graph) {
+        if scc.len() == 1 && !graph[&scc[0]].contains(&scc[0]) {
+            // acyclic singleton: one-shot transfer, no fixpoint needed.
+            let id = scc[0];
+            let info = by_id[&id];
+            let summary = transfer.transfer(info.signature(), info.body(), &summaries);
+            summaries.insert(id, summary);
+            continue;
+        }
+
+        // recursive SCC: seed every member at bottom, then worklist until
+        // no member's summary changes.
+        let members: HashSet<SpecializedFunction> = scc.iter().copied().collect();
+        for &id in &scc {
+            summaries.insert(id, T::Summary::bottom());
+        }
+
+        let mut queued: HashSet<SpecializedFunction> = members.clone();
+        let mut worklist: VecDeque<SpecializedFunction> = scc.into_iter().collect();
+
+        while let Some(id) = worklist.pop_front() {
+            queued.remove(&id);
+            let info = by_id[&id];
+            let new_summary = transfer.transfer(info.signature(), info.body(), &summaries);
+            let changed = summaries.get(&id) != Some(&new_summary);
+            summaries.insert(id, new_summary);
+
+            if changed {
+                for &caller in &graph[&id] {
+                    if members.contains(&caller) && queued.insert(caller) {
+                        worklist.push_back(caller);
+                    }
+                }
+            }
+        }
+    }
+
+    summaries
+}
+
+/// Tarjan's strongly connected components algorithm, returning SCCs in the
+/// order they're discovered (reverse-topological order of the condensation).
+fn tarjan_scc(
+    graph: &HashMap<SpecializedFunction, Vec<SpecializedFunction>>,
+) -> Vec<Vec<SpecializedFunction>> {
+    struct State {
+        index: HashMap<SpecializedFunction, usize>,
+        lowlink: HashMap<SpecializedFunction, usize>,
+        on_stack: HashSet<SpecializedFunction>,
+        stack: Vec<SpecializedFunction>,
+        next_index: usize,
+        sccs: Vec<Vec<SpecializedFunction>>,
+    }
+
+    fn strong_connect(
+        node: SpecializedFunction,
+        graph: &HashMap<SpecializedFunction, Vec<SpecializedFunction>>,
+        state: &mut State,
+    ) {
+        state.index.insert(node, state.next_index);
+        state.lowlink.insert(node, state.next_index);
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack.insert(node);
+
+        for &successor in &graph[&node] {
+            if !state.index.contains_key(&successor) {
+                strong_connect(successor, graph, state);
+                let successor_lowlink = state.lowlink[&successor];
+                let lowlink = state.lowlink.get_mut(&node).unwrap();
+                *lowlink = (*lowlink).min(successor_lowlink);
+            } else if state.on_stack.contains(&successor) {
+                let successor_index = state.index[&successor];
+                let lowlink = state.lowlink.get_mut(&node).unwrap();
+                *lowlink = (*lowlink).min(successor_index);
+            }
+        }
+
+        if state.lowlink[&node] == state.index[&node] {
+            let mut scc = Vec::new();
+            loop {
+                let member = state.stack.pop().unwrap();
+                state.on_stack.remove(&member);
+                scc.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            state.sccs.push(scc);
+        }
+    }
+
+    let mut state = State {
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        on_stack: HashSet::new(),
+        stack: Vec::new(),
+        next_index: 0,
+        sccs: Vec::new(),
+    };
+
+    for &node in graph.keys() {
+        if !state.index.contains_key(&node) {
+            strong_connect(node, graph, &mut state);
+        }
+    }
+
+    state.sccs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena::Id;
+    use crate::comptime::CompileTimeValue;
+    use crate::lattice::HasTop;
+    use crate::node::function::StagedFunction;
+    use crate::{HasArguments, HasArgumentsMut, HasRegions, HasRegionsMut, HasResults, HasResultsMut, HasSuccessors, HasSuccessorsMut, IsConstant, IsPure};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    struct TestType;
+
+    impl Lattice for TestType {
+        fn join(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn meet(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn is_subseteq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl HasBottom for TestType {
+        fn bottom() -> Self {
+            TestType
+        }
+    }
+
+    impl HasTop for TestType {
+        fn top() -> Self {
+            TestType
+        }
+    }
+
+    impl CompileTimeValue for TestType {}
+    impl crate::lattice::TypeLattice for TestType {}
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestLang;
+
+    impl<'a> HasArguments<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::SSAValue>;
+        fn arguments(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasArgumentsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::SSAValue>;
+        fn arguments_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasResults<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::ResultValue>;
+        fn results(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasResultsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::ResultValue>;
+        fn results_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasSuccessors<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::Block>;
+        fn successors(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasSuccessorsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::Block>;
+        fn successors_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasRegions<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::Region>;
+        fn regions(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasRegionsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::Region>;
+        fn regions_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl IsTerminator for TestLang {
+        fn is_terminator(&self) -> bool {
+            false
+        }
+    }
+    impl IsConstant for TestLang {
+        fn is_constant(&self) -> bool {
+            false
+        }
+    }
+    impl IsPure for TestLang {
+        fn is_pure(&self) -> bool {
+            true
+        }
+    }
+    impl Dialect for TestLang {
+        type TypeLattice = TestType;
+    }
+
+    fn specialized(n: usize) -> SpecializedFunction {
+        SpecializedFunction(StagedFunction::from(Id::from_raw(n)), 0)
+    }
+
+    #[test]
+    fn tarjan_scc_groups_acyclic_pair_as_singletons_and_recursive_pair_together() {
+        // a -> b (a calls b, so graph[b] = [a]); c and d call each other.
+        let (a, b, c, d) = (specialized(0), specialized(1), specialized(2), specialized(3));
+        let mut graph = HashMap::new();
+        graph.insert(a, vec![]);
+        graph.insert(b, vec![a]);
+        graph.insert(c, vec![d]);
+        graph.insert(d, vec![c]);
+
+        let sccs = tarjan_scc(&graph);
+
+        assert!(sccs.iter().any(|scc| scc.as_slice() == [a]));
+        assert!(sccs.iter().any(|scc| scc.as_slice() == [b]));
+        let cycle = sccs
+            .iter()
+            .find(|scc| scc.len() == 2)
+            .expect("the mutually-recursive pair should form one SCC");
+        let members: HashSet<_> = cycle.iter().copied().collect();
+        assert_eq!(members, HashSet::from([c, d]));
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Count(u8);
+
+    impl Lattice for Count {
+        fn join(&self, other: &Self) -> Self {
+            Count(self.0.max(other.0))
+        }
+        fn meet(&self, other: &Self) -> Self {
+            Count(self.0.min(other.0))
+        }
+        fn is_subseteq(&self, other: &Self) -> bool {
+            self.0 <= other.0
+        }
+    }
+
+    impl HasBottom for Count {
+        fn bottom() -> Self {
+            Count(0)
+        }
+    }
+
+    /// "One more than the deepest of my callees' summaries", capped at 2 so
+    /// a mutually-recursive pair is guaranteed to converge instead of
+    /// counting forever.
+    struct DepthTransfer {
+        callees: HashMap<Statement, Vec<SpecializedFunction>>,
+    }
+
+    impl Transfer<TestLang> for DepthTransfer {
+        type Summary = Count;
+
+        fn transfer(
+            &mut self,
+            _signature: &Signature<TestLang>,
+            body: &Statement,
+            callee_summaries: &HashMap<SpecializedFunction, Count>,
+        ) -> Count {
+            let deepest = self
+                .callees
+                .get(body)
+                .into_iter()
+                .flatten()
+                .map(|callee| callee_summaries.get(callee).copied().unwrap_or(Count::bottom()).0)
+                .max()
+                .unwrap_or(0);
+            Count((deepest + 1).min(2))
+        }
+    }
+
+    #[test]
+    fn analyze_gives_one_shot_acyclic_and_converges_recursive_cycle() {
+        let (fn_a, fn_b, fn_c, fn_d) = (specialized(0), specialized(1), specialized(2), specialized(3));
+        let (body_a, body_b, body_c, body_d) = (
+            Statement::from(Id::from_raw(10)),
+            Statement::from(Id::from_raw(11)),
+            Statement::from(Id::from_raw(12)),
+            Statement::from(Id::from_raw(13)),
+        );
+
+        let info_a = SpecializedFunctionInfo::builder()
+            .id(fn_a)
+            .signature(Signature(vec![]))
+            .return_type(TestType)
+            .body(body_a)
+            .new();
+        // a calls b, so b's backedges (its callers) record a.
+        let info_b = SpecializedFunctionInfo::builder()
+            .id(fn_b)
+            .signature(Signature(vec![]))
+            .return_type(TestType)
+            .body(body_b)
+            .backedges(vec![fn_a])
+            .new();
+        // c and d call each other.
+        let info_c = SpecializedFunctionInfo::builder()
+            .id(fn_c)
+            .signature(Signature(vec![]))
+            .return_type(TestType)
+            .body(body_c)
+            .backedges(vec![fn_d])
+            .new();
+        let info_d = SpecializedFunctionInfo::builder()
+            .id(fn_d)
+            .signature(Signature(vec![]))
+            .return_type(TestType)
+            .body(body_d)
+            .backedges(vec![fn_c])
+            .new();
+
+        let mut callees = HashMap::new();
+        callees.insert(body_a, vec![fn_b]);
+        callees.insert(body_c, vec![fn_d]);
+        callees.insert(body_d, vec![fn_c]);
+        let transfer = DepthTransfer { callees };
+
+        let functions = vec![info_a, info_b, info_c, info_d];
+        let summaries = analyze(&functions, transfer);
+
+        assert_eq!(summaries[&fn_a], Count(1));
+        assert_eq!(summaries[&fn_b], Count(1));
+        // the recursive pair's summaries settle once both hit the cap,
+        // proving the worklist actually reaches a fixpoint rather than
+        // looping forever or stopping after a single pass.
+        assert_eq!(summaries[&fn_c], Count(2));
+        assert_eq!(summaries[&fn_d], Count(2));
+    }
+}
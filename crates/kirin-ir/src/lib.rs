@@ -7,12 +7,33 @@ mod intern;
 mod language;
 mod lattice;
 mod node;
+mod serialize;
 mod visitor;
 
+/// Structural interning / CSE for pure statements over a `Context`.
+pub mod cse;
+
+/// Reconstructing a `Context` from a parsed surface-syntax tree.
+pub mod construct;
+
+/// Interprocedural fixpoint analysis over `SpecializedFunctionInfo::backedges`.
+pub mod interprocedural;
+
 /// Queries from the IRContext.
 pub mod query;
 
-pub use arena::GetInfo;
+/// Specialization/monomorphization cache for `StagedFunction`s, keyed on
+/// concrete argument `Signature`s.
+pub mod specialize;
+
+/// Greedy worklist-based rewrite passes (canonicalization, DCE, folding)
+/// over a `Context`.
+pub mod rewrite;
+
+/// Type-checking/verification pass with Hindley-Milner-style unification.
+pub mod verify;
+
+pub use arena::{GetInfo, Identifier};
 pub use comptime::{CompileTimeValue, Typeof};
 pub use context::Context;
 pub use detach::Detach;
@@ -21,13 +42,19 @@ pub use language::{
     Dialect, HasName, HasArguments, HasArgumentsMut, HasBlocks, HasBlocksMut, HasRegions, HasRegionsMut,
     HasResults, HasResultsMut, HasSuccessors, HasSuccessorsMut, IsConstant, IsPure, IsTerminator,
 };
-pub use lattice::{FiniteLattice, Lattice, TypeLattice};
+pub use lattice::{FiniteLattice, HasBottom, HasTop, Lattice, TypeLattice};
+pub use verify::ConstructError;
+pub use visitor::{
+    Foldable, Map, StatementFolder, StatementVisitor, StatementVisitorMut, Visit, Visitable,
+    VisitableMut, Walk, WalkMut,
+};
 pub use node::{
-    Block, BlockArgument, BlockInfo, CompileStage, DeletedSSAValue, Function, FunctionInfo,
-    LinkedList, LinkedListNode, Region, ResultValue, SSAInfo, SSAKind, SSAValue, Signature,
-    SpecializedFunction, SpecializedFunctionInfo, StagedFunction, StagedFunctionInfo, Statement,
-    StatementInfo, Successor, Symbol, TestSSAValue,
+    Block, BlockArgument, BlockInfo, CompileStage, Cursor, CursorMut, DeletedSSAValue, Function,
+    FunctionInfo, LinkedList, LinkedListNode, LinkedListStore, Region, ResultValue, SSAInfo,
+    SSAKind, SSAValue, Signature, SpecializedFunction, SpecializedFunctionInfo, StagedFunction,
+    StagedFunctionInfo, Statement, StatementInfo, Successor, Symbol, TestSSAValue,
 };
+pub use serialize::{Document, FromValue, FromValueError, HandleResolver, ToValue};
 
 #[cfg(feature = "derive")]
 pub use kirin_derive::{
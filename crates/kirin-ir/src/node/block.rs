@@ -6,7 +6,7 @@ use crate::{
 };
 
 use super::{
-    linked_list::{LinkedList, LinkedListNode},
+    linked_list::{LinkedList, LinkedListNode, LinkedListStore},
     ssa::BlockArgument,
     stmt::Statement,
 };
@@ -97,6 +97,16 @@ impl<L: Dialect> GetInfo<L> for Block {
     }
 }
 
+impl<L: Dialect> LinkedListStore<Block> for crate::Context<L> {
+    fn node(&self, ptr: Block) -> LinkedListNode<Block> {
+        ptr.expect_info(self).node
+    }
+
+    fn set_node(&mut self, ptr: Block, node: LinkedListNode<Block>) {
+        ptr.expect_info_mut(self).node = node;
+    }
+}
+
 impl Block {
     pub fn statements<'a, L: Dialect>(
         &self,
@@ -237,6 +237,10 @@ impl<L: Dialect> From<SpecializedFunctionInfo<L>> for SpecializedFunction {
 }
 
 impl<L: Dialect> SpecializedFunctionInfo<L> {
+    pub fn id(&self) -> SpecializedFunction {
+        self.id
+    }
+
     pub fn body(&self) -> &Statement {
         &self.body
     }
@@ -256,6 +260,10 @@ impl<L: Dialect> SpecializedFunctionInfo<L> {
     pub fn backedges(&self) -> &Vec<SpecializedFunction> {
         &self.backedges
     }
+
+    pub fn backedges_mut(&mut self) -> &mut Vec<SpecializedFunction> {
+        &mut self.backedges
+    }
 }
 
 impl<L: Dialect> Lattice for Signature<L> {
@@ -31,6 +31,28 @@ impl From<LinkedListNode<Block>> for Block {
     }
 }
 
+/// Abstraction over wherever the `prev`/`next` links for a `Ptr` actually
+/// live, so [`LinkedList`] itself stays a plain head/tail/len handle instead
+/// of owning its elements. IR nodes keep their links inside their own info
+/// struct in a [`Context`](crate::Context), reached through
+/// [`GetInfo`](crate::arena::GetInfo) — see the `LinkedListStore` impls next
+/// to `StatementId` and `Block`. Tests use a small `HashMap`-backed
+/// stand-in so cursor behavior can be exercised without building a `Context`.
+pub trait LinkedListStore<Ptr: Copy + PartialEq> {
+    /// Returns the current node for `ptr`. Callers only ever pass a pointer
+    /// a `LinkedList` handed back to them, so it is always present.
+    fn node(&self, ptr: Ptr) -> LinkedListNode<Ptr>;
+    /// Overwrites the node for `ptr`.
+    fn set_node(&mut self, ptr: Ptr, node: LinkedListNode<Ptr>);
+}
+
+/// A handle-based doubly-linked list: `head`/`tail`/`len` are the only state
+/// it owns, while the actual `prev`/`next` links for each element live
+/// wherever the caller's [`LinkedListStore`] keeps them. Every mutation
+/// (`push_back`, `insert_after`, `remove`, ...) and the [`Cursor`]/
+/// [`CursorMut`] walkers it hands out are O(1), which is what basic-block
+/// instruction lists need for rewriting passes that splice statements in
+/// and out mid-traversal.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct LinkedList<Ptr: Copy + PartialEq> {
     pub(crate) head: Option<Ptr>,
@@ -58,6 +80,142 @@ impl<Ptr: Copy + PartialEq> LinkedList<Ptr> {
     pub fn len(&self) -> usize {
         self.len
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `ptr` to the back of the list in O(1).
+    pub fn push_back(&mut self, store: &mut impl LinkedListStore<Ptr>, ptr: Ptr) {
+        let mut node = LinkedListNode::new(ptr);
+        node.prev = self.tail;
+        store.set_node(ptr, node);
+
+        match self.tail {
+            Some(tail) => {
+                let mut tail_node = store.node(tail);
+                tail_node.next = Some(ptr);
+                store.set_node(tail, tail_node);
+            }
+            None => self.head = Some(ptr),
+        }
+        self.tail = Some(ptr);
+        self.len += 1;
+    }
+
+    /// Prepends `ptr` to the front of the list in O(1).
+    pub fn push_front(&mut self, store: &mut impl LinkedListStore<Ptr>, ptr: Ptr) {
+        let mut node = LinkedListNode::new(ptr);
+        node.next = self.head;
+        store.set_node(ptr, node);
+
+        match self.head {
+            Some(head) => {
+                let mut head_node = store.node(head);
+                head_node.prev = Some(ptr);
+                store.set_node(head, head_node);
+            }
+            None => self.tail = Some(ptr),
+        }
+        self.head = Some(ptr);
+        self.len += 1;
+    }
+
+    /// Inserts `ptr` immediately after `after` in O(1). `after` must already
+    /// be in this list.
+    pub fn insert_after(&mut self, store: &mut impl LinkedListStore<Ptr>, after: Ptr, ptr: Ptr) {
+        let mut after_node = store.node(after);
+        let next = after_node.next;
+        after_node.next = Some(ptr);
+        store.set_node(after, after_node);
+
+        let mut node = LinkedListNode::new(ptr);
+        node.prev = Some(after);
+        node.next = next;
+        store.set_node(ptr, node);
+
+        match next {
+            Some(next) => {
+                let mut next_node = store.node(next);
+                next_node.prev = Some(ptr);
+                store.set_node(next, next_node);
+            }
+            None => self.tail = Some(ptr),
+        }
+        self.len += 1;
+    }
+
+    /// Inserts `ptr` immediately before `before` in O(1). `before` must
+    /// already be in this list.
+    pub fn insert_before(&mut self, store: &mut impl LinkedListStore<Ptr>, before: Ptr, ptr: Ptr) {
+        let mut before_node = store.node(before);
+        let prev = before_node.prev;
+        before_node.prev = Some(ptr);
+        store.set_node(before, before_node);
+
+        let mut node = LinkedListNode::new(ptr);
+        node.next = Some(before);
+        node.prev = prev;
+        store.set_node(ptr, node);
+
+        match prev {
+            Some(prev) => {
+                let mut prev_node = store.node(prev);
+                prev_node.next = Some(ptr);
+                store.set_node(prev, prev_node);
+            }
+            None => self.head = Some(ptr),
+        }
+        self.len += 1;
+    }
+
+    /// Removes `ptr` from the list in O(1), relinking its neighbors. `ptr`
+    /// must already be in this list.
+    pub fn remove(&mut self, store: &mut impl LinkedListStore<Ptr>, ptr: Ptr) {
+        let node = store.node(ptr);
+
+        match node.prev {
+            Some(prev) => {
+                let mut prev_node = store.node(prev);
+                prev_node.next = node.next;
+                store.set_node(prev, prev_node);
+            }
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => {
+                let mut next_node = store.node(next);
+                next_node.prev = node.prev;
+                store.set_node(next, next_node);
+            }
+            None => self.tail = node.prev,
+        }
+
+        store.set_node(ptr, LinkedListNode::new(ptr));
+        self.len -= 1;
+    }
+
+    /// A read-only cursor, starting just before the head.
+    pub fn cursor<'a, S: LinkedListStore<Ptr>>(&'a self, store: &'a S) -> Cursor<'a, Ptr, S> {
+        Cursor {
+            list: self,
+            store,
+            current: None,
+        }
+    }
+
+    /// A mutable cursor, starting just before the head, able to insert or
+    /// remove at its current position in O(1) as it walks.
+    pub fn cursor_mut<'a, S: LinkedListStore<Ptr>>(
+        &'a mut self,
+        store: &'a mut S,
+    ) -> CursorMut<'a, Ptr, S> {
+        CursorMut {
+            list: self,
+            store,
+            current: None,
+        }
+    }
 }
 
 impl<Ptr: Copy + PartialEq> Default for LinkedList<Ptr> {
@@ -65,3 +223,223 @@ impl<Ptr: Copy + PartialEq> Default for LinkedList<Ptr> {
         Self::new()
     }
 }
+
+/// A read-only walker over a [`LinkedList`]; see [`LinkedList::cursor`].
+pub struct Cursor<'a, Ptr: Copy + PartialEq, S: LinkedListStore<Ptr>> {
+    list: &'a LinkedList<Ptr>,
+    store: &'a S,
+    current: Option<Ptr>,
+}
+
+impl<'a, Ptr: Copy + PartialEq, S: LinkedListStore<Ptr>> Cursor<'a, Ptr, S> {
+    /// The pointer the cursor currently sits on, or `None` before the head
+    /// or after the tail.
+    pub fn current(&self) -> Option<Ptr> {
+        self.current
+    }
+
+    /// Moves to the next element, or to the head if the cursor was before
+    /// the start.
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            Some(ptr) => self.store.node(ptr).next,
+            None => self.list.head,
+        };
+    }
+
+    /// Moves to the previous element, or to the tail if the cursor was past
+    /// the end.
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            Some(ptr) => self.store.node(ptr).prev,
+            None => self.list.tail,
+        };
+    }
+}
+
+/// A mutable walker over a [`LinkedList`] that can insert or remove at its
+/// current position in O(1) — the operation rewriting passes need when
+/// splicing statements into (or out of) a basic block mid-traversal. See
+/// [`LinkedList::cursor_mut`].
+pub struct CursorMut<'a, Ptr: Copy + PartialEq, S: LinkedListStore<Ptr>> {
+    list: &'a mut LinkedList<Ptr>,
+    store: &'a mut S,
+    current: Option<Ptr>,
+}
+
+impl<'a, Ptr: Copy + PartialEq, S: LinkedListStore<Ptr>> CursorMut<'a, Ptr, S> {
+    /// The pointer the cursor currently sits on, or `None` before the head
+    /// or after the tail.
+    pub fn current(&self) -> Option<Ptr> {
+        self.current
+    }
+
+    /// Moves to the next element, or to the head if the cursor was before
+    /// the start.
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            Some(ptr) => self.store.node(ptr).next,
+            None => self.list.head,
+        };
+    }
+
+    /// Moves to the previous element, or to the tail if the cursor was past
+    /// the end.
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            Some(ptr) => self.store.node(ptr).prev,
+            None => self.list.tail,
+        };
+    }
+
+    /// Inserts `ptr` right before the current position (at the back if the
+    /// cursor is before the start or past the end) without moving the
+    /// cursor.
+    pub fn insert_before(&mut self, ptr: Ptr) {
+        match self.current {
+            Some(current) => self.list.insert_before(self.store, current, ptr),
+            None => self.list.push_back(self.store, ptr),
+        }
+    }
+
+    /// Inserts `ptr` right after the current position (at the front if the
+    /// cursor is before the start or past the end) without moving the
+    /// cursor.
+    pub fn insert_after(&mut self, ptr: Ptr) {
+        match self.current {
+            Some(current) => self.list.insert_after(self.store, current, ptr),
+            None => self.list.push_front(self.store, ptr),
+        }
+    }
+
+    /// Removes the element the cursor currently sits on in O(1), moving the
+    /// cursor to what was its successor (`None` if it was the tail). Returns
+    /// `None` if the cursor wasn't on an element.
+    pub fn remove_current(&mut self) -> Option<Ptr> {
+        let current = self.current?;
+        let next = self.store.node(current).next;
+        self.list.remove(self.store, current);
+        self.current = next;
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct TestStore(HashMap<usize, LinkedListNode<usize>>);
+
+    impl LinkedListStore<usize> for TestStore {
+        fn node(&self, ptr: usize) -> LinkedListNode<usize> {
+            self.0[&ptr]
+        }
+
+        fn set_node(&mut self, ptr: usize, node: LinkedListNode<usize>) {
+            self.0.insert(ptr, node);
+        }
+    }
+
+    fn collect(list: &LinkedList<usize>, store: &TestStore) -> Vec<usize> {
+        let mut cursor = list.cursor(store);
+        let mut out = Vec::new();
+        cursor.move_next();
+        while let Some(ptr) = cursor.current() {
+            out.push(ptr);
+            cursor.move_next();
+        }
+        out
+    }
+
+    #[test]
+    fn test_push_back_is_order_preserving() {
+        let mut list = LinkedList::new();
+        let mut store = TestStore::default();
+        list.push_back(&mut store, 1);
+        list.push_back(&mut store, 2);
+        list.push_back(&mut store, 3);
+
+        assert_eq!(collect(&list, &store), vec![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.tail(), Some(&3));
+    }
+
+    #[test]
+    fn test_duplicate_values_iterate_by_handle_identity() {
+        // Two distinct handles can carry "the same value" as far as the
+        // caller's `T` is concerned; the list must still treat them as
+        // separate elements instead of conflating them by value.
+        let mut list = LinkedList::new();
+        let mut store = TestStore::default();
+        list.push_back(&mut store, 7);
+        list.push_back(&mut store, 7);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(collect(&list, &store), vec![7, 7]);
+    }
+
+    #[test]
+    fn test_cursor_mut_remove_during_iteration() {
+        let mut list = LinkedList::new();
+        let mut store = TestStore::default();
+        list.push_back(&mut store, 1);
+        list.push_back(&mut store, 2);
+        list.push_back(&mut store, 3);
+
+        let mut cursor = list.cursor_mut(&mut store);
+        cursor.move_next(); // 1
+        cursor.move_next(); // 2
+        let removed = cursor.remove_current();
+
+        assert_eq!(removed, Some(2));
+        assert_eq!(cursor.current(), Some(3));
+        assert_eq!(collect(&list, &store), vec![1, 3]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert_after_splices_in_place() {
+        let mut list = LinkedList::new();
+        let mut store = TestStore::default();
+        list.push_back(&mut store, 1);
+        list.push_back(&mut store, 3);
+
+        let mut cursor = list.cursor_mut(&mut store);
+        cursor.move_next(); // 1
+        cursor.insert_after(2);
+
+        assert_eq!(collect(&list, &store), vec![1, 2, 3]);
+        assert_eq!(list.tail(), Some(&3));
+    }
+
+    #[test]
+    fn test_empty_list_cursor_yields_nothing() {
+        let list: LinkedList<usize> = LinkedList::new();
+        let store = TestStore::default();
+
+        assert!(list.is_empty());
+        assert_eq!(list.head(), None);
+        assert_eq!(list.tail(), None);
+        assert_eq!(collect(&list, &store), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_remove_head_and_tail_updates_list_ends() {
+        let mut list = LinkedList::new();
+        let mut store = TestStore::default();
+        list.push_back(&mut store, 1);
+        list.push_back(&mut store, 2);
+        list.push_back(&mut store, 3);
+
+        list.remove(&mut store, 1);
+        assert_eq!(list.head(), Some(&2));
+
+        list.remove(&mut store, 3);
+        assert_eq!(list.tail(), Some(&2));
+        assert_eq!(list.len(), 1);
+        assert_eq!(collect(&list, &store), vec![2]);
+    }
+}
@@ -11,7 +11,7 @@ pub use function::{
     CompileStage, Function, FunctionInfo, Signature, SpecializedFunction, SpecializedFunctionInfo,
     StagedFunction, StagedFunctionInfo,
 };
-pub use linked_list::{LinkedList, LinkedListNode};
+pub use linked_list::{Cursor, CursorMut, LinkedList, LinkedListNode, LinkedListStore};
 pub use region::{Region, RegionInfo};
 pub use ssa::{BlockArgument, ResultValue, SSAInfo, SSAKind, SSAValue, TestSSAValue};
 pub use stmt::{Statement, StatementInfo};
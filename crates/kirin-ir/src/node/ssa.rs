@@ -85,6 +85,13 @@ impl<L: Dialect> SSAInfo<L> {
         self.ty = ty;
     }
 
+    /// Attach (or clear) this value's name, e.g. so a value rebuilt by a
+    /// textual parser prints under the same `%name` it was parsed from,
+    /// regardless of its freshly-allocated id.
+    pub fn set_name(&mut self, name: Option<Symbol>) {
+        self.name = name;
+    }
+
     pub fn kind(&self) -> &SSAKind {
         &self.kind
     }
@@ -104,6 +111,20 @@ pub struct Use {
     operand_index: usize,
 }
 
+impl Use {
+    pub(crate) fn new(stmt: Statement, operand_index: usize) -> Self {
+        Self { stmt, operand_index }
+    }
+
+    pub(crate) fn stmt(&self) -> Statement {
+        self.stmt
+    }
+
+    pub(crate) fn operand_index(&self) -> usize {
+        self.operand_index
+    }
+}
+
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SSAKind {
@@ -1,6 +1,9 @@
 use crate::arena::{GetInfo, Id, Item};
 use crate::identifier;
-use crate::{Dialect, node::linked_list::LinkedListNode};
+use crate::{
+    Dialect,
+    node::linked_list::{LinkedListNode, LinkedListStore},
+};
 
 use super::block::Block;
 
@@ -75,3 +78,13 @@ impl<L: Dialect> GetInfo<L> for StatementId {
         context.statements.get_mut(*self)
     }
 }
+
+impl<L: Dialect> LinkedListStore<StatementId> for crate::Context<L> {
+    fn node(&self, ptr: StatementId) -> LinkedListNode<StatementId> {
+        ptr.expect_info(self).node
+    }
+
+    fn set_node(&mut self, ptr: StatementId, node: LinkedListNode<StatementId>) {
+        ptr.expect_info_mut(self).node = node;
+    }
+}
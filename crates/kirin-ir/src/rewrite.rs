@@ -0,0 +1,518 @@
+//! Greedy worklist-based rewrite passes over a [`Context`], modeled loosely
+//! on fayalite's `module::transform` passes (e.g. `simplify_enums`).
+//!
+//! A [`RewritePattern`] inspects one [`Statement`] at a time and reports
+//! what happened to it via [`RewriteResult`]. [`GreedyRewriteDriver`] drives
+//! a [`PatternSet`] to a fixpoint: it seeds a worklist from every statement
+//! reachable from a root, and whenever a pattern rewrites a statement it
+//! re-enqueues that statement's operands and users so the fixpoint keeps
+//! propagating. [`EraseDeadCode`] is the one pattern provided here; dialects
+//! build canonicalization and constant folding (gated on
+//! [`IsConstant`](crate::IsConstant)) the same way, against their own
+//! [`TypeLattice`](crate::TypeLattice).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::arena::GetInfo;
+use crate::node::ssa::Use;
+use crate::{
+    Context, Dialect, HasArguments, HasArgumentsMut, HasRegions, HasResults, IsPure, IsTerminator,
+    SSAKind, SSAValue, Statement,
+};
+
+/// What a [`RewritePattern`] did to the statement it was given.
+#[derive(Clone, Debug)]
+pub enum RewriteResult {
+    /// Replace every use of `stmt`'s results with these values (one per
+    /// result, in [`HasResults::results`] order), then erase `stmt`.
+    Replace(Vec<SSAValue>),
+    /// Erase `stmt` outright. Only valid when none of its results (if any)
+    /// have remaining uses -- returning this for a statement whose results
+    /// are still consumed leaves those uses dangling; return [`Replace`]
+    /// instead to migrate them first.
+    ///
+    /// [`Replace`]: RewriteResult::Replace
+    Erase,
+    /// `stmt` was rewritten in place (e.g. an operand was swapped); the
+    /// driver re-enqueues it and its neighbors but does not touch its uses.
+    Changed,
+}
+
+/// A single rewrite rule over a dialect's statements.
+///
+/// `match_and_rewrite` inspects `stmt` and, if it applies, mutates `context`
+/// accordingly and returns `Some`. Returning `None` means "doesn't apply" --
+/// the driver moves on to the next pattern without revisiting `stmt`.
+pub trait RewritePattern<L: Dialect> {
+    fn match_and_rewrite(&self, stmt: Statement, context: &mut Context<L>) -> Option<RewriteResult>;
+}
+
+/// An ordered collection of [`RewritePattern`]s tried against a statement in
+/// turn; the first one that matches wins.
+pub struct PatternSet<L: Dialect> {
+    patterns: Vec<Box<dyn RewritePattern<L>>>,
+}
+
+impl<L: Dialect> Default for PatternSet<L> {
+    fn default() -> Self {
+        Self { patterns: Vec::new() }
+    }
+}
+
+impl<L: Dialect> PatternSet<L> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `pattern` to the end of the set.
+    pub fn add(mut self, pattern: impl RewritePattern<L> + 'static) -> Self {
+        self.patterns.push(Box::new(pattern));
+        self
+    }
+
+    fn apply(&self, stmt: Statement, context: &mut Context<L>) -> Option<RewriteResult> {
+        self.patterns
+            .iter()
+            .find_map(|pattern| pattern.match_and_rewrite(stmt, context))
+    }
+}
+
+/// Outcome of a [`GreedyRewriteDriver::run`].
+#[derive(Clone, Debug, Default)]
+pub struct RewriteStats {
+    /// How many times some pattern in the set matched and rewrote a statement.
+    pub rewrites: usize,
+    /// Statements that hit [`GreedyRewriteDriver`]'s revisit cap without
+    /// reaching a fixpoint -- a sign of a non-terminating (or cyclic)
+    /// pattern rather than an IR error, so it's reported rather than panicking.
+    pub non_terminating: Vec<Statement>,
+}
+
+const DEFAULT_REVISIT_LIMIT: usize = 16;
+
+/// Drives a [`PatternSet`] to a fixpoint over the statements reachable from
+/// a root, via a worklist seeded once and re-fed as rewrites land.
+pub struct GreedyRewriteDriver<L: Dialect> {
+    patterns: PatternSet<L>,
+    revisit_limit: usize,
+}
+
+impl<L: Dialect> GreedyRewriteDriver<L> {
+    pub fn new(patterns: PatternSet<L>) -> Self {
+        Self {
+            patterns,
+            revisit_limit: DEFAULT_REVISIT_LIMIT,
+        }
+    }
+
+    /// Overrides how many times a single statement may be revisited before
+    /// it's reported as non-terminating instead of rewritten again.
+    pub fn revisit_limit(mut self, limit: usize) -> Self {
+        self.revisit_limit = limit;
+        self
+    }
+
+    /// Runs the pattern set to a fixpoint over every statement nested under
+    /// `root` (inclusive), rewiring and erasing statements as patterns
+    /// dictate.
+    ///
+    /// Use-tracking is only seeded for operands referenced from within
+    /// `root`'s own subtree, so DCE is only safe to run over a root whose
+    /// results (if any) aren't also consumed from outside it -- e.g. a
+    /// whole function body, not an arbitrary inner block.
+    pub fn run(&self, context: &mut Context<L>, root: Statement) -> RewriteStats {
+        let mut statements = Vec::new();
+        collect_statements(context, root, &mut statements);
+        seed_uses(context, &statements);
+
+        let mut queued: HashSet<Statement> = statements.iter().copied().collect();
+        let mut worklist: VecDeque<Statement> = statements.into_iter().collect();
+        let mut revisits: HashMap<Statement, usize> = HashMap::new();
+        let mut stats = RewriteStats::default();
+
+        while let Some(stmt) = worklist.pop_front() {
+            queued.remove(&stmt);
+            if context
+                .statement_arena()
+                .get(stmt)
+                .map(|item| item.deleted())
+                .unwrap_or(true)
+            {
+                continue;
+            }
+
+            let revisits_so_far = revisits.entry(stmt).or_insert(0);
+            *revisits_so_far += 1;
+            if *revisits_so_far > self.revisit_limit {
+                stats.non_terminating.push(stmt);
+                continue;
+            }
+
+            let old_operands: Vec<SSAValue> = stmt.definition(context).arguments().copied().collect();
+            let Some(result) = self.patterns.apply(stmt, context) else {
+                continue;
+            };
+            stats.rewrites += 1;
+
+            sync_operand_uses(context, stmt, &old_operands, &mut worklist, &mut queued);
+            requeue_neighbors(context, stmt, &mut worklist, &mut queued);
+            match result {
+                RewriteResult::Changed => enqueue(stmt, &mut worklist, &mut queued),
+                RewriteResult::Erase => erase_statement(context, stmt),
+                RewriteResult::Replace(new_values) => {
+                    rewire_results(context, stmt, &new_values, &mut worklist, &mut queued);
+                    erase_statement(context, stmt);
+                }
+            }
+        }
+        stats
+    }
+}
+
+/// Dead-code elimination: erases any pure, non-terminator statement whose
+/// results (if any) all have zero remaining uses. Gating on
+/// [`IsTerminator`] keeps a block's terminator from ever being erased, even
+/// though it may otherwise be pure.
+pub struct EraseDeadCode;
+
+impl<L: Dialect> RewritePattern<L> for EraseDeadCode {
+    fn match_and_rewrite(&self, stmt: Statement, context: &mut Context<L>) -> Option<RewriteResult> {
+        let definition = stmt.definition(context);
+        if definition.is_terminator() || !definition.is_pure() {
+            return None;
+        }
+        let all_dead = definition
+            .results()
+            .all(|result| SSAValue::from(*result).expect_info(context).uses().is_empty());
+        all_dead.then_some(RewriteResult::Erase)
+    }
+}
+
+/// Collects `stmt` and, recursively, every statement nested in its regions,
+/// mirroring [`crate::verify::Verifier::check_statement_tree`].
+fn collect_statements<L: Dialect>(context: &Context<L>, stmt: Statement, out: &mut Vec<Statement>) {
+    out.push(stmt);
+    for region in stmt.definition(context).regions() {
+        for block in region.blocks(context) {
+            for inner in block.statements(context) {
+                collect_statements(context, inner, out);
+            }
+        }
+    }
+}
+
+/// Populates each operand's [`SSAInfo::uses`](crate::SSAInfo::uses) from
+/// `statements`, so the driver can find a value's users without an O(n)
+/// scan every time a rewrite fires.
+fn seed_uses<L: Dialect>(context: &mut Context<L>, statements: &[Statement]) {
+    let mut edges = Vec::new();
+    for &stmt in statements {
+        for (index, operand) in stmt.definition(context).arguments().enumerate() {
+            edges.push((stmt, index, *operand));
+        }
+    }
+    for (stmt, index, operand) in edges {
+        operand
+            .expect_info_mut(context)
+            .uses_mut()
+            .insert(Use::new(stmt, index));
+    }
+}
+
+fn enqueue(stmt: Statement, worklist: &mut VecDeque<Statement>, queued: &mut HashSet<Statement>) {
+    if queued.insert(stmt) {
+        worklist.push_back(stmt);
+    }
+}
+
+/// Diffs `stmt`'s operands against `old_operands` (its operands just before
+/// the pattern ran) and moves each changed slot's `Use` entry over, so an
+/// in-place [`RewriteResult::Changed`] edit keeps `uses` accurate the same
+/// way [`rewire_results`] does for a full [`RewriteResult::Replace`].
+fn sync_operand_uses<L: Dialect>(
+    context: &mut Context<L>,
+    stmt: Statement,
+    old_operands: &[SSAValue],
+    worklist: &mut VecDeque<Statement>,
+    queued: &mut HashSet<Statement>,
+) {
+    let new_operands: Vec<SSAValue> = stmt.definition(context).arguments().copied().collect();
+    for (index, (&old, &new)) in old_operands.iter().zip(&new_operands).enumerate() {
+        if old == new {
+            continue;
+        }
+        old.expect_info_mut(context).uses_mut().remove(&Use::new(stmt, index));
+        new.expect_info_mut(context).uses_mut().insert(Use::new(stmt, index));
+        if let SSAKind::Result(producer, _) = old.expect_info(context).kind() {
+            enqueue(*producer, worklist, queued);
+        }
+    }
+}
+
+/// Re-enqueues `stmt`'s operand producers and result users: a rewrite to
+/// `stmt` may make one of its operands dead, or may need one of its users
+/// to be reconsidered against the new state.
+fn requeue_neighbors<L: Dialect>(
+    context: &Context<L>,
+    stmt: Statement,
+    worklist: &mut VecDeque<Statement>,
+    queued: &mut HashSet<Statement>,
+) {
+    for operand in stmt.definition(context).arguments() {
+        if let SSAKind::Result(producer, _) = operand.expect_info(context).kind() {
+            enqueue(*producer, worklist, queued);
+        }
+    }
+    for result in stmt.definition(context).results() {
+        for use_site in SSAValue::from(*result).expect_info(context).uses() {
+            enqueue(use_site.stmt(), worklist, queued);
+        }
+    }
+}
+
+/// Rewires every use of `stmt`'s results onto `new_values` (paired up in
+/// [`HasResults`] order), re-enqueueing each rewired user.
+fn rewire_results<L: Dialect>(
+    context: &mut Context<L>,
+    stmt: Statement,
+    new_values: &[SSAValue],
+    worklist: &mut VecDeque<Statement>,
+    queued: &mut HashSet<Statement>,
+) {
+    let old_results: Vec<SSAValue> = stmt
+        .definition(context)
+        .results()
+        .map(|result| SSAValue::from(*result))
+        .collect();
+    debug_assert_eq!(
+        old_results.len(),
+        new_values.len(),
+        "RewritePattern::match_and_rewrite replaced a statement with {} result(s) with {} value(s)",
+        old_results.len(),
+        new_values.len(),
+    );
+
+    for (old, &new) in old_results.iter().zip(new_values) {
+        let uses: Vec<Use> = old.expect_info(context).uses().iter().cloned().collect();
+        for use_site in uses {
+            if let Some(slot) = use_site
+                .stmt()
+                .expect_info_mut(context)
+                .definition
+                .arguments_mut()
+                .nth(use_site.operand_index())
+            {
+                *slot = new;
+            }
+            new.expect_info_mut(context)
+                .uses_mut()
+                .insert(Use::new(use_site.stmt(), use_site.operand_index()));
+            enqueue(use_site.stmt(), worklist, queued);
+        }
+        old.expect_info_mut(context).uses_mut().clear();
+        context.ssas.delete(*old);
+    }
+}
+
+/// Detaches `stmt` from its operands' use-sets and its parent block's
+/// statement list (including the block's `terminator` slot, if `stmt` was
+/// it), then tombstones it in the statement arena.
+fn erase_statement<L: Dialect>(context: &mut Context<L>, stmt: Statement) {
+    let operands: Vec<SSAValue> = stmt.definition(context).arguments().copied().collect();
+    for (index, operand) in operands.into_iter().enumerate() {
+        operand.expect_info_mut(context).uses_mut().remove(&Use::new(stmt, index));
+    }
+
+    if let Some(parent) = *stmt.parent(context) {
+        let mut list = {
+            let info = parent.expect_info_mut(context);
+            if info.terminator == Some(stmt) {
+                info.terminator = None;
+            }
+            std::mem::take(&mut info.statements)
+        };
+        list.remove(context, stmt);
+        parent.expect_info_mut(context).statements = list;
+    }
+
+    context.statements.delete(stmt);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comptime::CompileTimeValue;
+    use crate::lattice::{HasBottom, HasTop, Lattice, TypeLattice};
+    use crate::{HasRegionsMut, HasResultsMut, HasSuccessors, HasSuccessorsMut};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    struct TestType;
+
+    impl Lattice for TestType {
+        fn join(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn meet(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn is_subseteq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl HasBottom for TestType {
+        fn bottom() -> Self {
+            TestType
+        }
+    }
+
+    impl HasTop for TestType {
+        fn top() -> Self {
+            TestType
+        }
+    }
+
+    impl CompileTimeValue for TestType {}
+    impl TypeLattice for TestType {}
+
+    /// A statement shape with a `Root` variant (impure, holding a nested
+    /// region) so [`collect_statements`] has something to walk into, and a
+    /// `Dead` variant (pure, no operands, one result) to feed
+    /// [`EraseDeadCode`].
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestOp {
+        Root(crate::Region),
+        Dead(crate::ResultValue),
+    }
+
+    impl<'a> HasArguments<'a> for TestOp {
+        type Iter = std::iter::Empty<&'a SSAValue>;
+        fn arguments(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasArgumentsMut<'a> for TestOp {
+        type IterMut = std::iter::Empty<&'a mut SSAValue>;
+        fn arguments_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasResults<'a> for TestOp {
+        type Iter = std::option::IntoIter<&'a crate::ResultValue>;
+        fn results(&'a self) -> Self::Iter {
+            match self {
+                TestOp::Root(_) => None,
+                TestOp::Dead(r) => Some(r),
+            }
+            .into_iter()
+        }
+    }
+
+    impl<'a> HasResultsMut<'a> for TestOp {
+        type IterMut = std::option::IntoIter<&'a mut crate::ResultValue>;
+        fn results_mut(&'a mut self) -> Self::IterMut {
+            match self {
+                TestOp::Root(_) => None,
+                TestOp::Dead(r) => Some(r),
+            }
+            .into_iter()
+        }
+    }
+
+    impl<'a> HasSuccessors<'a> for TestOp {
+        type Iter = std::iter::Empty<&'a crate::Block>;
+        fn successors(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasSuccessorsMut<'a> for TestOp {
+        type IterMut = std::iter::Empty<&'a mut crate::Block>;
+        fn successors_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+
+    impl<'a> HasRegions<'a> for TestOp {
+        type Iter = std::option::IntoIter<&'a crate::Region>;
+        fn regions(&'a self) -> Self::Iter {
+            match self {
+                TestOp::Root(region) => Some(region),
+                TestOp::Dead(_) => None,
+            }
+            .into_iter()
+        }
+    }
+
+    impl<'a> HasRegionsMut<'a> for TestOp {
+        type IterMut = std::option::IntoIter<&'a mut crate::Region>;
+        fn regions_mut(&'a mut self) -> Self::IterMut {
+            match self {
+                TestOp::Root(region) => Some(region),
+                TestOp::Dead(_) => None,
+            }
+            .into_iter()
+        }
+    }
+
+    impl IsTerminator for TestOp {
+        fn is_terminator(&self) -> bool {
+            false
+        }
+    }
+
+    impl crate::IsConstant for TestOp {
+        fn is_constant(&self) -> bool {
+            false
+        }
+    }
+
+    impl IsPure for TestOp {
+        /// `Root` is deliberately impure so [`EraseDeadCode`] never erases
+        /// it for having no results of its own -- the same reason a real
+        /// function body or block terminator isn't pure either.
+        fn is_pure(&self) -> bool {
+            matches!(self, TestOp::Dead(_))
+        }
+    }
+
+    impl Dialect for TestOp {
+        type TypeLattice = TestType;
+    }
+
+    #[test]
+    fn erase_dead_code_removes_unused_pure_statement() {
+        let mut context: Context<TestOp> = Context::default();
+        let region = context.insert_region(None);
+        let block = context.insert_block(region, vec![]);
+
+        let (dead, _) = context.insert_statement(
+            Some(block),
+            TestOp::Dead(crate::ResultValue::from(crate::arena::Id::from_raw(0))),
+            vec![TestType],
+        );
+        let mut statements = {
+            let info = block.expect_info_mut(&mut context);
+            std::mem::take(&mut info.statements)
+        };
+        statements.push_back(&mut context, dead);
+        block.expect_info_mut(&mut context).statements = statements;
+
+        let (root, _) = context.insert_statement(None, TestOp::Root(region), vec![]);
+
+        let driver = GreedyRewriteDriver::new(PatternSet::new().add(EraseDeadCode));
+        let stats = driver.run(&mut context, root);
+
+        assert_eq!(stats.rewrites, 1);
+        assert!(stats.non_terminating.is_empty());
+        assert!(
+            context
+                .statement_arena()
+                .get(dead)
+                .map(|item| item.deleted())
+                .unwrap_or(true)
+        );
+    }
+}
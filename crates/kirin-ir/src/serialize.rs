@@ -0,0 +1,245 @@
+use crate::arena::Identifier;
+use crate::{Block, Dialect, Region, ResultValue, SSAValue, Successor};
+
+/// A structured, self-describing value emitted by `#[derive(Dialect)]`'s
+/// opt-in `#[kirin(serialize)]` mode (see [`ToValue`]/[`FromValue`]).
+///
+/// Unlike a bare serde `Value`, every [`Document::Node`] carries its own
+/// variant tag, so a document round-trips through [`FromValue::from_value`]
+/// without an external schema — the same way a syn-serde layer mirrors a
+/// `syn` AST into a serde-friendly tree. SSA-value/block/successor/region
+/// fields are written as [`Document::Handle`], a stable integer that only
+/// makes sense together with a [`HandleResolver`] for the arena being
+/// reloaded into; everything else round-trips through the plain variants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Document {
+    Unit,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    /// A stable integer handle standing in for an `SSAValue`/`ResultValue`/
+    /// `Block`/`Successor`/`Region` field, rebuilt by [`HandleResolver`].
+    Handle(usize),
+    Seq(Vec<Document>),
+    /// One tagged struct or enum variant: `tag` is the variant's (or the
+    /// struct's own) name, `fields` are its children in declaration order.
+    Node { tag: String, fields: Vec<Document> },
+}
+
+/// Resolves the integer handles written by [`ToValue::to_value`] back into
+/// live SSA-value/block/successor/region references for the arena a
+/// document is being reloaded into.
+pub trait HandleResolver<L: Dialect> {
+    fn resolve_ssa_value(&mut self, handle: usize) -> SSAValue;
+    fn resolve_result_value(&mut self, handle: usize) -> ResultValue;
+    fn resolve_block(&mut self, handle: usize) -> Block;
+    fn resolve_successor(&mut self, handle: usize) -> Successor;
+    fn resolve_region(&mut self, handle: usize) -> Region;
+}
+
+/// Emitted by `#[derive(Dialect)]` under `#[kirin(serialize)]`: serializes
+/// `self` into a [`Document`], writing SSA-value/block/successor/region
+/// fields as stable handles instead of inlining their arena-internal state.
+pub trait ToValue<L: Dialect> {
+    fn to_value(&self) -> Document;
+}
+
+/// The deserializing counterpart of [`ToValue`]: rebuilds `Self` from a
+/// [`Document`] previously produced by [`ToValue::to_value`], resolving
+/// handle fields through `resolver`.
+pub trait FromValue<L: Dialect>: Sized {
+    fn from_value(value: &Document, resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError>;
+}
+
+/// Why [`FromValue::from_value`] failed to rebuild a node from a [`Document`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromValueError {
+    /// The document's shape didn't match what this type expects (e.g. a
+    /// [`Document::Node`] where a [`Document::Handle`] was expected).
+    UnexpectedShape { expected: &'static str },
+    /// A [`Document::Node`] tag didn't match any variant of the target enum.
+    UnknownVariant(String),
+}
+
+impl std::fmt::Display for FromValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromValueError::UnexpectedShape { expected } => {
+                write!(f, "expected a document shaped like {expected}")
+            }
+            FromValueError::UnknownVariant(tag) => write!(f, "unknown variant tag {tag:?}"),
+        }
+    }
+}
+
+impl std::error::Error for FromValueError {}
+
+macro_rules! impl_handle_value {
+    ($ty:ty, $resolve:ident) => {
+        impl<L: Dialect> ToValue<L> for $ty {
+            fn to_value(&self) -> Document {
+                Document::Handle(Identifier::to_handle(*self))
+            }
+        }
+
+        impl<L: Dialect> FromValue<L> for $ty {
+            fn from_value(value: &Document, resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+                match value {
+                    Document::Handle(handle) => Ok(resolver.$resolve(*handle)),
+                    _ => Err(FromValueError::UnexpectedShape { expected: "Handle" }),
+                }
+            }
+        }
+    };
+}
+
+impl_handle_value!(SSAValue, resolve_ssa_value);
+impl_handle_value!(ResultValue, resolve_result_value);
+impl_handle_value!(Block, resolve_block);
+impl_handle_value!(Successor, resolve_successor);
+impl_handle_value!(Region, resolve_region);
+
+impl<L: Dialect> ToValue<L> for bool {
+    fn to_value(&self) -> Document {
+        Document::Bool(*self)
+    }
+}
+
+impl<L: Dialect> FromValue<L> for bool {
+    fn from_value(value: &Document, _resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+        match value {
+            Document::Bool(inner) => Ok(*inner),
+            _ => Err(FromValueError::UnexpectedShape { expected: "Bool" }),
+        }
+    }
+}
+
+macro_rules! impl_int_value {
+    ($ty:ty) => {
+        impl<L: Dialect> ToValue<L> for $ty {
+            fn to_value(&self) -> Document {
+                Document::Int(*self as i64)
+            }
+        }
+
+        impl<L: Dialect> FromValue<L> for $ty {
+            fn from_value(value: &Document, _resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+                match value {
+                    Document::Int(inner) => Ok(*inner as $ty),
+                    _ => Err(FromValueError::UnexpectedShape { expected: "Int" }),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_uint_value {
+    ($ty:ty) => {
+        impl<L: Dialect> ToValue<L> for $ty {
+            fn to_value(&self) -> Document {
+                Document::UInt(*self as u64)
+            }
+        }
+
+        impl<L: Dialect> FromValue<L> for $ty {
+            fn from_value(value: &Document, _resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+                match value {
+                    Document::UInt(inner) => Ok(*inner as $ty),
+                    _ => Err(FromValueError::UnexpectedShape { expected: "UInt" }),
+                }
+            }
+        }
+    };
+}
+
+impl_int_value!(i8);
+impl_int_value!(i16);
+impl_int_value!(i32);
+impl_int_value!(i64);
+impl_int_value!(isize);
+impl_uint_value!(u8);
+impl_uint_value!(u16);
+impl_uint_value!(u32);
+impl_uint_value!(u64);
+impl_uint_value!(usize);
+
+impl<L: Dialect> ToValue<L> for f32 {
+    fn to_value(&self) -> Document {
+        Document::Float(*self as f64)
+    }
+}
+
+impl<L: Dialect> FromValue<L> for f32 {
+    fn from_value(value: &Document, _resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+        match value {
+            Document::Float(inner) => Ok(*inner as f32),
+            _ => Err(FromValueError::UnexpectedShape { expected: "Float" }),
+        }
+    }
+}
+
+impl<L: Dialect> ToValue<L> for f64 {
+    fn to_value(&self) -> Document {
+        Document::Float(*self)
+    }
+}
+
+impl<L: Dialect> FromValue<L> for f64 {
+    fn from_value(value: &Document, _resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+        match value {
+            Document::Float(inner) => Ok(*inner),
+            _ => Err(FromValueError::UnexpectedShape { expected: "Float" }),
+        }
+    }
+}
+
+impl<L: Dialect> ToValue<L> for String {
+    fn to_value(&self) -> Document {
+        Document::String(self.clone())
+    }
+}
+
+impl<L: Dialect> FromValue<L> for String {
+    fn from_value(value: &Document, _resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+        match value {
+            Document::String(inner) => Ok(inner.clone()),
+            _ => Err(FromValueError::UnexpectedShape { expected: "String" }),
+        }
+    }
+}
+
+impl<L: Dialect, T: ToValue<L>> ToValue<L> for Vec<T> {
+    fn to_value(&self) -> Document {
+        Document::Seq(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<L: Dialect, T: FromValue<L>> FromValue<L> for Vec<T> {
+    fn from_value(value: &Document, resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+        match value {
+            Document::Seq(items) => items.iter().map(|item| T::from_value(item, resolver)).collect(),
+            _ => Err(FromValueError::UnexpectedShape { expected: "Seq" }),
+        }
+    }
+}
+
+impl<L: Dialect, T: ToValue<L>> ToValue<L> for Option<T> {
+    fn to_value(&self) -> Document {
+        match self {
+            Some(value) => Document::Seq(vec![value.to_value()]),
+            None => Document::Seq(Vec::new()),
+        }
+    }
+}
+
+impl<L: Dialect, T: FromValue<L>> FromValue<L> for Option<T> {
+    fn from_value(value: &Document, resolver: &mut impl HandleResolver<L>) -> Result<Self, FromValueError> {
+        match value {
+            Document::Seq(items) if items.is_empty() => Ok(None),
+            Document::Seq(items) if items.len() == 1 => Ok(Some(T::from_value(&items[0], resolver)?)),
+            _ => Err(FromValueError::UnexpectedShape { expected: "Seq" }),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Specialization/monomorphization cache for [`StagedFunction`]s, keyed on
+//! concrete argument [`Signature`]s.
+//!
+//! [`StagedFunctionInfo::specializations`] and [`SpecializedFunction`] have
+//! so far been inert id types: nothing looked a call site's concrete
+//! argument types up against the specializations already on file, or
+//! allocated a new one when none matched. [`Context::monomorphize`] is that
+//! driver -- given a [`StagedFunction`] and the [`Signature`] a call site
+//! wants it invoked at, it returns an existing [`SpecializedFunction`] whose
+//! signature matches exactly (so repeat call sites with the same argument
+//! types collapse onto one specialization) or produces a fresh one and
+//! registers it under the staged function.
+//!
+//! Producing a concrete body from the generic one is dialect-specific -- it
+//! needs to know how to rewrite a placeholder parameter type into a
+//! concrete one and what the resulting return type is -- so it's a
+//! hand-implemented extension point the same way
+//! [`ConstructFromAst`](crate::construct::ConstructFromAst) is: implement
+//! [`Instantiate`] once per dialect.
+//!
+//! Whichever path produces the callee's [`SpecializedFunction`] -- a fresh
+//! instantiation or a cache hit -- the caller is recorded in its
+//! `backedges`, so the call graph [`crate::interprocedural`] walks stays
+//! consistent.
+
+use crate::arena::GetInfo;
+use crate::node::function::{Signature, SpecializedFunction, SpecializedFunctionInfo, StagedFunction};
+use crate::{Context, Dialect, Statement};
+
+/// Dialect-specific hook for producing a concrete specialization's body from
+/// a staged function's generic one, given the concrete argument
+/// [`Signature`] a call site wants it invoked at.
+pub trait Instantiate<L: Dialect> {
+    /// Clones/rewrites `generic_body` for `signature`, allocating any new
+    /// blocks/statements/SSA values into `context`, and returns the new
+    /// body together with the specialization's return type.
+    fn instantiate(
+        context: &mut Context<L>,
+        generic_body: Statement,
+        signature: &Signature<L>,
+    ) -> (Statement, L::TypeLattice);
+}
+
+impl<L: Dialect> Context<L> {
+    /// Looks up an existing specialization of `staged` whose signature
+    /// matches `signature` exactly, or instantiates `generic_body` for it
+    /// via [`Instantiate::instantiate`] and registers the result as a new
+    /// specialization. Either way, records `caller` in the resulting
+    /// specialization's `backedges`.
+    pub fn monomorphize<I: Instantiate<L>>(
+        &mut self,
+        staged: StagedFunction,
+        generic_body: Statement,
+        signature: Signature<L>,
+        caller: SpecializedFunction,
+    ) -> SpecializedFunction {
+        let info = staged.expect_info(self);
+        let existing = info
+            .specializations()
+            .iter()
+            .find(|spec| spec.signature() == &signature)
+            .map(|spec| spec.id());
+
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let (body, return_type) = I::instantiate(self, generic_body, &signature);
+
+                let info = staged.expect_info_mut(self);
+                let id = SpecializedFunction(staged, info.specializations().len());
+                let specialized = SpecializedFunctionInfo::builder()
+                    .id(id)
+                    .signature(signature)
+                    .return_type(return_type)
+                    .body(body)
+                    .new();
+                info.add_specialization(specialized);
+                id
+            }
+        };
+
+        let callee = id.expect_info_mut(self);
+        if !callee.backedges().contains(&caller) {
+            callee.backedges_mut().push(caller);
+        }
+
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::arena::Id;
+    use crate::comptime::CompileTimeValue;
+    use crate::lattice::{HasTop, Lattice};
+    use crate::{HasArguments, HasArgumentsMut, HasRegions, HasRegionsMut, HasResults, HasResultsMut, HasSuccessors, HasSuccessorsMut, IsConstant, IsPure, IsTerminator, SSAValue};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    struct TestType;
+
+    impl Lattice for TestType {
+        fn join(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn meet(&self, _other: &Self) -> Self {
+            TestType
+        }
+        fn is_subseteq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl crate::lattice::HasBottom for TestType {
+        fn bottom() -> Self {
+            TestType
+        }
+    }
+
+    impl HasTop for TestType {
+        fn top() -> Self {
+            TestType
+        }
+    }
+
+    impl CompileTimeValue for TestType {}
+    impl crate::lattice::TypeLattice for TestType {}
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestLang;
+
+    impl<'a> HasArguments<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a SSAValue>;
+        fn arguments(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasArgumentsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut SSAValue>;
+        fn arguments_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasResults<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::ResultValue>;
+        fn results(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasResultsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::ResultValue>;
+        fn results_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasSuccessors<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::Block>;
+        fn successors(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasSuccessorsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::Block>;
+        fn successors_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasRegions<'a> for TestLang {
+        type Iter = std::iter::Empty<&'a crate::Region>;
+        fn regions(&'a self) -> Self::Iter {
+            std::iter::empty()
+        }
+    }
+    impl<'a> HasRegionsMut<'a> for TestLang {
+        type IterMut = std::iter::Empty<&'a mut crate::Region>;
+        fn regions_mut(&'a mut self) -> Self::IterMut {
+            std::iter::empty()
+        }
+    }
+    impl IsTerminator for TestLang {
+        fn is_terminator(&self) -> bool {
+            false
+        }
+    }
+    impl IsConstant for TestLang {
+        fn is_constant(&self) -> bool {
+            false
+        }
+    }
+    impl IsPure for TestLang {
+        fn is_pure(&self) -> bool {
+            true
+        }
+    }
+    impl Dialect for TestLang {
+        type TypeLattice = TestType;
+    }
+
+    static INSTANTIATE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingInstantiate;
+
+    impl Instantiate<TestLang> for CountingInstantiate {
+        fn instantiate(
+            _context: &mut Context<TestLang>,
+            generic_body: Statement,
+            _signature: &Signature<TestLang>,
+        ) -> (Statement, TestType) {
+            INSTANTIATE_CALLS.fetch_add(1, Ordering::SeqCst);
+            (generic_body, TestType)
+        }
+    }
+
+    #[test]
+    fn monomorphize_caches_repeat_signature_and_records_backedges() {
+        INSTANTIATE_CALLS.store(0, Ordering::SeqCst);
+
+        let mut context: Context<TestLang> = Context::default();
+        let staged = context.staged_function().new();
+        let generic_body = Statement::from(Id::from_raw(0));
+        let signature = Signature(vec![TestType]);
+        let caller_1 = SpecializedFunction(StagedFunction::from(Id::from_raw(100)), 0);
+        let caller_2 = SpecializedFunction(StagedFunction::from(Id::from_raw(101)), 0);
+
+        let spec_1 = context.monomorphize::<CountingInstantiate>(
+            staged,
+            generic_body,
+            signature.clone(),
+            caller_1,
+        );
+        assert_eq!(INSTANTIATE_CALLS.load(Ordering::SeqCst), 1);
+        assert!(spec_1.expect_info(&context).backedges().contains(&caller_1));
+
+        let spec_2 =
+            context.monomorphize::<CountingInstantiate>(staged, generic_body, signature, caller_2);
+
+        assert_eq!(spec_2, spec_1, "a repeat call with the same signature must hit the existing specialization");
+        assert_eq!(
+            INSTANTIATE_CALLS.load(Ordering::SeqCst),
+            1,
+            "a cache hit must not call Instantiate::instantiate again"
+        );
+        assert!(spec_2.expect_info(&context).backedges().contains(&caller_1));
+        assert!(spec_2.expect_info(&context).backedges().contains(&caller_2));
+    }
+}
@@ -0,0 +1,394 @@
+//! Type-checking/verification pass over a dialect's IR, modeled loosely on
+//! rust-analyzer's `infer`/`unify` and chalk's unification engine.
+//!
+//! [`Verifier`] walks the statement tree rooted at a [`Statement`], gives
+//! each one a type signature via [`TypeSignature`], and checks that every
+//! operand's type unifies with what the consuming statement expects -- and,
+//! across a [`Successor`] edge, that the passed [`SSAValue`]s unify with the
+//! target block's declared argument types. Dialects generic over a type
+//! parameter (e.g. `Arith<T>`) can leave signature slots as fresh
+//! [`TypeVar`]s; [`Unifier`] resolves them through a union-find over type
+//! terms. Mismatches are accumulated as [`TypeError`]s rather than causing a
+//! panic, so one bad statement doesn't stop verification of the rest of the
+//! function.
+
+use crate::{Block, Context, Dialect, HasArguments, HasRegions, HasResults, SSAValue, Statement};
+
+/// A placeholder for a not-yet-known type, introduced while checking a
+/// statement generic over a dialect's type parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TypeVar(usize);
+
+/// A type term in the unification problem: either a concrete dialect type or
+/// an unbound [`TypeVar`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeTerm<T> {
+    Concrete(T),
+    Var(TypeVar),
+}
+
+impl<T> From<T> for TypeTerm<T> {
+    fn from(ty: T) -> Self {
+        TypeTerm::Concrete(ty)
+    }
+}
+
+/// Why a [`Unifier::unify`] call failed.
+///
+/// There is no `Occurs` variant: [`TypeTerm`] has no compound constructor of
+/// its own, so a variable can never end up nested inside the concrete type
+/// it's bound to -- an occurs check has nothing to check against `T` as
+/// defined today. If a dialect's `TypeLattice` ever grows its own embedded
+/// type variables (making `T` itself capable of containing a `TypeVar`),
+/// `unify`'s `(Var, Concrete)` arm will need to walk `ty` for occurrences of
+/// `var` before binding, and this enum will need an `Occurs(TypeVar)` case
+/// to report it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnifyError<T> {
+    /// The two terms resolved to different concrete types.
+    Mismatch { expected: T, found: T },
+}
+
+/// Hindley-Milner-style union-find over [`TypeTerm`]s.
+///
+/// Each [`TypeVar`] is a union-find node; [`Unifier::unify`] merges two
+/// terms' representatives, and once a variable's representative is bound to
+/// a concrete type, every variable unified with it resolves to that type.
+#[derive(Debug)]
+pub struct Unifier<T> {
+    parent: Vec<usize>,
+    bound: Vec<Option<T>>,
+}
+
+impl<T> Default for Unifier<T> {
+    fn default() -> Self {
+        Self {
+            parent: Vec::new(),
+            bound: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Unifier<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Introduce a fresh, as-yet-unbound type variable.
+    pub fn fresh(&mut self) -> TypeVar {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.bound.push(None);
+        TypeVar(id)
+    }
+
+    /// Find the representative node of `var`'s union-find set, compressing
+    /// the path as it walks up.
+    fn find(&mut self, var: TypeVar) -> usize {
+        let mut root = var.0;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut cur = var.0;
+        while self.parent[cur] != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+        root
+    }
+
+    /// Resolve a term through the union-find: a bound variable resolves to
+    /// its concrete type, an unbound variable resolves to its representative.
+    pub fn resolve(&mut self, term: &TypeTerm<T>) -> TypeTerm<T> {
+        match term {
+            TypeTerm::Concrete(ty) => TypeTerm::Concrete(ty.clone()),
+            TypeTerm::Var(var) => {
+                let root = self.find(*var);
+                match &self.bound[root] {
+                    Some(ty) => TypeTerm::Concrete(ty.clone()),
+                    None => TypeTerm::Var(TypeVar(root)),
+                }
+            }
+        }
+    }
+
+    /// Unify two type terms, binding variables and checking concrete types
+    /// for equality.
+    pub fn unify(&mut self, a: &TypeTerm<T>, b: &TypeTerm<T>) -> Result<(), UnifyError<T>> {
+        match (self.resolve(a), self.resolve(b)) {
+            (TypeTerm::Concrete(a), TypeTerm::Concrete(b)) => {
+                if a == b {
+                    Ok(())
+                } else {
+                    Err(UnifyError::Mismatch {
+                        expected: a,
+                        found: b,
+                    })
+                }
+            }
+            (TypeTerm::Var(var), TypeTerm::Concrete(ty))
+            | (TypeTerm::Concrete(ty), TypeTerm::Var(var)) => {
+                let root = self.find(var);
+                self.bound[root] = Some(ty);
+                Ok(())
+            }
+            (TypeTerm::Var(a), TypeTerm::Var(b)) => {
+                let (ra, rb) = (self.find(a), self.find(b));
+                if ra != rb {
+                    self.parent[rb] = ra;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A statement's expected type signature: the type each operand must unify
+/// with, and the type each result takes on.
+///
+/// Implement this on a dialect's enum (the same place `IsTerminator`,
+/// `IsConstant`, etc. are implemented) to opt it into [`Verifier`].
+pub trait TypeSignature<L: Dialect> {
+    /// Operand type expectations, in the same order as
+    /// [`HasArguments::arguments`]. May allocate fresh `unifier.fresh()`
+    /// variables for slots that depend on the statement's own type parameter.
+    fn operand_types(&self, unifier: &mut Unifier<L::TypeLattice>) -> Vec<TypeTerm<L::TypeLattice>>;
+
+    /// Result type expectations, in the same order as
+    /// [`HasResults::results`].
+    fn result_types(&self, unifier: &mut Unifier<L::TypeLattice>) -> Vec<TypeTerm<L::TypeLattice>>;
+}
+
+/// A located type mismatch found while verifying a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeError {
+    /// The statement whose operand or successor argument was ill-typed.
+    pub statement: Statement,
+    pub message: String,
+}
+
+/// A located type mismatch found while constructing a statement, before it
+/// has a [`Statement`] handle of its own (see [`TypeError`] for the
+/// post-construction equivalent, raised by [`Verifier`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstructError {
+    /// The name of the field whose argument was ill-typed.
+    pub field: &'static str,
+    pub message: String,
+}
+
+/// Walks a dialect's statement tree, checking operand/successor types
+/// against each statement's [`TypeSignature`] and accumulating mismatches.
+pub struct Verifier<L: Dialect> {
+    unifier: Unifier<L::TypeLattice>,
+    errors: Vec<TypeError>,
+}
+
+impl<L: Dialect> Default for Verifier<L> {
+    fn default() -> Self {
+        Self {
+            unifier: Unifier::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl<L: Dialect + TypeSignature<L>> Verifier<L>
+where
+    L::TypeLattice: std::fmt::Display,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The mismatches found so far.
+    pub fn errors(&self) -> &[TypeError] {
+        &self.errors
+    }
+
+    /// Consume the verifier, returning every mismatch found.
+    pub fn into_errors(self) -> Vec<TypeError> {
+        self.errors
+    }
+
+    /// Check `stmt` and, recursively, every statement nested in its regions.
+    ///
+    /// This is the entry point for verifying a whole function: call it with
+    /// a [`SpecializedFunctionInfo::body`](crate::SpecializedFunctionInfo::body)
+    /// or any other root statement, then inspect [`Verifier::errors`].
+    pub fn check_statement_tree(&mut self, context: &Context<L>, stmt: Statement) {
+        self.check_statement(context, stmt);
+        for region in stmt.definition(context).regions() {
+            for block in region.blocks(context) {
+                for inner in block.statements(context) {
+                    self.check_statement_tree(context, inner);
+                }
+            }
+        }
+    }
+
+    /// Check a single statement's operands against its signature, and bind
+    /// its result types so downstream statements can unify against them.
+    pub fn check_statement(&mut self, context: &Context<L>, stmt: Statement) {
+        let definition = stmt.definition(context);
+        let expected_operands = definition.operand_types(&mut self.unifier);
+        let expected_results = definition.result_types(&mut self.unifier);
+
+        for (operand, expected) in definition.arguments().zip(&expected_operands) {
+            let found = TypeTerm::Concrete(operand.expect_info(context).ty().clone());
+            let result = self.unifier.unify(expected, &found);
+            self.record(stmt, result);
+        }
+        for (result_value, expected) in definition.results().zip(&expected_results) {
+            let found =
+                TypeTerm::Concrete(SSAValue::from(*result_value).expect_info(context).ty().clone());
+            let result = self.unifier.unify(expected, &found);
+            self.record(stmt, result);
+        }
+    }
+
+    /// Check that `args`, passed along a [`Successor`](crate::Successor)
+    /// edge from `stmt`, unify with `target`'s declared block arguments.
+    pub fn check_successor_args(
+        &mut self,
+        context: &Context<L>,
+        stmt: Statement,
+        target: Block,
+        args: &[SSAValue],
+    ) {
+        let declared = &target.expect_info(context).arguments;
+        if declared.len() != args.len() {
+            self.errors.push(TypeError {
+                statement: stmt,
+                message: format!(
+                    "block {target} expects {} argument(s), found {}",
+                    declared.len(),
+                    args.len()
+                ),
+            });
+            return;
+        }
+        for (&block_arg, &value) in declared.iter().zip(args) {
+            let expected = TypeTerm::Concrete(SSAValue::from(block_arg).expect_info(context).ty().clone());
+            let found = TypeTerm::Concrete(value.expect_info(context).ty().clone());
+            let result = self.unifier.unify(&expected, &found);
+            self.record(stmt, result);
+        }
+    }
+
+    fn record(&mut self, stmt: Statement, result: Result<(), UnifyError<L::TypeLattice>>) {
+        if let Err(err) = result {
+            self.errors.push(TypeError {
+                statement: stmt,
+                message: describe_unify_error(err),
+            });
+        }
+    }
+}
+
+fn describe_unify_error<T: std::fmt::Display>(err: UnifyError<T>) -> String {
+    match err {
+        UnifyError::Mismatch { expected, found } => {
+            format!("expected type `{expected}`, found `{found}`")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum SimpleType {
+        Int,
+        Bool,
+    }
+
+    impl std::fmt::Display for SimpleType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SimpleType::Int => write!(f, "int"),
+                SimpleType::Bool => write!(f, "bool"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unify_concrete_match() {
+        let mut unifier = Unifier::new();
+        assert!(
+            unifier
+                .unify(
+                    &TypeTerm::Concrete(SimpleType::Int),
+                    &TypeTerm::Concrete(SimpleType::Int)
+                )
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_unify_concrete_mismatch() {
+        let mut unifier = Unifier::new();
+        let err = unifier
+            .unify(
+                &TypeTerm::Concrete(SimpleType::Int),
+                &TypeTerm::Concrete(SimpleType::Bool),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            UnifyError::Mismatch {
+                expected: SimpleType::Int,
+                found: SimpleType::Bool
+            }
+        );
+    }
+
+    #[test]
+    fn test_unify_var_binds_to_concrete() {
+        let mut unifier: Unifier<SimpleType> = Unifier::new();
+        let var = unifier.fresh();
+        unifier
+            .unify(&TypeTerm::Var(var), &TypeTerm::Concrete(SimpleType::Int))
+            .unwrap();
+        assert_eq!(
+            unifier.resolve(&TypeTerm::Var(var)),
+            TypeTerm::Concrete(SimpleType::Int)
+        );
+    }
+
+    #[test]
+    fn test_unify_two_vars_share_binding() {
+        let mut unifier: Unifier<SimpleType> = Unifier::new();
+        let a = unifier.fresh();
+        let b = unifier.fresh();
+        unifier.unify(&TypeTerm::Var(a), &TypeTerm::Var(b)).unwrap();
+        unifier
+            .unify(&TypeTerm::Var(a), &TypeTerm::Concrete(SimpleType::Bool))
+            .unwrap();
+        assert_eq!(
+            unifier.resolve(&TypeTerm::Var(b)),
+            TypeTerm::Concrete(SimpleType::Bool)
+        );
+    }
+
+    #[test]
+    fn test_unify_var_after_binding_rejects_mismatch() {
+        let mut unifier: Unifier<SimpleType> = Unifier::new();
+        let var = unifier.fresh();
+        unifier
+            .unify(&TypeTerm::Var(var), &TypeTerm::Concrete(SimpleType::Int))
+            .unwrap();
+        let err = unifier
+            .unify(&TypeTerm::Var(var), &TypeTerm::Concrete(SimpleType::Bool))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            UnifyError::Mismatch {
+                expected: SimpleType::Int,
+                found: SimpleType::Bool
+            }
+        );
+    }
+}
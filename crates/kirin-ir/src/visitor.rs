@@ -1,6 +1,108 @@
-use crate::{Context, Dialect, Statement};
+use crate::{Block, Context, Dialect, Region, ResultValue, SSAValue, Statement, Successor};
 
 pub trait Visit<L: Dialect, T> {
     type Output;
     fn visit(&mut self, context: &Context<L>, item: &T) -> Self::Output;
 }
+
+/// Per-kind callback hooks for walking a statement's SSA-value/block/region
+/// fields without hand-writing the `match` over its variants. All methods
+/// default to a no-op, so callers only override the kinds they care about.
+/// Paired with `#[derive(Visit)]` on a dialect, which implements [`Visitable`].
+pub trait StatementVisitor<L: Dialect> {
+    fn visit_ssa_value(&mut self, _value: &SSAValue) {}
+    fn visit_block(&mut self, _block: &Block) {}
+    fn visit_region(&mut self, _region: &Region) {}
+}
+
+/// Emitted by `#[derive(Visit)]`: walks `self`'s SSA-value/block/region
+/// fields into a [`StatementVisitor`], recursing through `#[wraps]`
+/// delegation instead of visiting a wrapper variant's own fields.
+pub trait Visitable<L: Dialect> {
+    fn walk(&self, visitor: &mut impl StatementVisitor<L>);
+}
+
+/// The `&mut self` counterpart of [`StatementVisitor`]: per-kind callback
+/// hooks for walking a statement's SSA-value/block/region fields in place.
+/// All methods default to a no-op, so callers only override the kinds they
+/// care about. Paired with `#[derive(Visit)]`, which implements
+/// [`VisitableMut`].
+pub trait StatementVisitorMut<L: Dialect> {
+    fn visit_ssa_value_mut(&mut self, _value: &mut SSAValue) {}
+    fn visit_block_mut(&mut self, _block: &mut Block) {}
+    fn visit_region_mut(&mut self, _region: &mut Region) {}
+}
+
+/// Emitted by `#[derive(Visit)]`: walks `self`'s SSA-value/block/region
+/// fields in place into a [`StatementVisitorMut`], recursing through
+/// `#[wraps]` delegation instead of visiting a wrapper variant's own fields.
+pub trait VisitableMut<L: Dialect> {
+    fn walk_mut(&mut self, visitor: &mut impl StatementVisitorMut<L>);
+}
+
+/// Per-kind callback hooks for rewriting a statement's SSA-value/block/
+/// region fields. All methods default to identity, so callers only override
+/// the kinds they care about. Paired with `#[derive(Fold)]`, which
+/// implements [`Foldable`].
+pub trait StatementFolder<L: Dialect> {
+    fn fold_ssa_value(&mut self, value: SSAValue) -> SSAValue {
+        value
+    }
+    fn fold_block(&mut self, block: Block) -> Block {
+        block
+    }
+    fn fold_region(&mut self, region: Region) -> Region {
+        region
+    }
+}
+
+/// Emitted by `#[derive(Fold)]`: rebuilds `self` with every SSA-value/block/
+/// region field passed through a [`StatementFolder`], recursing through
+/// `#[wraps]` delegation instead of folding a wrapper variant's own fields.
+pub trait Foldable<L: Dialect>: Sized {
+    fn fold_with(self, folder: &mut impl StatementFolder<L>) -> Self;
+}
+
+/// Emitted by `#[derive(Walk)]`: visits `self`'s operand/result/block/
+/// successor/region fields one category at a time, in declaration order,
+/// instead of lumping every `SSAValue` field together the way [`Visitable`]
+/// does. This lets a pass tell an operand use apart from a result
+/// definition without hand-writing a `match` over the dialect's variants. A
+/// `#[wraps]` variant forwards to the wrapped field's own [`Walk`] impl
+/// rather than walking its own fields.
+pub trait Walk<L: Dialect> {
+    fn walk_operands(&self, visitor: &mut impl FnMut(&SSAValue));
+    fn walk_results(&self, visitor: &mut impl FnMut(&ResultValue));
+    fn walk_blocks(&self, visitor: &mut impl FnMut(&Block));
+    fn walk_successors(&self, visitor: &mut impl FnMut(&Successor));
+    fn walk_regions(&self, visitor: &mut impl FnMut(&Region));
+}
+
+/// The `&mut self` counterpart of [`Walk`]: same per-kind breakdown, but
+/// every callback receives a mutable reference so a pass can remap SSA
+/// values, substitute block arguments, or relink successors in place
+/// without hand-writing per-node match arms. Paired with `#[derive(Walk)]`.
+pub trait WalkMut<L: Dialect> {
+    fn walk_operands_mut(&mut self, visitor: &mut impl FnMut(&mut SSAValue));
+    fn walk_results_mut(&mut self, visitor: &mut impl FnMut(&mut ResultValue));
+    fn walk_blocks_mut(&mut self, visitor: &mut impl FnMut(&mut Block));
+    fn walk_successors_mut(&mut self, visitor: &mut impl FnMut(&mut Successor));
+    fn walk_regions_mut(&mut self, visitor: &mut impl FnMut(&mut Region));
+}
+
+/// The consuming counterpart of [`Walk`]: rebuilds `self` with every
+/// operand/result/block/successor/region of one category passed through a
+/// closure that returns a replacement, instead of visiting in place. This is
+/// the shape SSA renaming, inlining, and block-argument remapping actually
+/// need — `map_operands(f)` returns a new value with every operand replaced
+/// by `f(operand)`, rather than requiring the caller to pre-allocate a
+/// `&mut` target. Paired with `#[derive(Walk)]`, which implements this
+/// alongside [`Walk`]/[`WalkMut`]; a `#[wraps]` variant forwards to the
+/// wrapped field's own `Map` impl of the same method.
+pub trait Map<L: Dialect>: Sized {
+    fn map_operands(self, f: &mut impl FnMut(SSAValue) -> SSAValue) -> Self;
+    fn map_results(self, f: &mut impl FnMut(ResultValue) -> ResultValue) -> Self;
+    fn map_blocks(self, f: &mut impl FnMut(Block) -> Block) -> Self;
+    fn map_successors(self, f: &mut impl FnMut(Successor) -> Successor) -> Self;
+    fn map_regions(self, f: &mut impl FnMut(Region) -> Region) -> Self;
+}
@@ -60,6 +60,18 @@ pub enum Token<'src> {
     DoubleColon,
     #[token(";")]
     Semicolon,
+    #[token("?")]
+    Question,
+    #[token("|")]
+    Pipe,
+    #[token("*")]
+    Star,
+    #[token("+")]
+    Plus,
+    #[token("{{")]
+    EscapedLBrace,
+    #[token("}}")]
+    EscapedRBrace,
 }
 
 impl std::fmt::Display for Token<'_> {
@@ -91,6 +103,12 @@ impl std::fmt::Display for Token<'_> {
             Token::Ellipsis => write!(f, "..."),
             Token::DoubleColon => write!(f, "::"),
             Token::Semicolon => write!(f, ";"),
+            Token::Question => write!(f, "?"),
+            Token::Pipe => write!(f, "|"),
+            Token::Star => write!(f, "*"),
+            Token::Plus => write!(f, "+"),
+            Token::EscapedLBrace => write!(f, "{{{{"),
+            Token::EscapedRBrace => write!(f, "}}}}"),
         }
     }
 }
@@ -1,5 +1,7 @@
 extern crate proc_macro;
 
+mod pretty_print;
+
 use kirin_derive_core::stage;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
@@ -17,6 +19,17 @@ pub fn derive_render_stage(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derives `kirin_prettyless::PrettyPrint` from a `#[pretty("...")]` format
+/// string on each struct/variant, instead of a hand-written `pretty_print` impl.
+#[proc_macro_derive(PrettyPrint, attributes(pretty))]
+pub fn derive_pretty_print(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match pretty_print::derive(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
 fn generate(input: &DeriveInput) -> Result<TokenStream2, syn::Error> {
     let variants = stage::parse_stage_variants(input)?;
 
@@ -0,0 +1,317 @@
+//! `#[derive(PrettyPrint)]` driven by a `#[pretty("...")]` format string on each
+//! struct/variant, so dialect authors don't have to hand-write rendering logic
+//! that mirrors what `#[chumsky(format = "...")]` already says about syntax.
+//!
+//! The format-string grammar borrows from `derive_more`'s `Display` derive:
+//! - literal text is emitted verbatim
+//! - `{field}` interpolates a named field
+//! - `{0}` interpolates a positional (tuple) field
+//! - `{}` interpolates the next field in declaration order
+//! - `{field:SEP}` treats `field` as a `Vec<_>` and joins its elements with the
+//!   literal `SEP`
+//!
+//! A field typed `SSAValue`/`ResultValue` (or `Vec<SSAValue>`/`Vec<ResultValue>`)
+//! is rendered through [`kirin_prettyless::PrettyPrint`]; every other field falls
+//! back to its `Display` impl.
+
+use kirin_derive_core::misc::{is_type, is_vec_type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields};
+
+const PRINTED_TYPES: [&str; 2] = ["SSAValue", "ResultValue"];
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let format = parse_pretty_attr(&input.attrs)?
+                .ok_or_else(|| syn::Error::new_spanned(input, "missing `#[pretty(\"...\")]`"))?;
+            let pattern = fields_pattern(&data.fields);
+            let doc_expr = render_format(&format, &data.fields, input)?;
+            quote! {
+                let Self #pattern = self;
+                #doc_expr
+            }
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let format = parse_pretty_attr(&variant.attrs)?.ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        variant,
+                        format!(
+                            "variant `{}` is missing `#[pretty(\"...\")]`",
+                            variant.ident
+                        ),
+                    )
+                })?;
+                let variant_ident = &variant.ident;
+                let pattern = fields_pattern(&variant.fields);
+                let doc_expr = render_format(&format, &variant.fields, input)?;
+                arms.push(quote! {
+                    Self::#variant_ident #pattern => { #doc_expr }
+                });
+            }
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`#[derive(PrettyPrint)]` does not support unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::kirin_prettyless::PrettyPrint for #ident #ty_generics #where_clause {
+            fn pretty_print<'a, __L: ::kirin_ir::Dialect + ::kirin_prettyless::PrettyPrint>(
+                &self,
+                doc: &'a ::kirin_prettyless::Document<'a, __L>,
+            ) -> ::kirin_prettyless::ArenaDoc<'a>
+            where
+                __L::Type: ::std::fmt::Display,
+            {
+                #body
+            }
+        }
+    })
+}
+
+/// Read the `#[pretty("...")]` attribute off a struct/variant, if present.
+fn parse_pretty_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    for attr in attrs {
+        if attr.path().is_ident("pretty") {
+            let lit: syn::LitStr = attr.parse_args()?;
+            return Ok(Some(lit.value()));
+        }
+    }
+    Ok(None)
+}
+
+/// Build a `Self { .. }` / `Self(..)` / `Self` pattern that binds every field by name.
+fn fields_pattern(fields: &Fields) -> TokenStream2 {
+    match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote! { { #(#idents,)* .. } }
+        }
+        Fields::Unnamed(unnamed) => {
+            let idents = (0..unnamed.unnamed.len()).map(|i| format_ident!("field_{i}"));
+            quote! { ( #(#idents),* ) }
+        }
+        Fields::Unit => quote! {},
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field { placeholder: String, sep: Option<String> },
+}
+
+/// Scan a `#[pretty("...")]` format string into literal/placeholder segments.
+fn scan_format(format: &str) -> syn::Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '{' {
+            if !literal.is_empty() {
+                segments.push(Segment::Literal(std::mem::take(&mut literal)));
+            }
+            let mut inner = String::new();
+            let mut closed = false;
+            for c in chars.by_ref() {
+                if c == '}' {
+                    closed = true;
+                    break;
+                }
+                inner.push(c);
+            }
+            if !closed {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!("unterminated `{{` in pretty format string {format:?}"),
+                ));
+            }
+            let (placeholder, sep) = match inner.split_once(':') {
+                Some((name, sep)) => (name.to_string(), Some(sep.to_string())),
+                None => (inner, None),
+            };
+            segments.push(Segment::Field { placeholder, sep });
+        } else {
+            literal.push(c);
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// Resolve a placeholder (`name`, index, or empty-sequential) to the matching
+/// field's bound identifier and declared type.
+fn resolve_field<'a>(
+    placeholder: &str,
+    fields: &'a Fields,
+    next_sequential: &mut usize,
+) -> syn::Result<(proc_macro2::Ident, &'a syn::Type)> {
+    match fields {
+        Fields::Named(named) => {
+            let field = if placeholder.is_empty() {
+                let field = named.named.iter().nth(*next_sequential);
+                *next_sequential += 1;
+                field
+            } else {
+                named
+                    .named
+                    .iter()
+                    .find(|f| f.ident.as_ref().is_some_and(|i| i == placeholder))
+            };
+            let field = field.ok_or_else(|| {
+                syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!("no field named `{placeholder}` in pretty format string"),
+                )
+            })?;
+            Ok((field.ident.clone().unwrap(), &field.ty))
+        }
+        Fields::Unnamed(unnamed) => {
+            let index = if placeholder.is_empty() {
+                let index = *next_sequential;
+                *next_sequential += 1;
+                index
+            } else {
+                placeholder.parse::<usize>().map_err(|_| {
+                    syn::Error::new(
+                        proc_macro2::Span::call_site(),
+                        format!("`{placeholder}` is not a valid tuple field index"),
+                    )
+                })?
+            };
+            let field = unnamed.unnamed.iter().nth(index).ok_or_else(|| {
+                syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!("tuple field index {index} out of range"),
+                )
+            })?;
+            Ok((format_ident!("field_{index}"), &field.ty))
+        }
+        Fields::Unit => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "unit struct/variant has no fields to interpolate",
+        )),
+    }
+}
+
+fn render_format(
+    format: &str,
+    fields: &Fields,
+    span: &DeriveInput,
+) -> syn::Result<TokenStream2> {
+    let segments = scan_format(format)?;
+    let mut next_sequential = 0usize;
+    let mut parts = Vec::new();
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => parts.push(quote! { doc.text(#text) }),
+            Segment::Field { placeholder, sep } => {
+                let (ident, ty) = resolve_field(&placeholder, fields, &mut next_sequential)
+                    .map_err(|e| syn::Error::new_spanned(span, e.to_string()))?;
+                let is_printed = PRINTED_TYPES.iter().any(|name| is_type(ty, *name));
+                let is_printed_vec = PRINTED_TYPES.iter().any(|name| is_vec_type(ty, *name));
+                let part = match (is_printed_vec, &sep) {
+                    (true, Some(sep)) => quote! {
+                        {
+                            let mut acc = doc.nil();
+                            for (__i, __v) in #ident.iter().enumerate() {
+                                if __i > 0 {
+                                    acc += doc.text(#sep);
+                                }
+                                acc += ::kirin_prettyless::PrettyPrint::pretty_print(__v, doc);
+                            }
+                            acc
+                        }
+                    },
+                    (true, None) => {
+                        return Err(syn::Error::new_spanned(
+                            span,
+                            format!(
+                                "field `{ident}` is a repeated operand; use `{{{placeholder}:SEP}}` to join it"
+                            ),
+                        ));
+                    }
+                    (false, _) if is_printed => {
+                        quote! { ::kirin_prettyless::PrettyPrint::pretty_print(#ident, doc) }
+                    }
+                    (false, _) => quote! { doc.text(::std::format!("{}", #ident)) },
+                };
+                parts.push(part);
+            }
+        }
+    }
+    if parts.is_empty() {
+        return Ok(quote! { doc.nil() });
+    }
+    let mut iter = parts.into_iter();
+    let first = iter.next().unwrap();
+    Ok(iter.fold(first, |acc, part| quote! { (#acc) + (#part) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_format_literal_and_named() {
+        let segments = scan_format("add {lhs}, {rhs}").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Literal("add ".to_string()),
+                Segment::Field {
+                    placeholder: "lhs".to_string(),
+                    sep: None
+                },
+                Segment::Literal(", ".to_string()),
+                Segment::Field {
+                    placeholder: "rhs".to_string(),
+                    sep: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_format_separated_and_empty() {
+        let segments = scan_format("call {callee}({args:, })").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Literal("call ".to_string()),
+                Segment::Field {
+                    placeholder: "callee".to_string(),
+                    sep: None
+                },
+                Segment::Literal("(".to_string()),
+                Segment::Field {
+                    placeholder: "args".to_string(),
+                    sep: Some(", ".to_string())
+                },
+                Segment::Literal(")".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_format_unterminated_brace() {
+        assert!(scan_format("add {lhs").is_err());
+    }
+}
@@ -1,3 +1,18 @@
+use crate::style::ColorScheme;
+
+/// Controls how much structural detail [`Document`](crate::Document) emits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Verbosity {
+    /// Terse, user-facing form: just the printed IR (the default).
+    #[default]
+    Compact,
+    /// Fully-explicit structural form: every result's inferred type,
+    /// block-argument ids, and other provenance a pass author needs to
+    /// debug the IR rather than just read it.
+    Debug,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
@@ -8,6 +23,14 @@ pub struct Config {
     pub max_width: usize,
     /// Whether to include line numbers in the output.
     pub line_numbers: bool,
+    /// Whether to render styled text (keywords, SSA values, symbols, types,
+    /// ...) as ANSI escape sequences. Off by default so piping output to a
+    /// file or another tool stays plain text.
+    pub color: bool,
+    /// Color scheme consulted when `color` is enabled.
+    pub color_scheme: ColorScheme,
+    /// Compact (default) or fully-explicit debug printing. See [`Verbosity`].
+    pub verbosity: Verbosity,
 }
 
 impl Default for Config {
@@ -16,6 +39,9 @@ impl Default for Config {
             tab_spaces: 4,
             max_width: 120,
             line_numbers: true,
+            color: false,
+            color_scheme: ColorScheme::default(),
+            verbosity: Verbosity::default(),
         }
     }
 }
@@ -35,4 +61,22 @@ impl Config {
         self.line_numbers = line_numbers;
         self
     }
+
+    /// Enable or disable ANSI-styled rendering (see [`Config::color`]).
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Use a custom [`ColorScheme`] instead of the default one.
+    pub fn with_color_scheme(mut self, color_scheme: ColorScheme) -> Self {
+        self.color_scheme = color_scheme;
+        self
+    }
+
+    /// Select compact or debug printing (see [`Verbosity`]).
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
 }
@@ -4,11 +4,14 @@ use std::{borrow::Cow, ops::Deref};
 
 use kirin_ir::{
     Block, Context, DenseHint, Dialect, GetInfo, GlobalSymbol, Id, InternTable, Item, Region,
-    SSAInfo, Signature, SpecializedFunction, StagedFunction, Statement,
+    SSAInfo, Signature, SparseHint, SpecializedFunction, StagedFunction, Statement, Walk,
 };
 use prettyless::{Arena, DocAllocator};
 
-use crate::{ArenaDoc, Config, PrettyPrint, ScanResultWidth};
+use crate::{
+    ArenaDoc, Config, PrettyPrint, ScanResultWidth, Verbosity,
+    style::Style,
+};
 
 /// A document builder for pretty printing IR.
 ///
@@ -21,6 +24,7 @@ pub struct Document<'a, L: Dialect> {
     global_symbols: Option<&'a InternTable<String, GlobalSymbol>>,
     result_width: DenseHint<Statement, usize>,
     max_result_width: usize,
+    hints: Option<&'a SparseHint<Statement, String>>,
 }
 
 impl<'a, L: Dialect> Document<'a, L> {
@@ -38,6 +42,7 @@ impl<'a, L: Dialect> Document<'a, L> {
             global_symbols: None,
             result_width: context.statement_arena().hint().dense(),
             max_result_width: 0,
+            hints: None,
         }
     }
 
@@ -58,14 +63,35 @@ impl<'a, L: Dialect> Document<'a, L> {
             global_symbols: Some(global_symbols),
             result_width: context.statement_arena().hint().dense(),
             max_result_width: 0,
+            hints: None,
         }
     }
 
+    /// Attach a table of per-statement annotations, rendered as trailing
+    /// `// ...` comments after each statement (e.g. source locations, type
+    /// inference results, or other analysis facts).
+    ///
+    /// Passes surface their results by filling in a [`SparseHint`] keyed by
+    /// [`Statement`] and handing it to the document, instead of every
+    /// dialect re-implementing its own annotation printing.
+    pub fn with_hints(mut self, hints: &'a SparseHint<Statement, String>) -> Self {
+        self.hints = Some(hints);
+        self
+    }
+
     /// Returns a reference to the global symbol table, if available.
     pub fn global_symbols(&self) -> Option<&'a InternTable<String, GlobalSymbol>> {
         self.global_symbols
     }
 
+    /// Look up the annotation hint for a statement, if any hint table was
+    /// attached via [`Document::with_hints`].
+    fn statement_hint(&self, stmt: &Statement) -> Option<&'a str> {
+        self.hints
+            .and_then(|hints| hints.get(*stmt))
+            .map(String::as_str)
+    }
+
     /// Indent a document by the configured tab spaces.
     pub fn indent(&'a self, doc: ArenaDoc<'a>) -> ArenaDoc<'a> {
         doc.nest(self.config.tab_spaces as isize)
@@ -81,6 +107,21 @@ impl<'a, L: Dialect> Document<'a, L> {
         &self.config
     }
 
+    /// Print `text` tagged with `style`.
+    ///
+    /// When [`Config::color`] is enabled the text is wrapped in the ANSI
+    /// escape sequence [`Config::color_scheme`] assigns to `style`;
+    /// otherwise this is identical to printing `text` directly, so plain
+    /// `render`/`render_fmt` output (files, pipes, existing snapshot tests)
+    /// is unaffected unless color mode is explicitly turned on.
+    pub fn styled_text(&'a self, style: Style, text: impl Into<Cow<'a, str>>) -> ArenaDoc<'a> {
+        let text = text.into();
+        if !self.config.color {
+            return self.text(text);
+        }
+        self.text(self.config.color_scheme.wrap(style, &text))
+    }
+
     /// Returns a reference to the IR context.
     pub fn context(&self) -> &'a Context<L> {
         self.context
@@ -148,6 +189,19 @@ impl<'a, L: Dialect> Document<'a, L> {
         arena_doc.render_fmt(max_width, &mut buf)?;
         Ok(strip_trailing_whitespace(&buf))
     }
+
+    /// Render a node to a string with [`Verbosity::Debug`], regardless of
+    /// the document's configured verbosity, for a pass author's precise IR
+    /// dump (every result's inferred type, block-argument ids, ...).
+    pub fn debug_render<N>(&'a mut self, node: &N) -> Result<String, std::fmt::Error>
+    where
+        N: ScanResultWidth<L> + PrettyPrint,
+        L: PrettyPrint,
+        L::Type: std::fmt::Display,
+    {
+        self.config.verbosity = Verbosity::Debug;
+        self.render(node)
+    }
 }
 
 // Methods for printing IR nodes that need L: PrettyPrint bound
@@ -156,10 +210,31 @@ where
     L::Type: std::fmt::Display,
 {
     /// Pretty print a statement by printing its definition.
+    ///
+    /// In [`Verbosity::Debug`] the result types are appended (`%2 = add %0,
+    /// %1 : Int`); in [`Verbosity::Compact`] (the default) the output is
+    /// unchanged. Either way, the statement's annotation hint, if any was
+    /// attached via [`Document::with_hints`], is appended as a trailing
+    /// `// ...` comment.
     pub fn print_statement(&'a self, stmt: &Statement) -> ArenaDoc<'a> {
         let stmt_info = stmt.expect_info(self.context);
         let def = stmt_info.definition();
-        def.pretty_print(self)
+        let mut doc = def.pretty_print(self);
+
+        if self.config.verbosity == Verbosity::Debug {
+            let types = self.list(stmt.results(self.context), ", ", |result| {
+                let info: &Item<SSAInfo<L>> = result.expect_info(self.context);
+                self.text(format!("{}", info.ty()))
+            });
+            if !types.is_nil() {
+                doc += self.text(" : ") + types;
+            }
+        }
+
+        match self.statement_hint(stmt) {
+            Some(hint) => doc + self.text(format!("  // {hint}")),
+            None => doc,
+        }
     }
 
     /// Pretty print a block with its header and statements.
@@ -199,7 +274,16 @@ where
                 } else {
                     format!("{}", Id::from(*arg).raw())
                 };
-                args_doc += self.text(format!("%{}: {}", name, arg_info.ty()));
+                if self.config.verbosity == Verbosity::Debug {
+                    args_doc += self.text(format!(
+                        "%{}#{}: {}",
+                        name,
+                        Id::from(*arg).raw(),
+                        arg_info.ty()
+                    ));
+                } else {
+                    args_doc += self.text(format!("%{}: {}", name, arg_info.ty()));
+                }
             }
             header += args_doc.enclose("(", ")");
         }
@@ -339,6 +423,118 @@ where
     }
 }
 
+/// A statement's byte range within the `String` returned alongside it by
+/// [`Document::render_staged_function_structured`], keyed by the
+/// [`Statement`] it was printed from so tooling (editors, LSP servers, test
+/// harnesses) can map printed text back to the IR entity that produced it.
+///
+/// Scoped to statements: this does not track individual SSA values, blocks,
+/// regions, or successors within a statement's own printed text, only the
+/// outer extent of each statement (including any nested region it owns).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatementSpan {
+    pub statement: Statement,
+    pub range: std::ops::Range<usize>,
+}
+
+// Methods for structured (span-tracked) rendering, requiring `L: Walk<L>` so
+// a statement's nested regions can be discovered generically, without a
+// dialect-specific match.
+impl<'a, L: Dialect + PrettyPrint + Walk<L>> Document<'a, L>
+where
+    L::Type: std::fmt::Display,
+{
+    /// Render a staged function the same way [`Document::render`] would,
+    /// plus a span table locating every statement nested anywhere within it
+    /// (transitively, through every region a statement owns) in the
+    /// rendered text.
+    ///
+    /// Each statement's span is found by rendering it standalone and
+    /// searching for that text within the full output, starting from where
+    /// the previous statement (in pre-order) left off; a statement whose
+    /// standalone rendering doesn't match — e.g. because its line-wrapping
+    /// differs when rendered in isolation versus at its true indentation —
+    /// is silently omitted from the span table rather than reported with a
+    /// wrong range.
+    pub fn render_staged_function_structured(
+        &'a mut self,
+        func: &StagedFunction,
+    ) -> Result<(String, Vec<StatementSpan>), std::fmt::Error>
+    where
+        StagedFunction: ScanResultWidth<L>,
+    {
+        let full = self.render(func)?;
+
+        let info = func.expect_info(self.context);
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for spec in info.specializations().iter().filter(|s| !s.is_invalidated()) {
+            cursor = self.locate_statement(*spec.body(), &full, cursor, &mut spans);
+        }
+        Ok((full, spans))
+    }
+
+    /// Renders `stmt` standalone and locates its text in `full` starting
+    /// from `search_from`, recording its span and recursing into any
+    /// statements nested in its own regions before returning the offset
+    /// just past this statement's match (or `search_from` unchanged if no
+    /// match was found).
+    fn locate_statement(
+        &'a self,
+        stmt: Statement,
+        full: &str,
+        search_from: usize,
+        spans: &mut Vec<StatementSpan>,
+    ) -> usize {
+        let text = self.render_statement_text(&stmt);
+        let Some(offset) = full[search_from..].find(text.as_str()) else {
+            return search_from;
+        };
+        let start = search_from + offset;
+        let end = start + text.len();
+        spans.push(StatementSpan {
+            statement: stmt,
+            range: start..end,
+        });
+
+        let mut inner_cursor = start;
+        for child in self.direct_nested_statements(&stmt) {
+            inner_cursor = self.locate_statement(child, full, inner_cursor, spans);
+        }
+        end
+    }
+
+    /// The statements directly owned by `stmt`'s own regions (one level
+    /// down only — further nesting is handled by the caller's recursion).
+    fn direct_nested_statements(&self, stmt: &Statement) -> Vec<Statement> {
+        let def = stmt.definition(self.context);
+        let mut regions = Vec::new();
+        def.walk_regions(&mut |region| regions.push(*region));
+
+        let mut out = Vec::new();
+        for region in regions {
+            for block in region.blocks(self.context) {
+                out.extend(block.statements(self.context));
+                if let Some(terminator) = block.terminator(self.context) {
+                    out.push(terminator);
+                }
+            }
+        }
+        out
+    }
+
+    /// Renders a single statement's definition in isolation, the same way
+    /// [`Document::print_statement`] would within a full document.
+    fn render_statement_text(&'a self, stmt: &Statement) -> String {
+        let max_width = self.config.max_width;
+        let doc = self.print_statement(stmt);
+        let mut buf = String::new();
+        let _ = doc.render_fmt(max_width, &mut buf);
+        strip_trailing_whitespace(&buf)
+    }
+}
+
 impl<'a, L: Dialect> Deref for Document<'a, L> {
     type Target = Arena<'a>;
 
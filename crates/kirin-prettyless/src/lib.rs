@@ -3,6 +3,9 @@ use std::ops::Deref;
 use kirin_ir::*;
 use prettyless::{Arena, DocBuilder};
 
+mod style;
+pub use style::{ColorScheme, Style};
+
 pub use prettyless::DocAllocator;
 pub type ArenaDoc<'a> = DocBuilder<'a, Arena<'a>>;
 
@@ -6,7 +6,10 @@
 
 use std::io::{Write, stdout};
 
-use kirin_ir::{Dialect, Function, GlobalSymbol, InternTable, Pipeline, StageInfo, StagedFunction};
+use kirin_ir::{
+    CompileStage, Dialect, Function, GlobalSymbol, Id, InternTable, Pipeline, StageInfo,
+    StagedFunction,
+};
 
 use crate::{Config, Document, PrettyPrint, ScanResultWidth};
 
@@ -118,6 +121,157 @@ impl<'a, S: RenderStage> PipelineDocument<'a, S> {
         }
         Ok(output)
     }
+
+    /// Render a function's staged representation at a single stage, if it has
+    /// one there.
+    fn render_function_at_stage(
+        &self,
+        func: Function,
+        stage_id: CompileStage,
+    ) -> Result<Option<String>, std::fmt::Error> {
+        let gs = self.pipeline.global_symbols();
+        let func_info = self
+            .pipeline
+            .function_info(func)
+            .expect("Function ID not found in pipeline");
+
+        let Some(&sf_id) = func_info.staged_functions().get(&stage_id) else {
+            return Ok(None);
+        };
+        let Some(stage) = self.pipeline.stage(stage_id) else {
+            return Ok(None);
+        };
+        stage.render_staged_function(sf_id, &self.config, gs)
+    }
+
+    /// Renders a unified diff between each consecutive pair of stages a
+    /// function has a staged representation at, in stage-creation order,
+    /// each chunk preceded by a `<stage A prefix> -> <stage B prefix>`
+    /// header. Turns the per-stage printer into a pass-debugging tool that
+    /// shows what each compilation stage actually did to a function.
+    pub fn render_function_diff(&self, func: Function) -> Result<String, std::fmt::Error> {
+        let func_info = self
+            .pipeline
+            .function_info(func)
+            .expect("Function ID not found in pipeline");
+
+        let mut stages: Vec<CompileStage> = func_info.staged_functions().keys().copied().collect();
+        stages.sort_by_key(|&stage_id| Id::from(stage_id).raw());
+
+        let mut output = String::new();
+        for pair in stages.windows(2) {
+            let (stage_a, stage_b) = (pair[0], pair[1]);
+            let before = self
+                .render_function_at_stage(func, stage_a)?
+                .unwrap_or_default();
+            let after = self
+                .render_function_at_stage(func, stage_b)?
+                .unwrap_or_default();
+
+            if !output.is_empty() {
+                output.push_str("\n\n");
+            }
+            output.push_str(&format!(
+                "{} -> {}\n",
+                stage_prefix(&before),
+                stage_prefix(&after)
+            ));
+            output.push_str(&render_diff(&diff_lines(&before, &after), self.config.color));
+        }
+        Ok(output)
+    }
+}
+
+/// Pulls the `stage @name`/`stage <id>` prefix [`Document::print_function_header`]
+/// puts at the start of a staged function's first rendered line, falling
+/// back to a placeholder if the rendering carried no stage prefix at all.
+fn stage_prefix(rendered: &str) -> &str {
+    rendered
+        .lines()
+        .next()
+        .and_then(|line| line.split(" fn @").next())
+        .filter(|prefix| prefix.starts_with("stage"))
+        .unwrap_or("stage <unknown>")
+}
+
+/// One line of a [`diff_lines`] result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Line unchanged between the two renderings.
+    Context(String),
+    /// Line present only in the "before" rendering.
+    Removed(String),
+    /// Line present only in the "after" rendering.
+    Added(String),
+}
+
+/// Line-oriented diff of `before` against `after` via a standard
+/// longest-common-subsequence alignment, the same algorithm `diff -u` is
+/// built on.
+pub fn diff_lines(before: &str, after: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = before.lines().collect();
+    let b: Vec<&str> = after.lines().collect();
+
+    // dp[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            dp[i][j] = if a[i] == b[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            lines.push(DiffLine::Context(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            lines.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    while i < a.len() {
+        lines.push(DiffLine::Removed(a[i].to_string()));
+        i += 1;
+    }
+    while j < b.len() {
+        lines.push(DiffLine::Added(b[j].to_string()));
+        j += 1;
+    }
+    lines
+}
+
+/// ANSI color for added/removed diff lines, independent of [`crate::style::Style`]
+/// (which tags IR syntax, not diff markers).
+const DIFF_ADDED_COLOR: &str = "\x1b[32m"; // green
+const DIFF_REMOVED_COLOR: &str = "\x1b[31m"; // red
+const DIFF_RESET: &str = "\x1b[0m";
+
+/// Render a [`diff_lines`] result as unified-diff-style text: ` ` for context
+/// lines, `-`/`+` for removed/added ones, optionally colorized.
+pub fn render_diff(lines: &[DiffLine], color: bool) -> String {
+    let mut out = String::new();
+    for line in lines {
+        let (marker, text, ansi) = match line {
+            DiffLine::Context(s) => (' ', s.as_str(), None),
+            DiffLine::Removed(s) => ('-', s.as_str(), Some(DIFF_REMOVED_COLOR)),
+            DiffLine::Added(s) => ('+', s.as_str(), Some(DIFF_ADDED_COLOR)),
+        };
+        match (color, ansi) {
+            (true, Some(code)) => out.push_str(&format!("{code}{marker}{text}{DIFF_RESET}\n")),
+            _ => out.push_str(&format!("{marker}{text}\n")),
+        }
+    }
+    out
 }
 
 /// Extension trait for cross-stage printing on [`Function`] IDs.
@@ -144,6 +298,45 @@ pub trait FunctionPrintExt {
 
     /// Write a function across all stages to a writer with default config.
     fn write<S: RenderStage>(&self, writer: &mut impl Write, pipeline: &Pipeline<S>);
+
+    /// Render a unified diff between the function's renderings at
+    /// `stage_a` and `stage_b` with default config, so pass authors can see
+    /// exactly what a stage transition changed.
+    fn sprint_diff<S: RenderStage>(
+        &self,
+        pipeline: &Pipeline<S>,
+        stage_a: CompileStage,
+        stage_b: CompileStage,
+    ) -> String;
+
+    /// Render a unified diff between the function's renderings at
+    /// `stage_a` and `stage_b` with custom config.
+    ///
+    /// Colorized (`+`/`-` lines in green/red) when `config.color` is set,
+    /// otherwise plain unified-diff text.
+    fn sprint_diff_with_config<S: RenderStage>(
+        &self,
+        pipeline: &Pipeline<S>,
+        config: Config,
+        stage_a: CompileStage,
+        stage_b: CompileStage,
+    ) -> String;
+
+    /// Render a unified diff across every consecutive pair of stages this
+    /// function has a staged representation at, with default config.
+    fn sprint_diff_all<S: RenderStage>(&self, pipeline: &Pipeline<S>) -> String;
+
+    /// Render a unified diff across every consecutive pair of stages this
+    /// function has a staged representation at, with custom config.
+    fn sprint_diff_all_with_config<S: RenderStage>(&self, config: Config, pipeline: &Pipeline<S>) -> String;
+
+    /// Print a unified diff across every consecutive pair of stages this
+    /// function has a staged representation at to stdout, with default config.
+    fn print_diff<S: RenderStage>(&self, pipeline: &Pipeline<S>);
+
+    /// Print a unified diff across every consecutive pair of stages this
+    /// function has a staged representation at to stdout, with custom config.
+    fn print_diff_with_config<S: RenderStage>(&self, config: Config, pipeline: &Pipeline<S>);
 }
 
 impl FunctionPrintExt for Function {
@@ -173,4 +366,52 @@ impl FunctionPrintExt for Function {
         let output = self.sprint(pipeline);
         writer.write_all(output.as_bytes()).expect("write failed");
     }
+
+    fn sprint_diff<S: RenderStage>(
+        &self,
+        pipeline: &Pipeline<S>,
+        stage_a: CompileStage,
+        stage_b: CompileStage,
+    ) -> String {
+        self.sprint_diff_with_config(pipeline, Config::default(), stage_a, stage_b)
+    }
+
+    fn sprint_diff_with_config<S: RenderStage>(
+        &self,
+        pipeline: &Pipeline<S>,
+        config: Config,
+        stage_a: CompileStage,
+        stage_b: CompileStage,
+    ) -> String {
+        let doc = PipelineDocument::new(config.clone(), pipeline);
+        let before = doc
+            .render_function_at_stage(*self, stage_a)
+            .expect("render failed")
+            .unwrap_or_default();
+        let after = doc
+            .render_function_at_stage(*self, stage_b)
+            .expect("render failed")
+            .unwrap_or_default();
+        render_diff(&diff_lines(&before, &after), config.color)
+    }
+
+    fn sprint_diff_all<S: RenderStage>(&self, pipeline: &Pipeline<S>) -> String {
+        self.sprint_diff_all_with_config(Config::default(), pipeline)
+    }
+
+    fn sprint_diff_all_with_config<S: RenderStage>(&self, config: Config, pipeline: &Pipeline<S>) -> String {
+        PipelineDocument::new(config, pipeline)
+            .render_function_diff(*self)
+            .expect("render failed")
+    }
+
+    fn print_diff<S: RenderStage>(&self, pipeline: &Pipeline<S>) {
+        let output = self.sprint_diff_all(pipeline);
+        stdout().write_all(output.as_bytes()).expect("write failed");
+    }
+
+    fn print_diff_with_config<S: RenderStage>(&self, config: Config, pipeline: &Pipeline<S>) {
+        let output = self.sprint_diff_all_with_config(config, pipeline);
+        stdout().write_all(output.as_bytes()).expect("write failed");
+    }
 }
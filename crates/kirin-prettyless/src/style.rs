@@ -0,0 +1,91 @@
+//! ANSI styling for syntax-highlighted terminal output.
+
+/// Semantic category of a piece of pretty-printed text.
+///
+/// [`Document`](crate::Document) methods that print a particular kind of
+/// token (keywords, SSA values, global symbols, types, ...) tag the text
+/// they emit with the matching variant, so a [`ColorScheme`] can pick a
+/// color for it when [`Config::color`](crate::Config::color) is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// Dialect keywords, e.g. `add`, `constant`, `return`.
+    Keyword,
+    /// SSA values, e.g. `%0`.
+    Value,
+    /// Global symbols and block labels, e.g. `@foo`, `^bb0`.
+    Symbol,
+    /// Type annotations, e.g. `i32`.
+    Type,
+    /// Comments.
+    Comment,
+}
+
+/// ANSI escape sequence to reset styling, appended after every styled run.
+const RESET: &str = "\x1b[0m";
+
+/// Maps each [`Style`] to the ANSI escape sequence used to render it.
+///
+/// Carried by [`Config`](crate::Config) so callers can customize colors
+/// without touching `Document`'s printing logic. Escapes are only emitted
+/// when [`Config::color`](crate::Config::color) is `true`; plain-text
+/// rendering (the default) never consults this scheme.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorScheme {
+    pub keyword: String,
+    pub value: String,
+    pub symbol: String,
+    pub ty: String,
+    pub comment: String,
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self {
+            keyword: "\x1b[35m".to_string(), // magenta
+            value: "\x1b[36m".to_string(),   // cyan
+            symbol: "\x1b[33m".to_string(),  // yellow
+            ty: "\x1b[32m".to_string(),      // green
+            comment: "\x1b[90m".to_string(), // bright black
+        }
+    }
+}
+
+impl ColorScheme {
+    /// The escape sequence to open a run of text styled as `style`.
+    pub fn open(&self, style: Style) -> &str {
+        match style {
+            Style::Keyword => &self.keyword,
+            Style::Value => &self.value,
+            Style::Symbol => &self.symbol,
+            Style::Type => &self.ty,
+            Style::Comment => &self.comment,
+        }
+    }
+
+    /// Wrap `text` in the escape sequence for `style`, already reset at the end.
+    pub fn wrap(&self, style: Style, text: &str) -> String {
+        format!("{}{}{}", self.open(style), text, RESET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_includes_reset() {
+        let scheme = ColorScheme::default();
+        let wrapped = scheme.wrap(Style::Keyword, "add");
+        assert!(wrapped.starts_with(&scheme.keyword));
+        assert!(wrapped.ends_with(RESET));
+        assert!(wrapped.contains("add"));
+    }
+
+    #[test]
+    fn test_distinct_styles_use_distinct_codes() {
+        let scheme = ColorScheme::default();
+        assert_ne!(scheme.open(Style::Keyword), scheme.open(Style::Value));
+        assert_ne!(scheme.open(Style::Symbol), scheme.open(Style::Type));
+    }
+}
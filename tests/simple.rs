@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use kirin::ir::*;
 use kirin::pretty::*;
+use kirin_lexer::Token;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum SimpleTypeLattice {
@@ -50,6 +53,19 @@ impl FiniteLattice for SimpleTypeLattice {
 
 impl crate::TypeLattice for SimpleTypeLattice {}
 
+impl std::fmt::Display for SimpleTypeLattice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SimpleTypeLattice::Any => "Any",
+            SimpleTypeLattice::Int => "Int",
+            SimpleTypeLattice::Float => "Float",
+            SimpleTypeLattice::DataType => "DataType",
+            SimpleTypeLattice::Bottom => "Bottom",
+        };
+        write!(f, "{name}")
+    }
+}
+
 impl Typeof<SimpleTypeLattice> for i64 {
     fn type_of(&self) -> SimpleTypeLattice {
         SimpleTypeLattice::Int
@@ -117,26 +133,50 @@ pub enum SimpleLanguage {
     ),
 }
 
+/// Resolve `value`'s interned name if it has one, else fall back to its raw
+/// id, e.g. `%x` vs `%3`. Used for both operands and results so a value's
+/// printed spelling is the same wherever it's referenced.
+fn format_ssa(context: &Context<SimpleLanguage>, value: impl Into<SSAValue>) -> String {
+    let ssa: SSAValue = value.into();
+    let info = ssa.expect_info(context);
+    match info.name() {
+        Some(name) => context
+            .symbol_table()
+            .borrow()
+            .resolve(name)
+            .cloned()
+            .map(|name| format!("%{name}"))
+            .unwrap_or_else(|| ssa.to_string()),
+        None => ssa.to_string(),
+    }
+}
+
 impl PrettyPrint<SimpleLanguage> for SimpleLanguage {
     fn pretty_print<'a>(&self, doc: &'a Document<'a, SimpleLanguage>) -> ArenaDoc<'a> {
         match self {
-            SimpleLanguage::Add(lhs, rhs, _) => {
-                let doc = doc.text(format!("add {}, {}", *lhs, *rhs));
-                doc
-            }
-            SimpleLanguage::Constant(value, _) => {
-                let doc = match value {
-                    Value::I64(v) => doc.text(format!("constant {}", v)),
-                    Value::F64(v) => doc.text(format!("constant {}", v)),
+            SimpleLanguage::Add(lhs, rhs, result) => doc.text(format!(
+                "{} = add {}, {}",
+                format_ssa(doc.context(), *result),
+                format_ssa(doc.context(), *lhs),
+                format_ssa(doc.context(), *rhs),
+            )),
+            SimpleLanguage::Constant(value, result) => {
+                let value = match value {
+                    Value::I64(v) => v.to_string(),
+                    Value::F64(v) => v.to_string(),
                 };
-                doc
+                doc.text(format!(
+                    "{} = constant {}",
+                    format_ssa(doc.context(), *result),
+                    value
+                ))
             }
             SimpleLanguage::Return(retval) => {
-                let doc = doc.text(format!("return {}", *retval));
+                let doc = doc.text(format!("return {}", format_ssa(doc.context(), *retval)));
                 doc
             }
             SimpleLanguage::Function(region, _) => {
-                let region_doc = region.pretty_print(doc);
+                let region_doc = doc.print_region(region);
                 let doc = doc.text("function ").append(region_doc);
                 doc
             }
@@ -202,3 +242,358 @@ pub fn strip_trailing_whitespace(s: &str) -> String {
     }
     res
 }
+
+/// Errors produced while reparsing text emitted by [`PrettyPrint`] back into
+/// a [`Context`].
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("unexpected token {found}, expected {expected}")]
+    Unexpected { found: String, expected: String },
+    #[error("unexpected end of input, expected {0}")]
+    UnexpectedEof(String),
+    #[error("unknown opcode `{0}`")]
+    UnknownOpcode(String),
+    #[error("unknown type `{0}`")]
+    UnknownType(String),
+    #[error("reference to undefined value `%{0}`")]
+    DanglingValue(String),
+}
+
+/// Dialect-side hook mirroring [`PrettyPrint`]: given an opcode and its
+/// already-tokenized operands, build the matching statement.
+///
+/// `resolve` turns a `%name` token's text back into the [`SSAValue`] it was
+/// parsed from, so dialects never need to know how names are tracked.
+pub trait ParseStatement<L: Dialect>: Sized {
+    fn parse_statement(
+        context: &mut Context<L>,
+        opcode: &str,
+        operands: &[Token<'_>],
+        resolve: &mut dyn FnMut(&str) -> Result<SSAValue, ParseError>,
+    ) -> Result<(Statement, Option<ResultValue>), ParseError>;
+}
+
+fn unexpected(found: Option<&Token<'_>>, expected: &str) -> ParseError {
+    match found {
+        Some(token) => ParseError::Unexpected {
+            found: format!("{token:?}"),
+            expected: expected.to_string(),
+        },
+        None => ParseError::UnexpectedEof(expected.to_string()),
+    }
+}
+
+fn expect<'a>(
+    tokens: &[Token<'a>],
+    pos: &mut usize,
+    expected: Token<'a>,
+) -> Result<(), ParseError> {
+    match tokens.get(*pos) {
+        Some(token) if *token == expected => {
+            *pos += 1;
+            Ok(())
+        }
+        found => Err(unexpected(found, &format!("{expected:?}"))),
+    }
+}
+
+fn expect_identifier<'a>(tokens: &[Token<'a>], pos: &mut usize) -> Result<&'a str, ParseError> {
+    match tokens.get(*pos) {
+        Some(Token::Identifier(name)) => {
+            *pos += 1;
+            Ok(name)
+        }
+        found => Err(unexpected(found, "an identifier")),
+    }
+}
+
+fn parse_type(name: &str) -> Result<SimpleTypeLattice, ParseError> {
+    match name {
+        "Any" => Ok(SimpleTypeLattice::Any),
+        "Int" => Ok(SimpleTypeLattice::Int),
+        "Float" => Ok(SimpleTypeLattice::Float),
+        "DataType" => Ok(SimpleTypeLattice::DataType),
+        "Bottom" => Ok(SimpleTypeLattice::Bottom),
+        other => Err(ParseError::UnknownType(other.to_string())),
+    }
+}
+
+/// Rename `value` to the literal `%name` it was parsed from, so reprinting
+/// it produces the same text regardless of its freshly-allocated id.
+fn rename_value(context: &mut Context<SimpleLanguage>, value: impl Into<SSAValue>, name: &str) {
+    let symbol = context.symbol_table().borrow_mut().intern(name.to_string());
+    value
+        .into()
+        .expect_info_mut(context)
+        .set_name(Some(symbol));
+}
+
+impl ParseStatement<SimpleLanguage> for SimpleLanguage {
+    fn parse_statement(
+        context: &mut Context<SimpleLanguage>,
+        opcode: &str,
+        operands: &[Token<'_>],
+        resolve: &mut dyn FnMut(&str) -> Result<SSAValue, ParseError>,
+    ) -> Result<(Statement, Option<ResultValue>), ParseError> {
+        match opcode {
+            "add" => {
+                let [lhs, rhs] = operands else {
+                    return Err(ParseError::Unexpected {
+                        found: format!("{} operand(s)", operands.len()),
+                        expected: "2 operands".to_string(),
+                    });
+                };
+                let lhs = resolve(ssa_name(lhs)?)?;
+                let rhs = resolve(ssa_name(rhs)?)?;
+                let stmt = SimpleLanguage::op_add(context, lhs, rhs);
+                Ok((stmt.id, Some(stmt.result)))
+            }
+            "constant" => {
+                let [value] = operands else {
+                    return Err(ParseError::Unexpected {
+                        found: format!("{} operand(s)", operands.len()),
+                        expected: "1 operand".to_string(),
+                    });
+                };
+                let value: Value = match value {
+                    Token::Int(text) => text
+                        .parse::<i64>()
+                        .map_err(|_| unexpected(Some(value), "an integer literal"))?
+                        .into(),
+                    Token::Float(text) => text
+                        .parse::<f64>()
+                        .map_err(|_| unexpected(Some(value), "a float literal"))?
+                        .into(),
+                    other => return Err(unexpected(Some(other), "a numeric literal")),
+                };
+                let stmt = SimpleLanguage::op_constant(context, value);
+                Ok((stmt.id, Some(stmt.result)))
+            }
+            "return" => {
+                let [value] = operands else {
+                    return Err(ParseError::Unexpected {
+                        found: format!("{} operand(s)", operands.len()),
+                        expected: "1 operand".to_string(),
+                    });
+                };
+                let value = resolve(ssa_name(value)?)?;
+                let stmt = SimpleLanguage::op_return(context, value);
+                Ok((stmt.id, None))
+            }
+            other => Err(ParseError::UnknownOpcode(other.to_string())),
+        }
+    }
+}
+
+fn ssa_name<'a>(token: &'a Token<'_>) -> Result<&'a str, ParseError> {
+    match token {
+        Token::SSAValue(name) => Ok(name),
+        other => Err(unexpected(Some(other), "an SSA value reference")),
+    }
+}
+
+/// Parse the statement list of a single block (already past its header),
+/// consuming tokens up to and including the closing `}`.
+fn parse_block_body(
+    context: &mut Context<SimpleLanguage>,
+    tokens: &[Token<'_>],
+    pos: &mut usize,
+    bindings: &mut HashMap<String, SSAValue>,
+) -> Result<(Vec<Statement>, Statement), ParseError> {
+    let mut statements = Vec::new();
+    let mut terminator = None;
+
+    while terminator.is_none() {
+        let result_name = if let (Some(Token::SSAValue(name)), Some(Token::Equal)) =
+            (tokens.get(*pos), tokens.get(*pos + 1))
+        {
+            let name = name.to_string();
+            *pos += 2;
+            Some(name)
+        } else {
+            None
+        };
+
+        let opcode = expect_identifier(tokens, pos)?;
+
+        let mut operand_tokens = Vec::new();
+        while !matches!(tokens.get(*pos), Some(Token::Semicolon) | None) {
+            if !matches!(tokens.get(*pos), Some(Token::Comma)) {
+                operand_tokens.push(tokens[*pos].clone());
+            }
+            *pos += 1;
+        }
+        expect(tokens, pos, Token::Semicolon)?;
+
+        let mut resolve = |name: &str| -> Result<SSAValue, ParseError> {
+            bindings
+                .get(name)
+                .copied()
+                .ok_or_else(|| ParseError::DanglingValue(name.to_string()))
+        };
+        let (stmt, result) =
+            SimpleLanguage::parse_statement(context, opcode, &operand_tokens, &mut resolve)?;
+
+        if let Some(name) = result_name {
+            let result = result.ok_or_else(|| ParseError::Unexpected {
+                found: opcode.to_string(),
+                expected: "an opcode that produces a result".to_string(),
+            })?;
+            rename_value(context, result, &name);
+            bindings.insert(name, result.into());
+        }
+
+        if stmt.expect_info(context).definition.is_terminator() {
+            terminator = Some(stmt);
+        } else {
+            statements.push(stmt);
+        }
+    }
+
+    Ok((statements, terminator.expect("loop exits only once set")))
+}
+
+/// Parse one `^label(%arg: Type, ...) { ... }` block.
+fn parse_block(context: &mut Context<SimpleLanguage>, tokens: &[Token<'_>], pos: &mut usize) -> Result<Block, ParseError> {
+    match tokens.get(*pos) {
+        Some(Token::Block(_)) => *pos += 1,
+        found => return Err(unexpected(found, "a block label")),
+    }
+
+    let mut bindings = HashMap::new();
+    let mut arg_names = Vec::new();
+
+    if matches!(tokens.get(*pos), Some(Token::LParen)) {
+        *pos += 1;
+        while !matches!(tokens.get(*pos), Some(Token::RParen)) {
+            let name = match tokens.get(*pos) {
+                Some(Token::SSAValue(name)) => {
+                    *pos += 1;
+                    name.to_string()
+                }
+                found => return Err(unexpected(found, "a block argument")),
+            };
+            expect(tokens, pos, Token::Colon)?;
+            let ty = parse_type(expect_identifier(tokens, pos)?)?;
+
+            let index = arg_names.len();
+            let placeholder = context.block_argument(index);
+            bindings.insert(name.clone(), placeholder.into());
+            arg_names.push((name, ty));
+
+            if matches!(tokens.get(*pos), Some(Token::Comma)) {
+                *pos += 1;
+            }
+        }
+        expect(tokens, pos, Token::RParen)?;
+    }
+
+    expect(tokens, pos, Token::LBrace)?;
+    let (statements, terminator) = parse_block_body(context, tokens, pos, &mut bindings)?;
+    expect(tokens, pos, Token::RBrace)?;
+
+    let mut builder = context.block();
+    for (name, ty) in arg_names {
+        builder = builder.argument_with_name(name, ty);
+    }
+    for stmt in statements {
+        builder = builder.stmt(stmt);
+    }
+    Ok(builder.terminator(terminator).new())
+}
+
+/// Parse the body of a function, i.e. `function { ^block { ... } ... }`
+/// exactly as printed by `SimpleLanguage::Function`'s [`PrettyPrint`] impl.
+/// The caller is expected to provide a fresh [`Context`].
+pub fn parse_function_body(
+    context: &mut Context<SimpleLanguage>,
+    text: &str,
+) -> Result<Statement, ParseError> {
+    let tokens = Token::lexer(text)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ParseError::Unexpected {
+            found: "an invalid token".to_string(),
+            expected: "valid kirin IR text".to_string(),
+        })?;
+    let pos = &mut 0usize;
+
+    expect(&tokens, pos, Token::Identifier("function"))?;
+    expect(&tokens, pos, Token::LBrace)?;
+
+    let mut blocks = Vec::new();
+    while !matches!(tokens.get(*pos), Some(Token::RBrace)) {
+        blocks.push(parse_block(context, &tokens, pos)?);
+    }
+    expect(&tokens, pos, Token::RBrace)?;
+
+    let region = blocks
+        .into_iter()
+        .fold(context.region(), |region, block| region.add_block(block))
+        .new();
+    let fdef = SimpleLanguage::op_function(context, region);
+    Ok(fdef.id)
+}
+
+/// Build `function { ^0(%x: Int) { %0 = constant 1; %1 = add %x, %0; return %1; } }`,
+/// a small well-formed single-block function, distinct from [`test_block`]'s
+/// (unrelated, pre-existing) construction.
+fn build_roundtrip_function(context: &mut Context<SimpleLanguage>) -> Statement {
+    let x = context.block_argument(0);
+    let one = SimpleLanguage::op_constant(context, 1i64);
+    let sum = SimpleLanguage::op_add(context, x, one.result);
+    let ret = SimpleLanguage::op_return(context, sum.result);
+
+    let block = context
+        .block()
+        .argument_with_name("x", Int)
+        .stmt(one.id)
+        .stmt(sum.id)
+        .terminator(ret.id)
+        .new();
+    let body = context.region().add_block(block).new();
+    let fdef = SimpleLanguage::op_function(context, body);
+    fdef.id
+}
+
+fn render_function_body(context: &Context<SimpleLanguage>, stmt: Statement) -> String {
+    let info = stmt.expect_info(context);
+    let def = info.definition();
+    let doc = Document::new(Config::default(), context);
+    let mut output = String::new();
+    def.pretty_print(&doc)
+        .render_fmt(120, &mut output)
+        .expect("render should succeed");
+    output
+}
+
+#[test]
+fn test_roundtrip_single_block_function() {
+    let mut context: Context<SimpleLanguage> = Context::default();
+    let stmt = build_roundtrip_function(&mut context);
+    let printed = render_function_body(&context, stmt);
+
+    let mut reparsed_context: Context<SimpleLanguage> = Context::default();
+    let reparsed_stmt = parse_function_body(&mut reparsed_context, &printed)
+        .expect("printed function should reparse");
+    let reprinted = render_function_body(&reparsed_context, reparsed_stmt);
+
+    assert_eq!(printed, reprinted);
+}
+
+#[test]
+fn test_parse_rejects_unknown_opcode() {
+    let mut context: Context<SimpleLanguage> = Context::default();
+    let text = "function { ^0(%x: Int) { return %x; } }";
+    let err = parse_function_body(&mut context, text.replace("return", "multiply").as_str())
+        .expect_err("unknown opcode should fail to parse");
+    assert!(matches!(err, ParseError::UnknownOpcode(op) if op == "multiply"));
+}
+
+#[test]
+fn test_parse_rejects_dangling_value() {
+    let mut context: Context<SimpleLanguage> = Context::default();
+    let text = "function { ^0() { return %missing; } }";
+    let err =
+        parse_function_body(&mut context, text).expect_err("dangling value should fail to parse");
+    assert!(matches!(err, ParseError::DanglingValue(name) if name == "missing"));
+}